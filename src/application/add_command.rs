@@ -1,21 +1,45 @@
+use super::add_executable::replaced_entry;
+use super::launcher_cleanup::replace_launcher;
 use crate::domain::{
-    ExecutionMode, StartupCommand, StartupEntry, StartupRepository, StartupValidator,
+    ExecutionMode, LauncherStore, StartupCommand, StartupEntry, StartupLocation, StartupRepository,
+    StartupValidator,
 };
-use crate::shared::error::{Result, StartupError};
-use std::fs;
-use std::path::PathBuf;
+use crate::shared::error::Result;
 
 /// Use case for adding a command with arguments to startup.
 /// This follows the Single Responsibility Principle.
-pub struct AddCommandUseCase<'a, R: StartupRepository> {
+pub struct AddCommandUseCase<'a, R: StartupRepository, L: LauncherStore> {
     repository: &'a R,
+    launchers: &'a L,
+    location: Option<StartupLocation>,
+    force: bool,
 }
 
-impl<'a, R: StartupRepository> AddCommandUseCase<'a, R> {
-    pub fn new(repository: &'a R) -> Self {
-        Self { repository }
+impl<'a, R: StartupRepository, L: LauncherStore> AddCommandUseCase<'a, R, L> {
+    pub fn new(repository: &'a R, launchers: &'a L) -> Self {
+        Self {
+            repository,
+            launchers,
+            location: None,
+            force: false,
+        }
+    }
+
+    /// Stores the entry in `location` instead of the current user's Run key.
+    /// Scheduled tasks are always stored in Task Scheduler.
+    pub fn with_location(mut self, location: StartupLocation) -> Self {
+        self.location = Some(location);
+        self
+    }
+
+    /// Replaces an entry of the same name instead of failing.
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
     }
 
+    /// Adds the entry, writing its launcher first. Returns the entry it
+    /// replaced, if any.
     pub fn execute(
         &self,
         name: &str,
@@ -23,7 +47,7 @@ impl<'a, R: StartupRepository> AddCommandUseCase<'a, R> {
         args: Vec<String>,
         workdir: Option<&str>,
         mode: ExecutionMode,
-    ) -> Result<()> {
+    ) -> Result<Option<StartupEntry>> {
         // Validate inputs
         StartupValidator::validate_name(name)?;
 
@@ -32,6 +56,11 @@ impl<'a, R: StartupRepository> AddCommandUseCase<'a, R> {
             StartupValidator::validate_directory(dir)?;
         }
 
+        // Validate scheduled task options
+        if let ExecutionMode::ScheduledTask(settings) = &mode {
+            StartupValidator::validate_task_settings(settings)?;
+        }
+
         // Create command
         let startup_command = StartupCommand::CommandWithArgs {
             command: command.to_string(),
@@ -40,44 +69,44 @@ impl<'a, R: StartupRepository> AddCommandUseCase<'a, R> {
             mode,
         };
 
-        // If using VBScript mode, create the VBScript file
-        if mode == ExecutionMode::VBScript
-            && let Some((filename, content)) = startup_command.get_vbscript_content()
-        {
-            self.create_vbscript_file(&filename, &content)?;
+        // Convert to registry value and create entry
+        let registry_value = startup_command.to_registry_value(name, self.launchers);
+        let mut entry = StartupEntry::new(name, registry_value);
+        if let Some(location) = self.location {
+            entry = entry.with_location(location);
+        }
+        // Logon tasks are registered with Task Scheduler instead of a Run key
+        if let ExecutionMode::ScheduledTask(_) = mode {
+            entry = entry.with_location(StartupLocation::TaskScheduler);
         }
+        let replaced = replaced_entry(self.repository, &entry, self.force)?;
 
-        // Convert to registry value and create entry
-        let registry_value = startup_command.to_registry_value();
-        let entry = StartupEntry::new(name, registry_value);
+        // The launcher of the entry being overwritten, if any
+        let previous_launcher = self
+            .repository
+            .list()?
+            .into_iter()
+            .find(|entry| entry.name.eq_ignore_ascii_case(name))
+            .and_then(|entry| self.launchers.referenced_by(&entry.command));
+
+        // If using VBScript mode, create the VBScript file
+        let launcher = startup_command.get_vbscript_content(name);
+        if let Some((filename, content)) = &launcher {
+            self.launchers.write(filename, content)?;
+        }
 
         // Add to repository
         self.repository.add(&entry)?;
 
-        Ok(())
-    }
-
-    /// Creates the VBScript file in %APPDATA%\windows_startup_manager\
-    fn create_vbscript_file(&self, filename: &str, content: &str) -> Result<()> {
-        // Get APPDATA directory
-        let appdata = std::env::var("APPDATA").map_err(|_| {
-            StartupError::RegistryError("Failed to get APPDATA environment variable".to_string())
-        })?;
-
-        // Create directory path
-        let mut dir_path = PathBuf::from(appdata);
-        dir_path.push("windows_startup_manager");
-
-        // Create directory if it doesn't exist
-        fs::create_dir_all(&dir_path)?;
-
-        // Create file path
-        let mut file_path = dir_path;
-        file_path.push(filename);
-
-        // Write VBScript content
-        fs::write(&file_path, content)?;
+        // Record the new launcher and delete the one it replaces
+        replace_launcher(
+            self.repository,
+            self.launchers,
+            name,
+            launcher.as_ref().map(|(filename, _)| filename.as_str()),
+            previous_launcher,
+        )?;
 
-        Ok(())
+        Ok(replaced)
     }
 }
@@ -1,26 +1,78 @@
-use crate::domain::{StartupEntry, StartupRepository, StartupValidator};
-use crate::shared::error::Result;
+use crate::domain::{StartupEntry, StartupLocation, StartupRepository, StartupValidator};
+use crate::shared::error::{Result, StartupError};
 
 /// Use case for adding an executable to startup.
 /// This follows the Single Responsibility Principle.
 pub struct AddExecutableUseCase<'a, R: StartupRepository> {
     repository: &'a R,
+    location: Option<StartupLocation>,
+    force: bool,
 }
 
 impl<'a, R: StartupRepository> AddExecutableUseCase<'a, R> {
     pub fn new(repository: &'a R) -> Self {
-        Self { repository }
+        Self {
+            repository,
+            location: None,
+            force: false,
+        }
     }
 
-    pub fn execute(&self, name: &str, path: &str) -> Result<()> {
+    /// Stores the entry in `location` instead of the current user's Run key.
+    pub fn with_location(mut self, location: StartupLocation) -> Self {
+        self.location = Some(location);
+        self
+    }
+
+    /// Replaces an entry of the same name instead of failing.
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Adds the entry. Returns the entry it replaced, if any.
+    pub fn execute(&self, name: &str, path: &str) -> Result<Option<StartupEntry>> {
         // Validate inputs
         StartupValidator::validate_name(name)?;
         StartupValidator::validate_path(path)?;
 
         // Create and add entry
-        let entry = StartupEntry::new(name, path);
+        let mut entry = StartupEntry::new(name, path);
+        if let Some(location) = self.location {
+            entry = entry.with_location(location);
+        }
+        let replaced = replaced_entry(self.repository, &entry, self.force)?;
         self.repository.add(&entry)?;
 
-        Ok(())
+        Ok(replaced)
+    }
+}
+
+/// Returns the entry adding `entry` replaces: the one of the same name in
+/// its location. Fails when an entry of that name exists and `force` isn't
+/// set, and always when it exists in another location, where adding would
+/// leave two entries of that name.
+pub(crate) fn replaced_entry<R: StartupRepository>(
+    repository: &R,
+    entry: &StartupEntry,
+    force: bool,
+) -> Result<Option<StartupEntry>> {
+    if !repository.exists(&entry.name)? {
+        return Ok(None);
+    }
+    if !force {
+        return Err(StartupError::EntryAlreadyExists(entry.name.clone()));
+    }
+    let (replaced, elsewhere): (Vec<_>, Vec<_>) = repository
+        .list()?
+        .into_iter()
+        .filter(|e| e.name.eq_ignore_ascii_case(&entry.name))
+        .partition(|e| e.location == entry.location);
+    if let Some(other) = elsewhere.first() {
+        return Err(StartupError::EntryInOtherLocation(
+            other.name.clone(),
+            other.location.to_string(),
+        ));
     }
+    Ok(replaced.into_iter().next())
 }
@@ -0,0 +1,167 @@
+use super::launcher_cleanup::release_launchers;
+use super::plan_manifest::{EntryChange, Plan, PlanField, PlannedChange};
+use crate::domain::{
+    DesiredEntry, LauncherStore, StartupApproval, StartupEntry, StartupRepository,
+};
+use crate::shared::error::Result;
+use chrono::Utc;
+
+/// A step that reverts part of an apply that failed.
+enum Undo {
+    /// Removes an entry that was added.
+    Remove(String),
+    /// Puts back an entry as it was before.
+    Restore(StartupEntry),
+    /// Deletes a launcher that was written.
+    DeleteLauncher(String),
+}
+
+/// Use case for applying a plan made from a manifest.
+/// This follows the Single Responsibility Principle.
+pub struct ApplyManifestUseCase<'a, R: StartupRepository, L: LauncherStore> {
+    repository: &'a R,
+    launchers: &'a L,
+}
+
+impl<'a, R: StartupRepository, L: LauncherStore> ApplyManifestUseCase<'a, R, L> {
+    pub fn new(repository: &'a R, launchers: &'a L) -> Self {
+        Self {
+            repository,
+            launchers,
+        }
+    }
+
+    /// Applies every change of `plan`, in order. When a change fails, the
+    /// changes already made are reverted before the error is returned.
+    /// Returns the launchers that were deleted.
+    pub fn execute(&self, plan: &Plan) -> Result<Vec<String>> {
+        let mut undo = Vec::new();
+        if let Err(e) = self.apply(plan, &mut undo) {
+            self.revert(undo);
+            return Err(e);
+        }
+
+        // Record the launchers of the new entries and delete the ones they replace
+        let mut manifest = self.launchers.load_manifest()?;
+        let mut replaced = Vec::new();
+        for change in &plan.changes {
+            let (current, desired) = match change {
+                PlannedChange::Add(desired) => (None, Some(desired)),
+                PlannedChange::Change(change) => (Some(&change.current), Some(&change.desired)),
+                PlannedChange::Remove(current) => (Some(current), None),
+            };
+            if let Some(current) = current {
+                replaced.extend(self.launchers.referenced_by(&current.command));
+            }
+            let name = change.name();
+            let launcher =
+                desired.and_then(|desired| desired.command.get_vbscript_content(&desired.name));
+            match launcher {
+                Some((filename, _)) => replaced.extend(manifest.record(name, &filename)),
+                None => replaced.extend(manifest.remove_entry(name)),
+            }
+        }
+        let deleted = release_launchers(
+            self.launchers,
+            &mut manifest,
+            replaced,
+            &self.repository.list()?,
+        )?;
+        self.launchers.save_manifest(&manifest)?;
+        Ok(deleted)
+    }
+
+    fn apply(&self, plan: &Plan, undo: &mut Vec<Undo>) -> Result<()> {
+        for change in &plan.changes {
+            match change {
+                PlannedChange::Add(desired) => {
+                    self.add(desired, undo)?;
+                    undo.push(Undo::Remove(desired.name.clone()));
+                    self.approve(desired)?;
+                }
+                PlannedChange::Change(change) => {
+                    let EntryChange {
+                        current,
+                        desired,
+                        fields,
+                        ..
+                    } = change.as_ref();
+                    undo.push(Undo::Restore(current.clone()));
+                    // Moving an entry removes it from its old location first
+                    let moved = fields.contains(&PlanField::Location);
+                    if moved {
+                        self.repository.remove(&current.name)?;
+                    }
+                    if fields != &[PlanField::Enabled] {
+                        self.add(desired, undo)?;
+                    }
+                    if moved {
+                        undo.push(Undo::Remove(desired.name.clone()));
+                    }
+                    self.approve(desired)?;
+                }
+                PlannedChange::Remove(current) => {
+                    self.repository.remove(&current.name)?;
+                    undo.push(Undo::Restore(current.clone()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the entry's launcher, if it has one, and stores the entry.
+    fn add(&self, desired: &DesiredEntry, undo: &mut Vec<Undo>) -> Result<()> {
+        // Launchers are named after their content, so an existing one is reused
+        if let Some((filename, content)) = desired.command.get_vbscript_content(&desired.name)
+            && self.launchers.read(&filename)?.is_none()
+        {
+            self.launchers.write(&filename, &content)?;
+            undo.push(Undo::DeleteLauncher(filename));
+        }
+        let value = desired
+            .command
+            .to_registry_value(&desired.name, self.launchers);
+        let entry = StartupEntry::new(&desired.name, value).with_location(desired.location);
+        self.repository.add(&entry)
+    }
+
+    /// Enables or disables the entry as the manifest wants it.
+    fn approve(&self, desired: &DesiredEntry) -> Result<()> {
+        let current = self
+            .repository
+            .list()?
+            .into_iter()
+            .find(|entry| entry.name.eq_ignore_ascii_case(&desired.name));
+        if current.is_some_and(|entry| entry.enabled == desired.enabled) {
+            return Ok(());
+        }
+        let approval = if desired.enabled {
+            StartupApproval::ENABLED
+        } else {
+            StartupApproval::disabled_since(Utc::now())
+        };
+        self.repository.set_approval(&desired.name, approval)
+    }
+
+    /// Reverts the steps of a failed apply, most recent first. Failures are
+    /// ignored so that as much as possible is put back.
+    fn revert(&self, undo: Vec<Undo>) {
+        for step in undo.into_iter().rev() {
+            match step {
+                Undo::Remove(name) => {
+                    self.repository.remove(&name).ok();
+                }
+                Undo::Restore(entry) => {
+                    if self.repository.add(&entry).is_ok() {
+                        self.repository
+                            .set_approval(&entry.name, entry.approval())
+                            .ok();
+                    }
+                }
+                Undo::DeleteLauncher(filename) => {
+                    self.launchers.delete(&filename).ok();
+                }
+            }
+        }
+    }
+}
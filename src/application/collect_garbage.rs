@@ -0,0 +1,54 @@
+use super::launcher_cleanup::referenced_launchers;
+use crate::domain::{LauncherStore, StartupRepository};
+use crate::shared::error::Result;
+
+/// Use case for deleting VBScript launchers that no startup entry runs.
+/// This follows the Single Responsibility Principle.
+pub struct CollectGarbageUseCase<'a, R: StartupRepository, L: LauncherStore> {
+    repository: &'a R,
+    launchers: &'a L,
+}
+
+impl<'a, R: StartupRepository, L: LauncherStore> CollectGarbageUseCase<'a, R, L> {
+    pub fn new(repository: &'a R, launchers: &'a L) -> Self {
+        Self {
+            repository,
+            launchers,
+        }
+    }
+
+    /// Finds the orphaned launchers and, unless `dry_run` is set, deletes them.
+    /// Returns the orphaned launchers.
+    pub fn execute(&self, dry_run: bool) -> Result<Vec<String>> {
+        let referenced = referenced_launchers(self.launchers, &self.repository.list()?);
+
+        let orphans: Vec<String> = self
+            .launchers
+            .list()?
+            .into_iter()
+            .filter(|filename| !referenced.contains(&filename.to_lowercase()))
+            .collect();
+        if dry_run {
+            return Ok(orphans);
+        }
+
+        let mut manifest = self.launchers.load_manifest()?;
+        for filename in &orphans {
+            self.launchers.delete(filename)?;
+            manifest.remove_launcher(filename);
+        }
+        // Forget launchers that were deleted by hand
+        let existing = self.launchers.list()?;
+        let missing: Vec<String> = manifest
+            .iter()
+            .map(|(filename, _)| filename.to_string())
+            .filter(|filename| !existing.contains(filename))
+            .collect();
+        for filename in missing {
+            manifest.remove_launcher(&filename);
+        }
+        self.launchers.save_manifest(&manifest)?;
+
+        Ok(orphans)
+    }
+}
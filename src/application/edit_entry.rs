@@ -0,0 +1,154 @@
+use super::launcher_cleanup::replace_launcher;
+use crate::domain::{
+    ConsoleOptions, ExecutionMode, LauncherStore, StartupCommand, StartupEntry, StartupLocation,
+    StartupRepository, StartupValidator,
+};
+use crate::shared::error::{Result, StartupError};
+
+/// The parts of an entry's command that `edit` replaces. Parts left `None`
+/// keep their current value.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EntryEdits {
+    pub command: Option<String>,
+    pub args: Option<Vec<String>>,
+    /// `Some(None)` clears the working directory.
+    pub workdir: Option<Option<String>>,
+    pub mode: Option<ExecutionMode>,
+}
+
+impl EntryEdits {
+    /// Returns `command` with the edits made. A bare executable becomes a
+    /// command with arguments once its arguments, working directory or mode
+    /// change; it has no working directory to clear.
+    pub fn apply(&self, command: StartupCommand) -> StartupCommand {
+        match command {
+            StartupCommand::Executable { path }
+                if self.args.is_none()
+                    && !matches!(self.workdir, Some(Some(_)))
+                    && self.mode.is_none() =>
+            {
+                StartupCommand::Executable {
+                    path: self.command.clone().unwrap_or(path),
+                }
+            }
+            // A plain command line starts the program like the bare path did
+            StartupCommand::Executable { path } => self.apply(StartupCommand::CommandWithArgs {
+                command: path,
+                args: Vec::new(),
+                workdir: None,
+                mode: ExecutionMode::Visible(ConsoleOptions::default()),
+            }),
+            StartupCommand::CommandWithArgs {
+                command,
+                args,
+                workdir,
+                mode,
+            } => StartupCommand::CommandWithArgs {
+                command: self.command.clone().unwrap_or(command),
+                args: self.args.clone().unwrap_or(args),
+                workdir: self.workdir.clone().unwrap_or(workdir),
+                mode: self.mode.unwrap_or(mode),
+            },
+        }
+    }
+}
+
+/// An entry before and after `edit` changed it, with the command it runs now.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditedEntry {
+    pub before: StartupEntry,
+    pub after: StartupEntry,
+    pub command: StartupCommand,
+}
+
+/// Use case for changing the command of an entry in place.
+/// This follows the Single Responsibility Principle.
+pub struct EditEntryUseCase<'a, R: StartupRepository, L: LauncherStore> {
+    repository: &'a R,
+    launchers: &'a L,
+}
+
+impl<'a, R: StartupRepository, L: LauncherStore> EditEntryUseCase<'a, R, L> {
+    pub fn new(repository: &'a R, launchers: &'a L) -> Self {
+        Self {
+            repository,
+            launchers,
+        }
+    }
+
+    /// Decodes the entry's value, makes the edits and stores the new value,
+    /// writing its launcher first. The entry keeps its location and enabled
+    /// state.
+    pub fn execute(&self, name: &str, edits: &EntryEdits) -> Result<EditedEntry> {
+        // Validate inputs
+        StartupValidator::validate_name(name)?;
+        if let Some(Some(dir)) = &edits.workdir {
+            StartupValidator::validate_directory(dir)?;
+        }
+        if let Some(ExecutionMode::ScheduledTask(settings)) = &edits.mode {
+            StartupValidator::validate_task_settings(settings)?;
+        }
+
+        let entry = find_entry(self.repository, name)?;
+        let current = self.launchers.decode(&entry.command).ok_or_else(|| {
+            StartupError::Unsupported(format!(
+                "the value of entry '{}' cannot be decoded",
+                entry.name
+            ))
+        })?;
+        let command = edits.apply(current);
+        if let (StartupCommand::Executable { path }, Some(_)) = (&command, &edits.command) {
+            StartupValidator::validate_path(path)?;
+        }
+        // Tasks live in Task Scheduler, so the mode can't make an entry one
+        let is_task = matches!(command.mode(), Some(ExecutionMode::ScheduledTask(_)));
+        if is_task != (entry.location == StartupLocation::TaskScheduler) {
+            return Err(StartupError::Unsupported(format!(
+                "entry '{}' is stored in {}; remove it and add it again to switch to or from scheduled-task mode",
+                entry.name, entry.location
+            )));
+        }
+
+        let after = StartupEntry {
+            command: command.to_registry_value(&entry.name, self.launchers),
+            ..entry.clone()
+        };
+        if after.command != entry.command {
+            let previous_launcher = self.launchers.referenced_by(&entry.command);
+            let launcher = command.get_vbscript_content(&entry.name);
+            if let Some((filename, content)) = &launcher {
+                self.launchers.write(filename, content)?;
+            }
+
+            self.repository.update(&after)?;
+
+            // Record the new launcher and delete the one it replaces
+            replace_launcher(
+                self.repository,
+                self.launchers,
+                &entry.name,
+                launcher.as_ref().map(|(filename, _)| filename.as_str()),
+                previous_launcher,
+            )?;
+        }
+
+        Ok(EditedEntry {
+            before: entry,
+            after,
+            command,
+        })
+    }
+}
+
+/// Returns the single entry named `name`, ignoring case.
+pub(crate) fn find_entry<R: StartupRepository>(repository: &R, name: &str) -> Result<StartupEntry> {
+    let mut matching = repository
+        .list()?
+        .into_iter()
+        .filter(|entry| entry.name.eq_ignore_ascii_case(name));
+    match (matching.next(), matching.next()) {
+        (None, _) => Err(StartupError::EntryNotFound(name.to_string())),
+        (Some(entry), None) => Ok(entry),
+        _ => Err(StartupError::AmbiguousEntry(name.to_string())),
+    }
+}
@@ -0,0 +1,31 @@
+use crate::domain::{BackupEntry, LauncherStore, StartupBackup, StartupRepository};
+use crate::shared::error::Result;
+use chrono::Utc;
+
+/// Use case for exporting every startup entry to a backup.
+/// This follows the Single Responsibility Principle.
+pub struct ExportEntriesUseCase<'a, R: StartupRepository, L: LauncherStore> {
+    repository: &'a R,
+    launchers: &'a L,
+}
+
+impl<'a, R: StartupRepository, L: LauncherStore> ExportEntriesUseCase<'a, R, L> {
+    pub fn new(repository: &'a R, launchers: &'a L) -> Self {
+        Self {
+            repository,
+            launchers,
+        }
+    }
+
+    /// Describes every entry with its decoded command, enabled state and
+    /// the launcher it runs.
+    pub fn execute(&self) -> Result<StartupBackup> {
+        let mut backup = StartupBackup::new(Utc::now());
+        for entry in self.repository.list()? {
+            backup
+                .entries
+                .push(BackupEntry::export(&entry, self.launchers)?);
+        }
+        Ok(backup)
+    }
+}
@@ -0,0 +1,290 @@
+use super::add_command::AddCommandUseCase;
+use super::add_executable::AddExecutableUseCase;
+use super::remove_entry::RemoveEntryUseCase;
+use super::set_enabled::SetEnabledUseCase;
+use super::undo_changes::revert_changes;
+use crate::domain::{
+    BackupEntry, DesiredEntry, EntrySnapshot, LauncherStore, SnapshotChange, StartupApproval,
+    StartupBackup, StartupCommand, StartupEntry, StartupRepository,
+};
+use crate::shared::error::{Result, StartupError};
+use chrono::Utc;
+
+/// What `import` does with an entry whose name is already used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConflictStrategy {
+    /// Keeps the existing entry and leaves the imported one out.
+    #[default]
+    Skip,
+    /// Replaces the existing entry with the imported one.
+    Overwrite,
+    /// Imports the entry under a free name, like "Name (2)".
+    Rename,
+}
+
+/// What importing one entry of a backup did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportOutcome {
+    Added(String),
+    Replaced(String),
+    Renamed {
+        from: String,
+        to: String,
+    },
+    Skipped(String),
+    /// A .reg file deleted the entry.
+    Removed(String),
+}
+
+/// An entry to import, with the existing entry it replaces.
+struct ImportStep<'b> {
+    exported: &'b BackupEntry,
+    desired: DesiredEntry,
+    replaced: Option<StartupEntry>,
+    outcome: ImportOutcome,
+}
+
+/// Use case for restoring the entries of a backup.
+/// This follows the Single Responsibility Principle.
+pub struct ImportEntriesUseCase<'a, R: StartupRepository, L: LauncherStore> {
+    repository: &'a R,
+    launchers: &'a L,
+}
+
+impl<'a, R: StartupRepository, L: LauncherStore> ImportEntriesUseCase<'a, R, L> {
+    pub fn new(repository: &'a R, launchers: &'a L) -> Self {
+        Self {
+            repository,
+            launchers,
+        }
+    }
+
+    /// Adds the entries of `backup` like `add` and `add-command` do, then
+    /// enables or disables them as they were exported. Entries whose name
+    /// is already used are handled by `strategy`.
+    ///
+    /// Every entry is checked first: when a command can't be decoded, an
+    /// executable or working directory is missing, a name is ambiguous or a
+    /// disabled entry goes to a location that can't record it, nothing is
+    /// written and all the problems are reported at once. When writing an
+    /// entry still fails, the entries imported before it are taken back.
+    pub fn execute(
+        &self,
+        backup: &StartupBackup,
+        strategy: ConflictStrategy,
+    ) -> Result<Vec<ImportOutcome>> {
+        let current = self.repository.list()?;
+        let steps = self.plan(backup, &current, strategy)?;
+
+        let mut written = Vec::new();
+        let mut outcomes = Vec::new();
+        for step in steps {
+            if !matches!(step.outcome, ImportOutcome::Skipped(_)) {
+                let replaced = step
+                    .replaced
+                    .as_ref()
+                    .map(|entry| EntrySnapshot::capture(entry, self.launchers))
+                    .transpose()?;
+                let result = self.import(&step);
+                written.extend(self.changes(&step, replaced)?);
+                if let Err(e) = result {
+                    // The failure that stopped the import explains more than
+                    // a failed rollback would
+                    revert_changes(self.repository, self.launchers, &written).ok();
+                    return Err(e);
+                }
+            }
+            outcomes.push(step.outcome);
+        }
+        Ok(outcomes)
+    }
+
+    /// Returns how importing `step` changed the entries, given the entry it
+    /// replaced as it was before, so they can be put back.
+    fn changes(
+        &self,
+        step: &ImportStep,
+        replaced: Option<EntrySnapshot>,
+    ) -> Result<Vec<SnapshotChange>> {
+        let desired = &step.desired;
+        let stored = self.repository.list()?.into_iter().find(|entry| {
+            entry.location == desired.location && entry.name.eq_ignore_ascii_case(&desired.name)
+        });
+        let after = stored
+            .map(|entry| EntrySnapshot::capture(&entry, self.launchers))
+            .transpose()?;
+
+        let mut changes = Vec::new();
+        let (before, after) = match replaced {
+            Some(replaced) if replaced.location == desired.location => (Some(replaced), after),
+            // Putting back an entry of another location comes after taking
+            // the imported one out
+            Some(replaced) => {
+                changes.push(SnapshotChange {
+                    before: Some(replaced),
+                    after: None,
+                });
+                (None, after)
+            }
+            None => (None, after),
+        };
+        if before.is_some() || after.is_some() {
+            changes.push(SnapshotChange { before, after });
+        }
+        Ok(changes)
+    }
+
+    /// Validates every entry and resolves name conflicts, without writing.
+    fn plan<'b>(
+        &self,
+        backup: &'b StartupBackup,
+        current: &[StartupEntry],
+        strategy: ConflictStrategy,
+    ) -> Result<Vec<ImportStep<'b>>> {
+        let mut problems = Vec::new();
+        let mut entries: Vec<(&BackupEntry, DesiredEntry)> = Vec::new();
+        for exported in &backup.entries {
+            let name = &exported.entry.name;
+            if !exported.is_decoded() {
+                problems.push(format!(
+                    "'{}': its command could not be decoded from '{}'",
+                    name, exported.value
+                ));
+                continue;
+            }
+            let desired = match exported.entry.to_desired() {
+                Ok(desired) => desired,
+                Err(e) => {
+                    problems.push(e.to_string());
+                    continue;
+                }
+            };
+            if entries
+                .iter()
+                .any(|(_, other)| other.name.eq_ignore_ascii_case(name))
+            {
+                problems.push(format!("'{}': listed more than once", name));
+                continue;
+            }
+            if let Err(e) = desired.validate() {
+                problems.push(format!("'{}': {}", name, e));
+            }
+            if !desired.enabled && !self.repository.supports_approval(desired.location) {
+                problems.push(format!(
+                    "'{}': entries in {} cannot be disabled",
+                    name, desired.location
+                ));
+            }
+            // Overwriting removes the existing entry by name
+            if strategy == ConflictStrategy::Overwrite && matching(current, name).len() > 1 {
+                problems.push(format!(
+                    "'{}': {}",
+                    name,
+                    StartupError::AmbiguousEntry(name.clone())
+                ));
+            }
+            entries.push((exported, desired));
+        }
+        if !problems.is_empty() {
+            return Err(StartupError::ImportRejected(problems));
+        }
+
+        // Renamed entries avoid the existing names and the imported ones
+        let mut taken: Vec<String> = current
+            .iter()
+            .map(|entry| entry.name.clone())
+            .chain(entries.iter().map(|(_, desired)| desired.name.clone()))
+            .collect();
+        let mut steps = Vec::new();
+        for (exported, mut desired) in entries {
+            let existing = matching(current, &desired.name);
+            let name = desired.name.clone();
+            let (replaced, outcome) = match (existing.first(), strategy) {
+                (None, _) => (None, ImportOutcome::Added(name)),
+                (Some(_), ConflictStrategy::Skip) => (None, ImportOutcome::Skipped(name)),
+                (Some(existing), ConflictStrategy::Overwrite) => {
+                    (Some((*existing).clone()), ImportOutcome::Replaced(name))
+                }
+                (Some(_), ConflictStrategy::Rename) => {
+                    let renamed = free_name(&name, &taken);
+                    taken.push(renamed.clone());
+                    desired.name = renamed.clone();
+                    (
+                        None,
+                        ImportOutcome::Renamed {
+                            from: name,
+                            to: renamed,
+                        },
+                    )
+                }
+            };
+            steps.push(ImportStep {
+                exported,
+                desired,
+                replaced,
+                outcome,
+            });
+        }
+        Ok(steps)
+    }
+
+    /// Adds one entry through the `add` or `add-command` use case.
+    fn import(&self, step: &ImportStep) -> Result<()> {
+        let desired = &step.desired;
+        // Adding replaces an entry in the same location only
+        if let Some(replaced) = &step.replaced
+            && replaced.location != desired.location
+        {
+            RemoveEntryUseCase::new(self.repository, self.launchers).execute(&replaced.name)?;
+        }
+
+        // The conflict strategy already decided to replace the entry
+        match &desired.command {
+            StartupCommand::Executable { path } => AddExecutableUseCase::new(self.repository)
+                .with_location(desired.location)
+                .with_force(true)
+                .execute(&desired.name, path)?,
+            StartupCommand::CommandWithArgs {
+                command,
+                args,
+                workdir,
+                mode,
+            } => AddCommandUseCase::new(self.repository, self.launchers)
+                .with_location(desired.location)
+                .with_force(true)
+                .execute(
+                    &desired.name,
+                    command,
+                    args.clone(),
+                    workdir.as_deref(),
+                    *mode,
+                )?,
+        };
+
+        if desired.enabled {
+            SetEnabledUseCase::new(self.repository).execute(&desired.name, true)?;
+        } else {
+            // Keep the time the entry was disabled on the exporting machine
+            let since = step.exported.disabled_at.unwrap_or_else(Utc::now);
+            self.repository
+                .set_approval(&desired.name, StartupApproval::disabled_since(since))?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the entries named `name`, ignoring case.
+pub(super) fn matching<'c>(current: &'c [StartupEntry], name: &str) -> Vec<&'c StartupEntry> {
+    current
+        .iter()
+        .filter(|entry| entry.name.eq_ignore_ascii_case(name))
+        .collect()
+}
+
+/// Returns "`name` (2)", or the first higher number that isn't taken.
+pub(super) fn free_name(name: &str, taken: &[String]) -> String {
+    (2..)
+        .map(|n| format!("{} ({})", name, n))
+        .find(|candidate| !taken.iter().any(|t| t.eq_ignore_ascii_case(candidate)))
+        .unwrap_or_else(|| name.to_string())
+}
@@ -0,0 +1,166 @@
+use super::import_entries::{ConflictStrategy, ImportOutcome, free_name, matching};
+use super::remove_entry::RemoveEntryUseCase;
+use crate::domain::{LauncherStore, RegChange, StartupEntry, StartupRepository, StartupValidator};
+use crate::shared::error::{Result, StartupError};
+
+/// Use case for applying the Run-key values of a .reg file.
+/// This follows the Single Responsibility Principle.
+pub struct ImportRegFileUseCase<'a, R: StartupRepository, L: LauncherStore> {
+    repository: &'a R,
+    launchers: &'a L,
+}
+
+impl<'a, R: StartupRepository, L: LauncherStore> ImportRegFileUseCase<'a, R, L> {
+    pub fn new(repository: &'a R, launchers: &'a L) -> Self {
+        Self {
+            repository,
+            launchers,
+        }
+    }
+
+    /// Stores the values a .reg file sets as they are written, with the
+    /// enabled state it gives them, and removes the entries it deletes.
+    /// Entries whose name is already used are handled by `strategy`.
+    ///
+    /// Every change is checked first: when a name is empty or ambiguous or
+    /// a value runs a launcher missing from this machine, nothing is
+    /// written and all the problems are reported at once.
+    pub fn execute(
+        &self,
+        changes: &[RegChange],
+        strategy: ConflictStrategy,
+    ) -> Result<Vec<ImportOutcome>> {
+        let current = self.repository.list()?;
+        self.validate(changes, &current, strategy)?;
+
+        let mut taken: Vec<String> = current
+            .iter()
+            .map(|entry| entry.name.clone())
+            .chain(changes.iter().filter_map(|change| match change {
+                RegChange::Set(entry) => Some(entry.name.clone()),
+                RegChange::Delete { .. } => None,
+            }))
+            .collect();
+        let mut outcomes = Vec::new();
+        for change in changes {
+            match change {
+                RegChange::Set(entry) => {
+                    let existing = matching(&current, &entry.name).first().copied();
+                    let outcome = match (existing, strategy) {
+                        (None, _) => {
+                            self.store(entry, None)?;
+                            ImportOutcome::Added(entry.name.clone())
+                        }
+                        (Some(_), ConflictStrategy::Skip) => {
+                            ImportOutcome::Skipped(entry.name.clone())
+                        }
+                        (Some(existing), ConflictStrategy::Overwrite) => {
+                            self.store(entry, Some(existing))?;
+                            ImportOutcome::Replaced(entry.name.clone())
+                        }
+                        (Some(_), ConflictStrategy::Rename) => {
+                            let renamed = free_name(&entry.name, &taken);
+                            taken.push(renamed.clone());
+                            let mut copy = entry.clone();
+                            copy.name = renamed.clone();
+                            self.store(&copy, None)?;
+                            ImportOutcome::Renamed {
+                                from: entry.name.clone(),
+                                to: renamed,
+                            }
+                        }
+                    };
+                    outcomes.push(outcome);
+                }
+                RegChange::Delete { name, location } => {
+                    // Like regedit, deleting a missing value does nothing
+                    let existing = current
+                        .iter()
+                        .find(|e| e.location == *location && e.name.eq_ignore_ascii_case(name));
+                    if let Some(existing) = existing {
+                        RemoveEntryUseCase::new(self.repository, self.launchers)
+                            .execute(&existing.name)?;
+                        outcomes.push(ImportOutcome::Removed(existing.name.clone()));
+                    }
+                }
+            }
+        }
+        Ok(outcomes)
+    }
+
+    fn validate(
+        &self,
+        changes: &[RegChange],
+        current: &[StartupEntry],
+        strategy: ConflictStrategy,
+    ) -> Result<()> {
+        let mut problems = Vec::new();
+        let mut seen: Vec<&str> = Vec::new();
+        for change in changes {
+            let name = change_name(change);
+            if let Err(e) = StartupValidator::validate_name(name) {
+                problems.push(format!("'{}': {}", name, e));
+                continue;
+            }
+            // Entries are replaced and removed by name
+            let removes_by_name = match change {
+                RegChange::Set(_) => strategy == ConflictStrategy::Overwrite,
+                RegChange::Delete { location, .. } => matching(current, name)
+                    .iter()
+                    .any(|entry| entry.location == *location),
+            };
+            if removes_by_name && matching(current, name).len() > 1 {
+                problems.push(format!(
+                    "'{}': {}",
+                    name,
+                    StartupError::AmbiguousEntry(name.to_string())
+                ));
+            }
+            if let RegChange::Set(entry) = change {
+                if seen.iter().any(|other| other.eq_ignore_ascii_case(name)) {
+                    problems.push(format!("'{}': set more than once", name));
+                }
+                seen.push(name);
+                if let Some(launcher) = self.launchers.referenced_by(&entry.command)
+                    && self.launchers.read(&launcher)?.is_none()
+                {
+                    problems.push(format!(
+                        "'{}': runs the launcher {}, which doesn't exist on this machine",
+                        name, launcher
+                    ));
+                }
+            }
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(StartupError::ImportRejected(problems))
+        }
+    }
+
+    /// Stores `entry`, replacing `existing`, and gives it the file's state.
+    fn store(&self, entry: &StartupEntry, existing: Option<&StartupEntry>) -> Result<()> {
+        // Adding replaces an entry in the same location only
+        if let Some(existing) = existing
+            && existing.location != entry.location
+        {
+            RemoveEntryUseCase::new(self.repository, self.launchers).execute(&existing.name)?;
+        }
+        self.repository.add(entry)?;
+
+        // Entries the file doesn't disable are enabled, as in the file
+        if entry.location.startup_approved_key().is_some() && (!entry.enabled || existing.is_some())
+        {
+            self.repository
+                .set_approval(&entry.name, entry.approval())?;
+        }
+        Ok(())
+    }
+}
+
+fn change_name(change: &RegChange) -> &str {
+    match change {
+        RegChange::Set(entry) => &entry.name,
+        RegChange::Delete { name, .. } => name,
+    }
+}
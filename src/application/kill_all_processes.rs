@@ -1,44 +1,59 @@
-use crate::domain::StartupRepository;
-use crate::infrastructure::ProcessManager;
+use super::kill_process::entry_matcher;
+use super::process_terminator::{KillOptions, ProcessKillResult, ProcessTerminator};
+use crate::domain::{MatchStrategy, ProcessProvider, StartupRepository, extract_executable_name};
 use crate::shared::error::Result;
 use std::collections::HashSet;
 
 /// Use case for killing all processes associated with startup entries.
-pub struct KillAllProcessesUseCase<'a, R: StartupRepository> {
+pub struct KillAllProcessesUseCase<'a, R: StartupRepository, P: ProcessProvider> {
     repository: &'a R,
+    processes: &'a P,
 }
 
-impl<'a, R: StartupRepository> KillAllProcessesUseCase<'a, R> {
-    pub fn new(repository: &'a R) -> Self {
-        Self { repository }
+impl<'a, R: StartupRepository, P: ProcessProvider> KillAllProcessesUseCase<'a, R, P> {
+    pub fn new(repository: &'a R, processes: &'a P) -> Self {
+        Self {
+            repository,
+            processes,
+        }
     }
 
-    pub fn execute(&self) -> Result<Vec<(String, u32)>> {
+    /// Stops the processes of every entry, including their children.
+    /// Results are grouped by entry name, or by executable name when
+    /// matching by image name.
+    pub fn execute(&self, options: &KillOptions) -> Result<Vec<(String, Vec<ProcessKillResult>)>> {
         // Get all entries from repository
         let entries = self.repository.list()?;
+        let running = self.processes.list_processes()?;
+        let terminator = ProcessTerminator::new(self.processes, options.graceful_timeout);
 
-        // Extract unique executable names
-        let mut exe_names = HashSet::new();
+        let mut results: Vec<(String, Vec<ProcessKillResult>)> = Vec::new();
+        let mut seen = HashSet::new();
         for entry in &entries {
-            if let Some(exe_name) = ProcessManager::extract_executable_name(&entry.command) {
-                exe_names.insert(exe_name);
-            }
-        }
-
-        // Kill processes for each executable
-        let mut results = Vec::new();
-        for exe_name in exe_names {
-            match ProcessManager::kill_processes_by_name(&exe_name) {
-                Ok(count) => {
-                    if count > 0 {
-                        results.push((exe_name, count));
-                    }
-                }
-                Err(_) => {
-                    // Continue even if one fails
-                    continue;
+            // Entries whose process can't be identified are skipped
+            let Ok(matcher) = entry_matcher(entry, options.strategy) else {
+                continue;
+            };
+            let label = match options.strategy {
+                MatchStrategy::CommandLine => entry.name.clone(),
+                MatchStrategy::ImageName => {
+                    extract_executable_name(&entry.command).unwrap_or_default()
                 }
+            };
+
+            // Entries sharing an executable must not stop the same process twice
+            let roots: Vec<_> = matcher
+                .find(&running)
+                .into_iter()
+                .filter(|p| seen.insert(p.pid))
+                .collect();
+            if roots.is_empty() {
+                continue;
             }
+
+            let stopped = terminator.terminate(&roots, &running);
+            seen.extend(stopped.iter().map(|r| r.pid));
+            results.push((label, stopped));
         }
 
         Ok(results)
@@ -1,39 +1,61 @@
-use crate::domain::{StartupRepository, StartupValidator};
-use crate::infrastructure::ProcessManager;
+use super::edit_entry::find_entry;
+use super::process_terminator::{KillOptions, ProcessKillResult, ProcessTerminator};
+use crate::domain::{
+    MatchStrategy, ProcessMatcher, ProcessProvider, StartupEntry, StartupRepository,
+    StartupValidator, extract_executable_name,
+};
 use crate::shared::error::{Result, StartupError};
 
 /// Use case for killing a process associated with a startup entry.
-pub struct KillProcessUseCase<'a, R: StartupRepository> {
+pub struct KillProcessUseCase<'a, R: StartupRepository, P: ProcessProvider> {
     repository: &'a R,
+    processes: &'a P,
 }
 
-impl<'a, R: StartupRepository> KillProcessUseCase<'a, R> {
-    pub fn new(repository: &'a R) -> Self {
-        Self { repository }
+impl<'a, R: StartupRepository, P: ProcessProvider> KillProcessUseCase<'a, R, P> {
+    pub fn new(repository: &'a R, processes: &'a P) -> Self {
+        Self {
+            repository,
+            processes,
+        }
     }
 
-    pub fn execute(&self, name: &str) -> Result<u32> {
+    /// Stops the processes launched by the entry, including their children.
+    /// Returns one result per process in the affected trees.
+    pub fn execute(&self, name: &str, options: &KillOptions) -> Result<Vec<ProcessKillResult>> {
         // Validate input
         StartupValidator::validate_name(name)?;
 
         // Get the entry from repository
-        let entries = self.repository.list()?;
-        let entry = entries.iter().find(|e| e.name == name).ok_or_else(|| {
-            StartupError::RegistryError(format!("Entry '{}' not found in startup registry", name))
-        })?;
-
-        // Extract executable name from command
-        let exe_name =
-            ProcessManager::extract_executable_name(&entry.command).ok_or_else(|| {
-                StartupError::RegistryError(format!(
-                    "Could not determine executable name from command: {}",
-                    entry.command
-                ))
-            })?;
-
-        // Kill all processes with that name
-        let count = ProcessManager::kill_processes_by_name(&exe_name)?;
-
-        Ok(count)
+        let entry = find_entry(self.repository, name)?;
+
+        let matcher = entry_matcher(&entry, options.strategy)?;
+
+        // Stop only the processes attributed to the entry, with their children
+        let running = self.processes.list_processes()?;
+        let roots = matcher.find(&running);
+        let terminator = ProcessTerminator::new(self.processes, options.graceful_timeout);
+
+        Ok(terminator.terminate(&roots, &running))
     }
 }
+
+/// Builds the process matcher for an entry using the requested strategy.
+pub(crate) fn entry_matcher(
+    entry: &StartupEntry,
+    strategy: MatchStrategy,
+) -> Result<ProcessMatcher> {
+    let matcher = match strategy {
+        MatchStrategy::CommandLine => ProcessMatcher::for_entry(entry),
+        MatchStrategy::ImageName => {
+            extract_executable_name(&entry.command).map(ProcessMatcher::by_image_name)
+        }
+    };
+
+    matcher.ok_or_else(|| {
+        StartupError::ProcessError(format!(
+            "Could not determine the process started by command: {}",
+            entry.command
+        ))
+    })
+}
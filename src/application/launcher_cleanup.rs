@@ -0,0 +1,61 @@
+use crate::domain::{LauncherManifest, LauncherStore, StartupEntry, StartupRepository};
+use crate::shared::error::Result;
+use std::collections::HashSet;
+
+/// Returns the lowercase file names of the launchers `entries` run.
+pub(crate) fn referenced_launchers<L: LauncherStore>(
+    launchers: &L,
+    entries: &[StartupEntry],
+) -> HashSet<String> {
+    entries
+        .iter()
+        .filter_map(|entry| launchers.referenced_by(&entry.command))
+        .map(|filename| filename.to_lowercase())
+        .collect()
+}
+
+/// Deletes the `candidates` that none of `entries` runs and forgets them in
+/// the manifest. Returns the launchers that were deleted.
+pub(crate) fn release_launchers<L: LauncherStore>(
+    launchers: &L,
+    manifest: &mut LauncherManifest,
+    candidates: impl IntoIterator<Item = String>,
+    entries: &[StartupEntry],
+) -> Result<Vec<String>> {
+    let referenced = referenced_launchers(launchers, entries);
+    let mut deleted = Vec::new();
+    for filename in candidates {
+        if referenced.contains(&filename.to_lowercase()) || deleted.contains(&filename) {
+            continue;
+        }
+        manifest.remove_launcher(&filename);
+        if launchers.delete(&filename)? {
+            deleted.push(filename);
+        }
+    }
+    Ok(deleted)
+}
+
+/// Records `launcher` as the launcher of the entry `name` and deletes the
+/// launchers it replaces, `previous` included, once no entry runs them.
+/// Returns the launchers that were deleted.
+pub(crate) fn replace_launcher<R: StartupRepository, L: LauncherStore>(
+    repository: &R,
+    launchers: &L,
+    name: &str,
+    launcher: Option<&str>,
+    previous: Option<String>,
+) -> Result<Vec<String>> {
+    if launcher.is_none() && previous.is_none() {
+        return Ok(Vec::new());
+    }
+    let mut manifest = launchers.load_manifest()?;
+    let mut replaced: Vec<String> = previous.into_iter().collect();
+    match launcher {
+        Some(filename) => replaced.extend(manifest.record(name, filename)),
+        None => replaced.extend(manifest.remove_entry(name)),
+    }
+    let deleted = release_launchers(launchers, &mut manifest, replaced, &repository.list()?)?;
+    launchers.save_manifest(&manifest)?;
+    Ok(deleted)
+}
@@ -0,0 +1,27 @@
+use crate::domain::{AuditEvent, AuditFilter, AuditLog};
+use crate::shared::error::Result;
+
+/// Use case for querying the audit log.
+/// This follows the Single Responsibility Principle.
+pub struct ListAuditEventsUseCase<'a, A: AuditLog> {
+    log: &'a A,
+}
+
+impl<'a, A: AuditLog> ListAuditEventsUseCase<'a, A> {
+    pub fn new(log: &'a A) -> Self {
+        Self { log }
+    }
+
+    /// Returns the last `limit` events matching `filter`, oldest first.
+    pub fn execute(&self, filter: &AuditFilter, limit: usize) -> Result<Vec<AuditEvent>> {
+        let mut events: Vec<AuditEvent> = self
+            .log
+            .read()?
+            .into_iter()
+            .filter(|event| filter.matches(event))
+            .collect();
+        let excess = events.len().saturating_sub(limit);
+        events.drain(..excess);
+        Ok(events)
+    }
+}
@@ -0,0 +1,26 @@
+use crate::domain::{HistoryRecord, HistoryStore};
+use crate::shared::error::Result;
+
+/// Use case for listing the recorded changes.
+/// This follows the Single Responsibility Principle.
+pub struct ListHistoryUseCase<'a, H: HistoryStore> {
+    history: &'a H,
+}
+
+impl<'a, H: HistoryStore> ListHistoryUseCase<'a, H> {
+    pub fn new(history: &'a H) -> Self {
+        Self { history }
+    }
+
+    /// Returns the last `limit` records, most recent first.
+    pub fn execute(&self, limit: usize) -> Result<Vec<HistoryRecord>> {
+        Ok(self
+            .history
+            .load()?
+            .records
+            .into_iter()
+            .rev()
+            .take(limit)
+            .collect())
+    }
+}
@@ -1,13 +1,44 @@
 pub mod add_command;
 pub mod add_executable;
+pub mod apply_manifest;
+pub mod collect_garbage;
+pub mod edit_entry;
+pub mod export_entries;
+pub mod import_entries;
+pub mod import_reg_file;
 pub mod kill_all_processes;
 pub mod kill_process;
+mod launcher_cleanup;
+pub mod list_audit_events;
 pub mod list_entries;
+pub mod list_history;
+pub mod plan_manifest;
+pub mod process_terminator;
+pub mod record_audit;
+pub mod record_history;
 pub mod remove_entry;
+pub mod rename_entry;
+pub mod set_enabled;
+pub mod undo_changes;
 
 pub use add_command::AddCommandUseCase;
 pub use add_executable::AddExecutableUseCase;
+pub use apply_manifest::ApplyManifestUseCase;
+pub use collect_garbage::CollectGarbageUseCase;
+pub use edit_entry::{EditEntryUseCase, EditedEntry, EntryEdits};
+pub use export_entries::ExportEntriesUseCase;
+pub use import_entries::{ConflictStrategy, ImportEntriesUseCase, ImportOutcome};
+pub use import_reg_file::ImportRegFileUseCase;
 pub use kill_all_processes::KillAllProcessesUseCase;
 pub use kill_process::KillProcessUseCase;
+pub use list_audit_events::ListAuditEventsUseCase;
 pub use list_entries::ListEntriesUseCase;
+pub use list_history::ListHistoryUseCase;
+pub use plan_manifest::{EntryChange, Plan, PlanField, PlanManifestUseCase, PlannedChange};
+pub use process_terminator::{KillOptions, KillOutcome, ProcessKillResult, ProcessTerminator};
+pub use record_audit::RecordAuditUseCase;
+pub use record_history::RecordHistoryUseCase;
 pub use remove_entry::RemoveEntryUseCase;
+pub use rename_entry::RenameEntryUseCase;
+pub use set_enabled::SetEnabledUseCase;
+pub use undo_changes::UndoChangesUseCase;
@@ -0,0 +1,176 @@
+use crate::domain::{
+    DesiredEntry, LauncherStore, StartupCommand, StartupEntry, StartupLocation, StartupManifest,
+    StartupRepository,
+};
+use crate::shared::error::{Result, StartupError};
+
+/// A property of an entry that differs from the manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanField {
+    Command,
+    WorkingDirectory,
+    Mode,
+    Location,
+    Enabled,
+}
+
+/// One step bringing the startup entries in line with a manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlannedChange {
+    /// The manifest entry doesn't exist yet.
+    Add(DesiredEntry),
+    /// The entry exists but differs from the manifest.
+    Change(Box<EntryChange>),
+    /// The entry isn't in the manifest and is pruned.
+    Remove(StartupEntry),
+}
+
+/// An existing entry that differs from the manifest in `fields`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryChange {
+    pub current: StartupEntry,
+    /// What the current entry runs, when it can be decoded.
+    pub decoded: Option<StartupCommand>,
+    pub desired: DesiredEntry,
+    pub fields: Vec<PlanField>,
+}
+
+impl PlannedChange {
+    /// Returns the name of the entry the change applies to.
+    pub fn name(&self) -> &str {
+        match self {
+            PlannedChange::Add(desired) => &desired.name,
+            PlannedChange::Change(change) => &change.desired.name,
+            PlannedChange::Remove(current) => &current.name,
+        }
+    }
+}
+
+/// The changes applying a manifest makes, in the order they are applied.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Plan {
+    pub changes: Vec<PlannedChange>,
+}
+
+impl Plan {
+    /// Returns whether the entries already match the manifest.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Counts the additions, changes and removals.
+    pub fn counts(&self) -> (usize, usize, usize) {
+        self.changes
+            .iter()
+            .fold((0, 0, 0), |(add, change, remove), c| match c {
+                PlannedChange::Add(_) => (add + 1, change, remove),
+                PlannedChange::Change(_) => (add, change + 1, remove),
+                PlannedChange::Remove(_) => (add, change, remove + 1),
+            })
+    }
+}
+
+/// Use case for comparing a manifest with the startup entries.
+/// This follows the Single Responsibility Principle.
+pub struct PlanManifestUseCase<'a, R: StartupRepository, L: LauncherStore> {
+    repository: &'a R,
+    launchers: &'a L,
+}
+
+impl<'a, R: StartupRepository, L: LauncherStore> PlanManifestUseCase<'a, R, L> {
+    pub fn new(repository: &'a R, launchers: &'a L) -> Self {
+        Self {
+            repository,
+            launchers,
+        }
+    }
+
+    /// Lists the changes that make the entries match `manifest`: entries it
+    /// lacks are added and entries that differ are changed. With `prune`,
+    /// entries it doesn't list are removed from the locations it uses.
+    pub fn execute(&self, manifest: &StartupManifest, prune: bool) -> Result<Plan> {
+        let desired = manifest.desired_entries()?;
+        for entry in &desired {
+            entry.validate()?;
+        }
+
+        let current = self.repository.list()?;
+        let mut changes = Vec::new();
+        for entry in &desired {
+            let matching: Vec<_> = current
+                .iter()
+                .filter(|c| c.name.eq_ignore_ascii_case(&entry.name))
+                .collect();
+            match matching.as_slice() {
+                [] => changes.push(PlannedChange::Add(entry.clone())),
+                [existing] => {
+                    let decoded = self.launchers.decode(&existing.command);
+                    let fields = Self::differences(existing, decoded.as_ref(), entry);
+                    if !fields.is_empty() {
+                        changes.push(PlannedChange::Change(Box::new(EntryChange {
+                            current: (*existing).clone(),
+                            decoded,
+                            desired: entry.clone(),
+                            fields,
+                        })));
+                    }
+                }
+                _ => return Err(StartupError::AmbiguousEntry(entry.name.clone())),
+            }
+        }
+
+        if prune {
+            let managed: Vec<StartupLocation> = desired.iter().map(|e| e.location).collect();
+            for entry in current.iter().filter(|c| managed.contains(&c.location)) {
+                if desired
+                    .iter()
+                    .any(|d| d.name.eq_ignore_ascii_case(&entry.name))
+                {
+                    continue;
+                }
+                // Entries are removed by name, which must be unambiguous
+                if current
+                    .iter()
+                    .filter(|c| c.name.eq_ignore_ascii_case(&entry.name))
+                    .count()
+                    > 1
+                {
+                    return Err(StartupError::AmbiguousEntry(entry.name.clone()));
+                }
+                changes.push(PlannedChange::Remove(entry.clone()));
+            }
+        }
+
+        Ok(Plan { changes })
+    }
+
+    /// Returns the properties of `current` that differ from `desired`.
+    fn differences(
+        current: &StartupEntry,
+        decoded: Option<&StartupCommand>,
+        desired: &DesiredEntry,
+    ) -> Vec<PlanField> {
+        let mut fields = Vec::new();
+        match decoded {
+            Some(decoded) => {
+                if decoded.command_line() != desired.command.command_line() {
+                    fields.push(PlanField::Command);
+                }
+                if decoded.workdir() != desired.command.workdir() {
+                    fields.push(PlanField::WorkingDirectory);
+                }
+                if decoded.mode() != desired.command.mode() {
+                    fields.push(PlanField::Mode);
+                }
+            }
+            None => fields.push(PlanField::Command),
+        }
+        if current.location != desired.location {
+            fields.push(PlanField::Location);
+        }
+        if current.enabled != desired.enabled {
+            fields.push(PlanField::Enabled);
+        }
+        fields
+    }
+}
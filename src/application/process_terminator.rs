@@ -0,0 +1,132 @@
+use crate::domain::{MatchStrategy, ProcessInfo, ProcessProvider, process_tree};
+use std::collections::HashSet;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Options controlling how processes are stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KillOptions {
+    /// How processes are attributed to an entry.
+    pub strategy: MatchStrategy,
+    /// How long processes get to exit after a close request before they are
+    /// force-killed. Zero skips the graceful step entirely.
+    pub graceful_timeout: Duration,
+}
+
+impl Default for KillOptions {
+    fn default() -> Self {
+        Self {
+            strategy: MatchStrategy::CommandLine,
+            graceful_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+/// How a single process was stopped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KillOutcome {
+    /// The process exited without being force-killed, after a close
+    /// request or together with its parent.
+    Closed,
+    /// The process was force-killed.
+    Killed,
+    /// The process could not be stopped.
+    Failed(String),
+}
+
+/// The outcome of stopping one process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessKillResult {
+    pub pid: u32,
+    pub name: String,
+    pub outcome: KillOutcome,
+}
+
+impl ProcessKillResult {
+    /// Checks whether the process is no longer running.
+    pub fn is_stopped(&self) -> bool {
+        !matches!(self.outcome, KillOutcome::Failed(_))
+    }
+}
+
+/// Stops process trees: asks every process to close, waits for the
+/// configured timeout, then force-kills whatever is still running.
+pub struct ProcessTerminator<'a, P: ProcessProvider> {
+    processes: &'a P,
+    graceful_timeout: Duration,
+    poll_interval: Duration,
+}
+
+impl<'a, P: ProcessProvider> ProcessTerminator<'a, P> {
+    pub fn new(processes: &'a P, graceful_timeout: Duration) -> Self {
+        Self {
+            processes,
+            graceful_timeout,
+            poll_interval: Duration::from_millis(100),
+        }
+    }
+
+    /// Stops the given processes and all of their descendants.
+    /// `running` is the process snapshot the roots were selected from.
+    pub fn terminate(
+        &self,
+        roots: &[&ProcessInfo],
+        running: &[ProcessInfo],
+    ) -> Vec<ProcessKillResult> {
+        let root_pids: Vec<u32> = roots.iter().map(|p| p.pid).collect();
+        let tree = process_tree(&root_pids, running);
+
+        let mut closed = HashSet::new();
+        if !self.graceful_timeout.is_zero() {
+            // A failed request only means the process gets force-killed
+            let requested: Vec<u32> = tree
+                .iter()
+                .filter(|p| self.processes.request_close(p.pid).is_ok())
+                .map(|p| p.pid)
+                .collect();
+            closed = self.wait_for_exit(&requested);
+        }
+
+        tree.into_iter()
+            .map(|process| {
+                // Children often exit together with their parent
+                let exited = closed.contains(&process.pid)
+                    || !self.processes.is_running(process.pid).unwrap_or(true);
+                let outcome = if exited {
+                    KillOutcome::Closed
+                } else {
+                    match self.processes.kill_process(process.pid) {
+                        Ok(()) => KillOutcome::Killed,
+                        Err(e) => KillOutcome::Failed(e.to_string()),
+                    }
+                };
+                ProcessKillResult {
+                    pid: process.pid,
+                    name: process.name,
+                    outcome,
+                }
+            })
+            .collect()
+    }
+
+    /// Polls until every PID exited or the timeout elapsed.
+    /// Returns the PIDs that exited.
+    fn wait_for_exit(&self, pids: &[u32]) -> HashSet<u32> {
+        let deadline = Instant::now() + self.graceful_timeout;
+        let mut exited = HashSet::new();
+
+        loop {
+            for &pid in pids {
+                if !exited.contains(&pid) && !self.processes.is_running(pid).unwrap_or(true) {
+                    exited.insert(pid);
+                }
+            }
+
+            let now = Instant::now();
+            if exited.len() == pids.len() || now >= deadline {
+                return exited;
+            }
+            thread::sleep(self.poll_interval.min(deadline - now));
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use super::process_terminator::{KillOutcome, ProcessKillResult};
+use crate::domain::{AuditAction, AuditActor, AuditEvent, AuditLog, SnapshotChange};
+use crate::shared::error::{Result, StartupError};
+use chrono::Utc;
+
+/// Use case for writing what a command did to the audit log.
+/// This follows the Single Responsibility Principle.
+pub struct RecordAuditUseCase<'a, A: AuditLog> {
+    log: &'a A,
+    actor: &'a AuditActor,
+}
+
+impl<'a, A: AuditLog> RecordAuditUseCase<'a, A> {
+    pub fn new(log: &'a A, actor: &'a AuditActor) -> Self {
+        Self { log, actor }
+    }
+
+    /// Records one event per entry the command changed, with its old and
+    /// new value. A command that changed nothing, such as one that failed
+    /// before writing, is recorded as a single event about `entry`.
+    pub fn execute(
+        &self,
+        action: AuditAction,
+        entry: Option<&str>,
+        changes: &[SnapshotChange],
+        error: Option<&StartupError>,
+    ) -> Result<()> {
+        let mut event = AuditEvent::new(Utc::now(), self.actor, action);
+        if let Some(error) = error {
+            event = event.with_error(error.to_string());
+        }
+
+        if changes.is_empty() {
+            if let Some(entry) = entry {
+                event = event.with_entry(entry);
+            }
+            return self.log.append(&event);
+        }
+        for change in changes {
+            self.log.append(&event.clone().with_change(change))?;
+        }
+        Ok(())
+    }
+
+    /// Records one event per entry whose processes a kill stopped, listing
+    /// what happened to each process. Processes that couldn't be stopped
+    /// make the event a failure.
+    pub fn execute_kill(
+        &self,
+        entry: Option<&str>,
+        results: &[(String, Vec<ProcessKillResult>)],
+        error: Option<&StartupError>,
+    ) -> Result<()> {
+        let event = AuditEvent::new(Utc::now(), self.actor, AuditAction::Kill);
+        if let Some(error) = error {
+            let mut event = event.with_error(error.to_string());
+            if let Some(entry) = entry {
+                event = event.with_entry(entry);
+            }
+            return self.log.append(&event);
+        }
+        if results.is_empty() {
+            let mut event = event.with_detail("no running processes");
+            if let Some(entry) = entry {
+                event = event.with_entry(entry);
+            }
+            return self.log.append(&event);
+        }
+
+        for (label, processes) in results {
+            let detail: Vec<String> = processes
+                .iter()
+                .map(|process| {
+                    let outcome = match &process.outcome {
+                        KillOutcome::Closed => "closed".to_string(),
+                        KillOutcome::Killed => "killed".to_string(),
+                        KillOutcome::Failed(reason) => format!("failed ({})", reason),
+                    };
+                    format!("{} {} {}", process.pid, process.name, outcome)
+                })
+                .collect();
+            let mut event = event
+                .clone()
+                .with_entry(label)
+                .with_detail(detail.join(", "));
+            let failures: Vec<String> = processes
+                .iter()
+                .filter_map(|process| match &process.outcome {
+                    KillOutcome::Failed(reason) => Some(format!("{}: {}", process.pid, reason)),
+                    _ => None,
+                })
+                .collect();
+            if !failures.is_empty() {
+                event = event.with_error(failures.join("; "));
+            }
+            self.log.append(&event)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use crate::domain::{HistoryStore, SnapshotChange};
+use crate::shared::error::Result;
+use chrono::Utc;
+
+/// Use case for adding the changes a command made to the history.
+/// This follows the Single Responsibility Principle.
+pub struct RecordHistoryUseCase<'a, H: HistoryStore> {
+    history: &'a H,
+}
+
+impl<'a, H: HistoryStore> RecordHistoryUseCase<'a, H> {
+    pub fn new(history: &'a H) -> Self {
+        Self { history }
+    }
+
+    /// Records the `changes` made by `command`, and the records it
+    /// `reverts` when it is an `undo`. Commands that changed nothing are
+    /// not recorded. Returns the id of the new record.
+    pub fn execute(
+        &self,
+        command: &str,
+        changes: Vec<SnapshotChange>,
+        reverts: Vec<u64>,
+    ) -> Result<Option<u64>> {
+        if changes.is_empty() && reverts.is_empty() {
+            return Ok(None);
+        }
+        let mut history = self.history.load()?;
+        let id = history.record(command, Utc::now(), changes, reverts);
+        self.history.save(&history)?;
+        Ok(Some(id))
+    }
+}
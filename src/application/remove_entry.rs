@@ -1,24 +1,54 @@
-use crate::domain::{StartupRepository, StartupValidator};
+use super::launcher_cleanup::release_launchers;
+use crate::domain::{LauncherStore, StartupRepository, StartupValidator};
 use crate::shared::error::Result;
 
 /// Use case for removing a startup entry.
 /// This follows the Single Responsibility Principle.
-pub struct RemoveEntryUseCase<'a, R: StartupRepository> {
+pub struct RemoveEntryUseCase<'a, R: StartupRepository, L: LauncherStore> {
     repository: &'a R,
+    launchers: &'a L,
 }
 
-impl<'a, R: StartupRepository> RemoveEntryUseCase<'a, R> {
-    pub fn new(repository: &'a R) -> Self {
-        Self { repository }
+impl<'a, R: StartupRepository, L: LauncherStore> RemoveEntryUseCase<'a, R, L> {
+    pub fn new(repository: &'a R, launchers: &'a L) -> Self {
+        Self {
+            repository,
+            launchers,
+        }
     }
 
-    pub fn execute(&self, name: &str) -> Result<()> {
+    /// Removes the entry and deletes the VBScript launcher it owned.
+    /// Returns the launchers that were deleted.
+    pub fn execute(&self, name: &str) -> Result<Vec<String>> {
         // Validate input
         StartupValidator::validate_name(name)?;
 
+        let launcher = self
+            .repository
+            .list()?
+            .into_iter()
+            .find(|entry| entry.name.eq_ignore_ascii_case(name))
+            .and_then(|entry| self.launchers.referenced_by(&entry.command));
+
         // Remove from repository
         self.repository.remove(name)?;
 
-        Ok(())
+        let mut manifest = self.launchers.load_manifest()?;
+        let candidates: Vec<String> = launcher
+            .into_iter()
+            .chain(manifest.remove_entry(name))
+            .collect();
+        if candidates.is_empty() {
+            return Ok(Vec::new());
+        }
+        let deleted = release_launchers(
+            self.launchers,
+            &mut manifest,
+            candidates,
+            &self.repository.list()?,
+        )?;
+        self.launchers.save_manifest(&manifest)?;
+
+        Ok(deleted)
     }
 }
@@ -0,0 +1,95 @@
+use super::edit_entry::find_entry;
+use super::launcher_cleanup::release_launchers;
+use crate::domain::{LauncherStore, StartupEntry, StartupRepository, StartupValidator};
+use crate::shared::error::{Result, StartupError};
+
+/// Use case for renaming an entry together with its launcher.
+/// This follows the Single Responsibility Principle.
+pub struct RenameEntryUseCase<'a, R: StartupRepository, L: LauncherStore> {
+    repository: &'a R,
+    launchers: &'a L,
+}
+
+impl<'a, R: StartupRepository, L: LauncherStore> RenameEntryUseCase<'a, R, L> {
+    pub fn new(repository: &'a R, launchers: &'a L) -> Self {
+        Self {
+            repository,
+            launchers,
+        }
+    }
+
+    /// Renames the entry. An entry running a launcher gets a launcher named
+    /// after its new name; when storing it fails, the entry is renamed back
+    /// and the new launcher deleted. Returns the renamed entry.
+    pub fn execute(&self, name: &str, new_name: &str) -> Result<StartupEntry> {
+        // Validate inputs
+        StartupValidator::validate_name(name)?;
+        StartupValidator::validate_name(new_name)?;
+
+        let entry = find_entry(self.repository, name)?;
+        if !new_name.eq_ignore_ascii_case(&entry.name) && self.repository.exists(new_name)? {
+            return Err(StartupError::EntryAlreadyExists(new_name.to_string()));
+        }
+
+        // Launchers are named after their entry, so the launcher moves too
+        let previous_launcher = self.launchers.referenced_by(&entry.command);
+        let moved = previous_launcher
+            .as_ref()
+            .and_then(|_| self.launchers.decode(&entry.command))
+            .and_then(|command| {
+                let launcher = command.get_vbscript_content(new_name)?;
+                Some((
+                    launcher,
+                    command.to_registry_value(new_name, self.launchers),
+                ))
+            });
+        let renamed = StartupEntry {
+            name: new_name.to_string(),
+            command: moved
+                .as_ref()
+                .map_or_else(|| entry.command.clone(), |(_, value)| value.clone()),
+            ..entry.clone()
+        };
+        let written = match &moved {
+            Some(((filename, content), _)) if self.launchers.read(filename)?.is_none() => {
+                self.launchers.write(filename, content)?;
+                Some(filename.clone())
+            }
+            _ => None,
+        };
+
+        let stored = self.repository.rename(&entry.name, new_name).and_then(|_| {
+            if renamed.command == entry.command {
+                return Ok(());
+            }
+            self.repository.update(&renamed).inspect_err(|_| {
+                self.repository.rename(new_name, &entry.name).ok();
+            })
+        });
+        if let Err(e) = stored {
+            if let Some(filename) = written {
+                self.launchers.delete(&filename).ok();
+            }
+            return Err(e);
+        }
+
+        // Hand the launcher records over to the new name
+        let mut manifest = self.launchers.load_manifest()?;
+        let mut replaced: Vec<String> = previous_launcher
+            .into_iter()
+            .chain(manifest.remove_entry(&entry.name))
+            .collect();
+        if let Some(((filename, _), _)) = &moved {
+            replaced.extend(manifest.record(new_name, filename));
+        }
+        release_launchers(
+            self.launchers,
+            &mut manifest,
+            replaced,
+            &self.repository.list()?,
+        )?;
+        self.launchers.save_manifest(&manifest)?;
+
+        Ok(renamed)
+    }
+}
@@ -0,0 +1,44 @@
+use crate::domain::{StartupApproval, StartupRepository, StartupValidator};
+use crate::shared::error::Result;
+use chrono::Utc;
+
+/// Use case for enabling or disabling a startup entry without removing it.
+/// This follows the Single Responsibility Principle.
+pub struct SetEnabledUseCase<'a, R: StartupRepository> {
+    repository: &'a R,
+}
+
+impl<'a, R: StartupRepository> SetEnabledUseCase<'a, R> {
+    pub fn new(repository: &'a R) -> Self {
+        Self { repository }
+    }
+
+    /// Enables or disables the entry, recording the current time when it is
+    /// disabled. Returns `false` if the entry already was in that state.
+    pub fn execute(&self, name: &str, enabled: bool) -> Result<bool> {
+        // Validate input
+        StartupValidator::validate_name(name)?;
+
+        let matching: Vec<_> = self
+            .repository
+            .list()?
+            .into_iter()
+            .filter(|entry| entry.name.eq_ignore_ascii_case(name))
+            .collect();
+        // Keep the original time of entries that are already disabled
+        if let [entry] = matching.as_slice()
+            && entry.enabled == enabled
+        {
+            return Ok(false);
+        }
+
+        let approval = if enabled {
+            StartupApproval::ENABLED
+        } else {
+            StartupApproval::disabled_since(Utc::now())
+        };
+        self.repository.set_approval(name, approval)?;
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,129 @@
+use super::launcher_cleanup::release_launchers;
+use crate::domain::{
+    EntrySnapshot, HistoryRecord, HistoryStore, LauncherManifest, LauncherStore, SnapshotChange,
+    StartupEntry, StartupRepository,
+};
+use crate::shared::error::{Result, StartupError};
+
+/// Use case for reverting the last recorded commands.
+/// This follows the Single Responsibility Principle.
+pub struct UndoChangesUseCase<'a, R: StartupRepository, L: LauncherStore, H: HistoryStore> {
+    repository: &'a R,
+    launchers: &'a L,
+    history: &'a H,
+}
+
+impl<'a, R: StartupRepository, L: LauncherStore, H: HistoryStore> UndoChangesUseCase<'a, R, L, H> {
+    pub fn new(repository: &'a R, launchers: &'a L, history: &'a H) -> Self {
+        Self {
+            repository,
+            launchers,
+            history,
+        }
+    }
+
+    /// Puts back the entries and launchers the last `count` commands
+    /// changed, most recent first, and marks those commands as undone.
+    /// Returns the records that were reverted; none when there is nothing
+    /// left to undo.
+    ///
+    /// A command is only reverted when its entries are still as it left
+    /// them, so that undo never discards a later change.
+    pub fn execute(&self, count: usize) -> Result<Vec<HistoryRecord>> {
+        let mut history = self.history.load()?;
+        let records: Vec<HistoryRecord> = history.undoable().take(count).cloned().collect();
+        for record in &records {
+            self.revert(record)?;
+            history.mark_undone(record.id);
+            self.history.save(&history)?;
+        }
+        Ok(records)
+    }
+
+    fn revert(&self, record: &HistoryRecord) -> Result<()> {
+        let current = self.repository.list()?;
+        for change in &record.changes {
+            let stored = find(&current, change);
+            let unchanged = match (&change.after, stored) {
+                (None, None) => true,
+                (Some(after), Some(entry)) => after.matches(entry),
+                _ => false,
+            };
+            if !unchanged {
+                return Err(StartupError::UndoConflict(format!(
+                    "'{}' in {} changed after #{} ({})",
+                    change.name(),
+                    change.location().as_str(),
+                    record.id,
+                    record.command
+                )));
+            }
+        }
+
+        revert_changes(self.repository, self.launchers, &record.changes)
+    }
+}
+
+/// Puts back the entries and launchers `changes` affected, most recent
+/// first.
+pub(crate) fn revert_changes<R: StartupRepository, L: LauncherStore>(
+    repository: &R,
+    launchers: &L,
+    changes: &[SnapshotChange],
+) -> Result<()> {
+    let mut manifest = launchers.load_manifest()?;
+    let mut replaced = Vec::new();
+    for change in changes.iter().rev() {
+        if let Some(after) = &change.after {
+            replaced.extend(after.launcher.iter().map(|l| l.filename.clone()));
+        }
+        match &change.before {
+            Some(before) => restore(repository, launchers, before, &mut manifest, &mut replaced)?,
+            None => {
+                repository.remove(change.name())?;
+                replaced.extend(manifest.remove_entry(change.name()));
+            }
+        }
+    }
+
+    release_launchers(launchers, &mut manifest, replaced, &repository.list()?)?;
+    launchers.save_manifest(&manifest)
+}
+
+/// Stores the entry as it was, writing its launcher back if it was
+/// deleted.
+fn restore<R: StartupRepository, L: LauncherStore>(
+    repository: &R,
+    launchers: &L,
+    before: &EntrySnapshot,
+    manifest: &mut LauncherManifest,
+    replaced: &mut Vec<String>,
+) -> Result<()> {
+    match &before.launcher {
+        Some(launcher) => {
+            if launchers.read(&launcher.filename)?.is_none() {
+                launchers.write(&launcher.filename, &launcher.content)?;
+            }
+            replaced.extend(manifest.record(&before.name, &launcher.filename));
+        }
+        None => replaced.extend(manifest.remove_entry(&before.name)),
+    }
+
+    let entry = before.to_entry();
+    repository.add(&entry)?;
+    let stored = repository
+        .list()?
+        .into_iter()
+        .find(|e| e.location == entry.location && e.name.eq_ignore_ascii_case(&entry.name));
+    if stored.is_some_and(|stored| stored.approval() != entry.approval()) {
+        repository.set_approval(&entry.name, entry.approval())?;
+    }
+    Ok(())
+}
+
+/// Returns the entry `change` applies to, as it is stored now.
+fn find<'c>(current: &'c [StartupEntry], change: &SnapshotChange) -> Option<&'c StartupEntry> {
+    current.iter().find(|entry| {
+        entry.location == change.location() && entry.name.eq_ignore_ascii_case(change.name())
+    })
+}
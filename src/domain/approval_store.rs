@@ -0,0 +1,15 @@
+use super::startup_approved::StartupApproval;
+use crate::shared::error::Result;
+
+/// Storage for the StartupApproved values Task Manager keeps for the
+/// entries of one location, named after registry values or file names.
+pub trait ApprovalStore {
+    /// Reads the value named `name`; `None` when there is none.
+    fn get(&self, name: &str) -> Result<Option<StartupApproval>>;
+
+    /// Creates or replaces the value named `name`.
+    fn set(&self, name: &str, approval: StartupApproval) -> Result<()>;
+
+    /// Deletes the value named `name`, if there is one.
+    fn delete(&self, name: &str) -> Result<()>;
+}
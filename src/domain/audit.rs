@@ -0,0 +1,169 @@
+use super::history::SnapshotChange;
+use super::location::StartupLocation;
+use crate::shared::error::{Result, StartupError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// The kind of command an audit event records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AuditAction {
+    Add,
+    Edit,
+    Rename,
+    Remove,
+    Enable,
+    Disable,
+    Kill,
+    Apply,
+    Import,
+    Undo,
+}
+
+impl AuditAction {
+    /// Returns the name events are written with.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Add => "add",
+            AuditAction::Edit => "edit",
+            AuditAction::Rename => "rename",
+            AuditAction::Remove => "remove",
+            AuditAction::Enable => "enable",
+            AuditAction::Disable => "disable",
+            AuditAction::Kill => "kill",
+            AuditAction::Apply => "apply",
+            AuditAction::Import => "import",
+            AuditAction::Undo => "undo",
+        }
+    }
+}
+
+/// Whether the audited command succeeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AuditOutcome {
+    Success,
+    Failure,
+}
+
+/// The user and machine commands run as.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct AuditActor {
+    pub user: String,
+    pub host: String,
+}
+
+/// One line of the audit log: what a command did to one entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AuditEvent {
+    pub at: DateTime<Utc>,
+    pub user: String,
+    pub host: String,
+    pub action: AuditAction,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<StartupLocation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old_value: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_value: Option<String>,
+    pub outcome: AuditOutcome,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// What happened besides value changes, such as the processes a kill
+    /// stopped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl AuditEvent {
+    /// Creates a successful event of `actor` without an entry.
+    pub fn new(at: DateTime<Utc>, actor: &AuditActor, action: AuditAction) -> Self {
+        Self {
+            at,
+            user: actor.user.clone(),
+            host: actor.host.clone(),
+            action,
+            entry: None,
+            location: None,
+            old_value: None,
+            new_value: None,
+            outcome: AuditOutcome::Success,
+            error: None,
+            detail: None,
+        }
+    }
+
+    /// Sets the entry the event is about.
+    pub fn with_entry(mut self, name: impl Into<String>) -> Self {
+        self.entry = Some(name.into());
+        self
+    }
+
+    /// Sets the entry, location and values from a recorded change.
+    pub fn with_change(mut self, change: &SnapshotChange) -> Self {
+        self.entry = Some(change.name().to_string());
+        self.location = Some(change.location());
+        self.old_value = change.before.as_ref().map(|s| s.value.clone());
+        self.new_value = change.after.as_ref().map(|s| s.value.clone());
+        self
+    }
+
+    /// Marks the event as failed with `error`.
+    pub fn with_error(mut self, error: impl Into<String>) -> Self {
+        self.outcome = AuditOutcome::Failure;
+        self.error = Some(error.into());
+        self
+    }
+
+    /// Sets what happened besides value changes.
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    /// Serializes the event to a single JSON line, without the newline.
+    pub fn to_line(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Parses a line written by [`to_line`](Self::to_line).
+    pub fn from_line(line: &str) -> Result<Self> {
+        serde_json::from_str(line)
+            .map_err(|e| StartupError::InvalidFormat(format!("audit log: {}", e)))
+    }
+}
+
+/// Conditions events must meet to be listed by `log`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct AuditFilter {
+    /// Entry name, ignoring case.
+    pub entry: Option<String>,
+    pub action: Option<AuditAction>,
+    /// User name, ignoring case.
+    pub user: Option<String>,
+    /// Earliest time, inclusive.
+    pub since: Option<DateTime<Utc>>,
+    /// Latest time, exclusive.
+    pub until: Option<DateTime<Utc>>,
+    pub failures_only: bool,
+}
+
+impl AuditFilter {
+    /// Returns whether `event` meets every condition.
+    pub fn matches(&self, event: &AuditEvent) -> bool {
+        let same = |wanted: &Option<String>, actual: Option<&str>| {
+            wanted
+                .as_ref()
+                .is_none_or(|wanted| actual.is_some_and(|a| a.eq_ignore_ascii_case(wanted)))
+        };
+        same(&self.entry, event.entry.as_deref())
+            && same(&self.user, Some(&event.user))
+            && self.action.is_none_or(|action| action == event.action)
+            && self.since.is_none_or(|since| event.at >= since)
+            && self.until.is_none_or(|until| event.at < until)
+            && (!self.failures_only || event.outcome == AuditOutcome::Failure)
+    }
+}
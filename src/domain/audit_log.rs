@@ -0,0 +1,12 @@
+use super::audit::AuditEvent;
+use crate::shared::error::Result;
+
+/// Append-only storage for the audit events of the commands that change
+/// startup entries or stop their processes.
+pub trait AuditLog {
+    /// Adds an event after the existing ones.
+    fn append(&self, event: &AuditEvent) -> Result<()>;
+
+    /// Reads every event still kept, oldest first.
+    fn read(&self) -> Result<Vec<AuditEvent>>;
+}
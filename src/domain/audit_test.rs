@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+    use super::super::audit::*;
+    use super::super::{EntrySnapshot, SnapshotChange, StartupLocation};
+    use crate::shared::error::StartupError;
+    use chrono::{TimeZone, Utc};
+
+    fn actor() -> AuditActor {
+        AuditActor {
+            user: "CORP\\builder".to_string(),
+            host: "BUILD01".to_string(),
+        }
+    }
+
+    fn snapshot(value: &str) -> EntrySnapshot {
+        EntrySnapshot {
+            name: "Dev".to_string(),
+            location: StartupLocation::LocalMachineRun,
+            value: value.to_string(),
+            enabled: true,
+            disabled_at: None,
+            launcher: None,
+        }
+    }
+
+    #[test]
+    fn test_events_are_single_json_lines() {
+        let at = Utc.with_ymd_and_hms(2026, 3, 1, 8, 30, 0).unwrap();
+        let event = AuditEvent::new(at, &actor(), AuditAction::Add)
+            .with_change(&SnapshotChange {
+                before: Some(snapshot("old.exe")),
+                after: Some(snapshot("new.exe\n--flag")),
+            })
+            .with_error("Registry error: access denied");
+
+        let line = event.to_line();
+
+        assert!(!line.contains('\n'));
+        assert!(line.starts_with(r#"{"at":"2026-03-01T08:30:00Z","user":"CORP\\builder""#));
+        assert!(line.contains(r#""location":"hklm-run","old-value":"old.exe""#));
+        assert!(line.contains(r#""outcome":"failure""#));
+        assert_eq!(AuditEvent::from_line(&line).unwrap(), event);
+        assert!(matches!(
+            AuditEvent::from_line(r#"{"at":"2026-03-01T08:30:00Z"}"#),
+            Err(StartupError::InvalidFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_filter_matches_every_condition() {
+        let at = Utc.with_ymd_and_hms(2026, 3, 1, 8, 30, 0).unwrap();
+        let event = AuditEvent::new(at, &actor(), AuditAction::Remove).with_entry("Dev");
+        let failed = event.clone().with_error("Entry 'Dev' not found");
+        let filter = |f: AuditFilter| f.matches(&event);
+
+        assert!(filter(AuditFilter::default()));
+        assert!(filter(AuditFilter {
+            entry: Some("dev".to_string()),
+            user: Some("corp\\BUILDER".to_string()),
+            action: Some(AuditAction::Remove),
+            since: Some(at),
+            until: Some(at + chrono::Duration::seconds(1)),
+            failures_only: false,
+        }));
+        assert!(!filter(AuditFilter {
+            entry: Some("Api".to_string()),
+            ..AuditFilter::default()
+        }));
+        assert!(!filter(AuditFilter {
+            action: Some(AuditAction::Kill),
+            ..AuditFilter::default()
+        }));
+        assert!(!filter(AuditFilter {
+            until: Some(at),
+            ..AuditFilter::default()
+        }));
+        assert!(!filter(AuditFilter {
+            failures_only: true,
+            ..AuditFilter::default()
+        }));
+        assert!(
+            AuditFilter {
+                failures_only: true,
+                ..AuditFilter::default()
+            }
+            .matches(&failed)
+        );
+    }
+}
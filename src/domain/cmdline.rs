@@ -0,0 +1,225 @@
+/// Characters `cmd.exe` treats specially outside double quotes.
+const CMD_METACHARACTERS: &[char] = &['&', '|', '<', '>', '^', '(', ')'];
+
+fn is_blank(c: char) -> bool {
+    c == ' ' || c == '\t'
+}
+
+/// Splits a command line into arguments following `CommandLineToArgvW`,
+/// which is how Windows programs see the string they were started with.
+///
+/// The first argument is the program name, which ends at the next blank, or
+/// at the closing quote when it starts with one; backslashes are literal in
+/// it. The remaining arguments follow the MSVC CRT rules:
+///
+/// - blanks separate arguments unless they are inside double quotes;
+/// - `2n` backslashes followed by a quote produce `n` backslashes and the
+///   quote starts or ends a quoted section;
+/// - `2n + 1` backslashes followed by a quote produce `n` backslashes and
+///   a literal quote;
+/// - backslashes not followed by a quote are literal;
+/// - `""` inside a quoted section produces a literal quote.
+pub fn parse(command_line: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut chars = command_line.chars().peekable();
+
+    // The program name
+    let mut program = String::new();
+    if chars.peek() == Some(&'"') {
+        chars.next();
+        for c in chars.by_ref() {
+            if c == '"' {
+                break;
+            }
+            program.push(c);
+        }
+    } else {
+        while let Some(&c) = chars.peek() {
+            if is_blank(c) {
+                break;
+            }
+            program.push(c);
+            chars.next();
+        }
+    }
+    if command_line.is_empty() {
+        return args;
+    }
+    args.push(program);
+
+    // The arguments
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut in_quotes = false;
+    while let Some(c) = chars.next() {
+        match c {
+            c if is_blank(c) && !in_quotes => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            '\\' => {
+                let mut backslashes = 1;
+                while chars.peek() == Some(&'\\') {
+                    chars.next();
+                    backslashes += 1;
+                }
+                if chars.peek() == Some(&'"') {
+                    current.extend(std::iter::repeat_n('\\', backslashes / 2));
+                    if backslashes % 2 == 1 {
+                        chars.next();
+                        current.push('"');
+                    }
+                } else {
+                    current.extend(std::iter::repeat_n('\\', backslashes));
+                }
+                in_arg = true;
+            }
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                chars.next();
+                current.push('"');
+                in_arg = true;
+            }
+            '"' => {
+                in_quotes = !in_quotes;
+                in_arg = true;
+            }
+            c => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if in_arg {
+        args.push(current);
+    }
+
+    args
+}
+
+/// Splits a command line into its program name, read like [`parse`] does,
+/// and the rest of the line as it was written.
+pub fn split_program(command_line: &str) -> (String, &str) {
+    let (program, rest) = match command_line.strip_prefix('"') {
+        Some(quoted) => quoted.split_once('"').unwrap_or((quoted, "")),
+        None => command_line
+            .split_once(is_blank)
+            .unwrap_or((command_line, "")),
+    };
+    (program.to_string(), rest.trim_start_matches(is_blank))
+}
+
+/// Returns the lowercase file name of a program without its `.exe`
+/// extension, so `C:\Tools\Bun.EXE` and `bun` name the same program.
+pub fn program_stem(program: &str) -> String {
+    let file_name = program.rsplit(['\\', '/']).next().unwrap_or(program);
+    let lower = file_name.to_lowercase();
+    lower.strip_suffix(".exe").unwrap_or(&lower).to_string()
+}
+
+/// Quotes a single argument so that [`parse`] reads it back unchanged.
+/// Arguments without blanks or quotes are returned as they are.
+pub fn quote_arg(arg: &str) -> String {
+    if !arg.is_empty() && !arg.contains([' ', '\t', '"']) {
+        return arg.to_string();
+    }
+
+    let mut quoted = String::from('"');
+    let mut backslashes = 0;
+    for c in arg.chars() {
+        match c {
+            '\\' => backslashes += 1,
+            '"' => {
+                // Escape the backslashes and the quote itself
+                quoted.extend(std::iter::repeat_n('\\', backslashes * 2 + 1));
+                quoted.push('"');
+                backslashes = 0;
+            }
+            c => {
+                quoted.extend(std::iter::repeat_n('\\', backslashes));
+                quoted.push(c);
+                backslashes = 0;
+            }
+        }
+    }
+    // Backslashes before the closing quote must not escape it
+    quoted.extend(std::iter::repeat_n('\\', backslashes * 2));
+    quoted.push('"');
+    quoted
+}
+
+/// Quotes a program name. Program names cannot be escaped, so they are only
+/// wrapped in quotes when they contain blanks; quotes inside them are dropped
+/// because Windows paths cannot contain any.
+pub fn quote_program(program: &str) -> String {
+    let program = program.replace('"', "");
+    if program.is_empty() || program.contains([' ', '\t']) {
+        format!("\"{}\"", program)
+    } else {
+        program
+    }
+}
+
+/// Builds a command line from a program and its arguments.
+pub fn quote<S: AsRef<str>>(program: &str, args: &[S]) -> String {
+    let mut command_line = quote_program(program);
+    for arg in args {
+        command_line.push(' ');
+        command_line.push_str(&quote_arg(arg.as_ref()));
+    }
+    command_line
+}
+
+/// Renders an argument for a PowerShell command. Plain words are left bare
+/// and anything else becomes a single-quoted literal, in which only `'` needs
+/// escaping (by doubling it).
+pub fn quote_powershell(arg: &str) -> String {
+    // Windows PowerShell swallows a bare `--` meant for a native program
+    let plain = !arg.is_empty()
+        && arg != "--"
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./\\:=+".contains(c));
+    if plain {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "''"))
+    }
+}
+
+/// Escapes `cmd.exe` metacharacters outside double quotes with `^`, so that a
+/// command line run through `cmd.exe /c` reaches the program unchanged.
+/// `%VAR%` references are still expanded; `cmd.exe` offers no way to escape
+/// them on its command line.
+pub fn escape_for_cmd(command_line: &str) -> String {
+    let mut escaped = String::with_capacity(command_line.len());
+    let mut in_quotes = false;
+    for c in command_line.chars() {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        } else if !in_quotes && CMD_METACHARACTERS.contains(&c) {
+            escaped.push('^');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Removes the `^` escapes added by [`escape_for_cmd`].
+pub fn unescape_for_cmd(command_line: &str) -> String {
+    let mut unescaped = String::with_capacity(command_line.len());
+    let mut in_quotes = false;
+    let mut chars = command_line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                unescaped.push(c);
+            }
+            '^' if !in_quotes => unescaped.extend(chars.next()),
+            c => unescaped.push(c),
+        }
+    }
+    unescaped
+}
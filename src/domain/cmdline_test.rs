@@ -0,0 +1,147 @@
+#[cfg(test)]
+mod tests {
+    use super::super::cmdline::*;
+    use proptest::prelude::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_splits_on_blanks() {
+        assert_eq!(
+            parse("bun run\tdev  --port 3000"),
+            args(&["bun", "run", "dev", "--port", "3000"])
+        );
+        assert_eq!(parse("bun   "), args(&["bun"]));
+        assert!(parse("").is_empty());
+    }
+
+    #[test]
+    fn test_split_program_keeps_rest_verbatim() {
+        assert_eq!(
+            split_program(r#""C:\Program Files\app.exe"  --name "a b""#),
+            (r"C:\Program Files\app.exe".to_string(), r#"--name "a b""#)
+        );
+        assert_eq!(
+            split_program(r#"cmd.exe /c "cd /d "C:\x" && run""#),
+            ("cmd.exe".to_string(), r#"/c "cd /d "C:\x" && run""#)
+        );
+        assert_eq!(split_program("app.exe"), ("app.exe".to_string(), ""));
+    }
+
+    #[test]
+    fn test_program_stem_drops_directory_extension_and_case() {
+        assert_eq!(program_stem(r"C:\Tools\Bun.EXE"), "bun");
+        assert_eq!(program_stem("node"), "node");
+        assert_eq!(program_stem("/usr/bin/app.exe"), "app");
+        assert_eq!(program_stem("script.vbs"), "script.vbs");
+    }
+
+    #[test]
+    fn test_parse_quoted_program_keeps_backslashes() {
+        assert_eq!(
+            parse("\"C:\\Program Files\\app.exe\" x"),
+            args(&["C:\\Program Files\\app.exe", "x"])
+        );
+        assert_eq!(parse("C:\\a\\\"b c"), args(&["C:\\a\\\"b", "c"]));
+        assert_eq!(parse("\"\" x"), args(&["", "x"]));
+    }
+
+    #[test]
+    fn test_parse_backslash_quote_rules() {
+        // The examples from the MSVC documentation, after a program name
+        assert_eq!(parse("p \"abc\" d e"), args(&["p", "abc", "d", "e"]));
+        assert_eq!(
+            parse("p a\\\\\\b d\"e f\"g h"),
+            args(&["p", "a\\\\\\b", "de fg", "h"])
+        );
+        assert_eq!(parse("p a\\\\\\\"b c d"), args(&["p", "a\\\"b", "c", "d"]));
+        assert_eq!(
+            parse("p a\\\\\\\\\"b c\" d e"),
+            args(&["p", "a\\\\b c", "d", "e"])
+        );
+        assert_eq!(parse("p a\"b\"\" c d"), args(&["p", "ab\" c d"]));
+    }
+
+    #[test]
+    fn test_parse_empty_quoted_argument() {
+        assert_eq!(parse("p \"\" x"), args(&["p", "", "x"]));
+    }
+
+    #[test]
+    fn test_quote_arg() {
+        assert_eq!(quote_arg("dev"), "dev");
+        assert_eq!(quote_arg("C:\\dir\\"), "C:\\dir\\");
+        assert_eq!(quote_arg(""), "\"\"");
+        assert_eq!(quote_arg("C:\\My Projects"), "\"C:\\My Projects\"");
+        assert_eq!(quote_arg("C:\\My Projects\\"), "\"C:\\My Projects\\\\\"");
+        assert_eq!(quote_arg("say \"hi\""), "\"say \\\"hi\\\"\"");
+        assert_eq!(quote_arg("a\\\"b"), "\"a\\\\\\\"b\"");
+    }
+
+    #[test]
+    fn test_quote_builds_command_line() {
+        assert_eq!(
+            quote("C:\\Program Files\\app.exe", &["--dir", "C:\\My Projects"]),
+            "\"C:\\Program Files\\app.exe\" --dir \"C:\\My Projects\""
+        );
+        assert_eq!(quote("bun", &[] as &[&str]), "bun");
+    }
+
+    #[test]
+    fn test_quote_powershell() {
+        assert_eq!(quote_powershell("http.server"), "http.server");
+        assert_eq!(quote_powershell("C:\\My Projects"), "'C:\\My Projects'");
+        assert_eq!(quote_powershell("it's"), "'it''s'");
+        assert_eq!(quote_powershell("$env:PATH"), "'$env:PATH'");
+        assert_eq!(quote_powershell("--"), "'--'");
+        assert_eq!(quote_powershell(""), "''");
+    }
+
+    #[test]
+    fn test_escape_for_cmd_leaves_quoted_text_alone() {
+        assert_eq!(
+            escape_for_cmd("app a&b \"c & d\" (e)"),
+            "app a^&b \"c & d\" ^(e^)"
+        );
+        assert_eq!(unescape_for_cmd("app a^&b \"c ^ d\""), "app a&b \"c ^ d\"");
+    }
+
+    fn argument() -> impl Strategy<Value = String> {
+        proptest::collection::vec(
+            prop_oneof![
+                Just('\\'),
+                Just('"'),
+                Just(' '),
+                Just('\t'),
+                Just('a'),
+                Just('&'),
+                any::<char>(),
+            ],
+            0..12,
+        )
+        .prop_map(|chars| chars.into_iter().collect())
+    }
+
+    fn program() -> impl Strategy<Value = String> {
+        argument().prop_map(|p| p.replace('"', ""))
+    }
+
+    proptest! {
+        #[test]
+        fn prop_parse_inverts_quote(program in program(), arguments in proptest::collection::vec(argument(), 0..6)) {
+            let mut expected = vec![program.clone()];
+            expected.extend(arguments.iter().cloned());
+
+            prop_assert_eq!(parse(&quote(&program, &arguments)), expected);
+        }
+
+        #[test]
+        fn prop_unescape_for_cmd_inverts_escape(program in program(), arguments in proptest::collection::vec(argument(), 0..6)) {
+            let command_line = quote(&program, &arguments);
+
+            prop_assert_eq!(unescape_for_cmd(&escape_for_cmd(&command_line)), command_line);
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use super::launcher_store::LauncherStore;
+use super::location::StartupLocation;
+use super::models::StartupEntry;
+use super::startup_approved::StartupApproval;
+use super::startup_backup::BackupLauncher;
+use crate::shared::error::{Result, StartupError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Number of commands the history keeps; older ones are forgotten.
+pub const HISTORY_LIMIT: usize = 100;
+
+/// An entry as it was stored at one point, with the launcher it ran.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct EntrySnapshot {
+    pub name: String,
+    pub location: StartupLocation,
+    /// The registry value, shortcut command line or task action as stored.
+    pub value: String,
+    pub enabled: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disabled_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub launcher: Option<BackupLauncher>,
+}
+
+/// How a command changed one entry in one location. `before` is `None` for
+/// an entry the command added, `after` for one it removed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SnapshotChange {
+    pub before: Option<EntrySnapshot>,
+    pub after: Option<EntrySnapshot>,
+}
+
+/// The changes one command made.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct HistoryRecord {
+    pub id: u64,
+    pub at: DateTime<Utc>,
+    /// The command line, without the program name.
+    pub command: String,
+    pub changes: Vec<SnapshotChange>,
+    /// The records an `undo` reverted.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reverts: Vec<u64>,
+    /// Whether an `undo` reverted this record.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub undone: bool,
+}
+
+/// Every recorded command, oldest first.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct History {
+    #[serde(default)]
+    pub records: Vec<HistoryRecord>,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+impl EntrySnapshot {
+    /// Captures `entry` and the content of the launcher it runs.
+    pub fn capture<L: LauncherStore>(entry: &StartupEntry, launchers: &L) -> Result<Self> {
+        let launcher = match launchers.referenced_by(&entry.command) {
+            Some(filename) => launchers
+                .read(&filename)?
+                .map(|content| BackupLauncher { filename, content }),
+            None => None,
+        };
+        Ok(Self {
+            name: entry.name.clone(),
+            location: entry.location,
+            value: entry.command.clone(),
+            enabled: entry.enabled,
+            disabled_at: entry.disabled_at,
+            launcher,
+        })
+    }
+
+    /// Returns the entry as it was stored.
+    pub fn to_entry(&self) -> StartupEntry {
+        StartupEntry::new(&self.name, &self.value)
+            .with_location(self.location)
+            .with_approval(StartupApproval {
+                enabled: self.enabled,
+                disabled_at: self.disabled_at,
+            })
+    }
+
+    /// Returns whether `entry` is stored the way this snapshot saw it.
+    pub fn matches(&self, entry: &StartupEntry) -> bool {
+        self.location == entry.location
+            && self.name.eq_ignore_ascii_case(&entry.name)
+            && self.value == entry.command
+            && self.enabled == entry.enabled
+    }
+}
+
+impl SnapshotChange {
+    /// Returns the name of the changed entry.
+    pub fn name(&self) -> &str {
+        self.snapshot().map_or("", |snapshot| &snapshot.name)
+    }
+
+    /// Returns the location the change happened in.
+    pub fn location(&self) -> StartupLocation {
+        self.snapshot()
+            .map_or_else(StartupLocation::default, |snapshot| snapshot.location)
+    }
+
+    /// Returns whether the entry ended up as it was.
+    pub fn is_empty(&self) -> bool {
+        self.before == self.after
+    }
+
+    fn snapshot(&self) -> Option<&EntrySnapshot> {
+        self.after.as_ref().or(self.before.as_ref())
+    }
+}
+
+impl History {
+    /// Creates an empty history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a history written by [`to_json`](Self::to_json).
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json)
+            .map_err(|e| StartupError::InvalidFormat(format!("history: {}", e)))
+    }
+
+    /// Serializes the history to pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Appends the changes `command` made and returns the record's id.
+    /// Only the last [`HISTORY_LIMIT`] records are kept.
+    pub fn record(
+        &mut self,
+        command: impl Into<String>,
+        at: DateTime<Utc>,
+        changes: Vec<SnapshotChange>,
+        reverts: Vec<u64>,
+    ) -> u64 {
+        let id = self.records.last().map_or(1, |record| record.id + 1);
+        self.records.push(HistoryRecord {
+            id,
+            at,
+            command: command.into(),
+            changes,
+            reverts,
+            undone: false,
+        });
+        let excess = self.records.len().saturating_sub(HISTORY_LIMIT);
+        self.records.drain(..excess);
+        id
+    }
+
+    /// Returns the records `undo` can revert, most recent first. Records
+    /// already undone and the records of `undo` itself are left out.
+    pub fn undoable(&self) -> impl Iterator<Item = &HistoryRecord> {
+        self.records
+            .iter()
+            .rev()
+            .filter(|record| !record.undone && record.reverts.is_empty())
+    }
+
+    /// Marks the record `id` as undone.
+    pub fn mark_undone(&mut self, id: u64) {
+        if let Some(record) = self.records.iter_mut().find(|record| record.id == id) {
+            record.undone = true;
+        }
+    }
+}
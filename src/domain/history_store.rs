@@ -0,0 +1,21 @@
+use super::history::History;
+use crate::shared::error::Result;
+
+/// Storage for the history of the changes made to startup entries.
+pub trait HistoryStore {
+    /// Reads the history; a store without one has an empty history.
+    fn load(&self) -> Result<History>;
+
+    /// Replaces the history.
+    fn save(&self, history: &History) -> Result<()>;
+}
+
+impl<H: HistoryStore + ?Sized> HistoryStore for &H {
+    fn load(&self) -> Result<History> {
+        (**self).load()
+    }
+
+    fn save(&self, history: &History) -> Result<()> {
+        (**self).save(history)
+    }
+}
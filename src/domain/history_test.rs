@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod tests {
+    use super::super::history::*;
+    use super::super::{
+        BackupLauncher, LauncherStore, StartupApproval, StartupEntry, StartupLocation,
+    };
+    use crate::infrastructure::InMemoryLauncherStore;
+    use crate::shared::error::StartupError;
+    use chrono::{TimeZone, Utc};
+
+    fn snapshot(name: &str, value: &str) -> EntrySnapshot {
+        EntrySnapshot {
+            name: name.to_string(),
+            location: StartupLocation::CurrentUserRun,
+            value: value.to_string(),
+            enabled: true,
+            disabled_at: None,
+            launcher: None,
+        }
+    }
+
+    fn added(name: &str) -> Vec<SnapshotChange> {
+        vec![SnapshotChange {
+            before: None,
+            after: Some(snapshot(name, "app.exe")),
+        }]
+    }
+
+    #[test]
+    fn test_captures_entries_with_their_launcher() {
+        let launchers = InMemoryLauncherStore::new();
+        launchers.write("dev_1.vbs", "WScript.Quit").unwrap();
+        let disabled_at = Utc.with_ymd_and_hms(2026, 3, 1, 8, 30, 0).unwrap();
+        let entry = StartupEntry::new(
+            "Dev",
+            format!(
+                "wscript.exe //B //Nologo \"{}\"",
+                launchers.path_of("dev_1.vbs")
+            ),
+        )
+        .with_location(StartupLocation::LocalMachineRun)
+        .with_approval(StartupApproval::disabled_since(disabled_at));
+
+        let captured = EntrySnapshot::capture(&entry, &launchers).unwrap();
+
+        assert_eq!(
+            captured.launcher,
+            Some(BackupLauncher {
+                filename: "dev_1.vbs".to_string(),
+                content: "WScript.Quit".to_string(),
+            })
+        );
+        assert_eq!(captured.to_entry(), entry);
+        assert!(captured.matches(&entry));
+        assert!(!captured.matches(&entry.clone().with_approval(StartupApproval::ENABLED)));
+    }
+
+    #[test]
+    fn test_records_get_increasing_ids_and_old_ones_are_dropped() {
+        let mut history = History::new();
+        let at = Utc.with_ymd_and_hms(2026, 3, 1, 8, 30, 0).unwrap();
+
+        for n in 0..HISTORY_LIMIT + 5 {
+            history.record(format!("add App{}", n), at, added("App"), Vec::new());
+        }
+
+        assert_eq!(history.records.len(), HISTORY_LIMIT);
+        assert_eq!(history.records[0].id, 6);
+        assert_eq!(history.records.last().unwrap().id, HISTORY_LIMIT as u64 + 5);
+    }
+
+    #[test]
+    fn test_undo_skips_undone_records_and_undos() {
+        let mut history = History::new();
+        let at = Utc.with_ymd_and_hms(2026, 3, 1, 8, 30, 0).unwrap();
+        let first = history.record("add A", at, added("A"), Vec::new());
+        let second = history.record("add B", at, added("B"), Vec::new());
+        history.mark_undone(second);
+        history.record("undo", at, added("B"), vec![second]);
+
+        let ids: Vec<u64> = history.undoable().map(|record| record.id).collect();
+
+        assert_eq!(ids, [first]);
+    }
+
+    #[test]
+    fn test_history_round_trips_through_json() {
+        let mut history = History::new();
+        let at = Utc.with_ymd_and_hms(2026, 3, 1, 8, 30, 0).unwrap();
+        let mut changes = added("Dev");
+        changes.push(SnapshotChange {
+            before: Some(snapshot("Old", "old.exe")),
+            after: None,
+        });
+        history.record("import backup.json", at, changes, Vec::new());
+
+        let json = history.to_json();
+
+        assert!(json.contains(r#""location": "hkcu-run""#));
+        assert!(!json.contains("undone"));
+        assert_eq!(History::from_json(&json).unwrap(), history);
+        assert_eq!(history.records[0].changes[1].name(), "Old");
+        assert!(matches!(
+            History::from_json(r#"{"records": [{"id": 1}]}"#),
+            Err(StartupError::InvalidFormat(_))
+        ));
+    }
+}
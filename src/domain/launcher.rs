@@ -0,0 +1,140 @@
+use super::cmdline;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+/// Longest entry-name prefix kept in a launcher file name.
+const MAX_NAME_LENGTH: usize = 48;
+/// Number of hex digits of the content digest kept in a launcher file name.
+const DIGEST_LENGTH: usize = 16;
+
+/// Turns an entry name into a file-name-safe, lowercase slug.
+/// Runs of characters other than ASCII letters, digits, `-` and `_` become a
+/// single `_`.
+pub fn sanitize_entry_name(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.trim().chars() {
+        if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.ends_with('_') {
+            slug.push('_');
+        }
+    }
+
+    let slug: String = slug
+        .trim_matches('_')
+        .chars()
+        .take(MAX_NAME_LENGTH)
+        .collect();
+    if slug.is_empty() {
+        "entry".to_string()
+    } else {
+        slug
+    }
+}
+
+/// Returns the hex SHA-256 digest of `content`.
+pub fn content_digest(content: &str) -> String {
+    Sha256::digest(content.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Names the launcher of an entry: the sanitized entry name followed by a
+/// digest of the launcher's content, e.g. `bun_dev_1f2e3d4c5b6a7988.vbs`.
+/// The name only changes when the entry is renamed or the script changes.
+pub fn launcher_filename(entry_name: &str, content: &str) -> String {
+    format!(
+        "{}_{}.vbs",
+        sanitize_entry_name(entry_name),
+        &content_digest(content)[..DIGEST_LENGTH]
+    )
+}
+
+/// Name of the directory, under `%APPDATA%`, that holds the launchers.
+pub const LAUNCHER_DIR_NAME: &str = "windows_startup_manager";
+
+/// Returns the script a `wscript.exe //B //Nologo "<script>.vbs"` registry
+/// value runs. That is how launchers are run; other `wscript.exe` command
+/// lines run scripts of their own.
+pub fn launcher_script(command: &str) -> Option<String> {
+    match cmdline::parse(command).as_slice() {
+        [program, batch, logo, script]
+            if cmdline::program_stem(program) == "wscript"
+                && batch.eq_ignore_ascii_case("//B")
+                && logo.eq_ignore_ascii_case("//Nologo")
+                && script.to_lowercase().ends_with(".vbs") =>
+        {
+            Some(script.clone())
+        }
+        _ => None,
+    }
+}
+
+/// Records which startup entry owns which launcher file.
+/// Entry names are compared case-insensitively, like registry value names,
+/// and every entry owns at most one launcher.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LauncherManifest {
+    /// Launcher file name to owning entry name.
+    launchers: BTreeMap<String, String>,
+}
+
+impl LauncherManifest {
+    /// Creates an empty manifest.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a manifest from its JSON form.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Serializes the manifest to pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Records `filename` as the launcher of `entry_name`.
+    /// Returns the launcher the entry owned before, if it was a different file.
+    pub fn record(&mut self, entry_name: &str, filename: &str) -> Option<String> {
+        let previous = self.remove_entry(entry_name);
+        self.launchers
+            .insert(filename.to_string(), entry_name.to_string());
+        previous.filter(|previous| previous != filename)
+    }
+
+    /// Forgets the launcher of `entry_name` and returns its file name.
+    pub fn remove_entry(&mut self, entry_name: &str) -> Option<String> {
+        let filename = self.launcher_for(entry_name)?.to_string();
+        self.launchers.remove(&filename);
+        Some(filename)
+    }
+
+    /// Forgets a launcher file.
+    pub fn remove_launcher(&mut self, filename: &str) -> Option<String> {
+        self.launchers.remove(filename)
+    }
+
+    /// Returns the launcher file owned by `entry_name`.
+    pub fn launcher_for(&self, entry_name: &str) -> Option<&str> {
+        self.launchers
+            .iter()
+            .find(|(_, owner)| owner.eq_ignore_ascii_case(entry_name))
+            .map(|(filename, _)| filename.as_str())
+    }
+
+    /// Returns the entry that owns a launcher file.
+    pub fn owner(&self, filename: &str) -> Option<&str> {
+        self.launchers.get(filename).map(String::as_str)
+    }
+
+    /// Iterates over `(launcher file, owning entry)` pairs.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.launchers
+            .iter()
+            .map(|(filename, owner)| (filename.as_str(), owner.as_str()))
+    }
+}
@@ -0,0 +1,105 @@
+use super::launcher::{LauncherManifest, launcher_script};
+use super::models::StartupCommand;
+use crate::shared::error::Result;
+
+/// Storage for the VBScript launchers run by VBScript-mode entries and for
+/// the manifest recording which entry owns each of them.
+/// Registry values reference launchers through [`path_of`](Self::path_of),
+/// so a value always points at the file the store wrote.
+pub trait LauncherStore {
+    /// Returns the directory holding the launchers, as written in registry values.
+    fn location(&self) -> String;
+
+    /// Reads a launcher. Returns `None` when it doesn't exist.
+    fn read(&self, filename: &str) -> Result<Option<String>>;
+
+    /// Creates or replaces a launcher.
+    fn write(&self, filename: &str, content: &str) -> Result<()>;
+
+    /// Deletes a launcher. Returns `false` when it didn't exist.
+    fn delete(&self, filename: &str) -> Result<bool>;
+
+    /// Lists the file names of all launchers, sorted.
+    fn list(&self) -> Result<Vec<String>>;
+
+    /// Reads the manifest; a store without one has an empty manifest.
+    fn load_manifest(&self) -> Result<LauncherManifest>;
+
+    /// Replaces the manifest.
+    fn save_manifest(&self, manifest: &LauncherManifest) -> Result<()>;
+
+    /// Returns the path registry values use to run `filename`.
+    fn path_of(&self, filename: &str) -> String {
+        format!(
+            "{}\\{}",
+            self.location().trim_end_matches(['\\', '/']),
+            filename
+        )
+    }
+
+    /// Returns the file name of `path` when it points into this store.
+    fn filename_of(&self, path: &str) -> Option<String> {
+        let (dir, filename) = path.rsplit_once(['\\', '/'])?;
+        same_directory(dir, &self.location()).then(|| filename.to_string())
+    }
+
+    /// Returns the launcher of this store that a registry value runs.
+    fn referenced_by(&self, command: &str) -> Option<String> {
+        self.filename_of(&launcher_script(command)?)
+    }
+
+    /// Decodes a registry value like [`StartupCommand::parse`], reading the
+    /// launchers it runs from this store.
+    fn decode(&self, command: &str) -> Option<StartupCommand> {
+        StartupCommand::parse_with(command, |path| {
+            self.read(&self.filename_of(path)?).ok().flatten()
+        })
+    }
+}
+
+impl<L: LauncherStore + ?Sized> LauncherStore for &L {
+    fn location(&self) -> String {
+        (**self).location()
+    }
+
+    fn read(&self, filename: &str) -> Result<Option<String>> {
+        (**self).read(filename)
+    }
+
+    fn write(&self, filename: &str, content: &str) -> Result<()> {
+        (**self).write(filename, content)
+    }
+
+    fn delete(&self, filename: &str) -> Result<bool> {
+        (**self).delete(filename)
+    }
+
+    fn list(&self) -> Result<Vec<String>> {
+        (**self).list()
+    }
+
+    fn load_manifest(&self) -> Result<LauncherManifest> {
+        (**self).load_manifest()
+    }
+
+    fn save_manifest(&self, manifest: &LauncherManifest) -> Result<()> {
+        (**self).save_manifest(manifest)
+    }
+
+    fn path_of(&self, filename: &str) -> String {
+        (**self).path_of(filename)
+    }
+
+    fn filename_of(&self, path: &str) -> Option<String> {
+        (**self).filename_of(path)
+    }
+}
+
+/// Compares directories case-insensitively, ignoring separator style and
+/// trailing separators.
+pub fn same_directory(a: &str, b: &str) -> bool {
+    fn normalize(dir: &str) -> String {
+        dir.replace('/', "\\").trim_end_matches('\\').to_lowercase()
+    }
+    normalize(a) == normalize(b)
+}
@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod tests {
+    use super::super::launcher::*;
+
+    #[test]
+    fn test_sanitize_entry_name() {
+        assert_eq!(sanitize_entry_name("BunDevServer"), "bundevserver");
+        assert_eq!(sanitize_entry_name("  My App (dev)  "), "my_app_dev");
+        assert_eq!(sanitize_entry_name("a\\b/c:d*e"), "a_b_c_d_e");
+        assert_eq!(sanitize_entry_name("web-api_2"), "web-api_2");
+        assert_eq!(sanitize_entry_name("日本"), "entry");
+        assert_eq!(sanitize_entry_name(&"x".repeat(100)).len(), 48);
+    }
+
+    #[test]
+    fn test_content_digest_is_sha256() {
+        assert_eq!(
+            content_digest("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_launcher_filename_is_deterministic() {
+        assert_eq!(
+            launcher_filename("Bun Dev", "abc"),
+            "bun_dev_ba7816bf8f01cfea.vbs"
+        );
+        assert_eq!(
+            launcher_filename("Bun Dev", "abc"),
+            launcher_filename("Bun Dev", "abc")
+        );
+    }
+
+    #[test]
+    fn test_launcher_filename_differs_by_entry_and_content() {
+        let a = launcher_filename("Web", "cd a & bun run dev");
+        let b = launcher_filename("Web", "cd b & bun run dev");
+        let c = launcher_filename("Api", "cd a & bun run dev");
+
+        assert_ne!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_manifest_record_replaces_previous_launcher() {
+        let mut manifest = LauncherManifest::new();
+
+        assert_eq!(manifest.record("Web", "web_1.vbs"), None);
+        assert_eq!(
+            manifest.record("web", "web_2.vbs"),
+            Some("web_1.vbs".to_string())
+        );
+        assert_eq!(manifest.record("Web", "web_2.vbs"), None);
+
+        assert_eq!(manifest.launcher_for("WEB"), Some("web_2.vbs"));
+        assert_eq!(manifest.owner("web_1.vbs"), None);
+        assert_eq!(manifest.iter().count(), 1);
+    }
+
+    #[test]
+    fn test_manifest_remove() {
+        let mut manifest = LauncherManifest::new();
+        manifest.record("Web", "web_1.vbs");
+        manifest.record("Api", "api_1.vbs");
+
+        assert_eq!(manifest.remove_entry("web"), Some("web_1.vbs".to_string()));
+        assert_eq!(manifest.remove_entry("web"), None);
+        assert_eq!(
+            manifest.remove_launcher("api_1.vbs"),
+            Some("Api".to_string())
+        );
+        assert_eq!(manifest, LauncherManifest::new());
+    }
+
+    #[test]
+    fn test_manifest_json_round_trip() {
+        let mut manifest = LauncherManifest::new();
+        manifest.record("Web \"dev\"", "web_dev_1.vbs");
+
+        let json = manifest.to_json();
+
+        assert_eq!(LauncherManifest::from_json(&json).unwrap(), manifest);
+        assert!(LauncherManifest::from_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_launcher_script() {
+        assert_eq!(
+            launcher_script(
+                "wscript.exe //B //Nologo \"%APPDATA%\\windows_startup_manager\\web_1.vbs\""
+            ),
+            Some("%APPDATA%\\windows_startup_manager\\web_1.vbs".to_string())
+        );
+        assert_eq!(
+            launcher_script(
+                "C:\\Windows\\System32\\WScript.exe //b //nologo \"C:\\scripts\\tray.vbs\""
+            ),
+            Some("C:\\scripts\\tray.vbs".to_string())
+        );
+    }
+
+    #[test]
+    fn test_launcher_script_requires_wscript_and_vbs() {
+        assert_eq!(launcher_script("cscript.exe tray.vbs"), None);
+        assert_eq!(launcher_script("wscript.exe tray.js"), None);
+        assert_eq!(launcher_script("wscript.vbs"), None);
+        assert_eq!(
+            launcher_script("wscript.exe \"C:\\scripts\\tray.vbs\""),
+            None
+        );
+        assert_eq!(launcher_script(""), None);
+    }
+}
@@ -0,0 +1,194 @@
+use super::scheduled_task::TASK_FOLDER;
+use crate::shared::error::StartupError;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// A place Windows reads startup programs from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum StartupLocation {
+    /// `Run` key of the current user.
+    #[default]
+    CurrentUserRun,
+    /// `RunOnce` key of the current user; values are deleted once they ran.
+    CurrentUserRunOnce,
+    /// `Run` key shared by all users.
+    LocalMachineRun,
+    /// `RunOnce` key shared by all users.
+    LocalMachineRunOnce,
+    /// `Run` key of the 32-bit registry view (`WOW6432Node`).
+    LocalMachineRun32,
+    /// `RunOnce` key of the 32-bit registry view (`WOW6432Node`).
+    LocalMachineRunOnce32,
+    /// Startup folder of the current user (`shell:startup`).
+    UserStartupFolder,
+    /// Startup folder shared by all users (`shell:common startup`).
+    CommonStartupFolder,
+    /// Logon tasks in the tool's Task Scheduler folder.
+    TaskScheduler,
+}
+
+impl StartupLocation {
+    /// Every location, in the order they are listed.
+    pub const ALL: [StartupLocation; 9] = [
+        StartupLocation::CurrentUserRun,
+        StartupLocation::CurrentUserRunOnce,
+        StartupLocation::LocalMachineRun,
+        StartupLocation::LocalMachineRunOnce,
+        StartupLocation::LocalMachineRun32,
+        StartupLocation::LocalMachineRunOnce32,
+        StartupLocation::UserStartupFolder,
+        StartupLocation::CommonStartupFolder,
+        StartupLocation::TaskScheduler,
+    ];
+
+    /// Returns the name used to select this location on the command line.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StartupLocation::CurrentUserRun => "hkcu-run",
+            StartupLocation::CurrentUserRunOnce => "hkcu-run-once",
+            StartupLocation::LocalMachineRun => "hklm-run",
+            StartupLocation::LocalMachineRunOnce => "hklm-run-once",
+            StartupLocation::LocalMachineRun32 => "hklm-run32",
+            StartupLocation::LocalMachineRunOnce32 => "hklm-run-once32",
+            StartupLocation::UserStartupFolder => "startup-folder",
+            StartupLocation::CommonStartupFolder => "common-startup-folder",
+            StartupLocation::TaskScheduler => "task-scheduler",
+        }
+    }
+
+    /// Returns the registry key of this location, starting with its hive,
+    /// or `None` for startup folders and scheduled tasks.
+    pub fn registry_key(&self) -> Option<&'static str> {
+        match self {
+            StartupLocation::CurrentUserRun => {
+                Some(r"HKCU\SOFTWARE\Microsoft\Windows\CurrentVersion\Run")
+            }
+            StartupLocation::CurrentUserRunOnce => {
+                Some(r"HKCU\SOFTWARE\Microsoft\Windows\CurrentVersion\RunOnce")
+            }
+            StartupLocation::LocalMachineRun => {
+                Some(r"HKLM\SOFTWARE\Microsoft\Windows\CurrentVersion\Run")
+            }
+            StartupLocation::LocalMachineRunOnce => {
+                Some(r"HKLM\SOFTWARE\Microsoft\Windows\CurrentVersion\RunOnce")
+            }
+            StartupLocation::LocalMachineRun32 => {
+                Some(r"HKLM\SOFTWARE\WOW6432Node\Microsoft\Windows\CurrentVersion\Run")
+            }
+            StartupLocation::LocalMachineRunOnce32 => {
+                Some(r"HKLM\SOFTWARE\WOW6432Node\Microsoft\Windows\CurrentVersion\RunOnce")
+            }
+            StartupLocation::UserStartupFolder
+            | StartupLocation::CommonStartupFolder
+            | StartupLocation::TaskScheduler => None,
+        }
+    }
+
+    /// Returns the directory of this location with unexpanded environment
+    /// variables, or `None` for registry keys and scheduled tasks.
+    pub fn startup_folder(&self) -> Option<&'static str> {
+        match self {
+            StartupLocation::UserStartupFolder => {
+                Some(r"%APPDATA%\Microsoft\Windows\Start Menu\Programs\Startup")
+            }
+            StartupLocation::CommonStartupFolder => {
+                Some(r"%ProgramData%\Microsoft\Windows\Start Menu\Programs\StartUp")
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the Task Scheduler folder of this location, or `None` for
+    /// registry keys and startup folders.
+    pub fn task_folder(&self) -> Option<&'static str> {
+        match self {
+            StartupLocation::TaskScheduler => Some(TASK_FOLDER),
+            _ => None,
+        }
+    }
+
+    /// Returns the key Task Manager records the enabled state of this
+    /// location's entries in, or `None` for RunOnce keys, which cannot be
+    /// disabled, and for scheduled tasks, which carry their own state.
+    /// Values under it are named after registry values or, for
+    /// startup folders, after file names.
+    pub fn startup_approved_key(&self) -> Option<&'static str> {
+        match self {
+            StartupLocation::CurrentUserRun => {
+                Some(r"HKCU\SOFTWARE\Microsoft\Windows\CurrentVersion\Explorer\StartupApproved\Run")
+            }
+            StartupLocation::LocalMachineRun => {
+                Some(r"HKLM\SOFTWARE\Microsoft\Windows\CurrentVersion\Explorer\StartupApproved\Run")
+            }
+            StartupLocation::LocalMachineRun32 => Some(
+                r"HKLM\SOFTWARE\Microsoft\Windows\CurrentVersion\Explorer\StartupApproved\Run32",
+            ),
+            StartupLocation::UserStartupFolder => Some(
+                r"HKCU\SOFTWARE\Microsoft\Windows\CurrentVersion\Explorer\StartupApproved\StartupFolder",
+            ),
+            StartupLocation::CommonStartupFolder => Some(
+                r"HKLM\SOFTWARE\Microsoft\Windows\CurrentVersion\Explorer\StartupApproved\StartupFolder",
+            ),
+            StartupLocation::CurrentUserRunOnce
+            | StartupLocation::LocalMachineRunOnce
+            | StartupLocation::LocalMachineRunOnce32
+            | StartupLocation::TaskScheduler => None,
+        }
+    }
+
+    /// Checks whether changing this location needs administrator rights.
+    pub fn requires_elevation(&self) -> bool {
+        matches!(
+            self,
+            StartupLocation::LocalMachineRun
+                | StartupLocation::LocalMachineRunOnce
+                | StartupLocation::LocalMachineRun32
+                | StartupLocation::LocalMachineRunOnce32
+                | StartupLocation::CommonStartupFolder
+        )
+    }
+}
+
+impl fmt::Display for StartupLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(folder) = self.task_folder() {
+            return write!(f, "Task Scheduler folder {}", folder);
+        }
+        let path = self.registry_key().or(self.startup_folder());
+        f.write_str(path.unwrap_or(self.as_str()))
+    }
+}
+
+impl FromStr for StartupLocation {
+    type Err = StartupError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        Self::ALL
+            .into_iter()
+            .find(|location| location.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::ALL.iter().map(|l| l.as_str()).collect();
+                StartupError::InvalidLocation(format!(
+                    "'{}' (expected one of: {})",
+                    s,
+                    names.join(", ")
+                ))
+            })
+    }
+}
+
+/// Locations are written by the name `--location` takes.
+impl Serialize for StartupLocation {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for StartupLocation {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
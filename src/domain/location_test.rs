@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use crate::shared::error::StartupError;
+
+    #[test]
+    fn test_names_round_trip() {
+        for location in StartupLocation::ALL {
+            assert_eq!(
+                location.as_str().parse::<StartupLocation>().unwrap(),
+                location
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_ignores_case() {
+        assert_eq!(
+            "HKLM-Run32".parse::<StartupLocation>().unwrap(),
+            StartupLocation::LocalMachineRun32
+        );
+    }
+
+    #[test]
+    fn test_parse_unknown_lists_choices() {
+        let result = "hkcu".parse::<StartupLocation>();
+
+        match result {
+            Err(StartupError::InvalidLocation(msg)) => {
+                assert!(msg.contains("'hkcu'"));
+                assert!(msg.contains("hkcu-run-once"));
+            }
+            other => panic!("Expected InvalidLocation, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_every_location_is_a_key_a_folder_or_a_task_folder() {
+        for location in StartupLocation::ALL {
+            let kinds = [
+                location.registry_key().is_some(),
+                location.startup_folder().is_some(),
+                location.task_folder().is_some(),
+            ];
+            assert_eq!(
+                kinds.iter().filter(|&&kind| kind).count(),
+                1,
+                "{:?}",
+                location
+            );
+        }
+    }
+
+    #[test]
+    fn test_task_scheduler_displays_its_folder() {
+        assert_eq!(
+            StartupLocation::TaskScheduler.to_string(),
+            r"Task Scheduler folder \windows_startup_manager\"
+        );
+    }
+
+    #[test]
+    fn test_32_bit_views_use_wow6432node() {
+        assert_eq!(
+            StartupLocation::LocalMachineRunOnce32.to_string(),
+            r"HKLM\SOFTWARE\WOW6432Node\Microsoft\Windows\CurrentVersion\RunOnce"
+        );
+    }
+
+    #[test]
+    fn test_only_per_user_locations_need_no_elevation() {
+        let unelevated: Vec<_> = StartupLocation::ALL
+            .into_iter()
+            .filter(|l| !l.requires_elevation())
+            .collect();
+
+        assert_eq!(
+            unelevated,
+            vec![
+                StartupLocation::CurrentUserRun,
+                StartupLocation::CurrentUserRunOnce,
+                StartupLocation::UserStartupFolder,
+                StartupLocation::TaskScheduler,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_entries_default_to_current_user_run() {
+        let entry = StartupEntry::new("App", "app.exe");
+        assert_eq!(entry.location, StartupLocation::CurrentUserRun);
+
+        let entry = entry.with_location(StartupLocation::UserStartupFolder);
+        assert_eq!(entry.location, StartupLocation::UserStartupFolder);
+    }
+}
@@ -1,12 +1,84 @@
+pub mod approval_store;
+pub mod audit;
+pub mod audit_log;
+pub mod cmdline;
+pub mod history;
+pub mod history_store;
+pub mod launcher;
+pub mod launcher_store;
+pub mod location;
+pub mod mode_spec;
 pub mod models;
+pub mod process;
+pub mod process_matcher;
+pub mod reg_file;
 pub mod repository;
+pub mod scheduled_task;
+pub mod shell_link;
+pub mod startup_approved;
+pub mod startup_backup;
+pub mod startup_manifest;
+pub mod task_scheduler;
 pub mod validator;
+pub mod xml;
 
+#[cfg(test)]
+mod audit_test;
+#[cfg(test)]
+mod cmdline_test;
+#[cfg(test)]
+mod history_test;
+#[cfg(test)]
+mod launcher_test;
+#[cfg(test)]
+mod location_test;
+#[cfg(test)]
+mod mode_spec_test;
 #[cfg(test)]
 mod models_test;
 #[cfg(test)]
+mod process_matcher_test;
+#[cfg(test)]
+mod process_test;
+#[cfg(test)]
+mod reg_file_test;
+#[cfg(test)]
+mod scheduled_task_test;
+#[cfg(test)]
+mod shell_link_test;
+#[cfg(test)]
+mod startup_approved_test;
+#[cfg(test)]
+mod startup_backup_test;
+#[cfg(test)]
+mod startup_manifest_test;
+#[cfg(test)]
 mod validator_test;
+#[cfg(test)]
+mod xml_test;
 
-pub use models::{ExecutionMode, StartupCommand, StartupEntry};
+pub use approval_store::ApprovalStore;
+pub use audit::{AuditAction, AuditActor, AuditEvent, AuditFilter, AuditOutcome};
+pub use audit_log::AuditLog;
+pub use history::{EntrySnapshot, HISTORY_LIMIT, History, HistoryRecord, SnapshotChange};
+pub use history_store::HistoryStore;
+pub use launcher::{
+    LAUNCHER_DIR_NAME, LauncherManifest, launcher_filename, launcher_script, sanitize_entry_name,
+};
+pub use launcher_store::LauncherStore;
+pub use location::StartupLocation;
+pub use mode_spec::{ModeName, ModeSpec};
+pub use models::{
+    ConsoleOptions, ExecutionMode, PowerShellOptions, StartupCommand, StartupEntry, WindowStyle,
+};
+pub use process::{ProcessInfo, ProcessProvider, process_tree};
+pub use process_matcher::{MatchStrategy, ProcessMatcher, extract_executable_name};
+pub use reg_file::{REG_HEADER, RegChange, RegFile, RegKey, RegValue};
 pub use repository::StartupRepository;
+pub use scheduled_task::{RestartPolicy, TASK_FOLDER, TaskDefinition, TaskSettings};
+pub use shell_link::{ShellLink, ShowCommand};
+pub use startup_approved::StartupApproval;
+pub use startup_backup::{BACKUP_VERSION, BackupEntry, BackupLauncher, StartupBackup};
+pub use startup_manifest::{DesiredEntry, ManifestEntry, ManifestFormat, StartupManifest};
+pub use task_scheduler::TaskScheduler;
 pub use validator::StartupValidator;
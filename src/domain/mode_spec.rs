@@ -0,0 +1,167 @@
+use super::models::{ConsoleOptions, ExecutionMode, PowerShellOptions, WindowStyle};
+use super::scheduled_task::{RestartPolicy, TaskSettings};
+use std::time::Duration;
+
+/// Restart interval of scheduled tasks that set a restart count only.
+pub const DEFAULT_RESTART_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Names of the execution modes, as `add-command --mode` and manifests
+/// spell them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ModeName {
+    #[default]
+    Vbscript,
+    Powershell,
+    Visible,
+    ScheduledTask,
+}
+
+impl ModeName {
+    /// Every mode name.
+    pub const ALL: [ModeName; 4] = [
+        ModeName::Vbscript,
+        ModeName::Powershell,
+        ModeName::Visible,
+        ModeName::ScheduledTask,
+    ];
+
+    /// Returns the name of the mode.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModeName::Vbscript => "vbscript",
+            ModeName::Powershell => "powershell",
+            ModeName::Visible => "visible",
+            ModeName::ScheduledTask => "scheduled-task",
+        }
+    }
+
+    /// Looks a mode up by name, ignoring case.
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.as_str().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+/// An execution mode together with the options of every mode, as they are
+/// given on the command line or in a manifest. Options that don't apply to
+/// the chosen mode are rejected rather than ignored.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ModeSpec {
+    pub mode: ModeName,
+    /// Window style of the powershell mode.
+    pub window: Option<WindowStyle>,
+    /// Keeps the powershell or visible window open.
+    pub no_exit: bool,
+    pub delay: Option<Duration>,
+    pub restart_count: Option<u32>,
+    pub restart_interval: Option<Duration>,
+    pub hidden: bool,
+    pub highest: bool,
+}
+
+impl ModeSpec {
+    /// Creates a spec of `mode` without options.
+    pub fn new(mode: ModeName) -> Self {
+        Self {
+            mode,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the names of the options that are set but don't apply to the mode.
+    pub fn unused_options(&self) -> Vec<&'static str> {
+        let options = [
+            (
+                self.window.is_some() && self.mode != ModeName::Powershell,
+                "window",
+            ),
+            (
+                self.no_exit && !matches!(self.mode, ModeName::Powershell | ModeName::Visible),
+                "no-exit",
+            ),
+        ];
+        let task_options = [
+            (self.delay.is_some(), "delay"),
+            (self.restart_count.is_some(), "restart-count"),
+            (self.restart_interval.is_some(), "restart-interval"),
+            (self.hidden, "hidden"),
+            (self.highest, "highest"),
+        ];
+        let not_a_task = self.mode != ModeName::ScheduledTask;
+
+        options
+            .into_iter()
+            .chain(task_options.map(|(set, name)| (set && not_a_task, name)))
+            .filter(|(unused, _)| *unused)
+            .map(|(_, name)| name)
+            .collect()
+    }
+
+    /// Builds the execution mode. Fails with a message naming the options
+    /// in the wrong place when some don't apply to the mode.
+    pub fn execution_mode(&self) -> Result<ExecutionMode, String> {
+        let unused = self.unused_options();
+        if !unused.is_empty() {
+            return Err(format!(
+                "{} cannot be used with mode {}",
+                unused.join(", "),
+                self.mode.as_str()
+            ));
+        }
+
+        Ok(match self.mode {
+            ModeName::Vbscript => ExecutionMode::VBScript,
+            ModeName::Powershell => ExecutionMode::PowerShell(PowerShellOptions {
+                window: self.window.unwrap_or_default(),
+                no_exit: self.no_exit,
+            }),
+            ModeName::Visible => ExecutionMode::Visible(ConsoleOptions {
+                no_exit: self.no_exit,
+            }),
+            ModeName::ScheduledTask => {
+                let restart = match (self.restart_count, self.restart_interval) {
+                    (Some(count), interval) => Some(RestartPolicy {
+                        count,
+                        interval: interval.unwrap_or(DEFAULT_RESTART_INTERVAL),
+                    }),
+                    (None, Some(_)) => {
+                        return Err("restart-interval requires restart-count".to_string());
+                    }
+                    (None, None) => None,
+                };
+                ExecutionMode::ScheduledTask(TaskSettings {
+                    delay: self.delay,
+                    restart,
+                    hidden: self.hidden,
+                    highest_privileges: self.highest,
+                })
+            }
+        })
+    }
+
+    /// Describes an execution mode with the mode's options set, so that
+    /// [`execution_mode`](Self::execution_mode) returns it again.
+    pub fn from_mode(mode: &ExecutionMode) -> Self {
+        match mode {
+            ExecutionMode::VBScript => Self::new(ModeName::Vbscript),
+            ExecutionMode::PowerShell(options) => Self {
+                window: Some(options.window),
+                no_exit: options.no_exit,
+                ..Self::new(ModeName::Powershell)
+            },
+            ExecutionMode::Visible(options) => Self {
+                no_exit: options.no_exit,
+                ..Self::new(ModeName::Visible)
+            },
+            ExecutionMode::ScheduledTask(settings) => Self {
+                delay: settings.delay,
+                restart_count: settings.restart.map(|restart| restart.count),
+                restart_interval: settings.restart.map(|restart| restart.interval),
+                hidden: settings.hidden,
+                highest: settings.highest_privileges,
+                ..Self::new(ModeName::ScheduledTask)
+            },
+        }
+    }
+}
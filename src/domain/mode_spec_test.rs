@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod tests {
+    use super::super::mode_spec::*;
+    use super::super::{
+        ConsoleOptions, ExecutionMode, PowerShellOptions, RestartPolicy, TaskSettings, WindowStyle,
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn test_names_round_trip() {
+        for mode in ModeName::ALL {
+            assert_eq!(ModeName::parse(mode.as_str()), Some(mode));
+        }
+        assert_eq!(
+            ModeName::parse("Scheduled-Task"),
+            Some(ModeName::ScheduledTask)
+        );
+        assert_eq!(ModeName::parse("silent"), None);
+    }
+
+    #[test]
+    fn test_modes_round_trip() {
+        let modes = [
+            ExecutionMode::VBScript,
+            ExecutionMode::PowerShell(PowerShellOptions {
+                window: WindowStyle::Normal,
+                no_exit: true,
+            }),
+            ExecutionMode::Visible(ConsoleOptions { no_exit: true }),
+            ExecutionMode::ScheduledTask(TaskSettings {
+                delay: Some(Duration::from_secs(30)),
+                restart: Some(RestartPolicy {
+                    count: 2,
+                    interval: Duration::from_secs(300),
+                }),
+                hidden: true,
+                highest_privileges: false,
+            }),
+        ];
+
+        for mode in modes {
+            assert_eq!(ModeSpec::from_mode(&mode).execution_mode(), Ok(mode));
+        }
+    }
+
+    #[test]
+    fn test_options_of_other_modes_are_unused() {
+        let spec = ModeSpec {
+            window: Some(WindowStyle::Minimized),
+            no_exit: true,
+            hidden: true,
+            ..ModeSpec::new(ModeName::Visible)
+        };
+
+        assert_eq!(spec.unused_options(), ["window", "hidden"]);
+        assert_eq!(
+            spec.execution_mode(),
+            Err("window, hidden cannot be used with mode visible".to_string())
+        );
+    }
+
+    #[test]
+    fn test_restart_interval_requires_a_count() {
+        let spec = ModeSpec {
+            restart_interval: Some(Duration::from_secs(120)),
+            ..ModeSpec::new(ModeName::ScheduledTask)
+        };
+
+        assert!(spec.execution_mode().is_err());
+    }
+}
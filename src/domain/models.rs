@@ -1,18 +1,104 @@
+use super::cmdline;
+use super::launcher::{launcher_filename, launcher_script};
+use super::launcher_store::LauncherStore;
+use super::location::StartupLocation;
+use super::scheduled_task::{TaskDefinition, TaskSettings};
+use super::startup_approved::StartupApproval;
+use chrono::{DateTime, Utc};
+
 /// Represents a startup entry in the Windows registry.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StartupEntry {
     pub name: String,
     pub command: String,
+    /// Where the entry is stored.
+    pub location: StartupLocation,
+    /// Whether Windows runs the entry at logon.
+    pub enabled: bool,
+    /// When the entry was disabled, if it is and the time is known.
+    pub disabled_at: Option<DateTime<Utc>>,
 }
 
 impl StartupEntry {
-    /// Creates a new startup entry.
+    /// Creates a new startup entry in the current user's Run key.
     pub fn new(name: impl Into<String>, command: impl Into<String>) -> Self {
         Self {
             name: name.into(),
             command: command.into(),
+            location: StartupLocation::default(),
+            enabled: true,
+            disabled_at: None,
         }
     }
+
+    /// Sets where the entry is stored.
+    pub fn with_location(mut self, location: StartupLocation) -> Self {
+        self.location = location;
+        self
+    }
+
+    /// Sets whether the entry is enabled.
+    pub fn with_approval(mut self, approval: StartupApproval) -> Self {
+        self.enabled = approval.enabled;
+        self.disabled_at = approval.disabled_at;
+        self
+    }
+
+    /// Returns whether the entry is enabled.
+    pub fn approval(&self) -> StartupApproval {
+        StartupApproval {
+            enabled: self.enabled,
+            disabled_at: self.disabled_at,
+        }
+    }
+}
+
+/// Window style of a PowerShell launcher, as passed to `-WindowStyle`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WindowStyle {
+    #[default]
+    Hidden,
+    Minimized,
+    Normal,
+}
+
+impl WindowStyle {
+    /// Returns the value PowerShell's `-WindowStyle` takes.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WindowStyle::Hidden => "Hidden",
+            WindowStyle::Minimized => "Minimized",
+            WindowStyle::Normal => "Normal",
+        }
+    }
+
+    /// Looks a window style up by name, ignoring case.
+    pub fn parse(value: &str) -> Option<Self> {
+        [
+            WindowStyle::Hidden,
+            WindowStyle::Minimized,
+            WindowStyle::Normal,
+        ]
+        .into_iter()
+        .find(|style| style.as_str().eq_ignore_ascii_case(value))
+    }
+}
+
+/// Options of entries launched through PowerShell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PowerShellOptions {
+    pub window: WindowStyle,
+    /// Keeps PowerShell running after the command exits (`-NoExit`), so
+    /// its output can be read when debugging.
+    pub no_exit: bool,
+}
+
+/// Options of entries run in a visible console.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ConsoleOptions {
+    /// Keeps the console open after the command exits (`cmd.exe /k`), so
+    /// its output can be read when debugging.
+    pub no_exit: bool,
 }
 
 /// Execution mode for background commands.
@@ -21,19 +107,19 @@ pub enum ExecutionMode {
     /// Silent execution using VBScript (most reliable, no window flash).
     #[default]
     VBScript,
-    /// Silent execution using PowerShell with hidden window.
-    #[allow(dead_code)]
-    PowerShellHidden,
+    /// Execution through PowerShell, with a hidden window by default.
+    PowerShell(PowerShellOptions),
     /// Visible window (for debugging).
-    #[allow(dead_code)]
-    Visible,
+    Visible(ConsoleOptions),
+    /// Task Scheduler logon task, which can be delayed, restarted on
+    /// failure and run elevated.
+    ScheduledTask(TaskSettings),
 }
 
 /// Represents different types of startup commands.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum StartupCommand {
     /// A simple executable path.
-    #[allow(dead_code)]
     Executable { path: String },
     /// A command with arguments and optional working directory.
     CommandWithArgs {
@@ -45,8 +131,232 @@ pub enum StartupCommand {
 }
 
 impl StartupCommand {
+    /// Decodes a registry value back into the command it runs.
+    /// Recognizes the VBScript, PowerShell and `cmd /c cd /d` shapes and the
+    /// task definitions produced by [`to_registry_value`](Self::to_registry_value),
+    /// reading VBScript launchers from disk; any other value is a plain
+    /// executable with arguments.
+    /// Returns `None` for blank values and for launchers that cannot be read.
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::parse_with(value, |path| {
+            std::fs::read_to_string(expand_environment(path)).ok()
+        })
+    }
+
+    /// Same as [`parse`](Self::parse), reading launcher scripts with `read_launcher`.
+    pub fn parse_with(
+        value: &str,
+        read_launcher: impl FnOnce(&str) -> Option<String>,
+    ) -> Option<Self> {
+        let value = value.trim();
+        if value.is_empty() {
+            return None;
+        }
+        if value.trim_start_matches('\u{feff}').starts_with('<') {
+            return TaskDefinition::from_xml(value).ok().map(Self::from_task);
+        }
+
+        let args = cmdline::parse(value);
+        let decoded = match cmdline::program_stem(&args[0]).as_str() {
+            "wscript" => match launcher_script(value) {
+                Some(script) => Self::parse_launcher(&read_launcher(&script)?),
+                None => None,
+            },
+            "powershell" => Self::parse_powershell(&args),
+            "cmd" => Self::parse_cmd(value),
+            _ => None,
+        };
+
+        Some(decoded.unwrap_or_else(|| Self::parse_plain(value, args)))
+    }
+
+    /// Decodes the command a VBScript launcher runs.
+    fn parse_launcher(content: &str) -> Option<Self> {
+        let mut command_line = None;
+        let mut workdir = None;
+        for line in content.lines().map(str::trim) {
+            if let Some(rest) = line.strip_prefix("WshShell.CurrentDirectory = ") {
+                workdir = vbs_string_literal(rest);
+            } else if let Some(rest) = line.strip_prefix("WshShell.Run ") {
+                command_line = vbs_string_literal(rest);
+            }
+        }
+
+        let mut args = cmdline::parse(&command_line?);
+        if args.is_empty() {
+            return None;
+        }
+        let command = args.remove(0);
+        Some(StartupCommand::CommandWithArgs {
+            command,
+            args,
+            workdir,
+            mode: ExecutionMode::VBScript,
+        })
+    }
+
+    /// Decodes `powershell.exe -WindowStyle <style> [-NoExit] -NoProfile -Command "<script>"`.
+    fn parse_powershell(args: &[String]) -> Option<Self> {
+        let [_, style_flag, style, flags @ .., command_flag, script] = args else {
+            return None;
+        };
+        let no_exit = match flags {
+            [no_profile] if no_profile.eq_ignore_ascii_case("-NoProfile") => false,
+            [no_exit, no_profile]
+                if no_exit.eq_ignore_ascii_case("-NoExit")
+                    && no_profile.eq_ignore_ascii_case("-NoProfile") =>
+            {
+                true
+            }
+            _ => return None,
+        };
+        if !style_flag.eq_ignore_ascii_case("-WindowStyle")
+            || !command_flag.eq_ignore_ascii_case("-Command")
+        {
+            return None;
+        }
+        let window = WindowStyle::parse(style)?;
+
+        let mut rest = script.trim_start();
+        let mut workdir = None;
+        if let Some(location) = strip_prefix_ignore_case(rest, "Set-Location ") {
+            let location = location.trim_start();
+            let location = strip_prefix_ignore_case(location, "-LiteralPath ").unwrap_or(location);
+            let (dir, after) = powershell_word(location.trim_start())?;
+            workdir = Some(dir);
+            rest = after.trim_start().strip_prefix(';')?.trim_start();
+        }
+        let rest = rest.strip_prefix("& ").unwrap_or(rest);
+
+        let mut words = Vec::new();
+        let mut rest = rest.trim_start();
+        while !rest.is_empty() {
+            let (word, after) = powershell_word(rest)?;
+            words.push(word);
+            rest = after.trim_start();
+        }
+        if words.is_empty() {
+            return None;
+        }
+
+        let command = words.remove(0);
+        Some(StartupCommand::CommandWithArgs {
+            command,
+            args: words,
+            workdir,
+            mode: ExecutionMode::PowerShell(PowerShellOptions { window, no_exit }),
+        })
+    }
+
+    /// Decodes `cmd.exe /c "cd /d "<dir>" && <command line>"`, and the
+    /// `cmd.exe /k` form that keeps the console open, with or without
+    /// the `cd /d` prefix.
+    fn parse_cmd(value: &str) -> Option<Self> {
+        let lower = value.to_ascii_lowercase();
+        let (switch, no_exit) = match (lower.find(" /c "), lower.find(" /k ")) {
+            (Some(c), Some(k)) if k < c => (k, true),
+            (Some(c), _) => (c, false),
+            (None, Some(k)) => (k, true),
+            (None, None) => return None,
+        };
+        let rest = value[switch + 4..].trim_start();
+        let rest = rest.strip_prefix('"')?.strip_suffix('"')?;
+
+        let (workdir, inner) = match rest.strip_prefix("cd /d \"") {
+            Some(rest) => {
+                let (dir, rest) = rest.split_once('"')?;
+                (Some(dir.to_string()), rest.strip_prefix(" && ")?)
+            }
+            // Without a directory only the console kept open needs cmd.exe
+            None if no_exit => (None, rest),
+            None => return None,
+        };
+
+        let mut args = cmdline::parse(&cmdline::unescape_for_cmd(inner));
+        if args.is_empty() {
+            return None;
+        }
+        let command = args.remove(0);
+        Some(StartupCommand::CommandWithArgs {
+            command,
+            args,
+            workdir,
+            mode: ExecutionMode::Visible(ConsoleOptions { no_exit }),
+        })
+    }
+
+    /// Decodes the program a logon task starts.
+    fn from_task(task: TaskDefinition) -> Self {
+        // The program is known, so only the arguments need splitting
+        let mut args = cmdline::parse(&format!("program {}", task.arguments));
+        args.remove(0);
+        StartupCommand::CommandWithArgs {
+            command: task.command,
+            args,
+            workdir: task.working_dir,
+            mode: ExecutionMode::ScheduledTask(task.settings),
+        }
+    }
+
+    /// Decodes a value the tool did not wrap.
+    fn parse_plain(value: &str, mut args: Vec<String>) -> Self {
+        // Windows runs unquoted paths with spaces, like `C:\Program Files\app.exe`
+        let lower = value.to_ascii_lowercase();
+        let is_unquoted_path = !value.contains('"')
+            && [".exe", ".com", ".bat", ".cmd"]
+                .iter()
+                .any(|ext| lower.ends_with(ext));
+
+        if is_unquoted_path || args.len() == 1 {
+            let path = if is_unquoted_path {
+                value.to_string()
+            } else {
+                args.remove(0)
+            };
+            return StartupCommand::Executable { path };
+        }
+
+        let command = args.remove(0);
+        StartupCommand::CommandWithArgs {
+            command,
+            args,
+            workdir: None,
+            mode: ExecutionMode::Visible(ConsoleOptions::default()),
+        }
+    }
+
+    /// Returns the command line of the program this command starts.
+    pub fn command_line(&self) -> String {
+        match self {
+            StartupCommand::Executable { path } => cmdline::quote_program(path),
+            StartupCommand::CommandWithArgs { command, args, .. } => cmdline::quote(command, args),
+        }
+    }
+
+    /// Returns the directory the program is started in, if one is set.
+    pub fn workdir(&self) -> Option<&str> {
+        match self {
+            StartupCommand::Executable { .. } => None,
+            StartupCommand::CommandWithArgs { workdir, .. } => workdir.as_deref(),
+        }
+    }
+
+    /// Returns how the command is launched, or `None` for a bare executable.
+    pub fn mode(&self) -> Option<ExecutionMode> {
+        match self {
+            StartupCommand::Executable { .. } => None,
+            StartupCommand::CommandWithArgs { mode, .. } => Some(*mode),
+        }
+    }
+
     /// Converts the command to a registry-compatible string based on execution mode.
-    pub fn to_registry_value(&self) -> String {
+    /// VBScript commands run the launcher named after `entry_name` in `launchers`;
+    /// scheduled tasks are stored as their XML definition.
+    pub fn to_registry_value<L: LauncherStore + ?Sized>(
+        &self,
+        entry_name: &str,
+        launchers: &L,
+    ) -> String {
         match self {
             StartupCommand::Executable { path } => path.clone(),
             StartupCommand::CommandWithArgs {
@@ -54,85 +364,132 @@ impl StartupCommand {
                 args,
                 workdir,
                 mode,
-            } => {
-                let command_string = if args.is_empty() {
-                    command.clone()
-                } else {
-                    format!("{} {}", command, args.join(" "))
-                };
-
-                match mode {
-                    ExecutionMode::VBScript => {
-                        // VBScript provides the most reliable silent execution
-                        self.generate_vbscript_wrapper(&command_string, workdir.as_deref())
-                    }
-                    ExecutionMode::PowerShellHidden => {
-                        // PowerShell with hidden window
-                        if let Some(dir) = workdir {
-                            format!(
-                                "powershell.exe -WindowStyle Hidden -NoProfile -Command \"Set-Location '{}'; {}\"",
-                                dir, command_string
-                            )
-                        } else {
-                            format!(
-                                "powershell.exe -WindowStyle Hidden -NoProfile -Command \"{}\"",
-                                command_string
-                            )
-                        }
+            } => match mode {
+                ExecutionMode::VBScript => {
+                    // VBScript provides the most reliable silent execution
+                    let content =
+                        Self::vbscript_content(&cmdline::quote(command, args), workdir.as_deref());
+                    Self::launcher_command(
+                        &launchers.path_of(&launcher_filename(entry_name, &content)),
+                    )
+                }
+                ExecutionMode::PowerShell(options) => {
+                    // The script is one argument
+                    let script = Self::powershell_script(command, args, workdir.as_deref());
+                    let mut arguments = vec!["-WindowStyle", options.window.as_str()];
+                    if options.no_exit {
+                        arguments.push("-NoExit");
                     }
-                    ExecutionMode::Visible => {
-                        // Visible window for debugging
-                        if let Some(dir) = workdir {
-                            format!("cmd.exe /c \"cd /d \"{}\" && {}\"", dir, command_string)
-                        } else {
-                            command_string
+                    arguments.extend(["-NoProfile", "-Command", &script]);
+                    cmdline::quote("powershell.exe", &arguments)
+                }
+                ExecutionMode::Visible(options) => {
+                    // Visible window for debugging
+                    let command_line = cmdline::quote(command, args);
+                    let switch = if options.no_exit { "/k" } else { "/c" };
+                    match workdir {
+                        Some(dir) => format!(
+                            "cmd.exe {} \"cd /d \"{}\" && {}\"",
+                            switch,
+                            dir,
+                            cmdline::escape_for_cmd(&command_line)
+                        ),
+                        None if options.no_exit => {
+                            format!("cmd.exe /k \"{}\"", cmdline::escape_for_cmd(&command_line))
                         }
+                        None => command_line,
                     }
                 }
-            }
+                ExecutionMode::ScheduledTask(settings) => {
+                    Self::task(command, args, workdir.as_deref(), *settings).to_xml()
+                }
+            },
         }
     }
 
+    /// Returns the logon task running the command.
+    /// Only applicable for scheduled task execution mode.
+    pub fn task_definition(&self) -> Option<TaskDefinition> {
+        match self {
+            StartupCommand::CommandWithArgs {
+                command,
+                args,
+                workdir,
+                mode: ExecutionMode::ScheduledTask(settings),
+            } => Some(Self::task(command, args, workdir.as_deref(), *settings)),
+            _ => None,
+        }
+    }
+
+    fn task(
+        command: &str,
+        args: &[String],
+        workdir: Option<&str>,
+        settings: TaskSettings,
+    ) -> TaskDefinition {
+        let arguments: Vec<String> = args.iter().map(|arg| cmdline::quote_arg(arg)).collect();
+        TaskDefinition {
+            arguments: arguments.join(" "),
+            working_dir: workdir.map(str::to_string),
+            settings,
+            ..TaskDefinition::new(command)
+        }
+    }
+
+    /// Builds the PowerShell script that changes to `workdir` and runs the command.
+    fn powershell_script(command: &str, args: &[String], workdir: Option<&str>) -> String {
+        let program = cmdline::quote_powershell(command);
+        // A quoted program is a string expression unless invoked with `&`
+        let mut script = if program.starts_with('\'') {
+            format!("& {}", program)
+        } else {
+            program
+        };
+        for arg in args {
+            script.push(' ');
+            script.push_str(&cmdline::quote_powershell(arg));
+        }
+
+        match workdir {
+            Some(dir) => format!(
+                "Set-Location -LiteralPath {}; {}",
+                cmdline::quote_powershell(dir),
+                script
+            ),
+            None => script,
+        }
+    }
+
+    /// Returns the registry value that runs a VBScript launcher silently.
+    fn launcher_command(path: &str) -> String {
+        // Note: The VBScript file needs to be created separately
+        format!("wscript.exe //B //Nologo \"{}\"", path)
+    }
+
     /// Generates a VBScript wrapper for truly silent execution.
     /// This is the most reliable method to avoid any window flash.
-    fn generate_vbscript_wrapper(&self, command: &str, workdir: Option<&str>) -> String {
-        let _vbs_content = if let Some(dir) = workdir {
+    fn vbscript_content(command_line: &str, workdir: Option<&str>) -> String {
+        if let Some(dir) = workdir {
             format!(
                 "Set WshShell = CreateObject(\"WScript.Shell\")\n\
                  WshShell.CurrentDirectory = \"{}\"\n\
                  WshShell.Run \"{}\", 0, False",
                 dir.replace("\"", "\"\""),
-                command.replace("\"", "\"\"")
+                command_line.replace("\"", "\"\"")
             )
         } else {
             format!(
                 "Set WshShell = CreateObject(\"WScript.Shell\")\n\
                  WshShell.Run \"{}\", 0, False",
-                command.replace("\"", "\"\"")
+                command_line.replace("\"", "\"\"")
             )
-        };
-
-        // Create a unique VBScript filename based on command hash
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-
-        let mut hasher = DefaultHasher::new();
-        command.hash(&mut hasher);
-        let hash = hasher.finish();
-
-        let vbs_path = format!(
-            "%APPDATA%\\windows_startup_manager\\launcher_{:x}.vbs",
-            hash
-        );
-
-        // Note: The VBScript file needs to be created separately
-        // This returns the command to execute the VBScript
-        format!("wscript.exe //B //Nologo \"{}\"", vbs_path)
+        }
     }
 
-    /// Returns the VBScript content that needs to be written to disk.
+    /// Returns the launcher file name and VBScript content that need to be
+    /// written to disk for the entry `entry_name`.
     /// Only applicable for VBScript execution mode.
-    pub fn get_vbscript_content(&self) -> Option<(String, String)> {
+    pub fn get_vbscript_content(&self, entry_name: &str) -> Option<(String, String)> {
         match self {
             StartupCommand::CommandWithArgs {
                 command,
@@ -140,41 +497,82 @@ impl StartupCommand {
                 workdir,
                 mode: ExecutionMode::VBScript,
             } => {
-                let command_string = if args.is_empty() {
-                    command.clone()
-                } else {
-                    format!("{} {}", command, args.join(" "))
-                };
-
-                let vbs_content = if let Some(dir) = workdir {
-                    format!(
-                        "Set WshShell = CreateObject(\"WScript.Shell\")\n\
-                         WshShell.CurrentDirectory = \"{}\"\n\
-                         WshShell.Run \"{}\", 0, False",
-                        dir.replace("\"", "\"\""),
-                        command_string.replace("\"", "\"\"")
-                    )
-                } else {
-                    format!(
-                        "Set WshShell = CreateObject(\"WScript.Shell\")\n\
-                         WshShell.Run \"{}\", 0, False",
-                        command_string.replace("\"", "\"\"")
-                    )
-                };
+                let content =
+                    Self::vbscript_content(&cmdline::quote(command, args), workdir.as_deref());
+                Some((launcher_filename(entry_name, &content), content))
+            }
+            _ => None,
+        }
+    }
+}
 
-                // Generate filename
-                use std::collections::hash_map::DefaultHasher;
-                use std::hash::{Hash, Hasher};
+fn strip_prefix_ignore_case<'t>(text: &'t str, prefix: &str) -> Option<&'t str> {
+    let head = text.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix)
+        .then(|| &text[prefix.len()..])
+}
 
-                let mut hasher = DefaultHasher::new();
-                command_string.hash(&mut hasher);
-                let hash = hasher.finish();
+/// Reads one PowerShell word from the start of `text`: a single-quoted
+/// literal (with `''` escapes) or a bare word. Returns the word and the rest.
+fn powershell_word(text: &str) -> Option<(String, &str)> {
+    let Some(literal) = text.strip_prefix('\'') else {
+        let end = text
+            .find(|c: char| c.is_whitespace() || c == ';')
+            .unwrap_or(text.len());
+        return (end > 0).then(|| (text[..end].to_string(), &text[end..]));
+    };
 
-                let filename = format!("launcher_{:x}.vbs", hash);
+    let mut word = String::new();
+    let mut chars = literal.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if c != '\'' {
+            word.push(c);
+        } else if chars.peek().is_some_and(|&(_, next)| next == '\'') {
+            chars.next();
+            word.push('\'');
+        } else {
+            return Some((word, &literal[i + 1..]));
+        }
+    }
+    None
+}
 
-                Some((filename, vbs_content))
+/// Reads the leading VBScript string literal from `text`, unescaping `""`.
+fn vbs_string_literal(text: &str) -> Option<String> {
+    let mut chars = text.strip_prefix('"')?.chars().peekable();
+    let mut value = String::new();
+    while let Some(c) = chars.next() {
+        if c == '"' {
+            if chars.peek() == Some(&'"') {
+                chars.next();
+                value.push('"');
+            } else {
+                return Some(value);
             }
-            _ => None,
+        } else {
+            value.push(c);
+        }
+    }
+    None
+}
+
+/// Expands `%VAR%` references using the current environment.
+pub(crate) fn expand_environment(path: &str) -> String {
+    let mut result = String::new();
+    let mut rest = path;
+    while let Some(start) = rest.find('%') {
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('%') else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        let name = &after[..end];
+        match std::env::var(name) {
+            Ok(value) => result.push_str(&value),
+            Err(_) => result.push_str(&rest[start..start + end + 2]),
         }
+        rest = &after[end + 1..];
     }
+    result.push_str(rest);
+    result
 }
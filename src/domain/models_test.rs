@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod tests {
     use super::super::*;
+    use crate::domain::cmdline;
+    use crate::infrastructure::InMemoryLauncherStore;
 
     #[test]
     fn test_startup_entry_creation() {
@@ -35,7 +37,7 @@ mod tests {
             mode: ExecutionMode::VBScript,
         };
 
-        let registry_value = command.to_registry_value();
+        let registry_value = command.to_registry_value("Test", &InMemoryLauncherStore::new());
 
         assert!(registry_value.contains("wscript.exe"));
         assert!(registry_value.contains("//B"));
@@ -52,7 +54,7 @@ mod tests {
             mode: ExecutionMode::VBScript,
         };
 
-        let registry_value = command.to_registry_value();
+        let registry_value = command.to_registry_value("Test", &InMemoryLauncherStore::new());
 
         assert!(registry_value.contains("wscript.exe"));
         assert!(registry_value.contains("//B"));
@@ -65,10 +67,10 @@ mod tests {
             command: "python".to_string(),
             args: vec!["-m".to_string(), "http.server".to_string()],
             workdir: Some("C:\\www".to_string()),
-            mode: ExecutionMode::PowerShellHidden,
+            mode: ExecutionMode::PowerShell(PowerShellOptions::default()),
         };
 
-        let registry_value = command.to_registry_value();
+        let registry_value = command.to_registry_value("Test", &InMemoryLauncherStore::new());
 
         assert!(registry_value.contains("powershell.exe"));
         assert!(registry_value.contains("-WindowStyle Hidden"));
@@ -84,10 +86,10 @@ mod tests {
             command: "cmd".to_string(),
             args: vec!["/c".to_string(), "echo test".to_string()],
             workdir: Some("C:\\temp".to_string()),
-            mode: ExecutionMode::Visible,
+            mode: ExecutionMode::Visible(ConsoleOptions::default()),
         };
 
-        let registry_value = command.to_registry_value();
+        let registry_value = command.to_registry_value("Test", &InMemoryLauncherStore::new());
 
         assert!(registry_value.contains("cmd.exe /c"));
         assert!(registry_value.contains("cd /d"));
@@ -108,13 +110,13 @@ mod tests {
             mode: ExecutionMode::VBScript,
         };
 
-        let registry_value = command.to_registry_value();
+        let registry_value = command.to_registry_value("Test", &InMemoryLauncherStore::new());
 
         // VBScript wraps the command, so check it's in the generated VBScript content
         assert!(registry_value.contains("wscript.exe"));
 
         // Check the VBScript content contains the full command
-        let vbs_content = command.get_vbscript_content();
+        let vbs_content = command.get_vbscript_content("Test");
         assert!(vbs_content.is_some());
         let (_, content) = vbs_content.unwrap();
         assert!(content.contains("bun run dev --port 3000"));
@@ -129,13 +131,13 @@ mod tests {
             mode: ExecutionMode::VBScript,
         };
 
-        let registry_value = command.to_registry_value();
+        let registry_value = command.to_registry_value("Test", &InMemoryLauncherStore::new());
 
         // VBScript wraps the command
         assert!(registry_value.contains("wscript.exe"));
 
         // Check VBScript content has the command
-        let vbs_content = command.get_vbscript_content();
+        let vbs_content = command.get_vbscript_content("Test");
         assert!(vbs_content.is_some());
         let (_, content) = vbs_content.unwrap();
         assert!(content.contains("notepad.exe"));
@@ -150,12 +152,12 @@ mod tests {
             mode: ExecutionMode::VBScript,
         };
 
-        let result = command.get_vbscript_content();
+        let result = command.get_vbscript_content("Test");
 
         assert!(result.is_some());
         let (filename, content) = result.unwrap();
 
-        assert!(filename.starts_with("launcher_"));
+        assert!(filename.starts_with("test_"));
         assert!(filename.ends_with(".vbs"));
         assert!(content.contains("WScript.Shell"));
         assert!(content.contains("CurrentDirectory"));
@@ -169,10 +171,10 @@ mod tests {
             command: "test".to_string(),
             args: vec![],
             workdir: None,
-            mode: ExecutionMode::PowerShellHidden,
+            mode: ExecutionMode::PowerShell(PowerShellOptions::default()),
         };
 
-        let result = command.get_vbscript_content();
+        let result = command.get_vbscript_content("Test");
         assert!(result.is_none());
     }
 
@@ -182,7 +184,7 @@ mod tests {
             path: "C:\\Program Files\\App\\app.exe".to_string(),
         };
 
-        let registry_value = command.to_registry_value();
+        let registry_value = command.to_registry_value("Test", &InMemoryLauncherStore::new());
         assert_eq!(registry_value, "C:\\Program Files\\App\\app.exe");
     }
 
@@ -195,11 +197,293 @@ mod tests {
             mode: ExecutionMode::VBScript,
         };
 
-        let result = command.get_vbscript_content();
+        let result = command.get_vbscript_content("Test");
         assert!(result.is_some());
 
         let (_, content) = result.unwrap();
         // VBScript should escape quotes
         assert!(content.contains("\"\""));
     }
+
+    #[test]
+    fn test_visible_mode_quotes_arguments() {
+        let command = StartupCommand::CommandWithArgs {
+            command: "C:\\My Tools\\app.exe".to_string(),
+            args: vec!["C:\\My Projects".to_string(), "say \"hi\"".to_string()],
+            workdir: None,
+            mode: ExecutionMode::Visible(ConsoleOptions::default()),
+        };
+
+        let registry_value = command.to_registry_value("Test", &InMemoryLauncherStore::new());
+
+        assert_eq!(
+            registry_value,
+            "\"C:\\My Tools\\app.exe\" \"C:\\My Projects\" \"say \\\"hi\\\"\""
+        );
+        assert_eq!(
+            cmdline::parse(&registry_value),
+            vec!["C:\\My Tools\\app.exe", "C:\\My Projects", "say \"hi\""]
+        );
+    }
+
+    #[test]
+    fn test_visible_mode_escapes_cmd_metacharacters() {
+        let command = StartupCommand::CommandWithArgs {
+            command: "app".to_string(),
+            args: vec!["a&b".to_string(), "c & d".to_string()],
+            workdir: Some("C:\\temp".to_string()),
+            mode: ExecutionMode::Visible(ConsoleOptions::default()),
+        };
+
+        assert_eq!(
+            command.to_registry_value("Test", &InMemoryLauncherStore::new()),
+            "cmd.exe /c \"cd /d \"C:\\temp\" && app a^&b \"c & d\"\""
+        );
+    }
+
+    #[test]
+    fn test_powershell_script_is_a_single_argument() {
+        let command = StartupCommand::CommandWithArgs {
+            command: "C:\\My Tools\\app.exe".to_string(),
+            args: vec!["it's".to_string(), "--port".to_string()],
+            workdir: Some("C:\\My Projects".to_string()),
+            mode: ExecutionMode::PowerShell(PowerShellOptions::default()),
+        };
+
+        let args =
+            cmdline::parse(&command.to_registry_value("Test", &InMemoryLauncherStore::new()));
+
+        assert_eq!(args.len(), 6);
+        assert_eq!(
+            args[5],
+            "Set-Location -LiteralPath 'C:\\My Projects'; & 'C:\\My Tools\\app.exe' 'it''s' --port"
+        );
+    }
+
+    #[test]
+    fn test_vbscript_content_quotes_arguments() {
+        let command = StartupCommand::CommandWithArgs {
+            command: "bun".to_string(),
+            args: vec!["run".to_string(), "C:\\My Projects\\".to_string()],
+            workdir: None,
+            mode: ExecutionMode::VBScript,
+        };
+
+        let (_, content) = command.get_vbscript_content("Test").unwrap();
+
+        assert!(content.contains("WshShell.Run \"bun run \"\"C:\\My Projects\\\\\"\"\", 0, False"));
+    }
+
+    fn command_with_args(
+        command: &str,
+        args: &[&str],
+        workdir: Option<&str>,
+        mode: ExecutionMode,
+    ) -> StartupCommand {
+        StartupCommand::CommandWithArgs {
+            command: command.to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            workdir: workdir.map(str::to_string),
+            mode,
+        }
+    }
+
+    #[test]
+    fn test_parse_decodes_vbscript_launcher() {
+        let command = command_with_args(
+            "bun",
+            &["run", "dev", "--name", "say \"hi\""],
+            Some("C:\\My Projects\\app"),
+            ExecutionMode::VBScript,
+        );
+        let (filename, content) = command.get_vbscript_content("Test").unwrap();
+        let value = command.to_registry_value("Test", &InMemoryLauncherStore::new());
+
+        let mut requested = None;
+        let decoded = StartupCommand::parse_with(&value, |path| {
+            requested = Some(path.to_string());
+            Some(content)
+        });
+
+        assert_eq!(decoded, Some(command));
+        assert!(requested.unwrap().ends_with(&filename));
+    }
+
+    #[test]
+    fn test_parse_missing_launcher_fails() {
+        let value = "wscript.exe //B //Nologo \"C:\\nonexistent\\launcher_0.vbs\"";
+
+        assert_eq!(StartupCommand::parse_with(value, |_| None), None);
+    }
+
+    #[test]
+    fn test_parse_foreign_vbscript_is_plain() {
+        let value = "wscript.exe \"C:\\scripts\\tray.vbs\"";
+
+        assert_eq!(
+            StartupCommand::parse_with(value, |_| panic!("launcher read")),
+            Some(command_with_args(
+                "wscript.exe",
+                &["C:\\scripts\\tray.vbs"],
+                None,
+                ExecutionMode::Visible(ConsoleOptions::default())
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_round_trips_powershell_and_cmd() {
+        let commands = [
+            command_with_args(
+                "C:\\My Tools\\app.exe",
+                &["it's", "--port", "3000"],
+                Some("C:\\My Projects"),
+                ExecutionMode::PowerShell(PowerShellOptions::default()),
+            ),
+            command_with_args(
+                "python",
+                &["-m", "http.server"],
+                None,
+                ExecutionMode::PowerShell(PowerShellOptions::default()),
+            ),
+            command_with_args(
+                "app",
+                &["a&b", "c & \"d\""],
+                Some("C:\\temp"),
+                ExecutionMode::Visible(ConsoleOptions::default()),
+            ),
+            command_with_args(
+                "bun",
+                &["run", "dev"],
+                None,
+                ExecutionMode::Visible(ConsoleOptions::default()),
+            ),
+        ];
+
+        for command in commands {
+            assert_eq!(
+                StartupCommand::parse(
+                    &command.to_registry_value("Test", &InMemoryLauncherStore::new())
+                ),
+                Some(command)
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_round_trips_debugging_options() {
+        let minimized = PowerShellOptions {
+            window: WindowStyle::Minimized,
+            no_exit: true,
+        };
+        let keep_open = ConsoleOptions { no_exit: true };
+        let commands = [
+            command_with_args(
+                "bun",
+                &["run", "dev"],
+                Some("C:\\app"),
+                ExecutionMode::PowerShell(minimized),
+            ),
+            command_with_args(
+                "bun",
+                &["run", "dev"],
+                None,
+                ExecutionMode::Visible(keep_open),
+            ),
+            command_with_args(
+                "bun",
+                &["run", "dev"],
+                Some("C:\\app"),
+                ExecutionMode::Visible(keep_open),
+            ),
+        ];
+
+        for command in commands {
+            assert_eq!(
+                StartupCommand::parse(
+                    &command.to_registry_value("Test", &InMemoryLauncherStore::new())
+                ),
+                Some(command)
+            );
+        }
+    }
+
+    #[test]
+    fn test_debugging_options_keep_the_window_open() {
+        let store = InMemoryLauncherStore::new();
+        let powershell = command_with_args(
+            "bun",
+            &["run", "dev"],
+            None,
+            ExecutionMode::PowerShell(PowerShellOptions {
+                window: WindowStyle::Minimized,
+                no_exit: true,
+            }),
+        );
+        let console = command_with_args(
+            "bun",
+            &["run", "dev"],
+            None,
+            ExecutionMode::Visible(ConsoleOptions { no_exit: true }),
+        );
+
+        assert!(
+            powershell
+                .to_registry_value("Test", &store)
+                .starts_with("powershell.exe -WindowStyle Minimized -NoExit -NoProfile -Command ")
+        );
+        assert_eq!(
+            console.to_registry_value("Test", &store),
+            "cmd.exe /k \"bun run dev\""
+        );
+    }
+
+    #[test]
+    fn test_parse_legacy_powershell_value() {
+        let value = "powershell.exe -WindowStyle Hidden -NoProfile -Command \"Set-Location 'C:\\www'; python -m http.server\"";
+
+        assert_eq!(
+            StartupCommand::parse(value),
+            Some(command_with_args(
+                "python",
+                &["-m", "http.server"],
+                Some("C:\\www"),
+                ExecutionMode::PowerShell(PowerShellOptions::default())
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_plain_executables() {
+        assert_eq!(
+            StartupCommand::parse("C:\\Program Files\\App\\app.exe"),
+            Some(StartupCommand::Executable {
+                path: "C:\\Program Files\\App\\app.exe".to_string()
+            })
+        );
+        assert_eq!(
+            StartupCommand::parse("\"C:\\Program Files\\App\\app.exe\" --minimized"),
+            Some(command_with_args(
+                "C:\\Program Files\\App\\app.exe",
+                &["--minimized"],
+                None,
+                ExecutionMode::Visible(ConsoleOptions::default())
+            ))
+        );
+        assert_eq!(StartupCommand::parse("   "), None);
+    }
+
+    #[test]
+    fn test_command_line_and_workdir_accessors() {
+        let command = command_with_args(
+            "bun",
+            &["run", "C:\\My App"],
+            Some("C:\\src"),
+            ExecutionMode::VBScript,
+        );
+
+        assert_eq!(command.command_line(), "bun run \"C:\\My App\"");
+        assert_eq!(command.workdir(), Some("C:\\src"));
+        assert_eq!(command.mode(), Some(ExecutionMode::VBScript));
+    }
 }
@@ -0,0 +1,153 @@
+use super::cmdline;
+use crate::shared::error::Result;
+use chrono::{DateTime, Utc};
+use std::collections::{HashSet, VecDeque};
+
+/// Information about a running process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    /// PID of the process that created this one, when known.
+    pub parent_pid: Option<u32>,
+    pub name: String,
+    /// Full path of the executable image, when known.
+    pub executable_path: Option<String>,
+    pub command_line: String,
+    /// Current working directory, when the provider can read it.
+    pub workdir: Option<String>,
+    /// When the process was started, when known.
+    pub start_time: Option<DateTime<Utc>>,
+}
+
+impl ProcessInfo {
+    /// Creates a new process description.
+    pub fn new(pid: u32, name: impl Into<String>, command_line: impl Into<String>) -> Self {
+        Self {
+            pid,
+            parent_pid: None,
+            name: name.into(),
+            executable_path: None,
+            command_line: command_line.into(),
+            workdir: None,
+            start_time: None,
+        }
+    }
+
+    /// Sets the parent process ID.
+    pub fn with_parent(mut self, parent_pid: u32) -> Self {
+        self.parent_pid = Some(parent_pid);
+        self
+    }
+
+    /// Sets the full path of the executable image.
+    pub fn with_executable_path(mut self, path: impl Into<String>) -> Self {
+        self.executable_path = Some(path.into());
+        self
+    }
+
+    /// Sets the start time of the process.
+    pub fn with_start_time(mut self, start_time: DateTime<Utc>) -> Self {
+        self.start_time = Some(start_time);
+        self
+    }
+
+    /// Sets the working directory of the process.
+    pub fn with_workdir(mut self, workdir: impl Into<String>) -> Self {
+        self.workdir = Some(workdir.into());
+        self
+    }
+
+    /// Checks whether this process runs the given image name.
+    /// Only the file name is compared, case-insensitively, and a trailing
+    /// `.exe` is ignored on both sides so `bun.exe` also matches `bun`.
+    pub fn matches_image_name(&self, image: &str) -> bool {
+        !image.is_empty() && cmdline::program_stem(&self.name) == cmdline::program_stem(image)
+    }
+}
+
+/// Provider trait for inspecting and terminating processes.
+/// This follows the Dependency Inversion Principle: use cases depend on this
+/// abstraction rather than on a specific operating system API.
+pub trait ProcessProvider {
+    /// Lists all running processes.
+    fn list_processes(&self) -> Result<Vec<ProcessInfo>>;
+
+    /// Forcefully terminates a process by PID.
+    fn kill_process(&self, pid: u32) -> Result<()>;
+
+    /// Asks a process to shut down on its own.
+    /// Returns once the request is delivered, not when the process exits.
+    fn request_close(&self, pid: u32) -> Result<()>;
+
+    /// Checks whether a process is still running.
+    fn is_running(&self, pid: u32) -> Result<bool> {
+        Ok(self.list_processes()?.iter().any(|p| p.pid == pid))
+    }
+
+    /// Kills all processes whose image name matches `name`.
+    /// Returns the number of processes that were terminated.
+    fn kill_processes_by_name(&self, name: &str) -> Result<u32> {
+        let mut count = 0;
+        for process in self.list_processes()? {
+            if process.matches_image_name(name) && self.kill_process(process.pid).is_ok() {
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+}
+
+impl<P: ProcessProvider + ?Sized> ProcessProvider for &P {
+    fn list_processes(&self) -> Result<Vec<ProcessInfo>> {
+        (**self).list_processes()
+    }
+
+    fn kill_process(&self, pid: u32) -> Result<()> {
+        (**self).kill_process(pid)
+    }
+
+    fn request_close(&self, pid: u32) -> Result<()> {
+        (**self).request_close(pid)
+    }
+
+    fn is_running(&self, pid: u32) -> Result<bool> {
+        (**self).is_running(pid)
+    }
+
+    fn kill_processes_by_name(&self, name: &str) -> Result<u32> {
+        (**self).kill_processes_by_name(name)
+    }
+}
+
+/// Returns the given root processes followed by all of their descendants,
+/// in breadth-first order so parents always precede their children.
+pub fn process_tree(roots: &[u32], processes: &[ProcessInfo]) -> Vec<ProcessInfo> {
+    let mut tree = Vec::new();
+    let mut seen = HashSet::new();
+    let mut queue: VecDeque<u32> = roots.iter().copied().collect();
+
+    while let Some(pid) = queue.pop_front() {
+        if !seen.insert(pid) {
+            continue;
+        }
+        let parent = processes.iter().find(|p| p.pid == pid);
+        if let Some(process) = parent {
+            tree.push(process.clone());
+        }
+        let parent_start = parent.and_then(|p| p.start_time);
+        queue.extend(
+            processes
+                .iter()
+                // PID 0 is its own parent on Windows
+                .filter(|p| p.parent_pid == Some(pid) && p.pid != pid)
+                // A child older than its parent names a reused parent PID
+                .filter(|p| match (parent_start, p.start_time) {
+                    (Some(parent_start), Some(start)) => start >= parent_start,
+                    _ => true,
+                })
+                .map(|p| p.pid),
+        );
+    }
+
+    tree
+}
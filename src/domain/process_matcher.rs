@@ -0,0 +1,181 @@
+use super::cmdline;
+use super::models::{ExecutionMode, StartupCommand, StartupEntry};
+use super::process::ProcessInfo;
+
+/// How processes are attributed to a startup entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchStrategy {
+    /// Match the full command line (and working directory, when known)
+    /// of the process the entry actually launches.
+    #[default]
+    CommandLine,
+    /// Match every process whose image name equals the entry's executable.
+    /// This is the legacy behavior and can hit unrelated processes.
+    ImageName,
+}
+
+/// Decides which running processes belong to a startup entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessMatcher {
+    command_line: String,
+    workdir: Option<String>,
+    image_name: Option<String>,
+    strategy: MatchStrategy,
+}
+
+impl ProcessMatcher {
+    /// Creates a matcher for a process started with `command_line`,
+    /// optionally from `workdir`.
+    pub fn new(command_line: impl Into<String>, workdir: Option<String>) -> Self {
+        Self {
+            command_line: command_line.into(),
+            workdir,
+            image_name: None,
+            strategy: MatchStrategy::CommandLine,
+        }
+    }
+
+    /// Creates a matcher that compares image names only.
+    pub fn by_image_name(image_name: impl Into<String>) -> Self {
+        Self {
+            command_line: String::new(),
+            workdir: None,
+            image_name: Some(image_name.into()),
+            strategy: MatchStrategy::ImageName,
+        }
+    }
+
+    /// Builds the command-line matcher for a startup entry.
+    /// For VBScript entries the launcher script is read so that the command
+    /// it runs is matched instead of the short-lived `wscript.exe`; logon
+    /// tasks match the program of their definition.
+    /// Returns `None` when the entry's process cannot be identified.
+    pub fn for_entry(entry: &StartupEntry) -> Option<Self> {
+        match StartupCommand::parse(&entry.command)? {
+            command @ StartupCommand::CommandWithArgs {
+                mode: ExecutionMode::VBScript | ExecutionMode::ScheduledTask(_),
+                ..
+            } => Some(ProcessMatcher::new(
+                command.command_line(),
+                command.workdir().map(str::to_string),
+            )),
+            // PowerShell and cmd.exe stay alive as the parent of the program
+            _ => Some(ProcessMatcher::new(entry.command.clone(), None)),
+        }
+    }
+
+    /// Returns the strategy this matcher applies.
+    pub fn strategy(&self) -> MatchStrategy {
+        self.strategy
+    }
+
+    /// Checks whether a process belongs to the entry.
+    pub fn matches(&self, process: &ProcessInfo) -> bool {
+        match self.strategy {
+            MatchStrategy::ImageName => self
+                .image_name
+                .as_deref()
+                .is_some_and(|image| process.matches_image_name(image)),
+            MatchStrategy::CommandLine => {
+                if !same_command_line(&self.command_line, &process.command_line) {
+                    return false;
+                }
+                match (&self.workdir, &process.workdir) {
+                    (None, _) => true,
+                    (Some(expected), Some(actual)) => same_path(expected, actual),
+                    // The directory can't be confirmed, so don't claim the process
+                    (Some(_), None) => false,
+                }
+            }
+        }
+    }
+
+    /// Returns the processes belonging to the entry.
+    pub fn find<'p>(&self, processes: &'p [ProcessInfo]) -> Vec<&'p ProcessInfo> {
+        processes.iter().filter(|p| self.matches(p)).collect()
+    }
+}
+
+/// Extracts the executable name from a command string, the image name
+/// `MatchStrategy::ImageName` looks for.
+pub fn extract_executable_name(command: &str) -> Option<String> {
+    // Handle VBScript wrapper
+    if command.contains("wscript.exe") {
+        return Some("wscript.exe".to_string());
+    }
+
+    // Handle PowerShell
+    if command.contains("powershell.exe") {
+        // Try to extract the actual command being run
+        if let Some(start) = command.find("Command \"") {
+            let after_command = &command[start + 9..];
+            if let Some(end) = after_command.find('"') {
+                let inner_command = &after_command[..end];
+                // Get first word (executable name)
+                if let Some(exe) = inner_command.split_whitespace().next() {
+                    return Some(format!("{}.exe", exe));
+                }
+            }
+        }
+        return Some("powershell.exe".to_string());
+    }
+
+    // Handle CMD
+    if command.contains("cmd.exe") {
+        return Some("cmd.exe".to_string());
+    }
+
+    // Direct executable
+    if let Some(exe) = command.split_whitespace().next()
+        && exe.ends_with(".exe")
+    {
+        return Some(exe.to_string());
+    }
+
+    None
+}
+
+/// Compares two command lines argument by argument.
+/// The program may be given as a bare name on one side and a full path on the other.
+fn same_command_line(expected: &str, actual: &str) -> bool {
+    let expected = cmdline::parse(expected);
+    let actual = cmdline::parse(actual);
+    if expected.is_empty() || actual.is_empty() {
+        return false;
+    }
+
+    // Unquoted paths with spaces parse differently from their quoted form
+    if expected.join(" ").eq_ignore_ascii_case(&actual.join(" ")) {
+        return true;
+    }
+
+    expected.len() == actual.len()
+        && same_program(&expected[0], &actual[0])
+        && expected[1..] == actual[1..]
+}
+
+fn same_program(expected: &str, actual: &str) -> bool {
+    fn split(program: &str) -> (bool, String) {
+        (
+            program.contains(['\\', '/']),
+            cmdline::program_stem(program),
+        )
+    }
+
+    if expected.eq_ignore_ascii_case(actual) {
+        return true;
+    }
+    let (expected_dir, expected_stem) = split(expected);
+    let (actual_dir, actual_stem) = split(actual);
+    // Two different full paths are different programs
+    !(expected_dir && actual_dir) && expected_stem == actual_stem
+}
+
+fn same_path(expected: &str, actual: &str) -> bool {
+    fn normalize(path: &str) -> String {
+        path.replace('/', "\\")
+            .trim_end_matches('\\')
+            .to_lowercase()
+    }
+    normalize(expected) == normalize(actual)
+}
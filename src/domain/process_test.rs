@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn test_matches_image_name_ignores_case() {
+        let info = ProcessInfo::new(1, "Bun.EXE", "bun run dev");
+
+        assert!(info.matches_image_name("bun.exe"));
+    }
+
+    #[test]
+    fn test_matches_image_name_ignores_exe_suffix() {
+        let windows = ProcessInfo::new(1, "node.exe", "node server.js");
+        let linux = ProcessInfo::new(2, "node", "node server.js");
+
+        assert!(windows.matches_image_name("node"));
+        assert!(linux.matches_image_name("node.exe"));
+    }
+
+    #[test]
+    fn test_matches_image_name_compares_file_name_only() {
+        let info = ProcessInfo::new(1, "myapp.exe", "C:\\Apps\\myapp.exe");
+
+        assert!(info.matches_image_name("C:\\Apps\\myapp.exe"));
+        assert!(info.matches_image_name("/opt/apps/myapp"));
+    }
+
+    #[test]
+    fn test_matches_image_name_rejects_other_names() {
+        let info = ProcessInfo::new(1, "bun.exe", "bun run dev");
+
+        assert!(!info.matches_image_name("bunx.exe"));
+        assert!(!info.matches_image_name(""));
+    }
+
+    #[test]
+    fn test_process_tree_includes_all_descendants() {
+        let processes = vec![
+            ProcessInfo::new(1, "explorer.exe", "explorer.exe"),
+            ProcessInfo::new(10, "bun.exe", "bun run dev").with_parent(1),
+            ProcessInfo::new(11, "node.exe", "node a.js").with_parent(10),
+            ProcessInfo::new(12, "esbuild.exe", "esbuild").with_parent(11),
+            ProcessInfo::new(13, "node.exe", "node b.js").with_parent(10),
+        ];
+
+        let pids: Vec<_> = process_tree(&[10], &processes)
+            .iter()
+            .map(|p| p.pid)
+            .collect();
+
+        assert_eq!(pids, vec![10, 11, 13, 12]);
+    }
+
+    #[test]
+    fn test_process_tree_handles_self_parented_and_cycles() {
+        let processes = vec![
+            ProcessInfo::new(0, "System Idle Process", "").with_parent(0),
+            ProcessInfo::new(5, "a.exe", "a").with_parent(6),
+            ProcessInfo::new(6, "b.exe", "b").with_parent(5),
+        ];
+
+        assert_eq!(process_tree(&[0], &processes).len(), 1);
+        assert_eq!(process_tree(&[5], &processes).len(), 2);
+    }
+
+    #[test]
+    fn test_process_tree_skips_unknown_roots() {
+        let processes = vec![ProcessInfo::new(1, "a.exe", "a")];
+
+        assert!(process_tree(&[99], &processes).is_empty());
+    }
+
+    #[test]
+    fn test_process_tree_skips_children_older_than_parent() {
+        let started = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let processes = vec![
+            ProcessInfo::new(10, "bun.exe", "bun run dev").with_start_time(started),
+            // PID 10 was reused: this process predates it
+            ProcessInfo::new(11, "stale.exe", "stale")
+                .with_parent(10)
+                .with_start_time(started - chrono::Duration::seconds(60)),
+            ProcessInfo::new(12, "node.exe", "node a.js")
+                .with_parent(10)
+                .with_start_time(started + chrono::Duration::seconds(1)),
+            ProcessInfo::new(13, "unknown.exe", "unknown").with_parent(10),
+        ];
+
+        let pids: Vec<_> = process_tree(&[10], &processes)
+            .iter()
+            .map(|p| p.pid)
+            .collect();
+
+        assert_eq!(pids, vec![10, 12, 13]);
+    }
+
+    #[test]
+    fn test_process_info_creation() {
+        let info = ProcessInfo {
+            pid: 1234,
+            parent_pid: None,
+            name: "test.exe".to_string(),
+            executable_path: None,
+            command_line: "test.exe --arg".to_string(),
+            workdir: None,
+            start_time: None,
+        };
+
+        assert_eq!(info.pid, 1234);
+        assert_eq!(info.name, "test.exe");
+        assert_eq!(info.command_line, "test.exe --arg");
+    }
+
+    #[test]
+    fn test_process_info_clone() {
+        let info = ProcessInfo {
+            pid: 5678,
+            parent_pid: None,
+            name: "app.exe".to_string(),
+            executable_path: None,
+            command_line: "app.exe".to_string(),
+            workdir: None,
+            start_time: None,
+        };
+
+        let cloned = info.clone();
+        assert_eq!(cloned.pid, info.pid);
+        assert_eq!(cloned.name, info.name);
+    }
+}
@@ -0,0 +1,517 @@
+use super::location::StartupLocation;
+use super::models::StartupEntry;
+use super::startup_approved::StartupApproval;
+use crate::shared::error::{Result, StartupError};
+
+/// First line of the .reg files `regedit` writes.
+pub const REG_HEADER: &str = "Windows Registry Editor Version 5.00";
+
+/// Registry type of `hex:` data.
+const REG_BINARY: u32 = 3;
+/// Registry type of `hex(2):` data.
+const REG_EXPAND_SZ: u32 = 2;
+/// Longest line the writer emits before continuing hex data on the next line.
+const MAX_LINE_LENGTH: usize = 80;
+
+/// Hive names `regedit` writes, with the abbreviations used elsewhere.
+const HIVES: [(&str, &str); 2] = [
+    ("HKEY_CURRENT_USER", "HKCU"),
+    ("HKEY_LOCAL_MACHINE", "HKLM"),
+];
+
+/// The data of a value in a .reg file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegValue {
+    /// `"text"`, a REG_SZ value.
+    String(String),
+    /// `hex(2):`, a REG_EXPAND_SZ value, whose environment variables
+    /// are expanded when it is read.
+    ExpandString(String),
+    /// `dword:`, a REG_DWORD value.
+    Dword(u32),
+    /// `hex:` (REG_BINARY) or `hex(kind):` data of any other type.
+    Binary { kind: u32, data: Vec<u8> },
+    /// `-`, which deletes the value.
+    Delete,
+}
+
+/// A key of a .reg file and the values it sets or deletes.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RegKey {
+    /// Full path of the key, starting with its hive.
+    pub path: String,
+    /// `[-path]`, which deletes the key.
+    pub delete: bool,
+    /// Values by name; the default value (`@`) has an empty name.
+    pub values: Vec<(String, RegValue)>,
+}
+
+/// A file in the Windows Registry Editor 5.00 format.
+///
+/// `regedit` writes these files as UTF-16LE with a byte order mark and
+/// CRLF line endings. Names and strings are quoted with `\\` and `\"`
+/// escapes, and long hex data continues on the next line after a `\`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RegFile {
+    pub keys: Vec<RegKey>,
+}
+
+/// A change a .reg file makes to the startup entries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegChange {
+    /// Adds or replaces the entry.
+    Set(StartupEntry),
+    /// Deletes the entry named `name` from `location`.
+    Delete {
+        name: String,
+        location: StartupLocation,
+    },
+}
+
+impl RegKey {
+    /// Creates a key that sets values.
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Adds a value.
+    pub fn with_value(mut self, name: impl Into<String>, value: RegValue) -> Self {
+        self.values.push((name.into(), value));
+        self
+    }
+}
+
+impl RegFile {
+    /// Decodes a .reg file: UTF-16LE with a byte order mark, as `regedit`
+    /// writes it, or UTF-8.
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        let text = match bytes {
+            [0xFF, 0xFE, rest @ ..] => utf16_le(rest).map_err(invalid)?,
+            [0xEF, 0xBB, 0xBF, rest @ ..] => {
+                String::from_utf8(rest.to_vec()).map_err(|e| invalid(e.to_string()))?
+            }
+            _ => String::from_utf8(bytes.to_vec())
+                .map_err(|_| invalid("expected UTF-16LE with a byte order mark".to_string()))?,
+        };
+        Self::parse(&text)
+    }
+
+    /// Encodes the file as `regedit` does: UTF-16LE with a byte order mark.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend(self.to_text().encode_utf16().flat_map(u16::to_le_bytes));
+        bytes
+    }
+
+    /// Parses the text of a .reg file.
+    pub fn parse(text: &str) -> Result<Self> {
+        let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+        let mut lines = logical_lines(text).into_iter();
+
+        match lines.find(|(_, line)| !line.is_empty()) {
+            Some((_, header)) if header == REG_HEADER => {}
+            Some((_, header)) if header == "REGEDIT4" => {
+                return Err(invalid("REGEDIT4 files are not supported".to_string()));
+            }
+            _ => return Err(invalid(format!("expected the header '{}'", REG_HEADER))),
+        }
+
+        let mut keys: Vec<RegKey> = Vec::new();
+        for (number, line) in lines {
+            let at_line = |message: String| invalid(format!("line {}: {}", number, message));
+            if line.is_empty() || line.starts_with(';') {
+                continue;
+            }
+
+            if let Some(path) = line.strip_prefix('[') {
+                let path = path
+                    .strip_suffix(']')
+                    .ok_or_else(|| at_line("unterminated key".to_string()))?;
+                let (path, delete) = match path.strip_prefix('-') {
+                    Some(path) => (path, true),
+                    None => (path, false),
+                };
+                if path.is_empty() {
+                    return Err(at_line("empty key".to_string()));
+                }
+                keys.push(RegKey {
+                    path: path.to_string(),
+                    delete,
+                    values: Vec::new(),
+                });
+                continue;
+            }
+
+            let key = keys
+                .last_mut()
+                .ok_or_else(|| at_line("value outside a key".to_string()))?;
+            let (name, rest) = match line.strip_prefix('@') {
+                Some(rest) => (String::new(), rest),
+                None => parse_quoted(&line).map_err(at_line)?,
+            };
+            let data = rest
+                .trim_start()
+                .strip_prefix('=')
+                .ok_or_else(|| at_line("expected '=' after the value name".to_string()))?;
+            key.values
+                .push((name, parse_data(data.trim()).map_err(at_line)?));
+        }
+        Ok(Self { keys })
+    }
+
+    /// Writes the file as text with CRLF line endings.
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{}\r\n\r\n", REG_HEADER);
+        for key in &self.keys {
+            let marker = if key.delete { "-" } else { "" };
+            text.push_str(&format!("[{}{}]\r\n", marker, key.path));
+            for (name, value) in &key.values {
+                let name = if name.is_empty() {
+                    "@".to_string()
+                } else {
+                    quote(name)
+                };
+                text.push_str(&format_value(&name, value));
+                text.push_str("\r\n");
+            }
+            text.push_str("\r\n");
+        }
+        text
+    }
+
+    /// Writes the entries stored in Run keys, with the StartupApproved
+    /// values of the disabled ones. Entries stored elsewhere are left out.
+    pub fn from_entries(entries: &[StartupEntry]) -> Self {
+        let mut keys = Vec::new();
+        for location in StartupLocation::ALL {
+            let Some(run_key) = location.registry_key() else {
+                continue;
+            };
+            let stored: Vec<_> = entries.iter().filter(|e| e.location == location).collect();
+            if stored.is_empty() {
+                continue;
+            }
+
+            keys.push(RegKey {
+                path: full_key(run_key),
+                delete: false,
+                values: stored
+                    .iter()
+                    .map(|e| (e.name.clone(), RegValue::String(e.command.clone())))
+                    .collect(),
+            });
+            let disabled: Vec<_> = stored.iter().filter(|e| !e.enabled).collect();
+            if let Some(approved_key) = location.startup_approved_key()
+                && !disabled.is_empty()
+            {
+                keys.push(RegKey {
+                    path: full_key(approved_key),
+                    delete: false,
+                    values: disabled
+                        .iter()
+                        .map(|e| {
+                            let data = e.approval().to_bytes().to_vec();
+                            let value = RegValue::Binary {
+                                kind: REG_BINARY,
+                                data,
+                            };
+                            (e.name.clone(), value)
+                        })
+                        .collect(),
+                });
+            }
+        }
+        Self { keys }
+    }
+
+    /// Maps the values of Run keys onto startup entries, and the
+    /// StartupApproved values of the same file onto their enabled state.
+    /// Keys of other locations, key deletions and values that aren't
+    /// strings are rejected.
+    pub fn startup_changes(&self) -> Result<Vec<RegChange>> {
+        let mut changes = Vec::new();
+        let mut approvals = Vec::new();
+        for key in &self.keys {
+            if key.delete {
+                return Err(invalid(format!(
+                    "deleting the key [{}] is not supported",
+                    key.path
+                )));
+            }
+            let (location, approved) = key_location(&key.path).ok_or_else(|| {
+                invalid(format!(
+                    "[{}] is not a supported startup location",
+                    key.path
+                ))
+            })?;
+
+            for (name, value) in &key.values {
+                let value_error = |message: &str| {
+                    invalid(format!("value '{}' of [{}] {}", name, key.path, message))
+                };
+                if name.is_empty() {
+                    return Err(invalid(format!(
+                        "the default value of [{}] is not a startup entry",
+                        key.path
+                    )));
+                }
+                match (value, approved) {
+                    (RegValue::String(command) | RegValue::ExpandString(command), false) => {
+                        let entry =
+                            StartupEntry::new(name, command.as_str()).with_location(location);
+                        changes.push(RegChange::Set(entry));
+                    }
+                    (RegValue::Delete, false) => changes.push(RegChange::Delete {
+                        name: name.clone(),
+                        location,
+                    }),
+                    (_, false) => return Err(value_error("is not a string")),
+                    (RegValue::Binary { data, .. }, true) => {
+                        let approval = StartupApproval::from_bytes(data)
+                            .ok_or_else(|| value_error("is not a 12-byte StartupApproved value"))?;
+                        approvals.push((name, location, approval, &key.path));
+                    }
+                    // Without a value, the entry is enabled
+                    (RegValue::Delete, true) => {
+                        approvals.push((name, location, StartupApproval::ENABLED, &key.path))
+                    }
+                    (_, true) => return Err(value_error("is not binary")),
+                }
+            }
+        }
+
+        for (name, location, approval, path) in approvals {
+            let entry = changes.iter_mut().find_map(|change| match change {
+                RegChange::Set(entry)
+                    if entry.location == location && entry.name.eq_ignore_ascii_case(name) =>
+                {
+                    Some(entry)
+                }
+                _ => None,
+            });
+            match entry {
+                Some(entry) => {
+                    entry.enabled = approval.enabled;
+                    entry.disabled_at = approval.disabled_at;
+                }
+                None => {
+                    return Err(invalid(format!(
+                        "value '{}' of [{}] approves an entry the file doesn't set",
+                        name, path
+                    )));
+                }
+            }
+        }
+        Ok(changes)
+    }
+}
+
+fn invalid(message: String) -> StartupError {
+    StartupError::InvalidFormat(format!("reg file: {}", message))
+}
+
+/// Splits the text into trimmed lines, joining lines that end with `\` to
+/// the next one. Each line keeps the number of the line it starts on.
+fn logical_lines(text: &str) -> Vec<(usize, String)> {
+    let mut lines = Vec::new();
+    let mut pending: Option<(usize, String)> = None;
+    for (index, raw) in text.lines().enumerate() {
+        let line = raw.trim();
+        let (number, mut joined) = match pending.take() {
+            Some((number, start)) => (number, start + line),
+            None => (index + 1, line.to_string()),
+        };
+        // Only hex data continues; keys and comments end where they end
+        if joined.ends_with('\\') && !joined.starts_with(['[', ';']) {
+            joined.pop();
+            pending = Some((number, joined));
+        } else {
+            lines.push((number, joined));
+        }
+    }
+    lines.extend(pending);
+    lines
+}
+
+/// Reads a quoted string at the start of `text`, returning it unescaped
+/// with the text after the closing quote.
+fn parse_quoted(text: &str) -> std::result::Result<(String, &str), String> {
+    let rest = text
+        .strip_prefix('"')
+        .ok_or_else(|| "expected a quoted name or @".to_string())?;
+    let mut value = String::new();
+    let mut chars = rest.char_indices();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '"' => return Ok((value, &rest[index + 1..])),
+            '\\' => match chars.next() {
+                Some((_, escaped @ ('\\' | '"'))) => value.push(escaped),
+                Some((_, other)) => {
+                    value.push('\\');
+                    value.push(other);
+                }
+                None => break,
+            },
+            c => value.push(c),
+        }
+    }
+    Err("unterminated string".to_string())
+}
+
+fn parse_data(data: &str) -> std::result::Result<RegValue, String> {
+    if data == "-" {
+        return Ok(RegValue::Delete);
+    }
+    if data.starts_with('"') {
+        let (value, rest) = parse_quoted(data)?;
+        if !rest.trim().is_empty() {
+            return Err(format!("unexpected text after the string: {}", rest.trim()));
+        }
+        return Ok(RegValue::String(value));
+    }
+    if let Some(digits) = data.strip_prefix("dword:") {
+        let digits = digits.trim();
+        if digits.is_empty() || digits.len() > 8 {
+            return Err(format!("invalid dword '{}'", digits));
+        }
+        return u32::from_str_radix(digits, 16)
+            .map(RegValue::Dword)
+            .map_err(|_| format!("invalid dword '{}'", digits));
+    }
+    if let Some(rest) = data.strip_prefix("hex") {
+        let (kind, bytes) = match rest.strip_prefix(':') {
+            Some(bytes) => (REG_BINARY, bytes),
+            None => {
+                let (kind, bytes) = rest
+                    .strip_prefix('(')
+                    .and_then(|rest| rest.split_once("):"))
+                    .ok_or_else(|| format!("invalid hex type in '{}'", data))?;
+                let kind = u32::from_str_radix(kind.trim(), 16)
+                    .map_err(|_| format!("invalid hex type '{}'", kind))?;
+                (kind, bytes)
+            }
+        };
+        let data = parse_hex_bytes(bytes)?;
+        if kind == REG_EXPAND_SZ {
+            return decode_utf16(&data).map(RegValue::ExpandString);
+        }
+        return Ok(RegValue::Binary { kind, data });
+    }
+    Err(format!("unsupported value data '{}'", data))
+}
+
+fn parse_hex_bytes(text: &str) -> std::result::Result<Vec<u8>, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(Vec::new());
+    }
+    text.split(',')
+        .map(|byte| {
+            let byte = byte.trim();
+            if byte.len() != 2 {
+                return Err(format!("invalid hex byte '{}'", byte));
+            }
+            u8::from_str_radix(byte, 16).map_err(|_| format!("invalid hex byte '{}'", byte))
+        })
+        .collect()
+}
+
+/// Decodes UTF-16LE text.
+fn utf16_le(bytes: &[u8]) -> std::result::Result<String, String> {
+    if !bytes.len().is_multiple_of(2) {
+        return Err("UTF-16 text has an odd number of bytes".to_string());
+    }
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect();
+    String::from_utf16(&units).map_err(|e| e.to_string())
+}
+
+/// Decodes REG_EXPAND_SZ data, dropping the terminating NUL.
+fn decode_utf16(data: &[u8]) -> std::result::Result<String, String> {
+    let text = utf16_le(data).map_err(|e| format!("hex(2) data: {}", e))?;
+    Ok(text.trim_end_matches('\0').to_string())
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn format_value(name: &str, value: &RegValue) -> String {
+    match value {
+        RegValue::String(text) => format!("{}={}", name, quote(text)),
+        RegValue::Dword(number) => format!("{}=dword:{:08x}", name, number),
+        RegValue::Delete => format!("{}=-", name),
+        RegValue::ExpandString(text) => {
+            let data: Vec<u8> = text
+                .encode_utf16()
+                .chain([0])
+                .flat_map(u16::to_le_bytes)
+                .collect();
+            format_hex(&format!("{}=hex(2):", name), &data)
+        }
+        RegValue::Binary { kind, data } if *kind == REG_BINARY => {
+            format_hex(&format!("{}=hex:", name), data)
+        }
+        RegValue::Binary { kind, data } => format_hex(&format!("{}=hex({:x}):", name, kind), data),
+    }
+}
+
+/// Writes hex bytes after `prefix`, continuing long data on indented lines.
+fn format_hex(prefix: &str, data: &[u8]) -> String {
+    let mut text = String::new();
+    let mut line = prefix.to_string();
+    for (index, byte) in data.iter().enumerate() {
+        let last = index + 1 == data.len();
+        let piece = if last {
+            format!("{:02x}", byte)
+        } else {
+            format!("{:02x},", byte)
+        };
+        // Keep room for the continuation backslash
+        if line.len() + piece.len() > MAX_LINE_LENGTH - 1 && line.len() > 2 {
+            text.push_str(&line);
+            text.push_str("\\\r\n");
+            line = "  ".to_string();
+        }
+        line.push_str(&piece);
+    }
+    text.push_str(&line);
+    text
+}
+
+/// Returns the key with its hive written out, as `regedit` writes it.
+/// Hive abbreviations are recognized ignoring case.
+fn full_key(key: &str) -> String {
+    if let Some((hive, rest)) = key.split_once('\\')
+        && let Some((long, _)) = HIVES
+            .iter()
+            .find(|(_, short)| short.eq_ignore_ascii_case(hive))
+    {
+        return format!("{}\\{}", long, rest);
+    }
+    key.to_string()
+}
+
+/// Finds the location whose Run key, or StartupApproved key when the
+/// flag is set, is `path`.
+fn key_location(path: &str) -> Option<(StartupLocation, bool)> {
+    let path = full_key(path.trim_end_matches('\\'));
+    let same = |key: Option<&str>| key.is_some_and(|key| full_key(key).eq_ignore_ascii_case(&path));
+    StartupLocation::ALL
+        .into_iter()
+        .filter(|location| location.registry_key().is_some())
+        .find_map(|location| {
+            if same(location.registry_key()) {
+                Some((location, false))
+            } else if same(location.startup_approved_key()) {
+                Some((location, true))
+            } else {
+                None
+            }
+        })
+}
@@ -0,0 +1,223 @@
+#[cfg(test)]
+mod tests {
+    use super::super::reg_file::*;
+    use super::super::{StartupApproval, StartupEntry, StartupLocation};
+    use crate::shared::error::StartupError;
+    use chrono::{NaiveDate, TimeZone, Utc};
+
+    const FIXTURE: &[u8] = include_bytes!("../../tests/fixtures/startup_utf16.reg");
+    const RUN: &str = r"HKEY_CURRENT_USER\SOFTWARE\Microsoft\Windows\CurrentVersion\Run";
+
+    fn parse_error(text: &str) -> String {
+        let error = RegFile::parse(text).unwrap_err();
+        assert!(matches!(error, StartupError::InvalidFormat(_)), "{}", error);
+        error.to_string()
+    }
+
+    fn changes_error(text: &str) -> String {
+        let error = RegFile::parse(text)
+            .and_then(|file| file.startup_changes())
+            .unwrap_err();
+        assert!(matches!(error, StartupError::InvalidFormat(_)), "{}", error);
+        error.to_string()
+    }
+
+    #[test]
+    fn test_decodes_regedit_exports() {
+        let file = RegFile::decode(FIXTURE).unwrap();
+
+        assert_eq!(file.keys.len(), 3);
+        assert_eq!(file.keys[0].path, RUN);
+        assert_eq!(
+            file.keys[0].values,
+            [
+                (
+                    "Notes".to_string(),
+                    RegValue::String(r#""C:\Program Files\Notes\notes.exe" --tray"#.to_string())
+                ),
+                (
+                    "Sync".to_string(),
+                    RegValue::ExpandString(
+                        r"%LOCALAPPDATA%\Programs\Sync\sync.exe --background".to_string()
+                    )
+                ),
+                ("Legacy".to_string(), RegValue::Delete),
+            ]
+        );
+        assert!(matches!(
+            &file.keys[1].values[0].1,
+            RegValue::Binary { kind: 3, data } if data.len() == 12
+        ));
+    }
+
+    #[test]
+    fn test_maps_run_keys_onto_entries() {
+        let changes = RegFile::decode(FIXTURE).unwrap().startup_changes().unwrap();
+
+        let RegChange::Set(notes) = &changes[0] else {
+            panic!("expected Notes to be set");
+        };
+        assert_eq!(notes.location, StartupLocation::CurrentUserRun);
+        assert!(!notes.enabled);
+        assert_eq!(
+            notes.disabled_at.map(|at| at.date_naive()),
+            NaiveDate::from_ymd_opt(2026, 3, 1)
+        );
+        assert_eq!(
+            changes[1],
+            RegChange::Set(StartupEntry::new(
+                "Sync",
+                r"%LOCALAPPDATA%\Programs\Sync\sync.exe --background"
+            ))
+        );
+        assert_eq!(
+            changes[2],
+            RegChange::Delete {
+                name: "Legacy".to_string(),
+                location: StartupLocation::CurrentUserRun,
+            }
+        );
+        assert_eq!(
+            changes[3],
+            RegChange::Set(
+                StartupEntry::new("Agent", r"C:\Agent\agent.exe")
+                    .with_location(StartupLocation::LocalMachineRun32)
+            )
+        );
+    }
+
+    #[test]
+    fn test_round_trip_through_utf16() {
+        let file = RegFile {
+            keys: vec![
+                RegKey::new(RUN)
+                    .with_value("Quote \"and\" slash\\", RegValue::String("a\\b".into()))
+                    .with_value("", RegValue::Dword(0x2a))
+                    .with_value("Path", RegValue::ExpandString("%WINDIR%\\x.exe".into()))
+                    .with_value(
+                        "Data",
+                        RegValue::Binary {
+                            kind: 7,
+                            data: (0..=255).collect(),
+                        },
+                    )
+                    .with_value("Old", RegValue::Delete),
+                RegKey {
+                    path: r"HKEY_CURRENT_USER\Software\Old".to_string(),
+                    delete: true,
+                    values: Vec::new(),
+                },
+            ],
+        };
+
+        let bytes = file.encode();
+        let text = file.to_text();
+
+        assert_eq!(&bytes[..2], &[0xFF, 0xFE]);
+        assert!(text.starts_with("Windows Registry Editor Version 5.00\r\n\r\n"));
+        assert!(text.contains(r#""Quote \"and\" slash\\"="a\\b""#));
+        assert!(text.contains("@=dword:0000002a\r\n"));
+        assert!(text.contains("\"Data\"=hex(7):00,01,"));
+        assert!(text.contains("[-HKEY_CURRENT_USER\\Software\\Old]"));
+        assert!(text.lines().all(|line| line.len() <= 80));
+        assert_eq!(RegFile::decode(&bytes).unwrap(), file);
+    }
+
+    #[test]
+    fn test_reads_utf8_and_abbreviated_hives() {
+        let text = "\u{feff}Windows Registry Editor Version 5.00\n\n[HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Run\\]\n\"App\"=\"app.exe\"\n";
+
+        let changes = RegFile::decode(text.as_bytes())
+            .unwrap()
+            .startup_changes()
+            .unwrap();
+
+        assert_eq!(
+            changes,
+            [RegChange::Set(StartupEntry::new("App", "app.exe"))]
+        );
+    }
+
+    #[test]
+    fn test_rejects_malformed_files() {
+        let header = "Windows Registry Editor Version 5.00\n";
+        assert!(parse_error("REGEDIT4\n").contains("REGEDIT4"));
+        assert!(parse_error("[HKEY_CURRENT_USER\\Run]").contains("header"));
+        assert!(parse_error(&format!("{}\"a\"=\"b\"", header)).contains("outside a key"));
+        for (value, message) in [
+            ("\"a\"=\"b", "line 3: unterminated string"),
+            ("\"a\"=dword:123456789", "invalid dword"),
+            ("\"a\"=hex:0g", "invalid hex byte '0g'"),
+            ("\"a\"=hex(2):41", "odd number of bytes"),
+            ("\"a\"=qword:1", "unsupported value data"),
+            ("a=\"b\"", "expected a quoted name"),
+        ] {
+            let error = parse_error(&format!("{}[HKEY_CURRENT_USER\\Run]\n{}", header, value));
+            assert!(error.contains(message), "{}: {}", value, error);
+        }
+        assert!(
+            parse_error(&format!("{}[HKEY_CURRENT_USER\\Run", header)).contains("unterminated key")
+        );
+    }
+
+    #[test]
+    fn test_rejects_keys_and_values_outside_startup_locations() {
+        let file = |key: &str, value: &str| {
+            format!(
+                "Windows Registry Editor Version 5.00\n\n[{}]\n{}\n",
+                key, value
+            )
+        };
+
+        assert!(
+            changes_error(&file(r"HKEY_CURRENT_USER\Software\Classes", "\"a\"=\"b\""))
+                .contains("not a supported startup location")
+        );
+        assert!(changes_error(&file(&format!("-{}", RUN), "")).contains("deleting the key"));
+        assert!(changes_error(&file(RUN, "@=\"b\"")).contains("default value"));
+        assert!(changes_error(&file(RUN, "\"a\"=dword:1")).contains("is not a string"));
+        assert!(
+            changes_error(&file(
+                r"HKEY_CURRENT_USER\SOFTWARE\Microsoft\Windows\CurrentVersion\Explorer\StartupApproved\Run",
+                "\"a\"=hex:03,00,00,00,00,00,00,00,00,00,00,00"
+            ))
+            .contains("approves an entry the file doesn't set")
+        );
+    }
+
+    #[test]
+    fn test_writes_run_entries_with_the_state_of_disabled_ones() {
+        let disabled_at = Utc.with_ymd_and_hms(2026, 3, 1, 8, 30, 0).unwrap();
+        let entries = [
+            StartupEntry::new("Dev", r#""C:\dev.exe" --watch"#)
+                .with_approval(StartupApproval::disabled_since(disabled_at)),
+            StartupEntry::new("Once", "once.exe")
+                .with_location(StartupLocation::LocalMachineRunOnce),
+            StartupEntry::new("Notes", "notes.lnk")
+                .with_location(StartupLocation::UserStartupFolder),
+        ];
+
+        let file = RegFile::from_entries(&entries);
+        let paths: Vec<_> = file.keys.iter().map(|key| key.path.as_str()).collect();
+
+        assert_eq!(
+            paths,
+            [
+                RUN,
+                r"HKEY_CURRENT_USER\SOFTWARE\Microsoft\Windows\CurrentVersion\Explorer\StartupApproved\Run",
+                r"HKEY_LOCAL_MACHINE\SOFTWARE\Microsoft\Windows\CurrentVersion\RunOnce",
+            ]
+        );
+        let changes = RegFile::decode(&file.encode())
+            .unwrap()
+            .startup_changes()
+            .unwrap();
+        assert_eq!(
+            changes,
+            [
+                RegChange::Set(entries[0].clone()),
+                RegChange::Set(entries[1].clone())
+            ]
+        );
+    }
+}
@@ -1,5 +1,7 @@
+use super::location::StartupLocation;
 use super::models::StartupEntry;
-use crate::shared::error::Result;
+use super::startup_approved::StartupApproval;
+use crate::shared::error::{Result, StartupError};
 
 /// Repository trait for managing startup entries.
 /// This follows the Repository pattern and Dependency Inversion Principle.
@@ -14,6 +16,110 @@ pub trait StartupRepository {
     fn list(&self) -> Result<Vec<StartupEntry>>;
 
     /// Checks if an entry with the given name exists.
-    #[allow(dead_code)]
     fn exists(&self, name: &str) -> Result<bool>;
+
+    /// Enables or disables an entry without removing it.
+    /// Repositories that cannot record the state report it as unsupported.
+    fn set_approval(&self, name: &str, approval: StartupApproval) -> Result<()> {
+        let _ = approval;
+        Err(StartupError::Unsupported(format!(
+            "entry '{}' cannot be enabled or disabled in this location",
+            name
+        )))
+    }
+
+    /// Checks whether `set_approval` can record the state of entries added
+    /// to `location`.
+    fn supports_approval(&self, location: StartupLocation) -> bool {
+        let _ = location;
+        false
+    }
+
+    /// Replaces the value of an existing entry, keeping its location and
+    /// enabled state. Fails when no entry has that name.
+    fn update(&self, entry: &StartupEntry) -> Result<()> {
+        if !self.exists(&entry.name)? {
+            return Err(StartupError::EntryNotFound(entry.name.clone()));
+        }
+        self.add(entry)
+    }
+
+    /// Gives the entry `name` the name `new_name`, keeping its value,
+    /// location and enabled state. Fails when another entry is named
+    /// `new_name`.
+    ///
+    /// The default removes the entry and adds it under its new name,
+    /// putting it back when that fails.
+    fn rename(&self, name: &str, new_name: &str) -> Result<()> {
+        let mut matching = self
+            .list()?
+            .into_iter()
+            .filter(|entry| entry.name.eq_ignore_ascii_case(name));
+        let entry = match (matching.next(), matching.next()) {
+            (None, _) => return Err(StartupError::EntryNotFound(name.to_string())),
+            (Some(entry), None) => entry,
+            _ => return Err(StartupError::AmbiguousEntry(name.to_string())),
+        };
+        // Changing only the case of a name renames the entry itself
+        if !new_name.eq_ignore_ascii_case(&entry.name) && self.exists(new_name)? {
+            return Err(StartupError::EntryAlreadyExists(new_name.to_string()));
+        }
+
+        let renamed = StartupEntry {
+            name: new_name.to_string(),
+            ..entry.clone()
+        };
+        let store = |entry: &StartupEntry| {
+            self.add(entry)?;
+            if entry.enabled {
+                Ok(())
+            } else {
+                self.set_approval(&entry.name, entry.approval())
+            }
+        };
+        self.remove(&entry.name)?;
+        if let Err(e) = store(&renamed) {
+            self.remove(new_name).ok();
+            store(&entry).ok();
+            return Err(e);
+        }
+        Ok(())
+    }
+}
+
+/// Lets use cases take a repository chosen at runtime, such as
+/// `&dyn StartupRepository`. The other domain ports are implemented for
+/// references the same way.
+impl<R: StartupRepository + ?Sized> StartupRepository for &R {
+    fn add(&self, entry: &StartupEntry) -> Result<()> {
+        (**self).add(entry)
+    }
+
+    fn remove(&self, name: &str) -> Result<()> {
+        (**self).remove(name)
+    }
+
+    fn list(&self) -> Result<Vec<StartupEntry>> {
+        (**self).list()
+    }
+
+    fn exists(&self, name: &str) -> Result<bool> {
+        (**self).exists(name)
+    }
+
+    fn set_approval(&self, name: &str, approval: StartupApproval) -> Result<()> {
+        (**self).set_approval(name, approval)
+    }
+
+    fn supports_approval(&self, location: StartupLocation) -> bool {
+        (**self).supports_approval(location)
+    }
+
+    fn update(&self, entry: &StartupEntry) -> Result<()> {
+        (**self).update(entry)
+    }
+
+    fn rename(&self, name: &str, new_name: &str) -> Result<()> {
+        (**self).rename(name, new_name)
+    }
 }
@@ -0,0 +1,336 @@
+use super::cmdline;
+use super::xml::Element;
+use crate::shared::error::{Result, StartupError};
+use std::time::Duration;
+
+/// Namespace of Task Scheduler task definitions.
+pub const TASK_NAMESPACE: &str = "http://schemas.microsoft.com/windows/2004/02/mit/task";
+
+/// Task Scheduler folder the tool registers its tasks in.
+pub const TASK_FOLDER: &str = r"\windows_startup_manager\";
+
+/// How Task Scheduler restarts a task whose program exits with an error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestartPolicy {
+    /// How many times the task is restarted.
+    pub count: u32,
+    /// Time between restarts; Task Scheduler accepts one minute to 31 days.
+    pub interval: Duration,
+}
+
+/// Options of entries that run as a logon task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TaskSettings {
+    /// Time to wait after logon before starting the program.
+    pub delay: Option<Duration>,
+    /// Restarts the program when it fails.
+    pub restart: Option<RestartPolicy>,
+    /// Runs the program in a non-interactive session (S4U logon), so no
+    /// window it opens is ever shown. Such programs cannot use the user's
+    /// network credentials.
+    pub hidden: bool,
+    /// Runs the program with the user's full administrator token when the
+    /// user has one.
+    pub highest_privileges: bool,
+}
+
+/// A Task Scheduler 1.2 task starting a program when a user logs on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskDefinition {
+    pub command: String,
+    /// Arguments passed to the program, as one command line string.
+    pub arguments: String,
+    pub working_dir: Option<String>,
+    pub settings: TaskSettings,
+    /// Account whose logon starts the task (`DOMAIN\user`); without one
+    /// the task starts at every user's logon.
+    pub user_id: Option<String>,
+    pub description: Option<String>,
+    pub enabled: bool,
+}
+
+impl TaskDefinition {
+    /// Creates an enabled task starting `command` without arguments.
+    pub fn new(command: impl Into<String>) -> Self {
+        Self {
+            command: command.into(),
+            arguments: String::new(),
+            working_dir: None,
+            settings: TaskSettings::default(),
+            user_id: None,
+            description: None,
+            enabled: true,
+        }
+    }
+
+    /// Returns the command line of the program the task starts.
+    pub fn command_line(&self) -> String {
+        let program = cmdline::quote_program(&self.command);
+        if self.arguments.is_empty() {
+            program
+        } else {
+            format!("{} {}", program, self.arguments)
+        }
+    }
+
+    /// Renders the task as an XML document for `schtasks /Create /XML`.
+    pub fn to_xml(&self) -> String {
+        let text = |name: &str, value: &str| Element::new(name).with_text(value);
+        let flag = |name: &str, value: bool| text(name, if value { "true" } else { "false" });
+
+        let mut registration = Element::new("RegistrationInfo");
+        if let Some(description) = &self.description {
+            registration = registration.with_child(text("Description", description));
+        }
+
+        let mut trigger = Element::new("LogonTrigger").with_child(flag("Enabled", true));
+        let mut principal = Element::new("Principal").with_attribute("id", "Author");
+        if let Some(user_id) = &self.user_id {
+            trigger = trigger.with_child(text("UserId", user_id));
+            principal = principal.with_child(text("UserId", user_id));
+        }
+        if let Some(delay) = self.settings.delay {
+            trigger = trigger.with_child(text("Delay", &format_duration(delay)));
+        }
+        let principal = principal
+            .with_child(text(
+                "LogonType",
+                if self.settings.hidden {
+                    "S4U"
+                } else {
+                    "InteractiveToken"
+                },
+            ))
+            .with_child(text(
+                "RunLevel",
+                if self.settings.highest_privileges {
+                    "HighestAvailable"
+                } else {
+                    "LeastPrivilege"
+                },
+            ));
+
+        // Startup programs run for the whole session, on battery as well
+        let mut settings = Element::new("Settings")
+            .with_child(text("MultipleInstancesPolicy", "IgnoreNew"))
+            .with_child(flag("DisallowStartIfOnBatteries", false))
+            .with_child(flag("StopIfGoingOnBatteries", false))
+            .with_child(text("ExecutionTimeLimit", "PT0S"))
+            .with_child(flag("Enabled", self.enabled));
+        if let Some(restart) = self.settings.restart {
+            settings = settings.with_child(
+                Element::new("RestartOnFailure")
+                    .with_child(text("Interval", &format_duration(restart.interval)))
+                    .with_child(text("Count", &restart.count.to_string())),
+            );
+        }
+
+        let mut exec = Element::new("Exec").with_child(text("Command", &self.command));
+        if !self.arguments.is_empty() {
+            exec = exec.with_child(text("Arguments", &self.arguments));
+        }
+        if let Some(dir) = &self.working_dir {
+            exec = exec.with_child(text("WorkingDirectory", dir));
+        }
+
+        Element::new("Task")
+            .with_attribute("version", "1.2")
+            .with_attribute("xmlns", TASK_NAMESPACE)
+            .with_child(registration)
+            .with_child(Element::new("Triggers").with_child(trigger))
+            .with_child(Element::new("Principals").with_child(principal))
+            .with_child(settings)
+            .with_child(
+                Element::new("Actions")
+                    .with_attribute("Context", "Author")
+                    .with_child(exec),
+            )
+            .to_document("UTF-16")
+    }
+
+    /// Reads a task definition, such as one exported by `schtasks /Query /XML`.
+    /// The first program the task runs is its command; elements the tool
+    /// does not write are ignored.
+    pub fn from_xml(xml: &str) -> Result<Self> {
+        let invalid = |message: &str| StartupError::InvalidFormat(format!("task: {}", message));
+
+        let task = Element::parse(xml)?;
+        if task.name.rsplit(':').next() != Some("Task") {
+            return Err(invalid("the root element is not <Task>"));
+        }
+        let exec = task
+            .descendant(&["Actions", "Exec"])
+            .ok_or_else(|| invalid("the task does not start a program"))?;
+        // Tasks created in the Task Scheduler UI quote paths with spaces
+        let command = exec
+            .text_at(&["Command"])
+            .map(|c| {
+                c.strip_prefix('"')
+                    .and_then(|c| c.strip_suffix('"'))
+                    .unwrap_or(c)
+            })
+            .filter(|c| !c.is_empty())
+            .ok_or_else(|| invalid("the task does not start a program"))?;
+
+        let duration = |path: &[&str]| -> Result<Option<Duration>> {
+            task.text_at(path)
+                .map(|text| {
+                    parse_duration(text)
+                        .ok_or_else(|| invalid(&format!("invalid duration '{}'", text)))
+                })
+                .transpose()
+        };
+        let restart = match task.descendant(&["Settings", "RestartOnFailure"]) {
+            Some(restart) => Some(RestartPolicy {
+                count: restart
+                    .text_at(&["Count"])
+                    .and_then(|c| c.parse().ok())
+                    .ok_or_else(|| invalid("invalid restart count"))?,
+                interval: duration(&["Settings", "RestartOnFailure", "Interval"])?
+                    .ok_or_else(|| invalid("missing restart interval"))?,
+            }),
+            None => None,
+        };
+        let optional = |path: &[&str]| {
+            task.text_at(path)
+                .filter(|text| !text.is_empty())
+                .map(str::to_string)
+        };
+
+        Ok(Self {
+            command: command.to_string(),
+            arguments: exec.text_at(&["Arguments"]).unwrap_or_default().to_string(),
+            working_dir: exec
+                .text_at(&["WorkingDirectory"])
+                .filter(|dir| !dir.is_empty())
+                .map(str::to_string),
+            settings: TaskSettings {
+                delay: duration(&["Triggers", "LogonTrigger", "Delay"])?,
+                restart,
+                hidden: task.text_at(&["Principals", "Principal", "LogonType"]) == Some("S4U"),
+                highest_privileges: task.text_at(&["Principals", "Principal", "RunLevel"])
+                    == Some("HighestAvailable"),
+            },
+            user_id: optional(&["Triggers", "LogonTrigger", "UserId"])
+                .or_else(|| optional(&["Principals", "Principal", "UserId"])),
+            description: optional(&["RegistrationInfo", "Description"]),
+            enabled: task.text_at(&["Settings", "Enabled"]) != Some("false"),
+        })
+    }
+}
+
+/// Formats a duration as an ISO 8601 duration such as `PT1M30S`.
+pub fn format_duration(duration: Duration) -> String {
+    let total = duration.as_secs();
+    let (days, hours, minutes, seconds) = (
+        total / 86_400,
+        total % 86_400 / 3_600,
+        total % 3_600 / 60,
+        total % 60,
+    );
+
+    let mut text = String::from("P");
+    if days > 0 {
+        text.push_str(&format!("{}D", days));
+    }
+    if hours + minutes + seconds > 0 || days == 0 {
+        text.push('T');
+        if hours > 0 {
+            text.push_str(&format!("{}H", hours));
+        }
+        if minutes > 0 {
+            text.push_str(&format!("{}M", minutes));
+        }
+        if seconds > 0 || total == 0 {
+            text.push_str(&format!("{}S", seconds));
+        }
+    }
+    text
+}
+
+/// Parses an ISO 8601 duration made of weeks, days, hours, minutes and
+/// whole seconds. Years and months have no fixed length and are rejected.
+pub fn parse_duration(text: &str) -> Option<Duration> {
+    let rest = text.trim().strip_prefix('P')?;
+    let (date, time) = match rest.split_once('T') {
+        Some((date, time)) if !time.is_empty() => (date, Some(time)),
+        Some(_) => return None,
+        None => (rest, None),
+    };
+
+    let mut seconds = 0u64;
+    let mut any = false;
+    for (part, units) in [
+        (date, &[('W', 604_800), ('D', 86_400)][..]),
+        (
+            time.unwrap_or_default(),
+            &[('H', 3_600), ('M', 60), ('S', 1)][..],
+        ),
+    ] {
+        let mut rest = part;
+        let mut units = units.iter();
+        while !rest.is_empty() {
+            let end = rest.find(|c: char| !c.is_ascii_digit())?;
+            let value: u64 = rest[..end].parse().ok()?;
+            let designator = rest[end..].chars().next()?;
+            // Designators must appear in order, each at most once
+            let (_, scale) = units.by_ref().find(|(unit, _)| *unit == designator)?;
+            seconds = seconds.checked_add(value.checked_mul(*scale)?)?;
+            any = true;
+            rest = &rest[end + 1..];
+        }
+    }
+    any.then(|| Duration::from_secs(seconds))
+}
+
+/// Parses a duration written like `90s`, `1m30s`, `2h` or `1d`, or as an
+/// ISO 8601 duration such as `PT1M30S`. A bare number is a number of seconds.
+pub fn parse_short_duration(text: &str) -> Option<Duration> {
+    let text = text.trim();
+    if text.starts_with(['P', 'p']) {
+        return parse_duration(&text.to_ascii_uppercase());
+    }
+    if text.is_empty() {
+        return None;
+    }
+    if text.bytes().all(|b| b.is_ascii_digit()) {
+        return text.parse().ok().map(Duration::from_secs);
+    }
+
+    let mut units = [('d', 86_400u64), ('h', 3_600), ('m', 60), ('s', 1)].iter();
+    let mut seconds = 0u64;
+    let mut rest = text;
+    while !rest.is_empty() {
+        let end = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .filter(|&end| end > 0)?;
+        let value: u64 = rest[..end].parse().ok()?;
+        let unit = rest[end..].chars().next()?.to_ascii_lowercase();
+        // Units must appear from the largest to the smallest, each at most once
+        let (_, scale) = units.by_ref().find(|(u, _)| *u == unit)?;
+        seconds = seconds.checked_add(value.checked_mul(*scale)?)?;
+        rest = &rest[end + 1..];
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+/// Formats a duration the way [`parse_short_duration`] reads it, such as `1m30s`.
+pub fn format_short_duration(duration: Duration) -> String {
+    let total = duration.as_secs();
+    let parts = [
+        (total / 86_400, 'd'),
+        (total % 86_400 / 3_600, 'h'),
+        (total % 3_600 / 60, 'm'),
+        (total % 60, 's'),
+    ];
+    let text: String = parts
+        .iter()
+        .filter(|(value, _)| *value > 0)
+        .map(|(value, unit)| format!("{}{}", value, unit))
+        .collect();
+    if text.is_empty() {
+        "0s".to_string()
+    } else {
+        text
+    }
+}
@@ -0,0 +1,262 @@
+#[cfg(test)]
+mod tests {
+    use super::super::scheduled_task::*;
+    use super::super::{ExecutionMode, StartupCommand};
+    use crate::domain::xml::Element;
+    use crate::infrastructure::InMemoryLauncherStore;
+    use crate::shared::error::StartupError;
+    use proptest::prelude::*;
+    use std::time::Duration;
+
+    const EXPORTED: &str = include_str!("../../tests/fixtures/task_exported.xml");
+
+    fn settings() -> TaskSettings {
+        TaskSettings {
+            delay: Some(Duration::from_secs(30)),
+            restart: Some(RestartPolicy {
+                count: 3,
+                interval: Duration::from_secs(60),
+            }),
+            hidden: true,
+            highest_privileges: true,
+        }
+    }
+
+    #[test]
+    fn test_render_logon_task() {
+        let task = TaskDefinition {
+            arguments: "run dev".to_string(),
+            working_dir: Some(r"C:\projects\app".to_string()),
+            settings: settings(),
+            user_id: Some(r"PC\dev".to_string()),
+            ..TaskDefinition::new("bun")
+        };
+
+        let root = Element::parse(&task.to_xml()).unwrap();
+
+        assert_eq!(root.attribute("version"), Some("1.2"));
+        assert_eq!(root.attribute("xmlns"), Some(TASK_NAMESPACE));
+        assert_eq!(
+            root.text_at(&["Triggers", "LogonTrigger", "UserId"]),
+            Some(r"PC\dev")
+        );
+        assert_eq!(
+            root.text_at(&["Triggers", "LogonTrigger", "Delay"]),
+            Some("PT30S")
+        );
+        assert_eq!(
+            root.text_at(&["Principals", "Principal", "LogonType"]),
+            Some("S4U")
+        );
+        assert_eq!(
+            root.text_at(&["Principals", "Principal", "RunLevel"]),
+            Some("HighestAvailable")
+        );
+        assert_eq!(
+            root.text_at(&["Settings", "RestartOnFailure", "Interval"]),
+            Some("PT1M")
+        );
+        assert_eq!(
+            root.text_at(&["Settings", "RestartOnFailure", "Count"]),
+            Some("3")
+        );
+        assert_eq!(
+            root.text_at(&["Settings", "ExecutionTimeLimit"]),
+            Some("PT0S")
+        );
+        assert_eq!(root.text_at(&["Actions", "Exec", "Command"]), Some("bun"));
+        assert_eq!(
+            root.text_at(&["Actions", "Exec", "Arguments"]),
+            Some("run dev")
+        );
+        assert_eq!(
+            root.text_at(&["Actions", "Exec", "WorkingDirectory"]),
+            Some(r"C:\projects\app")
+        );
+    }
+
+    #[test]
+    fn test_default_settings_run_interactively_without_elevation() {
+        let root = Element::parse(&TaskDefinition::new("app.exe").to_xml()).unwrap();
+
+        assert_eq!(
+            root.text_at(&["Principals", "Principal", "LogonType"]),
+            Some("InteractiveToken")
+        );
+        assert_eq!(
+            root.text_at(&["Principals", "Principal", "RunLevel"]),
+            Some("LeastPrivilege")
+        );
+        assert_eq!(
+            root.descendant(&["Triggers", "LogonTrigger", "Delay"]),
+            None
+        );
+        assert_eq!(root.descendant(&["Settings", "RestartOnFailure"]), None);
+        assert_eq!(root.descendant(&["Actions", "Exec", "Arguments"]), None);
+    }
+
+    #[test]
+    fn test_read_task_exported_by_windows() {
+        let task = TaskDefinition::from_xml(EXPORTED).unwrap();
+
+        assert_eq!(task.command, r"C:\Program Files\Sync\sync.exe");
+        assert_eq!(task.arguments, r#"--tray --profile "Work""#);
+        assert_eq!(task.working_dir.as_deref(), Some(r"C:\Program Files\Sync"));
+        assert_eq!(task.user_id.as_deref(), Some(r"DESKTOP-42\dev"));
+        assert_eq!(
+            task.description.as_deref(),
+            Some("Starts the sync client & its tray icon")
+        );
+        assert!(!task.enabled);
+        assert_eq!(
+            task.settings,
+            TaskSettings {
+                delay: Some(Duration::from_secs(90)),
+                restart: Some(RestartPolicy {
+                    count: 3,
+                    interval: Duration::from_secs(300),
+                }),
+                hidden: false,
+                highest_privileges: true,
+            }
+        );
+        assert_eq!(
+            task.command_line(),
+            r#""C:\Program Files\Sync\sync.exe" --tray --profile "Work""#
+        );
+    }
+
+    #[test]
+    fn test_read_rejects_tasks_without_a_program() {
+        let no_exec = "<Task xmlns=\"http://schemas.microsoft.com/windows/2004/02/mit/task\">\
+                       <Actions><ComHandler/></Actions></Task>";
+
+        for xml in [no_exec, "<Other/>", "not xml"] {
+            assert!(matches!(
+                TaskDefinition::from_xml(xml),
+                Err(StartupError::InvalidFormat(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_read_rejects_invalid_durations() {
+        let xml = TaskDefinition {
+            settings: settings(),
+            ..TaskDefinition::new("app.exe")
+        }
+        .to_xml()
+        .replace("PT30S", "P1M");
+
+        assert!(TaskDefinition::from_xml(&xml).is_err());
+    }
+
+    #[test]
+    fn test_format_durations() {
+        assert_eq!(format_duration(Duration::ZERO), "PT0S");
+        assert_eq!(format_duration(Duration::from_secs(45)), "PT45S");
+        assert_eq!(format_duration(Duration::from_secs(3600)), "PT1H");
+        assert_eq!(format_duration(Duration::from_secs(86_400)), "P1D");
+        assert_eq!(format_duration(Duration::from_secs(90_061)), "P1DT1H1M1S");
+    }
+
+    #[test]
+    fn test_parse_durations() {
+        let secs = |s| Some(Duration::from_secs(s));
+
+        assert_eq!(parse_duration("PT5M"), secs(300));
+        assert_eq!(parse_duration("P1W"), secs(604_800));
+        assert_eq!(parse_duration("P1DT2H"), secs(93_600));
+        assert_eq!(parse_duration("PT0S"), secs(0));
+        for invalid in [
+            "", "P", "PT", "5M", "P1M", "P1Y", "PT1.5S", "PT1S1M", "PT1M1M",
+        ] {
+            assert_eq!(parse_duration(invalid), None, "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_short_durations() {
+        let secs = |s| Some(Duration::from_secs(s));
+
+        assert_eq!(parse_short_duration("45"), secs(45));
+        assert_eq!(parse_short_duration("1m30s"), secs(90));
+        assert_eq!(parse_short_duration("1d2h"), secs(93_600));
+        assert_eq!(parse_short_duration("PT2M"), secs(120));
+        assert_eq!(parse_short_duration("pt2m"), secs(120));
+        for invalid in ["", "m", "30x", "30s1m", "1m1m", "1.5m", "P1Y"] {
+            assert_eq!(parse_short_duration(invalid), None, "{:?}", invalid);
+        }
+
+        for seconds in [0, 45, 90, 3_600, 93_601] {
+            let duration = Duration::from_secs(seconds);
+            assert_eq!(
+                parse_short_duration(&format_short_duration(duration)),
+                Some(duration)
+            );
+        }
+        assert_eq!(format_short_duration(Duration::from_secs(90)), "1m30s");
+    }
+
+    #[test]
+    fn test_scheduled_task_command_round_trips() {
+        let command = StartupCommand::CommandWithArgs {
+            command: r"C:\Program Files\App\app.exe".to_string(),
+            args: vec!["--name".to_string(), "a \"b\"".to_string()],
+            workdir: Some(r"C:\work".to_string()),
+            mode: ExecutionMode::ScheduledTask(settings()),
+        };
+
+        let value = command.to_registry_value("App", &InMemoryLauncherStore::new());
+
+        assert!(value.starts_with("<?xml"));
+        assert_eq!(StartupCommand::parse(&value), Some(command));
+    }
+
+    #[test]
+    fn test_other_modes_have_no_task_definition() {
+        let command = StartupCommand::CommandWithArgs {
+            command: "app.exe".to_string(),
+            args: vec![],
+            workdir: None,
+            mode: ExecutionMode::VBScript,
+        };
+
+        assert_eq!(command.task_definition(), None);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_written_tasks_read_back(
+            command in r"[A-Za-z]:\\[^\x00-\x1f]{1,30}",
+            arguments in "[^\x00-\x1f]{0,30}",
+            working_dir in proptest::option::of("[^\x00-\x1f]{1,30}"),
+            description in proptest::option::of("[^\x00-\x1f]{1,30}"),
+            delay in proptest::option::of(0u64..1_000_000),
+            restart in proptest::option::of((1u32..1000, 60u64..2_678_400)),
+            hidden in any::<bool>(),
+            highest_privileges in any::<bool>(),
+            enabled in any::<bool>(),
+        ) {
+            let task = TaskDefinition {
+                command: command.trim().to_string(),
+                arguments: arguments.trim().to_string(),
+                working_dir: working_dir.map(|d| d.trim().to_string()).filter(|d| !d.is_empty()),
+                settings: TaskSettings {
+                    delay: delay.map(Duration::from_secs),
+                    restart: restart.map(|(count, interval)| RestartPolicy {
+                        count,
+                        interval: Duration::from_secs(interval),
+                    }),
+                    hidden,
+                    highest_privileges,
+                },
+                user_id: Some(r"PC\user".to_string()),
+                description: description.map(|d| d.trim().to_string()).filter(|d| !d.is_empty()),
+                enabled,
+            };
+
+            prop_assert_eq!(TaskDefinition::from_xml(&task.to_xml()).unwrap(), task);
+        }
+    }
+}
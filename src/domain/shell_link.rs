@@ -0,0 +1,598 @@
+use super::cmdline;
+use crate::shared::error::{Result, StartupError};
+
+/// Size of the ShellLinkHeader structure.
+const HEADER_SIZE: u32 = 0x4C;
+/// LinkCLSID `00021401-0000-0000-C000-000000000046` as stored on disk.
+const LINK_CLSID: [u8; 16] = [
+    0x01, 0x14, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46,
+];
+
+// LinkFlags
+const HAS_LINK_TARGET_ID_LIST: u32 = 0x0000_0001;
+const HAS_LINK_INFO: u32 = 0x0000_0002;
+const HAS_NAME: u32 = 0x0000_0004;
+const HAS_RELATIVE_PATH: u32 = 0x0000_0008;
+const HAS_WORKING_DIR: u32 = 0x0000_0010;
+const HAS_ARGUMENTS: u32 = 0x0000_0020;
+const HAS_ICON_LOCATION: u32 = 0x0000_0040;
+const IS_UNICODE: u32 = 0x0000_0080;
+const FORCE_NO_LINK_INFO: u32 = 0x0000_0100;
+const HAS_EXP_STRING: u32 = 0x0000_0200;
+
+// LinkInfoFlags
+const VOLUME_ID_AND_LOCAL_BASE_PATH: u32 = 0x1;
+const COMMON_NETWORK_RELATIVE_LINK_AND_PATH_SUFFIX: u32 = 0x2;
+
+/// Signature of the EnvironmentVariableDataBlock.
+const ENVIRONMENT_VARIABLE_BLOCK: u32 = 0xA000_0001;
+/// Size of the EnvironmentVariableDataBlock.
+const ENVIRONMENT_VARIABLE_BLOCK_SIZE: u32 = 0x314;
+/// Size of each target field of the EnvironmentVariableDataBlock.
+const MAX_PATH: usize = 260;
+/// Signature of the extension block that holds a file entry's long name.
+const FILE_ENTRY_EXTENSION: u32 = 0xBEEF_0004;
+/// CLSID of the Computer folder, `20D04FE0-3AEA-1069-A2D8-08002B30309D`.
+const COMPUTER_CLSID: [u8; 16] = [
+    0xE0, 0x4F, 0xD0, 0x20, 0xEA, 0x3A, 0x69, 0x10, 0xA2, 0xD8, 0x08, 0x00, 0x2B, 0x30, 0x30, 0x9D,
+];
+/// DRIVE_FIXED, the drive type written for local targets.
+const DRIVE_FIXED: u32 = 3;
+
+/// How the window of a shortcut's target is shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ShowCommand {
+    /// `SW_SHOWNORMAL`.
+    #[default]
+    Normal,
+    /// `SW_SHOWMAXIMIZED`.
+    Maximized,
+    /// `SW_SHOWMINNOACTIVE`: minimized without taking the focus.
+    Minimized,
+    /// `SW_HIDE`. Explorer opens such shortcuts like normal ones, but the
+    /// value is kept so shortcuts written by other tools round-trip.
+    Hidden,
+}
+
+impl ShowCommand {
+    fn from_u32(value: u32) -> Self {
+        match value {
+            0 => ShowCommand::Hidden,
+            3 => ShowCommand::Maximized,
+            7 => ShowCommand::Minimized,
+            _ => ShowCommand::Normal,
+        }
+    }
+
+    fn to_u32(self) -> u32 {
+        match self {
+            ShowCommand::Hidden => 0,
+            ShowCommand::Normal => 1,
+            ShowCommand::Maximized => 3,
+            ShowCommand::Minimized => 7,
+        }
+    }
+}
+
+/// A Shell Link (`.lnk`) file, as described by [MS-SHLLINK].
+///
+/// Only the parts a startup shortcut needs are modelled. When reading, the
+/// target is taken from the environment variable block, the link info or
+/// the item ID list, in that order; the relative path is the last resort.
+///
+/// [MS-SHLLINK]: https://learn.microsoft.com/openspecs/windows_protocols/ms-shllink
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ShellLink {
+    /// Path of the target, possibly with `%VAR%` references.
+    pub target: String,
+    /// Command line arguments passed to the target.
+    pub arguments: String,
+    pub working_dir: Option<String>,
+    /// The shortcut's comment.
+    pub description: Option<String>,
+    pub icon_location: Option<String>,
+    pub icon_index: i32,
+    pub show_command: ShowCommand,
+}
+
+impl ShellLink {
+    /// Creates a shortcut to `target`.
+    pub fn new(target: impl Into<String>) -> Self {
+        Self {
+            target: target.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the command line the shortcut runs.
+    pub fn command_line(&self) -> String {
+        let program = cmdline::quote_program(&self.target);
+        if self.arguments.is_empty() {
+            program
+        } else {
+            format!("{} {}", program, self.arguments)
+        }
+    }
+
+    /// Decodes a Shell Link file.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = Reader::new(bytes);
+
+        // ShellLinkHeader
+        if reader.u32()? != HEADER_SIZE || reader.take(16)? != LINK_CLSID {
+            return Err(invalid("not a shell link"));
+        }
+        let flags = reader.u32()?;
+        reader.take(4 + 8 * 3 + 4)?; // attributes, times and file size
+        let icon_index = reader.u32()? as i32;
+        let show_command = ShowCommand::from_u32(reader.u32()?);
+        reader.take(2 + 2 + 4 + 4)?; // hot key and reserved fields
+
+        let mut id_list_target = None;
+        if flags & HAS_LINK_TARGET_ID_LIST != 0 {
+            let size = reader.u16()? as usize;
+            id_list_target = id_list_path(reader.take(size)?);
+        }
+
+        let mut link_info_target = None;
+        if flags & HAS_LINK_INFO != 0 {
+            let size = Reader::new(reader.peek(4)?).u32()? as usize;
+            let info = reader.take(size)?;
+            if flags & FORCE_NO_LINK_INFO == 0 {
+                link_info_target = link_info_path(info)?;
+            }
+        }
+
+        // StringData
+        let unicode = flags & IS_UNICODE != 0;
+        let mut string = |flag: u32| -> Result<Option<String>> {
+            if flags & flag == 0 {
+                return Ok(None);
+            }
+            let count = reader.u16()? as usize;
+            Ok(Some(if unicode {
+                utf16(reader.take(count * 2)?)
+            } else {
+                ansi(reader.take(count)?)
+            }))
+        };
+        let description = string(HAS_NAME)?;
+        let relative_path = string(HAS_RELATIVE_PATH)?;
+        let working_dir = string(HAS_WORKING_DIR)?;
+        let arguments = string(HAS_ARGUMENTS)?;
+        let icon_location = string(HAS_ICON_LOCATION)?;
+
+        // ExtraData
+        let mut environment_target = None;
+        while reader.remaining() >= 4 {
+            let size = Reader::new(reader.peek(4)?).u32()? as usize;
+            if size < 8 {
+                break;
+            }
+            let block = reader.take(size)?;
+            let signature = Reader::new(&block[4..]).u32()?;
+            if signature == ENVIRONMENT_VARIABLE_BLOCK && flags & HAS_EXP_STRING != 0 {
+                environment_target = environment_block_path(block);
+            }
+        }
+
+        let target = environment_target
+            .or(link_info_target)
+            .or(id_list_target)
+            .or(relative_path)
+            .unwrap_or_default();
+        Ok(Self {
+            target,
+            arguments: arguments.unwrap_or_default(),
+            working_dir,
+            description,
+            icon_location,
+            icon_index,
+            show_command,
+        })
+    }
+
+    /// Encodes the shortcut with Unicode strings.
+    ///
+    /// Targets with `%VAR%` references are stored in an environment variable
+    /// block, which the shell expands when the shortcut is opened; others
+    /// are stored in the link info as a local path and, for paths on a
+    /// drive, in an item ID list as well.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let expandable = self.target.contains('%');
+        let strings = [
+            (HAS_NAME, self.description.as_deref()),
+            (HAS_WORKING_DIR, self.working_dir.as_deref()),
+            (
+                HAS_ARGUMENTS,
+                Some(self.arguments.as_str()).filter(|a| !a.is_empty()),
+            ),
+            (HAS_ICON_LOCATION, self.icon_location.as_deref()),
+        ];
+
+        let id_list = (!expandable).then(|| id_list(&self.target)).flatten();
+
+        let mut flags = IS_UNICODE;
+        flags |= if expandable {
+            HAS_EXP_STRING | FORCE_NO_LINK_INFO
+        } else {
+            HAS_LINK_INFO
+        };
+        if id_list.is_some() {
+            flags |= HAS_LINK_TARGET_ID_LIST;
+        }
+        for (flag, value) in &strings {
+            if value.is_some() {
+                flags |= flag;
+            }
+        }
+
+        // ShellLinkHeader
+        let mut bytes = Vec::new();
+        put_u32(&mut bytes, HEADER_SIZE);
+        bytes.extend_from_slice(&LINK_CLSID);
+        put_u32(&mut bytes, flags);
+        bytes.extend_from_slice(&[0; 4 + 8 * 3 + 4]); // attributes, times and file size
+        put_u32(&mut bytes, self.icon_index as u32);
+        put_u32(&mut bytes, self.show_command.to_u32());
+        bytes.extend_from_slice(&[0; 2 + 2 + 4 + 4]); // hot key and reserved fields
+
+        if let Some(id_list) = id_list {
+            put_u16(&mut bytes, id_list.len() as u16);
+            bytes.extend(id_list);
+        }
+        if !expandable {
+            bytes.extend(link_info(&self.target));
+        }
+
+        // StringData
+        for (_, value) in strings {
+            if let Some(value) = value {
+                let units: Vec<u16> = value.encode_utf16().collect();
+                put_u16(&mut bytes, units.len() as u16);
+                for unit in units {
+                    put_u16(&mut bytes, unit);
+                }
+            }
+        }
+
+        // ExtraData
+        if expandable {
+            put_u32(&mut bytes, ENVIRONMENT_VARIABLE_BLOCK_SIZE);
+            put_u32(&mut bytes, ENVIRONMENT_VARIABLE_BLOCK);
+            let mut ansi_target = to_ansi(&self.target);
+            ansi_target.resize(MAX_PATH, 0);
+            bytes.extend(ansi_target);
+            let mut unicode_target: Vec<u16> = self.target.encode_utf16().collect();
+            unicode_target.resize(MAX_PATH, 0);
+            for unit in unicode_target {
+                put_u16(&mut bytes, unit);
+            }
+        }
+        put_u32(&mut bytes, 0); // TerminalBlock
+
+        bytes
+    }
+}
+
+/// Builds an item ID list for a path on a drive: the Computer folder, the
+/// volume and one file entry per component, without extension blocks.
+fn id_list(path: &str) -> Option<Vec<u8>> {
+    let (drive, rest) = path.split_at_checked(3)?;
+    if !drive.ends_with(":\\") || !drive.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let mut items: Vec<Vec<u8>> = vec![
+        [&[0x1F, 0x50][..], &COMPUTER_CLSID].concat(),
+        // Volume items have a fixed size
+        {
+            let mut volume = vec![0x2F];
+            volume.extend_from_slice(drive.as_bytes());
+            volume.resize(23, 0);
+            volume
+        },
+    ];
+    let components: Vec<&str> = rest.split('\\').filter(|c| !c.is_empty()).collect();
+    for (index, name) in components.iter().enumerate() {
+        let is_file = index + 1 == components.len();
+        let mut kind: u8 = if is_file { 0x32 } else { 0x31 };
+        if !name.is_ascii() {
+            kind |= 0x04;
+        }
+        let mut item = vec![kind, 0];
+        item.extend_from_slice(&[0; 4 + 4]); // file size and modification time
+        item.extend_from_slice(&(if is_file { 0x20u16 } else { 0x10 }).to_le_bytes());
+        if name.is_ascii() {
+            item.extend_from_slice(name.as_bytes());
+            item.push(0);
+            if item.len() % 2 == 1 {
+                item.push(0);
+            }
+        } else {
+            for unit in name.encode_utf16().chain([0]) {
+                put_u16(&mut item, unit);
+            }
+        }
+        items.push(item);
+    }
+
+    let mut list = Vec::new();
+    for item in items {
+        put_u16(&mut list, item.len() as u16 + 2);
+        list.extend(item);
+    }
+    put_u16(&mut list, 0); // TerminalID
+    Some(list)
+}
+
+/// Builds a LinkInfo structure pointing at a local path.
+fn link_info(path: &str) -> Vec<u8> {
+    const HEADER: u32 = 0x24;
+    // VolumeID: size, drive type, serial number, label offset and an empty label
+    let volume_id_size: u32 = 0x11;
+    let ansi_path = to_ansi(path);
+
+    let volume_id_offset = HEADER;
+    let local_base_path_offset = volume_id_offset + volume_id_size;
+    let common_path_suffix_offset = local_base_path_offset + ansi_path.len() as u32 + 1;
+    let local_base_path_unicode_offset = common_path_suffix_offset + 1;
+    let unicode_path: Vec<u16> = path.encode_utf16().chain([0]).collect();
+    let common_path_suffix_unicode_offset =
+        local_base_path_unicode_offset + unicode_path.len() as u32 * 2;
+    let size = common_path_suffix_unicode_offset + 2;
+
+    let mut bytes = Vec::with_capacity(size as usize);
+    put_u32(&mut bytes, size);
+    put_u32(&mut bytes, HEADER);
+    put_u32(&mut bytes, VOLUME_ID_AND_LOCAL_BASE_PATH);
+    put_u32(&mut bytes, volume_id_offset);
+    put_u32(&mut bytes, local_base_path_offset);
+    put_u32(&mut bytes, 0); // CommonNetworkRelativeLinkOffset
+    put_u32(&mut bytes, common_path_suffix_offset);
+    put_u32(&mut bytes, local_base_path_unicode_offset);
+    put_u32(&mut bytes, common_path_suffix_unicode_offset);
+
+    put_u32(&mut bytes, volume_id_size);
+    put_u32(&mut bytes, DRIVE_FIXED);
+    put_u32(&mut bytes, 0); // DriveSerialNumber
+    put_u32(&mut bytes, 0x10); // VolumeLabelOffset
+    bytes.push(0);
+
+    bytes.extend(ansi_path);
+    bytes.push(0);
+    bytes.push(0); // CommonPathSuffix
+    for unit in unicode_path {
+        put_u16(&mut bytes, unit);
+    }
+    put_u16(&mut bytes, 0); // CommonPathSuffixUnicode
+
+    bytes
+}
+
+/// Reads the target path of a LinkInfo structure.
+fn link_info_path(info: &[u8]) -> Result<Option<String>> {
+    let mut reader = Reader::new(info);
+    reader.take(4)?;
+    let header_size = reader.u32()?;
+    let flags = reader.u32()?;
+    reader.take(4)?; // VolumeIDOffset
+    let local_base_path_offset = reader.u32()? as usize;
+    let network_link_offset = reader.u32()? as usize;
+    let common_path_suffix_offset = reader.u32()? as usize;
+    let (local_base_path_unicode_offset, common_path_suffix_unicode_offset) = if header_size >= 0x24
+    {
+        (reader.u32()? as usize, reader.u32()? as usize)
+    } else {
+        (0, 0)
+    };
+
+    let suffix = if common_path_suffix_unicode_offset != 0 {
+        utf16_z(info, common_path_suffix_unicode_offset)
+    } else {
+        ansi_z(info, common_path_suffix_offset)
+    };
+
+    if flags & VOLUME_ID_AND_LOCAL_BASE_PATH != 0 {
+        let base = if local_base_path_unicode_offset != 0 {
+            utf16_z(info, local_base_path_unicode_offset)
+        } else {
+            ansi_z(info, local_base_path_offset)
+        };
+        return Ok(Some(base + &suffix));
+    }
+
+    if flags & COMMON_NETWORK_RELATIVE_LINK_AND_PATH_SUFFIX != 0 {
+        let link = info
+            .get(network_link_offset..)
+            .ok_or_else(|| invalid("link info is truncated"))?;
+        let mut reader = Reader::new(link);
+        reader.take(8)?; // size and flags
+        let net_name_offset = reader.u32()? as usize;
+        reader.take(8)?; // device name offset and provider type
+        let net_name = if net_name_offset > 0x14 {
+            utf16_z(link, reader.u32()? as usize)
+        } else {
+            ansi_z(link, net_name_offset)
+        };
+        return Ok(Some(if suffix.is_empty() {
+            net_name
+        } else {
+            format!("{}\\{}", net_name.trim_end_matches('\\'), suffix)
+        }));
+    }
+
+    Ok(None)
+}
+
+/// Rebuilds a file system path from an item ID list, if it starts at a
+/// drive and continues with file entries.
+fn id_list_path(list: &[u8]) -> Option<String> {
+    let mut path: Option<String> = None;
+    let mut reader = Reader::new(list);
+
+    while let Ok(size) = reader.u16() {
+        if size < 2 {
+            break;
+        }
+        let item = reader.take(size as usize - 2).ok()?;
+        let kind = *item.first()?;
+        match kind & 0x70 {
+            // Volume items hold the drive, such as `C:\`
+            0x20 => path = Some(ansi_z(item, 1)),
+            // File entry items hold a directory or file name
+            0x30 => {
+                let name = file_entry_name(item)?;
+                let path = path.as_mut()?;
+                if !path.ends_with('\\') {
+                    path.push('\\');
+                }
+                path.push_str(&name);
+            }
+            // The root folder (Computer) and other shell folders
+            _ => {}
+        }
+    }
+
+    path
+}
+
+/// Returns the long name of a file entry item, falling back to its primary
+/// (possibly 8.3) name.
+fn file_entry_name(item: &[u8]) -> Option<String> {
+    const PRIMARY_NAME: usize = 12;
+    let unicode = item[0] & 0x04 != 0;
+    let (primary, end) = if unicode {
+        let name = utf16_z(item, PRIMARY_NAME);
+        let end = PRIMARY_NAME + (name.encode_utf16().count() + 1) * 2;
+        (name, end)
+    } else {
+        let name = ansi_z(item, PRIMARY_NAME);
+        let end = PRIMARY_NAME + name.len() + 1;
+        (name, end)
+    };
+
+    // Extension blocks are 2-byte aligned relative to the item's size field
+    let start = end + end % 2;
+    let long_name = item.get(start..).and_then(|extension| {
+        let mut reader = Reader::new(extension);
+        reader.u16().ok()?;
+        let version = reader.u16().ok()?;
+        if reader.u32().ok()? != FILE_ENTRY_EXTENSION {
+            return None;
+        }
+        let offset = match version {
+            9.. => 0x2E,
+            8 => 0x2A,
+            7 => 0x26,
+            3..=6 => 0x14,
+            _ => return None,
+        };
+        Some(utf16_z(extension, offset)).filter(|name| !name.is_empty())
+    });
+
+    Some(long_name.unwrap_or(primary))
+}
+
+/// Reads the target of an EnvironmentVariableDataBlock.
+fn environment_block_path(block: &[u8]) -> Option<String> {
+    let unicode = block
+        .get(8 + MAX_PATH..8 + MAX_PATH * 3)
+        .map(|target| utf16_z(target, 0))
+        .filter(|target| !target.is_empty());
+    unicode.or_else(|| {
+        block
+            .get(8..8 + MAX_PATH)
+            .map(|target| ansi_z(target, 0))
+            .filter(|target| !target.is_empty())
+    })
+}
+
+fn invalid(reason: &str) -> StartupError {
+    StartupError::InvalidFormat(format!("shell link: {}", reason))
+}
+
+/// Decodes UTF-16LE text.
+fn utf16(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+/// Decodes null-terminated UTF-16LE text starting at `offset`.
+fn utf16_z(bytes: &[u8], offset: usize) -> String {
+    let bytes = bytes.get(offset..).unwrap_or_default();
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .take_while(|&unit| unit != 0)
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+/// Decodes text in the system code page, read as Latin-1.
+fn ansi(bytes: &[u8]) -> String {
+    bytes.iter().map(|&b| b as char).collect()
+}
+
+/// Decodes null-terminated code page text starting at `offset`.
+fn ansi_z(bytes: &[u8], offset: usize) -> String {
+    let bytes = bytes.get(offset..).unwrap_or_default();
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    ansi(&bytes[..end])
+}
+
+/// Encodes text for code page fields; characters outside Latin-1 become `?`.
+fn to_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| u8::try_from(u32::from(c)).unwrap_or(b'?'))
+        .collect()
+}
+
+fn put_u16(bytes: &mut Vec<u8>, value: u16) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_u32(bytes: &mut Vec<u8>, value: u32) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+/// Reads little-endian fields, failing on truncated input.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.pos
+    }
+
+    fn peek(&self, len: usize) -> Result<&'a [u8]> {
+        self.bytes
+            .get(self.pos..self.pos + len)
+            .ok_or_else(|| invalid("unexpected end of file"))
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let bytes = self.peek(len)?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+}
@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use crate::shared::error::StartupError;
+    use proptest::prelude::*;
+
+    const LOCAL: &[u8] = include_bytes!("../../tests/fixtures/shortcut_local.lnk");
+    const ENVIRONMENT: &[u8] = include_bytes!("../../tests/fixtures/shortcut_environment.lnk");
+    const ID_LIST: &[u8] = include_bytes!("../../tests/fixtures/shortcut_idlist.lnk");
+    const NETWORK: &[u8] = include_bytes!("../../tests/fixtures/shortcut_network.lnk");
+
+    #[test]
+    fn test_read_local_target_from_link_info() {
+        let link = ShellLink::from_bytes(LOCAL).unwrap();
+
+        assert_eq!(link.target, r"C:\test\a.txt");
+        assert_eq!(link.working_dir.as_deref(), Some(r"C:\test"));
+        assert_eq!(link.arguments, "");
+        assert_eq!(link.show_command, ShowCommand::Normal);
+    }
+
+    #[test]
+    fn test_read_environment_target_with_arguments() {
+        let link = ShellLink::from_bytes(ENVIRONMENT).unwrap();
+
+        assert_eq!(link.target, r"%LOCALAPPDATA%\Programs\App\app.exe");
+        assert_eq!(link.arguments, r#"--minimized --profile "Work""#);
+        assert_eq!(
+            link.working_dir.as_deref(),
+            Some(r"%LOCALAPPDATA%\Programs\App")
+        );
+        assert_eq!(link.description.as_deref(), Some("Starts App minimized"));
+        assert_eq!(link.icon_location.as_deref(), Some(link.target.as_str()));
+        assert_eq!(link.icon_index, 0);
+        assert_eq!(link.show_command, ShowCommand::Minimized);
+    }
+
+    #[test]
+    fn test_read_target_from_id_list_long_names() {
+        let link = ShellLink::from_bytes(ID_LIST).unwrap();
+
+        assert_eq!(link.target, r"C:\Program Files\Example App\example.exe");
+        assert_eq!(link.arguments, "-q");
+        assert_eq!(link.working_dir, None);
+    }
+
+    #[test]
+    fn test_read_network_target() {
+        let link = ShellLink::from_bytes(NETWORK).unwrap();
+
+        assert_eq!(link.target, r"\\server\share\tools\sync.exe");
+        assert_eq!(link.arguments, "/silent");
+        assert_eq!(link.show_command, ShowCommand::Maximized);
+    }
+
+    #[test]
+    fn test_fixtures_round_trip() {
+        for bytes in [LOCAL, ENVIRONMENT, ID_LIST, NETWORK] {
+            let link = ShellLink::from_bytes(bytes).unwrap();
+
+            assert_eq!(ShellLink::from_bytes(&link.to_bytes()).unwrap(), link);
+        }
+    }
+
+    #[test]
+    fn test_written_id_list_resolves_to_target() {
+        let link = ShellLink::new(r"C:\Tools\Ünïcode\app.exe");
+        let mut bytes = link.to_bytes();
+
+        // Drop the link info flag so only the item ID list is left to read
+        bytes[20] &= !0x02;
+        let info_start = 0x4C + 2 + u16::from_le_bytes([bytes[0x4C], bytes[0x4D]]) as usize;
+        let info_size = u32::from_le_bytes(bytes[info_start..info_start + 4].try_into().unwrap());
+        bytes.drain(info_start..info_start + info_size as usize);
+
+        assert_eq!(ShellLink::from_bytes(&bytes).unwrap().target, link.target);
+    }
+
+    #[test]
+    fn test_hidden_show_command_round_trips() {
+        let link = ShellLink {
+            show_command: ShowCommand::Hidden,
+            ..ShellLink::new(r"C:\app.exe")
+        };
+
+        assert_eq!(
+            ShellLink::from_bytes(&link.to_bytes())
+                .unwrap()
+                .show_command,
+            ShowCommand::Hidden
+        );
+    }
+
+    #[test]
+    fn test_command_line_quotes_target() {
+        let link = ShellLink {
+            arguments: "--flag value".to_string(),
+            ..ShellLink::new(r"C:\Program Files\App\app.exe")
+        };
+
+        assert_eq!(
+            link.command_line(),
+            r#""C:\Program Files\App\app.exe" --flag value"#
+        );
+        assert_eq!(ShellLink::new(r"C:\app.exe").command_line(), r"C:\app.exe");
+    }
+
+    #[test]
+    fn test_rejects_other_files() {
+        assert!(matches!(
+            ShellLink::from_bytes(b"[InternetShortcut]\r\nURL=https://example.com\r\n"),
+            Err(StartupError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            ShellLink::from_bytes(&LOCAL[..100]),
+            Err(StartupError::InvalidFormat(_))
+        ));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_written_links_read_back(
+            target in r"([A-Z]:\\|%[A-Z]{3,8}%\\)[^\x00%]{1,40}",
+            arguments in "[^\x00]{0,40}",
+            working_dir in proptest::option::of("[^\x00]{1,40}"),
+            description in proptest::option::of("[^\x00]{1,40}"),
+            icon_index in any::<i32>(),
+            show in 0usize..4,
+        ) {
+            let link = ShellLink {
+                target,
+                arguments,
+                icon_location: working_dir.clone(),
+                working_dir,
+                description,
+                icon_index,
+                show_command: [
+                    ShowCommand::Normal,
+                    ShowCommand::Maximized,
+                    ShowCommand::Minimized,
+                    ShowCommand::Hidden,
+                ][show],
+            };
+
+            prop_assert_eq!(ShellLink::from_bytes(&link.to_bytes()).unwrap(), link);
+        }
+    }
+}
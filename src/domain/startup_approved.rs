@@ -0,0 +1,76 @@
+use crate::shared::time::{from_filetime, to_filetime};
+use chrono::{DateTime, Utc};
+
+/// Flag Task Manager writes for enabled entries.
+const ENABLED_FLAG: u32 = 0x02;
+/// Flag Task Manager writes for disabled entries.
+const DISABLED_FLAG: u32 = 0x03;
+
+/// Whether Windows runs a startup entry at logon, as recorded by Task
+/// Manager under `Explorer\StartupApproved`.
+///
+/// Each entry's state is a 12-byte binary value: a little-endian flag word
+/// whose lowest bit marks the entry as disabled, followed by the FILETIME
+/// at which it was disabled (zero while it is enabled). Entries without a
+/// value are enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StartupApproval {
+    pub enabled: bool,
+    /// When the entry was disabled, if it is and the time is known.
+    pub disabled_at: Option<DateTime<Utc>>,
+}
+
+impl StartupApproval {
+    /// Length of the binary value.
+    pub const LEN: usize = 12;
+
+    /// The state of entries Windows runs.
+    pub const ENABLED: StartupApproval = StartupApproval {
+        enabled: true,
+        disabled_at: None,
+    };
+
+    /// The state of an entry disabled at `at`.
+    pub fn disabled_since(at: DateTime<Utc>) -> Self {
+        Self {
+            enabled: false,
+            disabled_at: Some(at),
+        }
+    }
+
+    /// Decodes a StartupApproved value.
+    /// Returns `None` if the value is not 12 bytes long.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes: &[u8; Self::LEN] = bytes.try_into().ok()?;
+        let flag = u32::from_le_bytes(bytes[..4].try_into().ok()?);
+        let filetime = u64::from_le_bytes(bytes[4..].try_into().ok()?);
+
+        if flag & 1 == 0 {
+            return Some(Self::ENABLED);
+        }
+        Some(Self {
+            enabled: false,
+            disabled_at: (filetime != 0).then(|| from_filetime(filetime)).flatten(),
+        })
+    }
+
+    /// Encodes the state the way Task Manager does.
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let (flag, filetime) = if self.enabled {
+            (ENABLED_FLAG, 0)
+        } else {
+            (DISABLED_FLAG, self.disabled_at.map_or(0, to_filetime))
+        };
+
+        let mut bytes = [0; Self::LEN];
+        bytes[..4].copy_from_slice(&flag.to_le_bytes());
+        bytes[4..].copy_from_slice(&filetime.to_le_bytes());
+        bytes
+    }
+}
+
+impl Default for StartupApproval {
+    fn default() -> Self {
+        Self::ENABLED
+    }
+}
@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use chrono::{TimeZone, Utc};
+
+    /// The value Task Manager writes for an entry disabled on
+    /// 2024-03-01 12:00:00 UTC.
+    const DISABLED: [u8; 12] = [
+        0x03, 0x00, 0x00, 0x00, 0x00, 0xa0, 0x87, 0xfc, 0xcf, 0x6b, 0xda, 0x01,
+    ];
+
+    #[test]
+    fn test_decode_disabled_value() {
+        let approval = StartupApproval::from_bytes(&DISABLED).unwrap();
+
+        assert!(!approval.enabled);
+        assert_eq!(
+            approval.disabled_at,
+            Some(Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_encode_disabled_value() {
+        let at = Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap();
+
+        assert_eq!(StartupApproval::disabled_since(at).to_bytes(), DISABLED);
+    }
+
+    #[test]
+    fn test_encode_enabled_value() {
+        assert_eq!(
+            StartupApproval::ENABLED.to_bytes(),
+            [0x02, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn test_enabled_value_ignores_timestamp() {
+        // Re-enabled entries may keep the time they were disabled at
+        let mut bytes = DISABLED;
+        bytes[0] = 0x02;
+
+        assert_eq!(
+            StartupApproval::from_bytes(&bytes),
+            Some(StartupApproval::ENABLED)
+        );
+    }
+
+    #[test]
+    fn test_lowest_flag_bit_marks_disabled() {
+        let mut bytes = [0; 12];
+        bytes[0] = 0x06;
+        assert!(StartupApproval::from_bytes(&bytes).unwrap().enabled);
+
+        bytes[0] = 0x07;
+        let approval = StartupApproval::from_bytes(&bytes).unwrap();
+        assert!(!approval.enabled);
+        assert_eq!(approval.disabled_at, None);
+    }
+
+    #[test]
+    fn test_decode_rejects_wrong_length() {
+        assert_eq!(StartupApproval::from_bytes(&DISABLED[..8]), None);
+        assert_eq!(StartupApproval::from_bytes(&[0; 13]), None);
+        assert_eq!(StartupApproval::from_bytes(&[]), None);
+    }
+
+    #[test]
+    fn test_round_trip_keeps_100ns_precision() {
+        let at = Utc.timestamp_opt(1_700_000_000, 123_456_700).unwrap();
+        let approval = StartupApproval::disabled_since(at);
+
+        assert_eq!(
+            StartupApproval::from_bytes(&approval.to_bytes()),
+            Some(approval)
+        );
+    }
+
+    #[test]
+    fn test_entry_approval_accessors() {
+        let at = Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap();
+        let entry = StartupEntry::new("App", "app.exe");
+        assert!(entry.enabled);
+
+        let entry = entry.with_approval(StartupApproval::disabled_since(at));
+
+        assert!(!entry.enabled);
+        assert_eq!(entry.disabled_at, Some(at));
+        assert_eq!(entry.approval(), StartupApproval::disabled_since(at));
+    }
+}
@@ -0,0 +1,112 @@
+use super::launcher_store::LauncherStore;
+use super::models::StartupEntry;
+use super::startup_manifest::{DesiredEntry, ManifestEntry};
+use crate::shared::error::{Result, StartupError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Version of the backup format `export` writes.
+pub const BACKUP_VERSION: u32 = 1;
+
+/// Every startup entry of a machine, as `export` writes it to JSON and
+/// `import` restores it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct StartupBackup {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    #[serde(default)]
+    pub entries: Vec<BackupEntry>,
+}
+
+/// One exported entry. The decoded command, mode, location and enabled
+/// state are written like a manifest entry; the stored value and the
+/// launcher it runs are kept as they were.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct BackupEntry {
+    /// Sets neither `path` nor `command` when the value couldn't be decoded.
+    #[serde(flatten)]
+    pub entry: ManifestEntry,
+    /// The registry value, shortcut command line or task action as stored.
+    pub value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disabled_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub launcher: Option<BackupLauncher>,
+}
+
+/// A VBScript launcher run by an exported entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BackupLauncher {
+    pub filename: String,
+    pub content: String,
+}
+
+impl StartupBackup {
+    /// Creates an empty backup made at `exported_at`.
+    pub fn new(exported_at: DateTime<Utc>) -> Self {
+        Self {
+            version: BACKUP_VERSION,
+            exported_at,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Parses a backup, rejecting versions this build doesn't know.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let backup: Self = serde_json::from_str(json)
+            .map_err(|e| StartupError::InvalidFormat(format!("backup: {}", e)))?;
+        if backup.version != BACKUP_VERSION {
+            return Err(StartupError::InvalidFormat(format!(
+                "backup: unsupported version {} (expected {})",
+                backup.version, BACKUP_VERSION
+            )));
+        }
+        Ok(backup)
+    }
+
+    /// Serializes the backup to pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+impl BackupEntry {
+    /// Exports `entry`, decoding its value and reading its launcher from
+    /// `launchers`.
+    pub fn export<L: LauncherStore>(entry: &StartupEntry, launchers: &L) -> Result<Self> {
+        let described = match launchers.decode(&entry.command) {
+            Some(command) => ManifestEntry::from_desired(&DesiredEntry {
+                name: entry.name.clone(),
+                command,
+                location: entry.location,
+                enabled: entry.enabled,
+            }),
+            None => ManifestEntry {
+                name: entry.name.clone(),
+                location: Some(entry.location.as_str().to_string()),
+                enabled: Some(entry.enabled),
+                ..ManifestEntry::default()
+            },
+        };
+        let launcher = match launchers.referenced_by(&entry.command) {
+            Some(filename) => launchers
+                .read(&filename)?
+                .map(|content| BackupLauncher { filename, content }),
+            None => None,
+        };
+        Ok(Self {
+            entry: described,
+            value: entry.command.clone(),
+            disabled_at: entry.disabled_at,
+            launcher,
+        })
+    }
+
+    /// Returns whether the entry's command was decoded when it was exported.
+    pub fn is_decoded(&self) -> bool {
+        self.entry.path.is_some() || self.entry.command.is_some()
+    }
+}
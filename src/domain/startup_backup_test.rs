@@ -0,0 +1,138 @@
+#[cfg(test)]
+mod tests {
+    use super::super::startup_backup::*;
+    use super::super::{
+        DesiredEntry, ExecutionMode, LauncherStore, ManifestEntry, PowerShellOptions,
+        RestartPolicy, StartupApproval, StartupCommand, StartupEntry, StartupLocation,
+        TaskSettings, WindowStyle,
+    };
+    use crate::infrastructure::InMemoryLauncherStore;
+    use crate::shared::error::StartupError;
+    use chrono::{TimeZone, Utc};
+    use std::time::Duration;
+
+    fn command(mode: ExecutionMode) -> StartupCommand {
+        StartupCommand::CommandWithArgs {
+            command: "bun".to_string(),
+            args: vec!["run".to_string(), "dev".to_string()],
+            workdir: Some(r"C:\app".to_string()),
+            mode,
+        }
+    }
+
+    #[test]
+    fn test_manifest_entries_describe_every_mode() {
+        let modes = [
+            ExecutionMode::VBScript,
+            ExecutionMode::PowerShell(PowerShellOptions {
+                window: WindowStyle::Minimized,
+                no_exit: true,
+            }),
+            ExecutionMode::ScheduledTask(TaskSettings {
+                delay: Some(Duration::from_secs(90)),
+                restart: Some(RestartPolicy {
+                    count: 3,
+                    interval: Duration::from_secs(300),
+                }),
+                hidden: true,
+                highest_privileges: true,
+            }),
+        ];
+        for mode in modes {
+            let location = match mode {
+                ExecutionMode::ScheduledTask(_) => StartupLocation::TaskScheduler,
+                _ => StartupLocation::LocalMachineRun,
+            };
+            let desired = DesiredEntry {
+                name: "Dev".to_string(),
+                command: command(mode),
+                location,
+                enabled: false,
+            };
+
+            assert_eq!(
+                ManifestEntry::from_desired(&desired).to_desired().unwrap(),
+                desired
+            );
+        }
+    }
+
+    #[test]
+    fn test_exports_decoded_vbscript_entries_with_their_launcher() {
+        let launchers = InMemoryLauncherStore::new();
+        let command = command(ExecutionMode::VBScript);
+        let (filename, content) = command.get_vbscript_content("Dev").unwrap();
+        launchers.write(&filename, &content).unwrap();
+        let disabled_at = Utc.with_ymd_and_hms(2026, 3, 1, 8, 30, 0).unwrap();
+        let entry = StartupEntry::new("Dev", command.to_registry_value("Dev", &launchers))
+            .with_approval(StartupApproval::disabled_since(disabled_at));
+
+        let exported = BackupEntry::export(&entry, &launchers).unwrap();
+
+        assert!(exported.is_decoded());
+        assert_eq!(exported.entry.command.as_deref(), Some("bun"));
+        assert_eq!(exported.entry.workdir.as_deref(), Some(r"C:\app"));
+        assert_eq!(exported.entry.mode.as_deref(), Some("vbscript"));
+        assert_eq!(exported.entry.location.as_deref(), Some("hkcu-run"));
+        assert_eq!(exported.entry.enabled, Some(false));
+        assert_eq!(exported.value, entry.command);
+        assert_eq!(exported.disabled_at, Some(disabled_at));
+        assert_eq!(
+            exported.launcher,
+            Some(BackupLauncher { filename, content })
+        );
+    }
+
+    #[test]
+    fn test_keeps_the_value_of_entries_that_cannot_be_decoded() {
+        // The launcher of this entry was never written
+        let launchers = InMemoryLauncherStore::new();
+        let value = command(ExecutionMode::VBScript).to_registry_value("Gone", &launchers);
+        let entry = StartupEntry::new("Gone", value.as_str());
+
+        let exported = BackupEntry::export(&entry, &launchers).unwrap();
+
+        assert!(!exported.is_decoded());
+        assert_eq!(exported.value, value);
+        assert_eq!(exported.launcher, None);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let launchers = InMemoryLauncherStore::new();
+        let mut backup = StartupBackup::new(Utc.with_ymd_and_hms(2026, 3, 1, 8, 30, 0).unwrap());
+        for entry in [
+            StartupEntry::new("Notes", r"C:\Notes\notes.exe")
+                .with_location(StartupLocation::UserStartupFolder),
+            StartupEntry::new(
+                "Api",
+                command(ExecutionMode::PowerShell(PowerShellOptions::default()))
+                    .to_registry_value("Api", &launchers),
+            ),
+        ] {
+            backup
+                .entries
+                .push(BackupEntry::export(&entry, &launchers).unwrap());
+        }
+
+        let json = backup.to_json();
+
+        assert!(json.contains(r#""exported-at": "2026-03-01T08:30:00Z""#));
+        assert!(json.contains(r#""location": "startup-folder""#));
+        assert!(!json.contains("no-exit"));
+        assert_eq!(StartupBackup::from_json(&json).unwrap(), backup);
+    }
+
+    #[test]
+    fn test_rejects_unknown_versions_and_invalid_json() {
+        for json in [
+            r#"{"version": 2, "exported-at": "2026-03-01T08:30:00Z", "entries": []}"#,
+            r#"{"version": 1, "entries": []}"#,
+            r#"{"version": 1, "exported-at": "2026-03-01T08:30:00Z", "entries": [{}]}"#,
+            "[",
+        ] {
+            let error = StartupBackup::from_json(json).unwrap_err();
+            assert!(matches!(error, StartupError::InvalidFormat(_)), "{}", json);
+        }
+    }
+}
@@ -0,0 +1,274 @@
+use super::location::StartupLocation;
+use super::mode_spec::{ModeName, ModeSpec};
+use super::models::{ExecutionMode, StartupCommand, WindowStyle};
+use super::scheduled_task::{format_short_duration, parse_short_duration};
+use super::validator::StartupValidator;
+use crate::shared::error::{Result, StartupError};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+
+/// File format of a startup manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestFormat {
+    Toml,
+    Yaml,
+}
+
+impl ManifestFormat {
+    /// Picks the format from the file extension: `.yaml` and `.yml` files
+    /// are YAML, anything else is TOML.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml") => {
+                ManifestFormat::Yaml
+            }
+            _ => ManifestFormat::Toml,
+        }
+    }
+}
+
+/// The startup entries a machine should have, as written in a TOML or YAML
+/// manifest. Entries are identified by name, ignoring case.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StartupManifest {
+    #[serde(default)]
+    pub entries: Vec<ManifestEntry>,
+}
+
+/// One entry of a manifest. An entry either starts an executable by `path`,
+/// like `add` does, or runs a `command` with `args` like `add-command` does,
+/// taking the same mode options.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct ManifestEntry {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workdir: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub window: Option<String>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub no_exit: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delay: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restart_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restart_interval: Option<String>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub hidden: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub highest: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+/// A startup entry as a manifest describes it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DesiredEntry {
+    pub name: String,
+    pub command: StartupCommand,
+    pub location: StartupLocation,
+    pub enabled: bool,
+}
+
+impl DesiredEntry {
+    /// Validates the entry like `add` and `add-command` do: the executable
+    /// or working directory must exist and task settings must be in range.
+    pub fn validate(&self) -> Result<()> {
+        StartupValidator::validate_name(&self.name)?;
+        match &self.command {
+            StartupCommand::Executable { path } => StartupValidator::validate_path(path),
+            StartupCommand::CommandWithArgs { workdir, mode, .. } => {
+                if let Some(dir) = workdir {
+                    StartupValidator::validate_directory(dir)?;
+                }
+                if let ExecutionMode::ScheduledTask(settings) = mode {
+                    StartupValidator::validate_task_settings(settings)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl StartupManifest {
+    /// Parses a manifest written in `format`.
+    pub fn parse(text: &str, format: ManifestFormat) -> Result<Self> {
+        let manifest = match format {
+            ManifestFormat::Toml => toml::from_str(text).map_err(|e| e.message().to_string()),
+            ManifestFormat::Yaml => serde_yaml::from_str(text).map_err(|e| e.to_string()),
+        };
+        manifest.map_err(|e| StartupError::InvalidFormat(format!("manifest: {}", e)))
+    }
+
+    /// Returns the entries the manifest describes, rejecting entries with
+    /// invalid options and names used more than once.
+    pub fn desired_entries(&self) -> Result<Vec<DesiredEntry>> {
+        let mut desired: Vec<DesiredEntry> = Vec::new();
+        for entry in &self.entries {
+            let entry = entry.to_desired()?;
+            if desired
+                .iter()
+                .any(|other| other.name.eq_ignore_ascii_case(&entry.name))
+            {
+                return Err(StartupError::InvalidFormat(format!(
+                    "manifest: entry '{}' is listed more than once",
+                    entry.name
+                )));
+            }
+            desired.push(entry);
+        }
+        Ok(desired)
+    }
+}
+
+impl ManifestEntry {
+    /// Describes `desired` with every option spelled out, so that
+    /// [`to_desired`](Self::to_desired) returns it again.
+    pub fn from_desired(desired: &DesiredEntry) -> Self {
+        let mut entry = ManifestEntry {
+            name: desired.name.clone(),
+            location: Some(desired.location.as_str().to_string()),
+            enabled: Some(desired.enabled),
+            ..Self::default()
+        };
+        match &desired.command {
+            StartupCommand::Executable { path } => entry.path = Some(path.clone()),
+            StartupCommand::CommandWithArgs {
+                command,
+                args,
+                workdir,
+                mode,
+            } => {
+                let spec = ModeSpec::from_mode(mode);
+                entry.command = Some(command.clone());
+                entry.args = args.clone();
+                entry.workdir = workdir.clone();
+                entry.mode = Some(spec.mode.as_str().to_string());
+                entry.window = spec.window.map(|w| w.as_str().to_lowercase());
+                entry.no_exit = spec.no_exit;
+                entry.delay = spec.delay.map(format_short_duration);
+                entry.restart_count = spec.restart_count;
+                entry.restart_interval = spec.restart_interval.map(format_short_duration);
+                entry.hidden = spec.hidden;
+                entry.highest = spec.highest;
+            }
+        }
+        entry
+    }
+
+    /// Builds the entry this manifest entry describes.
+    pub fn to_desired(&self) -> Result<DesiredEntry> {
+        let invalid = |message: String| {
+            StartupError::InvalidFormat(format!("manifest entry '{}': {}", self.name, message))
+        };
+        let duration = |value: &Option<String>| -> Result<Option<Duration>> {
+            value
+                .as_deref()
+                .map(|text| {
+                    parse_short_duration(text)
+                        .ok_or_else(|| invalid(format!("invalid duration '{}'", text)))
+                })
+                .transpose()
+        };
+
+        let command = match (&self.path, &self.command) {
+            (Some(path), None) => {
+                if !self.args.is_empty() || self.workdir.is_some() || self.has_mode_options() {
+                    return Err(invalid(
+                        "path entries take no args, workdir or mode options".to_string(),
+                    ));
+                }
+                StartupCommand::Executable { path: path.clone() }
+            }
+            (None, Some(command)) => {
+                let mode = match &self.mode {
+                    Some(name) => ModeName::parse(name).ok_or_else(|| {
+                        let names: Vec<_> = ModeName::ALL.iter().map(|m| m.as_str()).collect();
+                        invalid(format!(
+                            "unknown mode '{}' (expected one of: {})",
+                            name,
+                            names.join(", ")
+                        ))
+                    })?,
+                    None => ModeName::default(),
+                };
+                let window = match &self.window {
+                    Some(name) => Some(
+                        WindowStyle::parse(name)
+                            .ok_or_else(|| invalid(format!("unknown window style '{}'", name)))?,
+                    ),
+                    None => None,
+                };
+                let spec = ModeSpec {
+                    mode,
+                    window,
+                    no_exit: self.no_exit,
+                    delay: duration(&self.delay)?,
+                    restart_count: self.restart_count,
+                    restart_interval: duration(&self.restart_interval)?,
+                    hidden: self.hidden,
+                    highest: self.highest,
+                };
+                StartupCommand::CommandWithArgs {
+                    command: command.clone(),
+                    args: self.args.clone(),
+                    workdir: self.workdir.clone(),
+                    mode: spec.execution_mode().map_err(invalid)?,
+                }
+            }
+            _ => return Err(invalid("set either path or command".to_string())),
+        };
+
+        let is_task = matches!(command.mode(), Some(ExecutionMode::ScheduledTask(_)));
+        let location = match &self.location {
+            Some(location) => location.parse().map_err(|e: StartupError| match e {
+                StartupError::InvalidLocation(message) => {
+                    invalid(format!("unknown location {}", message))
+                }
+                e => e,
+            })?,
+            None if is_task => StartupLocation::TaskScheduler,
+            None => StartupLocation::default(),
+        };
+        if is_task != (location == StartupLocation::TaskScheduler) {
+            return Err(invalid(
+                "scheduled-task entries, and only they, are stored in task-scheduler".to_string(),
+            ));
+        }
+
+        Ok(DesiredEntry {
+            name: self.name.clone(),
+            command,
+            location,
+            enabled: self.enabled.unwrap_or(true),
+        })
+    }
+
+    fn has_mode_options(&self) -> bool {
+        self.mode.is_some()
+            || self.window.is_some()
+            || self.no_exit
+            || self.delay.is_some()
+            || self.restart_count.is_some()
+            || self.restart_interval.is_some()
+            || self.hidden
+            || self.highest
+    }
+}
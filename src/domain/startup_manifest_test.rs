@@ -0,0 +1,184 @@
+#[cfg(test)]
+mod tests {
+    use super::super::startup_manifest::*;
+    use super::super::{
+        ExecutionMode, PowerShellOptions, RestartPolicy, StartupCommand, StartupLocation,
+        TaskSettings, WindowStyle,
+    };
+    use crate::shared::error::StartupError;
+    use std::path::Path;
+    use std::time::Duration;
+
+    const TOML: &str = include_str!("../../tests/fixtures/startup_manifest.toml");
+    const YAML: &str = include_str!("../../tests/fixtures/startup_manifest.yaml");
+
+    fn desired(text: &str, format: ManifestFormat) -> Vec<DesiredEntry> {
+        StartupManifest::parse(text, format)
+            .unwrap()
+            .desired_entries()
+            .unwrap()
+    }
+
+    fn entry_error(toml: &str) -> String {
+        let error = StartupManifest::parse(toml, ManifestFormat::Toml)
+            .and_then(|manifest| manifest.desired_entries())
+            .unwrap_err();
+        assert!(matches!(error, StartupError::InvalidFormat(_)), "{}", error);
+        error.to_string()
+    }
+
+    #[test]
+    fn test_format_follows_the_extension() {
+        assert_eq!(
+            ManifestFormat::from_path(Path::new("startup.toml")),
+            ManifestFormat::Toml
+        );
+        assert_eq!(
+            ManifestFormat::from_path(Path::new("startup.YML")),
+            ManifestFormat::Yaml
+        );
+        assert_eq!(
+            ManifestFormat::from_path(Path::new("startup.yaml")),
+            ManifestFormat::Yaml
+        );
+        assert_eq!(
+            ManifestFormat::from_path(Path::new("startup")),
+            ManifestFormat::Toml
+        );
+    }
+
+    #[test]
+    fn test_toml_and_yaml_describe_the_same_entries() {
+        assert_eq!(
+            desired(TOML, ManifestFormat::Toml),
+            desired(YAML, ManifestFormat::Yaml)
+        );
+    }
+
+    #[test]
+    fn test_reads_every_kind_of_entry() {
+        let entries = desired(TOML, ManifestFormat::Toml);
+
+        assert_eq!(
+            entries[0],
+            DesiredEntry {
+                name: "BunDevServer".to_string(),
+                command: StartupCommand::CommandWithArgs {
+                    command: "bun".to_string(),
+                    args: vec!["run".to_string(), "dev".to_string()],
+                    workdir: Some(r"C:\projects\my-app".to_string()),
+                    mode: ExecutionMode::VBScript,
+                },
+                location: StartupLocation::CurrentUserRun,
+                enabled: true,
+            }
+        );
+        assert_eq!(
+            entries[1].command.mode(),
+            Some(ExecutionMode::PowerShell(PowerShellOptions {
+                window: WindowStyle::Minimized,
+                no_exit: true,
+            }))
+        );
+        assert_eq!(
+            entries[2].command.mode(),
+            Some(ExecutionMode::ScheduledTask(TaskSettings {
+                delay: Some(Duration::from_secs(60)),
+                restart: Some(RestartPolicy {
+                    count: 3,
+                    interval: Duration::from_secs(60),
+                }),
+                hidden: false,
+                highest_privileges: true,
+            }))
+        );
+        assert_eq!(entries[2].location, StartupLocation::TaskScheduler);
+        assert_eq!(
+            entries[3],
+            DesiredEntry {
+                name: "Notes".to_string(),
+                command: StartupCommand::Executable {
+                    path: r"C:\Program Files\Notes\notes.exe".to_string(),
+                },
+                location: StartupLocation::UserStartupFolder,
+                enabled: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_empty_manifest_has_no_entries() {
+        assert!(desired("", ManifestFormat::Toml).is_empty());
+        assert!(desired("entries: []", ManifestFormat::Yaml).is_empty());
+    }
+
+    #[test]
+    fn test_rejects_unknown_keys_and_syntax_errors() {
+        for (text, format) in [
+            (
+                "[[entries]]\nname = \"a\"\ncommand = \"b\"\nworkdri = \"c\"",
+                ManifestFormat::Toml,
+            ),
+            ("[[entries]\nname = \"a\"", ManifestFormat::Toml),
+            ("entries:\n  - name: a\n    comand: b", ManifestFormat::Yaml),
+            ("entries: [", ManifestFormat::Yaml),
+        ] {
+            let error = StartupManifest::parse(text, format).unwrap_err();
+            assert!(matches!(error, StartupError::InvalidFormat(_)), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_entries_set_either_path_or_command() {
+        assert!(entry_error("[[entries]]\nname = \"a\"").contains("either path or command"));
+        assert!(
+            entry_error("[[entries]]\nname = \"a\"\npath = \"a.exe\"\ncommand = \"a\"")
+                .contains("either path or command")
+        );
+        assert!(
+            entry_error("[[entries]]\nname = \"a\"\npath = \"a.exe\"\nmode = \"visible\"")
+                .contains("path entries")
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_mode_options() {
+        let entry = |options: &str| {
+            entry_error(&format!(
+                "[[entries]]\nname = \"a\"\ncommand = \"a\"\n{}",
+                options
+            ))
+        };
+
+        assert!(entry("mode = \"silent\"").contains("unknown mode 'silent'"));
+        assert!(entry("mode = \"powershell\"\nwindow = \"tiny\"").contains("'tiny'"));
+        assert!(entry("delay = \"30s\"").contains("delay cannot be used with mode vbscript"));
+        assert!(entry("mode = \"scheduled-task\"\ndelay = \"soon\"").contains("'soon'"));
+        assert!(entry("location = \"hkcu-runn\"").contains("unknown location"));
+    }
+
+    #[test]
+    fn test_only_scheduled_tasks_are_stored_in_task_scheduler() {
+        assert!(
+            entry_error(
+                "[[entries]]\nname = \"a\"\ncommand = \"a\"\nmode = \"scheduled-task\"\nlocation = \"hkcu-run\""
+            )
+            .contains("task-scheduler")
+        );
+        assert!(
+            entry_error(
+                "[[entries]]\nname = \"a\"\ncommand = \"a\"\nlocation = \"task-scheduler\""
+            )
+            .contains("task-scheduler")
+        );
+    }
+
+    #[test]
+    fn test_rejects_names_listed_twice() {
+        let error = entry_error(
+            "[[entries]]\nname = \"Dev\"\ncommand = \"a\"\n\n[[entries]]\nname = \"dev\"\npath = \"b.exe\"",
+        );
+
+        assert!(error.contains("'dev' is listed more than once"));
+    }
+}
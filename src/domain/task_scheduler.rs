@@ -0,0 +1,20 @@
+use crate::shared::error::Result;
+
+/// Access to the tasks the tool registers in its Task Scheduler folder
+/// ([`TASK_FOLDER`](super::scheduled_task::TASK_FOLDER)).
+/// Tasks are named after their entries and exchanged as XML definitions.
+/// Names are compared ignoring case, as Task Scheduler does.
+pub trait TaskScheduler {
+    /// Lists the tasks in the folder as `(name, XML definition)` pairs,
+    /// sorted by name.
+    fn list(&self) -> Result<Vec<(String, String)>>;
+
+    /// Creates or replaces the task `name`.
+    fn register(&self, name: &str, xml: &str) -> Result<()>;
+
+    /// Deletes the task `name`. Returns `false` when it didn't exist.
+    fn delete(&self, name: &str) -> Result<bool>;
+
+    /// Enables or disables the task `name`.
+    fn set_enabled(&self, name: &str, enabled: bool) -> Result<()>;
+}
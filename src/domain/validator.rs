@@ -1,5 +1,7 @@
+use super::scheduled_task::TaskSettings;
 use crate::shared::error::{Result, StartupError};
 use std::path::Path;
+use std::time::Duration;
 
 /// Validates startup-related inputs.
 /// This follows the Single Responsibility Principle.
@@ -35,4 +37,25 @@ impl StartupValidator {
         }
         Ok(())
     }
+
+    /// Validates that scheduled task options are within the ranges Task
+    /// Scheduler accepts.
+    pub fn validate_task_settings(settings: &TaskSettings) -> Result<()> {
+        const MINUTE: Duration = Duration::from_secs(60);
+        const MAX_RESTART_INTERVAL: Duration = Duration::from_secs(31 * 24 * 3600);
+
+        if let Some(restart) = settings.restart {
+            if restart.count == 0 {
+                return Err(StartupError::InvalidTaskSettings(
+                    "restart count must be at least 1".to_string(),
+                ));
+            }
+            if restart.interval < MINUTE || restart.interval > MAX_RESTART_INTERVAL {
+                return Err(StartupError::InvalidTaskSettings(
+                    "restart interval must be between 1 minute and 31 days".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
 }
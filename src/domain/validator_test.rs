@@ -129,4 +129,23 @@ mod tests {
         let result = StartupValidator::validate_name("应用程序");
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_validate_task_settings_restart_range() {
+        use std::time::Duration;
+
+        let restart = |count: u32, minutes: u64| TaskSettings {
+            restart: Some(RestartPolicy {
+                count,
+                interval: Duration::from_secs(minutes * 60),
+            }),
+            ..TaskSettings::default()
+        };
+
+        assert!(StartupValidator::validate_task_settings(&TaskSettings::default()).is_ok());
+        assert!(StartupValidator::validate_task_settings(&restart(3, 1)).is_ok());
+        assert!(StartupValidator::validate_task_settings(&restart(0, 1)).is_err());
+        assert!(StartupValidator::validate_task_settings(&restart(3, 0)).is_err());
+        assert!(StartupValidator::validate_task_settings(&restart(3, 32 * 24 * 60)).is_err());
+    }
 }
@@ -0,0 +1,316 @@
+use crate::shared::error::{Result, StartupError};
+
+/// An XML element with its attributes, child elements and text.
+///
+/// This is the small subset of XML the tool exchanges with Windows: the
+/// reader skips the declaration, comments, processing instructions and
+/// doctypes, decodes entities and CDATA sections, and joins the text of
+/// mixed content. Namespace prefixes are kept in names and ignored by
+/// lookups.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Element>,
+    pub text: String,
+}
+
+impl Element {
+    /// Creates an empty element.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Adds an attribute.
+    pub fn with_attribute(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.attributes.push((name.into(), value.into()));
+        self
+    }
+
+    /// Appends a child element.
+    pub fn with_child(mut self, child: Element) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Sets the text of the element.
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// Returns the value of the attribute `name`.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| local_name(n) == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the first child element named `name`.
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|c| local_name(&c.name) == name)
+    }
+
+    /// Follows a path of child element names.
+    pub fn descendant(&self, path: &[&str]) -> Option<&Element> {
+        path.iter()
+            .try_fold(self, |element, name| element.child(name))
+    }
+
+    /// Returns the trimmed text of the element at `path`.
+    pub fn text_at(&self, path: &[&str]) -> Option<&str> {
+        self.descendant(path).map(|e| e.text.trim())
+    }
+
+    /// Parses a document and returns its root element.
+    pub fn parse(document: &str) -> Result<Element> {
+        let mut reader = Reader {
+            text: document.strip_prefix('\u{feff}').unwrap_or(document),
+            pos: 0,
+        };
+        reader.skip_misc()?;
+        if reader.rest().is_empty() {
+            return Err(reader.error("document has no root element"));
+        }
+        let root = reader.element()?;
+        reader.skip_misc()?;
+        if !reader.rest().is_empty() {
+            return Err(reader.error("unexpected content after the root element"));
+        }
+        Ok(root)
+    }
+
+    /// Writes the element as an indented document with an XML declaration
+    /// naming `encoding`.
+    pub fn to_document(&self, encoding: &str) -> String {
+        let mut out = format!("<?xml version=\"1.0\" encoding=\"{}\"?>\n", encoding);
+        self.write(&mut out, 0);
+        out
+    }
+
+    fn write(&self, out: &mut String, depth: usize) {
+        let indent = "  ".repeat(depth);
+        out.push_str(&indent);
+        out.push('<');
+        out.push_str(&self.name);
+        for (name, value) in &self.attributes {
+            out.push_str(&format!(" {}=\"{}\"", name, escape_attribute(value)));
+        }
+
+        if self.children.is_empty() {
+            if self.text.is_empty() {
+                out.push_str(" />\n");
+            } else {
+                out.push_str(&format!(">{}</{}>\n", escape(&self.text), self.name));
+            }
+            return;
+        }
+
+        out.push_str(">\n");
+        for child in &self.children {
+            child.write(out, depth + 1);
+        }
+        out.push_str(&format!("{}</{}>\n", indent, self.name));
+    }
+}
+
+/// Escapes text for use as element content.
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Escapes text for use in a double-quoted attribute value.
+pub fn escape_attribute(text: &str) -> String {
+    escape(text).replace('"', "&quot;")
+}
+
+/// Strips the namespace prefix from a name.
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+/// Cursor over the document being parsed.
+struct Reader<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    fn error(&self, message: &str) -> StartupError {
+        StartupError::InvalidFormat(format!("xml: {} at offset {}", message, self.pos))
+    }
+
+    fn eat(&mut self, prefix: &str) -> bool {
+        if self.rest().starts_with(prefix) {
+            self.pos += prefix.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, prefix: &str) -> Result<()> {
+        if self.eat(prefix) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", prefix)))
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Consumes everything up to and including `end`, returning what came before it.
+    fn until(&mut self, end: &str) -> Result<&'a str> {
+        let rest = self.rest();
+        let Some(at) = rest.find(end) else {
+            return Err(self.error(&format!("missing '{}'", end)));
+        };
+        self.pos += at + end.len();
+        Ok(&rest[..at])
+    }
+
+    /// Skips a comment, processing instruction or doctype starting here.
+    /// Returns whether anything was skipped.
+    fn skip_markup(&mut self) -> Result<bool> {
+        if self.eat("<!--") {
+            self.until("-->")?;
+        } else if self.eat("<?") {
+            self.until("?>")?;
+        } else if self.eat("<!DOCTYPE") {
+            if self.until(">")?.contains('[') {
+                return Err(self.error("internal doctype subsets are not supported"));
+            }
+        } else {
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    /// Skips the prolog or the content after the root element.
+    fn skip_misc(&mut self) -> Result<()> {
+        loop {
+            self.skip_whitespace();
+            if !self.skip_markup()? {
+                return Ok(());
+            }
+        }
+    }
+
+    fn name(&mut self) -> Result<&'a str> {
+        let rest = self.rest();
+        let end = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '/' | '>' | '=' | '<'))
+            .unwrap_or(rest.len());
+        if end == 0 {
+            return Err(self.error("expected a name"));
+        }
+        self.pos += end;
+        Ok(&rest[..end])
+    }
+
+    fn element(&mut self) -> Result<Element> {
+        self.expect("<")?;
+        let mut element = Element::new(self.name()?);
+
+        loop {
+            self.skip_whitespace();
+            if self.eat("/>") {
+                return Ok(element);
+            }
+            if self.eat(">") {
+                break;
+            }
+            let name = self.name()?;
+            self.skip_whitespace();
+            self.expect("=")?;
+            self.skip_whitespace();
+            let quote = if self.eat("\"") {
+                "\""
+            } else if self.eat("'") {
+                "'"
+            } else {
+                return Err(self.error("expected a quoted attribute value"));
+            };
+            let value = self.until(quote)?;
+            if value.contains('<') {
+                return Err(self.error("'<' in attribute value"));
+            }
+            element
+                .attributes
+                .push((name.to_string(), self.unescape(value)?));
+        }
+
+        loop {
+            let rest = self.rest();
+            let text_end = rest.find('<').unwrap_or(rest.len());
+            element.text.push_str(&self.unescape(&rest[..text_end])?);
+            self.pos += text_end;
+
+            if self.rest().is_empty() {
+                return Err(self.error(&format!("missing '</{}>'", element.name)));
+            }
+            if self.eat("<![CDATA[") {
+                element.text.push_str(self.until("]]>")?);
+            } else if self.eat("</") {
+                let name = self.name()?;
+                if name != element.name {
+                    return Err(self.error(&format!(
+                        "'</{}>' does not close '<{}>'",
+                        name, element.name
+                    )));
+                }
+                self.skip_whitespace();
+                self.expect(">")?;
+                return Ok(element);
+            } else if !self.skip_markup()? {
+                element.children.push(self.element()?);
+            }
+        }
+    }
+
+    /// Decodes the entity and character references in `text`.
+    fn unescape(&self, text: &str) -> Result<String> {
+        let mut result = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find('&') {
+            result.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let end = after
+                .find(';')
+                .ok_or_else(|| self.error("unterminated entity reference"))?;
+            let entity = &after[..end];
+            let decoded = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => entity
+                    .strip_prefix("#x")
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| entity.strip_prefix('#').map(str::parse))
+                    .and_then(|code| code.ok())
+                    .and_then(char::from_u32),
+            };
+            let decoded =
+                decoded.ok_or_else(|| self.error(&format!("unknown entity '&{};'", entity)))?;
+            result.push(decoded);
+            rest = &after[end + 1..];
+        }
+        result.push_str(rest);
+        Ok(result)
+    }
+}
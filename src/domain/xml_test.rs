@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+    use super::super::xml::*;
+    use crate::shared::error::StartupError;
+
+    #[test]
+    fn test_parse_nested_elements_and_attributes() {
+        let root = Element::parse(
+            "<?xml version=\"1.0\"?>\n<!-- settings -->\n\
+             <a x=\"1\" y='two'><b>text</b><c/></a>\n",
+        )
+        .unwrap();
+
+        assert_eq!(root.name, "a");
+        assert_eq!(root.attribute("x"), Some("1"));
+        assert_eq!(root.attribute("y"), Some("two"));
+        assert_eq!(root.text_at(&["b"]), Some("text"));
+        assert!(root.child("c").unwrap().children.is_empty());
+        assert_eq!(root.child("d"), None);
+    }
+
+    #[test]
+    fn test_parse_decodes_entities_and_cdata() {
+        let root =
+            Element::parse("<a t=\"&quot;x&quot;\">&lt;&amp;&gt;&#65;&#x42;<![CDATA[<&>]]></a>")
+                .unwrap();
+
+        assert_eq!(root.attribute("t"), Some("\"x\""));
+        assert_eq!(root.text, "<&>AB<&>");
+    }
+
+    #[test]
+    fn test_lookups_ignore_namespace_prefixes() {
+        let root = Element::parse("<t:a xmlns:t=\"urn:x\"><t:b t:k=\"v\">1</t:b></t:a>").unwrap();
+
+        assert_eq!(root.text_at(&["b"]), Some("1"));
+        assert_eq!(root.child("b").unwrap().attribute("k"), Some("v"));
+    }
+
+    #[test]
+    fn test_parse_skips_byte_order_mark_and_comments_in_content() {
+        let root = Element::parse("\u{feff}<a>1<!-- two -->3<?pi x?></a>").unwrap();
+
+        assert_eq!(root.text, "13");
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_documents() {
+        for document in [
+            "",
+            "text",
+            "<a>",
+            "<a></b>",
+            "<a x=1/>",
+            "<a>&unknown;</a>",
+            "<a/><b/>",
+            "<!DOCTYPE a [<!ENTITY e \"x\">]><a/>",
+        ] {
+            assert!(
+                matches!(
+                    Element::parse(document),
+                    Err(StartupError::InvalidFormat(_))
+                ),
+                "{:?}",
+                document
+            );
+        }
+    }
+
+    #[test]
+    fn test_written_document_reads_back() {
+        let element = Element::new("Task")
+            .with_attribute("note", "a \"b\" & <c>")
+            .with_child(Element::new("Command").with_text(r#""C:\a b\app.exe" <&>"#))
+            .with_child(Element::new("Empty"));
+
+        let document = element.to_document("UTF-16");
+        let read = Element::parse(&document).unwrap();
+
+        assert!(document.starts_with("<?xml version=\"1.0\" encoding=\"UTF-16\"?>\n<Task "));
+        assert!(document.contains("  <Empty />\n"));
+        assert_eq!(read.attribute("note"), Some("a \"b\" & <c>"));
+        assert_eq!(read.text_at(&["Command"]), Some(r#""C:\a b\app.exe" <&>"#));
+        assert_eq!(read.children.len(), 2);
+    }
+}
@@ -0,0 +1,143 @@
+#[cfg(windows)]
+use crate::domain::StartupLocation;
+use crate::domain::{ApprovalStore, StartupApproval};
+use crate::shared::error::Result;
+#[cfg(windows)]
+use crate::shared::error::StartupError;
+use std::cell::RefCell;
+#[cfg(windows)]
+use std::io::{self, ErrorKind};
+#[cfg(windows)]
+use winreg::RegKey;
+#[cfg(windows)]
+use winreg::enums::*;
+
+/// In-memory implementation of the ApprovalStore trait.
+/// Names compare without regard to case, like registry value names.
+#[derive(Debug, Default)]
+pub struct InMemoryApprovalStore {
+    values: RefCell<Vec<(String, StartupApproval)>>,
+}
+
+impl InMemoryApprovalStore {
+    /// Creates a store without values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the stored `(name, approval)` pairs, in the order they were
+    /// first set.
+    pub fn values(&self) -> Vec<(String, StartupApproval)> {
+        self.values.borrow().clone()
+    }
+}
+
+impl ApprovalStore for InMemoryApprovalStore {
+    fn get(&self, name: &str) -> Result<Option<StartupApproval>> {
+        Ok(self
+            .values
+            .borrow()
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, approval)| *approval))
+    }
+
+    fn set(&self, name: &str, approval: StartupApproval) -> Result<()> {
+        let mut values = self.values.borrow_mut();
+        match values
+            .iter_mut()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        {
+            Some((_, value)) => *value = approval,
+            None => values.push((name.to_string(), approval)),
+        }
+        Ok(())
+    }
+
+    fn delete(&self, name: &str) -> Result<()> {
+        self.values
+            .borrow_mut()
+            .retain(|(key, _)| !key.eq_ignore_ascii_case(name));
+        Ok(())
+    }
+}
+
+/// Registry implementation of the ApprovalStore trait, keeping the values
+/// under the StartupApproved key of a location.
+#[cfg(windows)]
+pub struct RegistryApprovalStore {
+    location: StartupLocation,
+    hive: RegKey,
+    path: &'static str,
+}
+
+#[cfg(windows)]
+impl RegistryApprovalStore {
+    /// Opens the StartupApproved key of `location`, or returns `None` for
+    /// locations whose entries cannot be enabled or disabled.
+    pub fn open(location: StartupLocation) -> Option<Self> {
+        let (hive, path) = location.startup_approved_key()?.split_once('\\')?;
+        let hive = match hive {
+            "HKLM" => HKEY_LOCAL_MACHINE,
+            _ => HKEY_CURRENT_USER,
+        };
+        Some(Self {
+            location,
+            hive: RegKey::predef(hive),
+            path,
+        })
+    }
+
+    /// Opens the key in the 64-bit view, which also holds the `Run32` key.
+    fn open_key(&self, access: u32) -> io::Result<RegKey> {
+        self.hive
+            .open_subkey_with_flags(self.path, access | KEY_WOW64_64KEY)
+    }
+
+    fn error(&self, e: io::Error, action: String) -> StartupError {
+        if e.kind() == ErrorKind::PermissionDenied {
+            StartupError::ElevationRequired(self.location.to_string())
+        } else {
+            StartupError::RegistryError(format!("{}: {}", action, e))
+        }
+    }
+}
+
+#[cfg(windows)]
+impl ApprovalStore for RegistryApprovalStore {
+    fn get(&self, name: &str) -> Result<Option<StartupApproval>> {
+        let result = self
+            .open_key(KEY_READ)
+            .and_then(|key| key.get_raw_value(name));
+        match result {
+            Ok(value) => Ok(StartupApproval::from_bytes(&value.bytes)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(self.error(e, format!("Failed to read the state of entry '{}'", name))),
+        }
+    }
+
+    fn set(&self, name: &str, approval: StartupApproval) -> Result<()> {
+        let (key, _) = self
+            .hive
+            .create_subkey_with_flags(self.path, KEY_SET_VALUE | KEY_WOW64_64KEY)
+            .map_err(|e| {
+                self.error(
+                    e,
+                    "Failed to open the StartupApproved registry key".to_string(),
+                )
+            })?;
+        key.set_value(name, &approval.to_bytes().to_vec())
+            .map_err(|e| self.error(e, format!("Failed to change the state of entry '{}'", name)))
+    }
+
+    fn delete(&self, name: &str) -> Result<()> {
+        let result = self
+            .open_key(KEY_SET_VALUE)
+            .and_then(|key| key.delete_value(name));
+        match result {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(self.error(e, format!("Failed to forget the state of entry '{}'", name))),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use crate::domain::{ApprovalStore, StartupApproval};
+
+    #[test]
+    fn test_in_memory_store_ignores_case() {
+        let store = InMemoryApprovalStore::new();
+        let disabled = StartupApproval::disabled_since(chrono::Utc::now());
+
+        store.set("App.lnk", disabled).unwrap();
+        store.set("APP.LNK", StartupApproval::ENABLED).unwrap();
+
+        assert_eq!(
+            store.values(),
+            vec![("App.lnk".to_string(), StartupApproval::ENABLED)]
+        );
+        assert_eq!(
+            store.get("app.lnk").unwrap(),
+            Some(StartupApproval::ENABLED)
+        );
+
+        store.delete("app.LNK").unwrap();
+        store.delete("Missing").unwrap();
+        assert_eq!(store.get("App.lnk").unwrap(), None);
+    }
+}
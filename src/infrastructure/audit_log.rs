@@ -0,0 +1,206 @@
+use crate::domain::{AuditActor, AuditEvent, AuditLog, LAUNCHER_DIR_NAME};
+use crate::shared::error::{Result, StartupError};
+use std::cell::RefCell;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+/// File the audit log is written to, next to the launchers.
+pub const AUDIT_LOG_FILE: &str = "audit.jsonl";
+
+/// Environment variable pointing the audit log at another file, such as a
+/// location every user of a shared machine writes to.
+pub const AUDIT_LOG_ENV: &str = "STARTUP_AUDIT_LOG";
+
+/// Size after which the log is rotated, by default.
+pub const DEFAULT_MAX_LOG_BYTES: u64 = 1024 * 1024;
+
+/// Number of rotated logs kept, by default.
+pub const DEFAULT_KEPT_LOGS: usize = 5;
+
+/// Filesystem implementation of the AuditLog trait.
+///
+/// Events are appended to a JSON-lines file. Once an event would grow the
+/// file past the size limit, the file is renamed to `<file>.1`, older logs
+/// move up to `<file>.2` and so on, and the oldest one is deleted.
+#[derive(Debug, Clone)]
+pub struct FileSystemAuditLog {
+    path: PathBuf,
+    max_bytes: u64,
+    keep: usize,
+}
+
+impl FileSystemAuditLog {
+    /// Creates a log written to `path`, rotated with the default limits.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            max_bytes: DEFAULT_MAX_LOG_BYTES,
+            keep: DEFAULT_KEPT_LOGS,
+        }
+    }
+
+    /// Creates the log named by `STARTUP_AUDIT_LOG`, or
+    /// %APPDATA%\windows_startup_manager\audit.jsonl when it isn't set.
+    pub fn from_env() -> Result<Self> {
+        if let Ok(path) = std::env::var(AUDIT_LOG_ENV)
+            && !path.is_empty()
+        {
+            return Ok(Self::new(path));
+        }
+        let appdata = std::env::var("APPDATA").map_err(|_| {
+            StartupError::RegistryError("Failed to get APPDATA environment variable".to_string())
+        })?;
+
+        Ok(Self::new(
+            PathBuf::from(appdata)
+                .join(LAUNCHER_DIR_NAME)
+                .join(AUDIT_LOG_FILE),
+        ))
+    }
+
+    /// Rotates the log once it would exceed `max_bytes`, keeping `keep`
+    /// rotated logs.
+    pub fn with_rotation(mut self, max_bytes: u64, keep: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self.keep = keep;
+        self
+    }
+
+    /// Returns the file events are appended to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the path of the `n`th rotated log; 0 is the current one.
+    pub fn rotated(&self, n: usize) -> PathBuf {
+        if n == 0 {
+            return self.path.clone();
+        }
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{}", n));
+        PathBuf::from(path)
+    }
+
+    fn rotate(&self) -> Result<()> {
+        if self.keep == 0 {
+            return remove_if_exists(&self.path);
+        }
+        remove_if_exists(&self.rotated(self.keep))?;
+        for n in (0..self.keep).rev() {
+            match fs::rename(self.rotated(n), self.rotated(n + 1)) {
+                Ok(()) => {}
+                Err(e) if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(())
+    }
+}
+
+fn remove_if_exists(path: &Path) -> Result<()> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+impl AuditLog for FileSystemAuditLog {
+    fn append(&self, event: &AuditEvent) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let line = event.to_line() + "\n";
+        let size = match fs::metadata(&self.path) {
+            Ok(metadata) => metadata.len(),
+            Err(e) if e.kind() == ErrorKind::NotFound => 0,
+            Err(e) => return Err(e.into()),
+        };
+        if size > 0 && size + line.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+
+        // A single write keeps lines of concurrent writers whole
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(line.as_bytes())?;
+        Ok(())
+    }
+
+    fn read(&self) -> Result<Vec<AuditEvent>> {
+        let mut events = Vec::new();
+        for n in (0..=self.keep).rev() {
+            let path = self.rotated(n);
+            let text = match fs::read_to_string(&path) {
+                Ok(text) => text,
+                Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            };
+            for (number, line) in text.lines().enumerate() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let event = AuditEvent::from_line(line).map_err(|e| {
+                    StartupError::InvalidFormat(format!(
+                        "{} line {}: {}",
+                        path.display(),
+                        number + 1,
+                        e
+                    ))
+                })?;
+                events.push(event);
+            }
+        }
+        Ok(events)
+    }
+}
+
+/// In-memory implementation of the AuditLog trait.
+#[derive(Debug, Default)]
+pub struct InMemoryAuditLog {
+    events: RefCell<Vec<AuditEvent>>,
+}
+
+impl InMemoryAuditLog {
+    /// Creates an empty log.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a copy of the events, oldest first.
+    pub fn events(&self) -> Vec<AuditEvent> {
+        self.events.borrow().clone()
+    }
+}
+
+impl AuditLog for InMemoryAuditLog {
+    fn append(&self, event: &AuditEvent) -> Result<()> {
+        self.events.borrow_mut().push(event.clone());
+        Ok(())
+    }
+
+    fn read(&self) -> Result<Vec<AuditEvent>> {
+        Ok(self.events())
+    }
+}
+
+/// Returns the user and machine the tool runs as, like `DOMAIN\user` on
+/// Windows.
+pub fn current_actor() -> AuditActor {
+    let var = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| std::env::var(name).ok().filter(|v| !v.is_empty()))
+    };
+    let user = var(&["USERNAME", "USER"]).unwrap_or_else(|| "unknown".to_string());
+    AuditActor {
+        user: match var(&["USERDOMAIN"]) {
+            Some(domain) => format!("{}\\{}", domain, user),
+            None => user,
+        },
+        host: var(&["COMPUTERNAME", "HOSTNAME"]).unwrap_or_else(|| "unknown".to_string()),
+    }
+}
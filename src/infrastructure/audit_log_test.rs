@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use crate::domain::{AuditAction, AuditActor, AuditEvent, AuditLog};
+    use crate::shared::error::StartupError;
+    use chrono::{TimeZone, Utc};
+    use std::fs;
+
+    fn temp_log(name: &str) -> FileSystemAuditLog {
+        let root = std::env::temp_dir().join(format!("wsm_audit_{}", name));
+        fs::remove_dir_all(&root).ok();
+        FileSystemAuditLog::new(root.join(AUDIT_LOG_FILE))
+    }
+
+    fn event(entry: &str) -> AuditEvent {
+        let at = Utc.with_ymd_and_hms(2026, 3, 1, 8, 30, 0).unwrap();
+        AuditEvent::new(at, &AuditActor::default(), AuditAction::Add).with_entry(entry)
+    }
+
+    #[test]
+    fn test_appends_json_lines() {
+        let log = temp_log("append");
+
+        assert!(log.read().unwrap().is_empty());
+        log.append(&event("A")).unwrap();
+        log.append(&event("B")).unwrap();
+
+        assert_eq!(log.read().unwrap(), [event("A"), event("B")]);
+        assert_eq!(fs::read_to_string(log.path()).unwrap().lines().count(), 2);
+        fs::remove_dir_all(log.path().parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn test_rotates_by_size_and_keeps_the_newest_logs() {
+        let line = event("A").to_line().len() as u64 + 1;
+        let log = temp_log("rotate").with_rotation(line * 2, 2);
+
+        for name in ["A", "B", "C", "D", "E", "F", "G"] {
+            log.append(&event(name)).unwrap();
+        }
+
+        assert!(log.rotated(2).exists());
+        assert!(!log.rotated(3).exists());
+        let names: Vec<_> = log
+            .read()
+            .unwrap()
+            .into_iter()
+            .filter_map(|event| event.entry)
+            .collect();
+        assert_eq!(names, ["C", "D", "E", "F", "G"]);
+        fs::remove_dir_all(log.path().parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn test_reports_the_line_of_corrupt_events() {
+        let log = temp_log("corrupt");
+        log.append(&event("A")).unwrap();
+        let mut text = fs::read_to_string(log.path()).unwrap();
+        text.push_str("{\"at\":\n");
+        fs::write(log.path(), text).unwrap();
+
+        let error = log.read().unwrap_err();
+
+        assert!(matches!(error, StartupError::InvalidFormat(_)), "{}", error);
+        assert!(error.to_string().contains("line 2"));
+        fs::remove_dir_all(log.path().parent().unwrap()).ok();
+    }
+}
@@ -0,0 +1,141 @@
+use crate::domain::{StartupApproval, StartupEntry, StartupLocation, StartupRepository};
+use crate::shared::error::{Result, StartupError};
+
+/// StartupRepository spanning several startup locations, each backed by its
+/// own repository.
+///
+/// Listing aggregates every location in the order they were added, tagging
+/// entries with the location they came from. Entries are added to the
+/// location they carry; removing, updating, renaming, enabling and
+/// disabling apply to the single location holding the entry.
+/// Selecting a location restricts every operation to it.
+#[derive(Default)]
+pub struct CompositeRepository {
+    repositories: Vec<(StartupLocation, Box<dyn StartupRepository>)>,
+    selected: Option<StartupLocation>,
+}
+
+impl CompositeRepository {
+    /// Creates a repository without any locations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the repository backing `location`, replacing any previous one.
+    pub fn with(
+        mut self,
+        location: StartupLocation,
+        repository: impl StartupRepository + 'static,
+    ) -> Self {
+        self.repositories.retain(|(l, _)| *l != location);
+        self.repositories.push((location, Box::new(repository)));
+        self
+    }
+
+    /// Restricts every operation to `location`, or lifts the restriction.
+    pub fn select(mut self, location: Option<StartupLocation>) -> Self {
+        self.selected = location;
+        self
+    }
+
+    /// Returns the locations that have a repository, in listing order.
+    pub fn locations(&self) -> Vec<StartupLocation> {
+        self.repositories.iter().map(|(l, _)| *l).collect()
+    }
+
+    fn repository(&self, location: StartupLocation) -> Result<&dyn StartupRepository> {
+        self.repositories
+            .iter()
+            .find(|(l, _)| *l == location)
+            .map(|(_, repository)| repository.as_ref())
+            .ok_or_else(|| {
+                StartupError::Unsupported(format!("startup location {} is not available", location))
+            })
+    }
+
+    /// The repository of the single location in scope holding `name`.
+    fn holder(&self, name: &str) -> Result<&dyn StartupRepository> {
+        let mut holders = Vec::new();
+        for (_, repository) in self.in_scope()? {
+            if repository.exists(name)? {
+                holders.push(repository);
+            }
+        }
+
+        match holders.as_slice() {
+            [] => Err(StartupError::EntryNotFound(name.to_string())),
+            [repository] => Ok(*repository),
+            _ => Err(StartupError::AmbiguousEntry(name.to_string())),
+        }
+    }
+
+    /// The repositories operations apply to.
+    fn in_scope(&self) -> Result<Vec<(StartupLocation, &dyn StartupRepository)>> {
+        match self.selected {
+            Some(location) => Ok(vec![(location, self.repository(location)?)]),
+            None => Ok(self
+                .repositories
+                .iter()
+                .map(|(l, repository)| (*l, repository.as_ref()))
+                .collect()),
+        }
+    }
+}
+
+impl StartupRepository for CompositeRepository {
+    fn add(&self, entry: &StartupEntry) -> Result<()> {
+        let location = self.selected.unwrap_or(entry.location);
+        let entry = entry.clone().with_location(location);
+        self.repository(location)?.add(&entry)
+    }
+
+    fn remove(&self, name: &str) -> Result<()> {
+        self.holder(name)?.remove(name)
+    }
+
+    fn list(&self) -> Result<Vec<StartupEntry>> {
+        let mut entries = Vec::new();
+        for (location, repository) in self.in_scope()? {
+            entries.extend(
+                repository
+                    .list()?
+                    .into_iter()
+                    .map(|entry| entry.with_location(location)),
+            );
+        }
+        Ok(entries)
+    }
+
+    fn exists(&self, name: &str) -> Result<bool> {
+        for (_, repository) in self.in_scope()? {
+            if repository.exists(name)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn set_approval(&self, name: &str, approval: StartupApproval) -> Result<()> {
+        self.holder(name)?.set_approval(name, approval)
+    }
+
+    fn supports_approval(&self, location: StartupLocation) -> bool {
+        let location = self.selected.unwrap_or(location);
+        self.repository(location)
+            .is_ok_and(|repository| repository.supports_approval(location))
+    }
+
+    fn update(&self, entry: &StartupEntry) -> Result<()> {
+        self.holder(&entry.name)?.update(entry)
+    }
+
+    /// Renames the entry within its location. The new name must not be used
+    /// by any location in scope.
+    fn rename(&self, name: &str, new_name: &str) -> Result<()> {
+        let holder = self.holder(name)?;
+        if !new_name.eq_ignore_ascii_case(name) && self.exists(new_name)? {
+            return Err(StartupError::EntryAlreadyExists(new_name.to_string()));
+        }
+        holder.rename(name, new_name)
+    }
+}
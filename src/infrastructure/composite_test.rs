@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use crate::domain::{StartupApproval, StartupEntry, StartupLocation, StartupRepository};
+    use crate::shared::error::StartupError;
+
+    fn composite(user: &[(&str, &str)], machine: &[(&str, &str)]) -> CompositeRepository {
+        CompositeRepository::new()
+            .with(
+                StartupLocation::CurrentUserRun,
+                InMemoryRepository::with_entries(user.iter().copied()),
+            )
+            .with(
+                StartupLocation::LocalMachineRun,
+                InMemoryRepository::with_entries(machine.iter().copied()),
+            )
+    }
+
+    #[test]
+    fn test_list_aggregates_and_tags_locations() {
+        let repo = composite(&[("A", "a.exe")], &[("B", "b.exe"), ("C", "c.exe")]);
+
+        let entries: Vec<_> = repo
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|e| (e.name, e.location))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                ("A".to_string(), StartupLocation::CurrentUserRun),
+                ("B".to_string(), StartupLocation::LocalMachineRun),
+                ("C".to_string(), StartupLocation::LocalMachineRun),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_add_uses_entry_location() {
+        let repo = composite(&[], &[]);
+
+        repo.add(&StartupEntry::new("A", "a.exe").with_location(StartupLocation::LocalMachineRun))
+            .unwrap();
+
+        let entries = repo.list().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].location, StartupLocation::LocalMachineRun);
+    }
+
+    #[test]
+    fn test_selected_location_overrides_entry_location() {
+        let repo = composite(&[], &[]).select(Some(StartupLocation::LocalMachineRun));
+
+        repo.add(&StartupEntry::new("A", "a.exe")).unwrap();
+
+        let entries = repo.select(None).list().unwrap();
+        assert_eq!(entries[0].location, StartupLocation::LocalMachineRun);
+    }
+
+    #[test]
+    fn test_add_to_missing_location_is_unsupported() {
+        let repo = composite(&[], &[]);
+
+        let result = repo.add(
+            &StartupEntry::new("A", "a.exe").with_location(StartupLocation::CurrentUserRunOnce),
+        );
+
+        assert!(matches!(result, Err(StartupError::Unsupported(_))));
+    }
+
+    #[test]
+    fn test_remove_finds_the_holding_location() {
+        let repo = composite(&[("A", "a.exe")], &[("B", "b.exe")]);
+
+        repo.remove("B").unwrap();
+
+        let names: Vec<_> = repo.list().unwrap().into_iter().map(|e| e.name).collect();
+        assert_eq!(names, vec!["A"]);
+    }
+
+    #[test]
+    fn test_remove_ambiguous_name_requires_selection() {
+        let repo = composite(&[("A", "user.exe")], &[("A", "machine.exe")]);
+
+        let result = repo.remove("A");
+        assert!(matches!(result, Err(StartupError::AmbiguousEntry(name)) if name == "A"));
+
+        let repo = repo.select(Some(StartupLocation::CurrentUserRun));
+        repo.remove("A").unwrap();
+        let remaining = repo.select(None).list().unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].command, "machine.exe");
+    }
+
+    #[test]
+    fn test_remove_missing_entry() {
+        let repo = composite(&[("A", "a.exe")], &[]);
+
+        let result = repo.remove("Missing");
+
+        assert!(matches!(result, Err(StartupError::EntryNotFound(name)) if name == "Missing"));
+    }
+
+    #[test]
+    fn test_selection_restricts_list_and_exists() {
+        let repo = composite(&[("A", "a.exe")], &[("B", "b.exe")])
+            .select(Some(StartupLocation::LocalMachineRun));
+
+        let names: Vec<_> = repo.list().unwrap().into_iter().map(|e| e.name).collect();
+        assert_eq!(names, vec!["B"]);
+        assert!(!repo.exists("A").unwrap());
+        assert!(repo.exists("B").unwrap());
+    }
+
+    #[test]
+    fn test_with_replaces_location_repository() {
+        let repo = composite(&[("A", "a.exe")], &[])
+            .with(StartupLocation::CurrentUserRun, InMemoryRepository::new());
+
+        assert_eq!(
+            repo.locations(),
+            vec![
+                StartupLocation::LocalMachineRun,
+                StartupLocation::CurrentUserRun
+            ]
+        );
+        assert!(repo.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_set_approval_routes_to_holding_location() {
+        let repo = composite(&[("A", "a.exe")], &[("B", "b.exe")]);
+
+        repo.set_approval("B", StartupApproval::disabled_since(chrono::Utc::now()))
+            .unwrap();
+
+        let enabled: Vec<_> = repo
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|e| e.enabled)
+            .collect();
+        assert_eq!(enabled, vec![true, false]);
+    }
+}
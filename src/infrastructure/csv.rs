@@ -0,0 +1,87 @@
+/// Decodes raw command output into text.
+/// Some tools write UTF-16LE when redirected, PowerShell may prepend a BOM,
+/// and everything else is treated as UTF-8.
+pub fn decode_output(bytes: &[u8]) -> String {
+    fn utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> String {
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|pair| from_bytes([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16_lossy(&units)
+    }
+
+    match bytes {
+        [0xFF, 0xFE, rest @ ..] => utf16(rest, u16::from_le_bytes),
+        [0xFE, 0xFF, rest @ ..] => utf16(rest, u16::from_be_bytes),
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8_lossy(rest).into_owned(),
+        // ASCII text encoded as UTF-16LE without a BOM
+        [first, 0, ..] if *first != 0 => utf16(bytes, u16::from_le_bytes),
+        _ => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+/// Splits CSV text into records following RFC 4180.
+/// Quoted fields may contain commas, line breaks and doubled quotes, and
+/// records may end with CRLF, LF or a lone CR. Quotes are only special at
+/// the start of a field; a "quoted" field followed by more text is kept
+/// verbatim, which is how some tools print command lines such as
+/// `"C:\app.exe" --flag` without quoting them.
+pub fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    // Raw text of the current quoted section, kept in case it isn't a field
+    let mut raw = String::new();
+    let mut in_quotes = false;
+    let mut at_field_start = true;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            raw.push(c);
+            if c != '"' {
+                field.push(c);
+            } else if chars.peek() == Some(&'"') {
+                raw.push(chars.next().unwrap_or('"'));
+                field.push('"');
+            } else {
+                in_quotes = false;
+                if !matches!(chars.peek(), None | Some(',' | '\r' | '\n')) {
+                    field = std::mem::take(&mut raw);
+                }
+            }
+            continue;
+        }
+
+        match c {
+            '"' if at_field_start => {
+                in_quotes = true;
+                raw = String::from('"');
+            }
+            ',' => {
+                record.push(std::mem::take(&mut field));
+                at_field_start = true;
+                continue;
+            }
+            '\r' | '\n' => {
+                if c == '\r' && chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+                at_field_start = true;
+                continue;
+            }
+            c => field.push(c),
+        }
+        at_field_start = false;
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    // Blank lines (wmic emits several) carry no data
+    records.retain(|r| r.iter().any(|f| !f.trim().is_empty()));
+    records
+}
@@ -0,0 +1,31 @@
+#[cfg(test)]
+mod tests {
+    use super::super::csv::*;
+
+    #[test]
+    fn test_decode_output_handles_boms_and_utf16() {
+        assert_eq!(decode_output(b"\xEF\xBB\xBFabc"), "abc");
+        assert_eq!(decode_output(b"\xFF\xFEa\0b\0"), "ab");
+        assert_eq!(decode_output(b"\xFE\xFF\0a\0b"), "ab");
+        assert_eq!(decode_output(b"a\0b\0"), "ab");
+        assert_eq!(decode_output(b"plain"), "plain");
+    }
+
+    #[test]
+    fn test_parse_csv_quoted_fields() {
+        let records = parse_csv("a,\"b,c\",\"say \"\"hi\"\"\"\r\n\"multi\nline\",,x\n");
+        assert_eq!(
+            records,
+            vec![vec!["a", "b,c", "say \"hi\""], vec!["multi\nline", "", "x"],]
+        );
+    }
+
+    #[test]
+    fn test_parse_csv_keeps_quotes_inside_unquoted_fields() {
+        let records = parse_csv("\"C:\\app.exe\" --flag,x\r\r\n\r\r\nsay \"hi\",y");
+        assert_eq!(
+            records,
+            vec![vec!["\"C:\\app.exe\" --flag", "x"], vec!["say \"hi\"", "y"]]
+        );
+    }
+}
@@ -0,0 +1,356 @@
+use crate::domain::{
+    LauncherManifest, LauncherStore, ProcessInfo, ProcessProvider, StartupApproval, StartupEntry,
+    StartupLocation, StartupRepository,
+};
+use crate::shared::error::{Result, StartupError};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A change a dry run kept from reaching a startup location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StagedChange {
+    /// The entry would be stored with its value, replacing the entry of the
+    /// same name in its location.
+    Write(StartupEntry),
+    /// The entry would be deleted.
+    Delete(StartupEntry),
+    /// The entry would be enabled or disabled, as its state says.
+    SetApproval(StartupEntry),
+}
+
+/// A launcher change a dry run kept from reaching the disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StagedLauncher {
+    Write { filename: String, content: String },
+    Delete(String),
+}
+
+/// StartupRepository recording the changes made through it instead of
+/// making them.
+///
+/// Reads return the entries of the wrapped repository with the recorded
+/// changes applied, so a command runs the same validation and sees the same
+/// entries as it would for real.
+pub struct DryRunRepository<'a, R: StartupRepository> {
+    repository: &'a R,
+    selected: Option<StartupLocation>,
+    entries: RefCell<Option<Vec<StartupEntry>>>,
+    changes: RefCell<Vec<StagedChange>>,
+}
+
+impl<'a, R: StartupRepository> DryRunRepository<'a, R> {
+    pub fn new(repository: &'a R) -> Self {
+        Self {
+            repository,
+            selected: None,
+            entries: RefCell::new(None),
+            changes: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Stores added entries in `location`, like the wrapped repository does
+    /// when it is limited to one location.
+    pub fn select(mut self, location: Option<StartupLocation>) -> Self {
+        self.selected = location;
+        self
+    }
+
+    /// Returns the changes recorded so far, in the order they were made.
+    pub fn changes(&self) -> Vec<StagedChange> {
+        self.changes.borrow().clone()
+    }
+
+    /// Runs `operation` on the entries as changed so far, reading the
+    /// wrapped repository the first time.
+    fn staged<T>(&self, operation: impl FnOnce(&mut Vec<StartupEntry>) -> Result<T>) -> Result<T> {
+        let mut entries = self.entries.borrow_mut();
+        if entries.is_none() {
+            *entries = Some(self.repository.list()?);
+        }
+        operation(entries.get_or_insert_with(Vec::new))
+    }
+
+    /// The index of the single entry named `name`.
+    fn holder(entries: &[StartupEntry], name: &str) -> Result<usize> {
+        let mut matching = entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.name.eq_ignore_ascii_case(name))
+            .map(|(index, _)| index);
+        match (matching.next(), matching.next()) {
+            (None, _) => Err(StartupError::EntryNotFound(name.to_string())),
+            (Some(index), None) => Ok(index),
+            _ => Err(StartupError::AmbiguousEntry(name.to_string())),
+        }
+    }
+}
+
+impl<R: StartupRepository> StartupRepository for DryRunRepository<'_, R> {
+    /// Replacing an entry keeps its enabled state, like the registry does.
+    fn add(&self, entry: &StartupEntry) -> Result<()> {
+        let location = self.selected.unwrap_or(entry.location);
+        let entry = entry.clone().with_location(location);
+        self.staged(|entries| {
+            let existing = entries
+                .iter()
+                .position(|e| e.location == location && e.name.eq_ignore_ascii_case(&entry.name));
+            let entry = match existing {
+                Some(index) => {
+                    let entry = entry.with_approval(entries[index].approval());
+                    entries[index] = entry.clone();
+                    entry
+                }
+                None => {
+                    entries.push(entry.clone());
+                    entry
+                }
+            };
+            self.changes.borrow_mut().push(StagedChange::Write(entry));
+            Ok(())
+        })
+    }
+
+    fn remove(&self, name: &str) -> Result<()> {
+        self.staged(|entries| {
+            let index = Self::holder(entries, name)?;
+            let entry = entries.remove(index);
+            self.changes.borrow_mut().push(StagedChange::Delete(entry));
+            Ok(())
+        })
+    }
+
+    fn list(&self) -> Result<Vec<StartupEntry>> {
+        self.staged(|entries| Ok(entries.clone()))
+    }
+
+    fn exists(&self, name: &str) -> Result<bool> {
+        self.staged(|entries| Ok(entries.iter().any(|e| e.name.eq_ignore_ascii_case(name))))
+    }
+
+    fn set_approval(&self, name: &str, approval: StartupApproval) -> Result<()> {
+        self.staged(|entries| {
+            let index = Self::holder(entries, name)?;
+            let location = entries[index].location;
+            // Only fail where the wrapped repository would
+            if !self.repository.supports_approval(location) {
+                return Err(StartupError::Unsupported(format!(
+                    "entries in {} cannot be enabled or disabled",
+                    location
+                )));
+            }
+            let entry = entries[index].clone().with_approval(approval);
+            entries[index] = entry.clone();
+            self.changes
+                .borrow_mut()
+                .push(StagedChange::SetApproval(entry));
+            Ok(())
+        })
+    }
+
+    fn supports_approval(&self, location: StartupLocation) -> bool {
+        self.repository
+            .supports_approval(self.selected.unwrap_or(location))
+    }
+
+    fn update(&self, entry: &StartupEntry) -> Result<()> {
+        self.staged(|entries| {
+            let index = Self::holder(entries, &entry.name)?;
+            let entry = StartupEntry {
+                command: entry.command.clone(),
+                ..entries[index].clone()
+            };
+            entries[index] = entry.clone();
+            self.changes.borrow_mut().push(StagedChange::Write(entry));
+            Ok(())
+        })
+    }
+
+    /// Records the entry as deleted and written again under its new name.
+    fn rename(&self, name: &str, new_name: &str) -> Result<()> {
+        self.staged(|entries| {
+            let index = Self::holder(entries, name)?;
+            let taken = entries
+                .iter()
+                .any(|e| e.name.eq_ignore_ascii_case(new_name));
+            if !new_name.eq_ignore_ascii_case(name) && taken {
+                return Err(StartupError::EntryAlreadyExists(new_name.to_string()));
+            }
+            let renamed = StartupEntry {
+                name: new_name.to_string(),
+                ..entries[index].clone()
+            };
+            let entry = entries.remove(index);
+            entries.push(renamed.clone());
+            let mut changes = self.changes.borrow_mut();
+            changes.push(StagedChange::Delete(entry));
+            changes.push(StagedChange::Write(renamed));
+            Ok(())
+        })
+    }
+}
+
+/// LauncherStore recording the launchers written and deleted through it
+/// instead of touching the wrapped store. Reads see the recorded changes.
+pub struct DryRunLauncherStore<'a, L: LauncherStore> {
+    launchers: &'a L,
+    /// Launchers written (`Some`) or deleted (`None`) so far.
+    files: RefCell<BTreeMap<String, Option<String>>>,
+    manifest: RefCell<Option<LauncherManifest>>,
+    changes: RefCell<Vec<StagedLauncher>>,
+}
+
+impl<'a, L: LauncherStore> DryRunLauncherStore<'a, L> {
+    pub fn new(launchers: &'a L) -> Self {
+        Self {
+            launchers,
+            files: RefCell::new(BTreeMap::new()),
+            manifest: RefCell::new(None),
+            changes: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Returns the launcher changes recorded so far, in the order they were
+    /// made.
+    pub fn changes(&self) -> Vec<StagedLauncher> {
+        self.changes.borrow().clone()
+    }
+}
+
+impl<L: LauncherStore> LauncherStore for DryRunLauncherStore<'_, L> {
+    fn location(&self) -> String {
+        self.launchers.location()
+    }
+
+    fn read(&self, filename: &str) -> Result<Option<String>> {
+        match self.files.borrow().get(filename) {
+            Some(content) => Ok(content.clone()),
+            None => self.launchers.read(filename),
+        }
+    }
+
+    fn write(&self, filename: &str, content: &str) -> Result<()> {
+        self.files
+            .borrow_mut()
+            .insert(filename.to_string(), Some(content.to_string()));
+        self.changes.borrow_mut().push(StagedLauncher::Write {
+            filename: filename.to_string(),
+            content: content.to_string(),
+        });
+        Ok(())
+    }
+
+    fn delete(&self, filename: &str) -> Result<bool> {
+        if self.read(filename)?.is_none() {
+            return Ok(false);
+        }
+        self.files.borrow_mut().insert(filename.to_string(), None);
+        self.changes
+            .borrow_mut()
+            .push(StagedLauncher::Delete(filename.to_string()));
+        Ok(true)
+    }
+
+    fn list(&self) -> Result<Vec<String>> {
+        let mut filenames: BTreeSet<String> = self.launchers.list()?.into_iter().collect();
+        for (filename, content) in self.files.borrow().iter() {
+            match content {
+                Some(_) => filenames.insert(filename.clone()),
+                None => filenames.remove(filename),
+            };
+        }
+        Ok(filenames.into_iter().collect())
+    }
+
+    fn load_manifest(&self) -> Result<LauncherManifest> {
+        match &*self.manifest.borrow() {
+            Some(manifest) => Ok(manifest.clone()),
+            None => self.launchers.load_manifest(),
+        }
+    }
+
+    fn save_manifest(&self, manifest: &LauncherManifest) -> Result<()> {
+        *self.manifest.borrow_mut() = Some(manifest.clone());
+        Ok(())
+    }
+
+    fn path_of(&self, filename: &str) -> String {
+        self.launchers.path_of(filename)
+    }
+
+    fn filename_of(&self, path: &str) -> Option<String> {
+        self.launchers.filename_of(path)
+    }
+}
+
+/// ProcessProvider recording the processes it is asked to stop instead of
+/// stopping them. Recorded processes are reported as exited, so callers
+/// waiting for them to close return at once.
+pub struct DryRunProcessProvider<'a, P: ProcessProvider> {
+    processes: &'a P,
+    /// The processes listed last, to describe the stopped ones.
+    listed: RefCell<Vec<ProcessInfo>>,
+    stopped: RefCell<Vec<ProcessInfo>>,
+}
+
+impl<'a, P: ProcessProvider> DryRunProcessProvider<'a, P> {
+    pub fn new(processes: &'a P) -> Self {
+        Self {
+            processes,
+            listed: RefCell::new(Vec::new()),
+            stopped: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Returns the processes that would have been stopped, in the order
+    /// they were asked to stop.
+    pub fn stopped(&self) -> Vec<ProcessInfo> {
+        self.stopped.borrow().clone()
+    }
+
+    fn is_stopped(&self, pid: u32) -> bool {
+        self.stopped.borrow().iter().any(|p| p.pid == pid)
+    }
+
+    fn stop(&self, pid: u32) -> Result<()> {
+        if self.is_stopped(pid) {
+            return Ok(());
+        }
+        let process = self
+            .listed
+            .borrow()
+            .iter()
+            .find(|p| p.pid == pid)
+            .cloned()
+            .ok_or_else(|| StartupError::ProcessError(format!("Process {} not found", pid)))?;
+        self.stopped.borrow_mut().push(process);
+        Ok(())
+    }
+}
+
+impl<P: ProcessProvider> ProcessProvider for DryRunProcessProvider<'_, P> {
+    fn list_processes(&self) -> Result<Vec<ProcessInfo>> {
+        let running: Vec<ProcessInfo> = self
+            .processes
+            .list_processes()?
+            .into_iter()
+            .filter(|p| !self.is_stopped(p.pid))
+            .collect();
+        *self.listed.borrow_mut() = running.clone();
+        Ok(running)
+    }
+
+    fn kill_process(&self, pid: u32) -> Result<()> {
+        self.stop(pid)
+    }
+
+    fn request_close(&self, pid: u32) -> Result<()> {
+        self.stop(pid)
+    }
+
+    fn is_running(&self, pid: u32) -> Result<bool> {
+        if self.is_stopped(pid) {
+            return Ok(false);
+        }
+        self.processes.is_running(pid)
+    }
+}
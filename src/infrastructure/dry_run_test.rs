@@ -0,0 +1,300 @@
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use crate::application::{
+        AddCommandUseCase, EditEntryUseCase, EntryEdits, KillOptions, KillProcessUseCase,
+        RemoveEntryUseCase, RenameEntryUseCase, SetEnabledUseCase,
+    };
+    use crate::domain::{
+        ExecutionMode, LauncherStore, ProcessInfo, ProcessProvider, StartupEntry, StartupLocation,
+        StartupRepository, TaskDefinition,
+    };
+    use crate::shared::error::StartupError;
+    use std::time::Duration;
+
+    fn add_dev(repository: &impl StartupRepository, launchers: &impl LauncherStore) {
+        let workdir = std::env::temp_dir();
+        AddCommandUseCase::new(repository, launchers)
+            .execute(
+                "Dev",
+                "bun",
+                vec!["run".to_string(), "dev".to_string()],
+                Some(workdir.to_str().unwrap()),
+                ExecutionMode::VBScript,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_add_command_is_recorded_but_not_stored() {
+        let repo = InMemoryRepository::with_entries([("Other", "other.exe")]);
+        let launchers = InMemoryLauncherStore::new();
+        let (staged, staged_launchers) = (
+            DryRunRepository::new(&repo),
+            DryRunLauncherStore::new(&launchers),
+        );
+
+        add_dev(&staged, &staged_launchers);
+
+        assert_eq!(repo.entries().len(), 1);
+        assert!(launchers.filenames().is_empty());
+        let changes = staged.changes();
+        let [StagedChange::Write(entry)] = changes.as_slice() else {
+            panic!("expected one write, got {:?}", changes);
+        };
+        let written = staged_launchers.changes();
+        let [StagedLauncher::Write { filename, content }] = written.as_slice() else {
+            panic!("expected one launcher, got {:?}", written);
+        };
+        assert_eq!(entry.name, "Dev");
+        assert_eq!(
+            entry.command,
+            format!(
+                "wscript.exe //B //Nologo \"{}\"",
+                launchers.path_of(filename)
+            )
+        );
+        assert!(content.contains("bun run dev"));
+        // Later reads see the staged entry and launcher
+        assert_eq!(staged.list().unwrap().len(), 2);
+        assert!(staged.exists("dev").unwrap());
+        assert_eq!(staged_launchers.list().unwrap(), vec![filename.clone()]);
+    }
+
+    #[test]
+    fn test_dry_run_matches_the_real_change() {
+        let (real, real_launchers) = (InMemoryRepository::new(), InMemoryLauncherStore::new());
+        let (repo, launchers) = (InMemoryRepository::new(), InMemoryLauncherStore::new());
+        let staged = DryRunRepository::new(&repo);
+        let staged_launchers = DryRunLauncherStore::new(&launchers);
+
+        add_dev(&real, &real_launchers);
+        add_dev(&staged, &staged_launchers);
+
+        assert_eq!(staged.list().unwrap(), real.entries());
+        let filename = &real_launchers.filenames()[0];
+        assert_eq!(
+            staged_launchers.read(filename).unwrap(),
+            real_launchers.file(filename)
+        );
+        assert_eq!(
+            staged_launchers.load_manifest().unwrap(),
+            real_launchers.manifest()
+        );
+    }
+
+    #[test]
+    fn test_edit_and_rename_match_the_real_ones() {
+        let entries = [("Tool", "C:\\tools\\tool.exe"), ("Other", "other.exe")];
+        let (real, repo) = (
+            InMemoryRepository::with_entries(entries),
+            InMemoryRepository::with_entries(entries),
+        );
+        let staged = DryRunRepository::new(&repo);
+        let launchers = InMemoryLauncherStore::new();
+        let edits = EntryEdits {
+            args: Some(vec!["--tray".to_string()]),
+            ..EntryEdits::default()
+        };
+        let run = |repository: &dyn StartupRepository| {
+            SetEnabledUseCase::new(&repository)
+                .execute("Tool", false)
+                .unwrap();
+            EditEntryUseCase::new(&repository, &launchers)
+                .execute("tool", &edits)
+                .unwrap();
+            RenameEntryUseCase::new(&repository, &launchers)
+                .execute("Tool", "Tray")
+                .unwrap();
+            let taken = repository.rename("Tray", "other");
+            assert!(matches!(taken, Err(StartupError::EntryAlreadyExists(_))));
+            repository
+                .list()
+                .unwrap()
+                .into_iter()
+                .map(|e| (e.name, e.command, e.location, e.enabled))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(run(&staged), run(&real));
+        assert_eq!(
+            repo.entries(),
+            InMemoryRepository::with_entries(entries).entries()
+        );
+        let changes: Vec<_> = staged
+            .changes()
+            .into_iter()
+            .map(|change| match change {
+                StagedChange::Write(e) => ("write", e.name),
+                StagedChange::Delete(e) => ("delete", e.name),
+                StagedChange::SetApproval(e) => ("approval", e.name),
+            })
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("approval", "Tool".to_string()),
+                ("write", "Tool".to_string()),
+                ("delete", "Tool".to_string()),
+                ("write", "Tray".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_remove_records_entry_and_launcher() {
+        let repo = InMemoryRepository::new();
+        let launchers = InMemoryLauncherStore::new();
+        add_dev(&repo, &launchers);
+        let staged = DryRunRepository::new(&repo);
+        let staged_launchers = DryRunLauncherStore::new(&launchers);
+
+        let deleted = RemoveEntryUseCase::new(&staged, &staged_launchers)
+            .execute("Dev")
+            .unwrap();
+
+        assert_eq!(repo.entries().len(), 1);
+        assert_eq!(launchers.filenames(), deleted);
+        assert_eq!(
+            staged.changes(),
+            vec![StagedChange::Delete(repo.entries()[0].clone())]
+        );
+        assert_eq!(
+            staged_launchers.changes(),
+            vec![StagedLauncher::Delete(deleted[0].clone())]
+        );
+        assert!(staged.list().unwrap().is_empty());
+        assert!(staged_launchers.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_changes_are_validated_like_the_real_ones() {
+        let repo = CompositeRepository::new()
+            .with(
+                StartupLocation::CurrentUserRun,
+                InMemoryRepository::with_entries([("Dev", "a.exe")]),
+            )
+            .with(
+                StartupLocation::LocalMachineRun,
+                InMemoryRepository::with_entries([("Dev", "b.exe")]),
+            )
+            .with(
+                StartupLocation::CurrentUserRunOnce,
+                InMemoryRepository::with_entries([("Once", "c.exe")]).without_approvals(),
+            );
+        let staged = DryRunRepository::new(&repo);
+
+        assert!(matches!(
+            staged.remove("Missing"),
+            Err(StartupError::EntryNotFound(_))
+        ));
+        assert!(matches!(
+            staged.remove("Dev"),
+            Err(StartupError::AmbiguousEntry(_))
+        ));
+        assert!(matches!(
+            SetEnabledUseCase::new(&staged).execute("Once", false),
+            Err(StartupError::Unsupported(_))
+        ));
+        assert!(staged.changes().is_empty());
+    }
+
+    #[test]
+    fn test_set_enabled_fails_in_the_same_locations_as_the_real_one() {
+        let folder = |name: &str| {
+            let dir = std::env::temp_dir().join(format!("wsm_dry_run_{}", name));
+            std::fs::remove_dir_all(&dir).ok();
+            dir
+        };
+        let (user_dir, common_dir) = (folder("user_folder"), folder("common_folder"));
+        let mut repo = CompositeRepository::new();
+        for location in StartupLocation::ALL {
+            repo = match location {
+                StartupLocation::UserStartupFolder => repo.with(
+                    location,
+                    StartupFolderRepository::new(&user_dir, location)
+                        .with_approvals(InMemoryApprovalStore::new()),
+                ),
+                StartupLocation::CommonStartupFolder => repo.with(
+                    location,
+                    StartupFolderRepository::new(&common_dir, location),
+                ),
+                StartupLocation::TaskScheduler => repo.with(
+                    location,
+                    ScheduledTaskRepository::new(InMemoryTaskScheduler::new()),
+                ),
+                _ if location.startup_approved_key().is_none() => {
+                    repo.with(location, InMemoryRepository::new().without_approvals())
+                }
+                _ => repo.with(location, InMemoryRepository::new()),
+            };
+        }
+        for (i, location) in StartupLocation::ALL.into_iter().enumerate() {
+            let command = match location {
+                StartupLocation::TaskScheduler => TaskDefinition::new("app.exe").to_xml(),
+                _ => "app.exe".to_string(),
+            };
+            let entry = StartupEntry::new(format!("App{}", i), command).with_location(location);
+            repo.add(&entry).unwrap();
+        }
+
+        let outcomes: Vec<_> = (0..StartupLocation::ALL.len())
+            .map(|i| {
+                let name = format!("App{}", i);
+                let staged = DryRunRepository::new(&repo);
+                let staged = SetEnabledUseCase::new(&staged).execute(&name, false);
+                let real = SetEnabledUseCase::new(&repo).execute(&name, false);
+                (
+                    staged.map_err(|e| std::mem::discriminant(&e)),
+                    real.map_err(|e| std::mem::discriminant(&e)),
+                )
+            })
+            .collect();
+
+        std::fs::remove_dir_all(&user_dir).ok();
+        std::fs::remove_dir_all(&common_dir).ok();
+        for (location, (staged, real)) in StartupLocation::ALL.into_iter().zip(outcomes) {
+            assert_eq!(staged, real, "{}", location);
+        }
+    }
+
+    #[test]
+    fn test_selected_location_is_recorded() {
+        let repo = InMemoryRepository::new();
+        let staged = DryRunRepository::new(&repo).select(Some(StartupLocation::LocalMachineRun));
+
+        staged.add(&StartupEntry::new("App", "app.exe")).unwrap();
+
+        let changes = staged.changes();
+        let [StagedChange::Write(entry)] = changes.as_slice() else {
+            panic!("expected one write, got {:?}", changes);
+        };
+        assert_eq!(entry.location, StartupLocation::LocalMachineRun);
+    }
+
+    #[test]
+    fn test_kill_records_process_tree_without_stopping_it() {
+        let repo = InMemoryRepository::with_entries([("Dev", "bun run dev")]);
+        let processes = FakeProcessProvider::with_processes(vec![
+            ProcessInfo::new(10, "bun.exe", "bun run dev"),
+            ProcessInfo::new(11, "node.exe", "node server.js").with_parent(10),
+            ProcessInfo::new(12, "other.exe", "other.exe"),
+        ]);
+        let staged = DryRunProcessProvider::new(&processes);
+        let options = KillOptions {
+            graceful_timeout: Duration::from_secs(60),
+            ..KillOptions::default()
+        };
+
+        KillProcessUseCase::new(&repo, &staged)
+            .execute("Dev", &options)
+            .unwrap();
+
+        let stopped: Vec<_> = staged.stopped().iter().map(|p| p.pid).collect();
+        assert_eq!(stopped, vec![10, 11]);
+        assert!(processes.killed().is_empty());
+        assert_eq!(processes.processes().len(), 3);
+        assert!(!staged.is_running(10).unwrap());
+        assert_eq!(staged.list_processes().unwrap().len(), 1);
+    }
+}
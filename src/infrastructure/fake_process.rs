@@ -0,0 +1,140 @@
+use crate::domain::{ProcessInfo, ProcessProvider};
+use crate::shared::error::{Result, StartupError};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+
+/// Scriptable implementation of the ProcessProvider trait.
+/// Holds a fake process table that can be seeded, made to fail and
+/// inspected afterwards, so process handling is testable on any platform.
+#[derive(Debug, Default)]
+pub struct FakeProcessProvider {
+    processes: RefCell<Vec<ProcessInfo>>,
+    list_failure: RefCell<Option<String>>,
+    kill_failures: RefCell<HashMap<u32, String>>,
+    ignored_close: RefCell<HashSet<u32>>,
+    exit_with_parent: RefCell<HashSet<u32>>,
+    killed: RefCell<Vec<u32>>,
+    closed: RefCell<Vec<u32>>,
+}
+
+impl FakeProcessProvider {
+    /// Creates a provider with an empty process table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a provider seeded with the given processes.
+    pub fn with_processes(processes: impl IntoIterator<Item = ProcessInfo>) -> Self {
+        let provider = Self::new();
+        for process in processes {
+            provider.spawn(process);
+        }
+        provider
+    }
+
+    /// Adds a process to the table.
+    pub fn spawn(&self, process: ProcessInfo) {
+        self.processes.borrow_mut().push(process);
+    }
+
+    /// Returns the processes that are still "running".
+    pub fn processes(&self) -> Vec<ProcessInfo> {
+        self.processes.borrow().clone()
+    }
+
+    /// Makes `list_processes` fail with `message` until cleared.
+    pub fn fail_list(&self, message: impl Into<String>) {
+        *self.list_failure.borrow_mut() = Some(message.into());
+    }
+
+    /// Makes killing `pid` fail with `message` until cleared.
+    pub fn fail_kill(&self, pid: u32, message: impl Into<String>) {
+        self.kill_failures.borrow_mut().insert(pid, message.into());
+    }
+
+    /// Makes `pid` ignore graceful close requests, so only a kill stops it.
+    pub fn ignore_close(&self, pid: u32) {
+        self.ignored_close.borrow_mut().insert(pid);
+    }
+
+    /// Makes `pid` exit as soon as its parent stops, like a console child
+    /// whose parent closed the console.
+    pub fn exit_with_parent(&self, pid: u32) {
+        self.exit_with_parent.borrow_mut().insert(pid);
+    }
+
+    /// Removes all injected failures.
+    pub fn clear_failures(&self) {
+        self.list_failure.borrow_mut().take();
+        self.kill_failures.borrow_mut().clear();
+        self.ignored_close.borrow_mut().clear();
+    }
+
+    /// Returns the PIDs that were successfully killed, in order.
+    pub fn killed(&self) -> Vec<u32> {
+        self.killed.borrow().clone()
+    }
+
+    /// Returns the PIDs that exited after a close request, in order.
+    pub fn closed(&self) -> Vec<u32> {
+        self.closed.borrow().clone()
+    }
+
+    fn take_process(&self, pid: u32) -> Result<()> {
+        let mut processes = self.processes.borrow_mut();
+        let index = processes
+            .iter()
+            .position(|p| p.pid == pid)
+            .ok_or_else(|| StartupError::ProcessError(format!("Process {} not found", pid)))?;
+        processes.remove(index);
+
+        // Take dependent children down with their parent
+        let exit_with_parent = self.exit_with_parent.borrow();
+        let mut stopped = vec![pid];
+        while let Some(parent) = stopped.pop() {
+            processes.retain(|p| {
+                let exits = p.parent_pid == Some(parent) && exit_with_parent.contains(&p.pid);
+                if exits {
+                    stopped.push(p.pid);
+                }
+                !exits
+            });
+        }
+        Ok(())
+    }
+}
+
+impl ProcessProvider for FakeProcessProvider {
+    fn list_processes(&self) -> Result<Vec<ProcessInfo>> {
+        if let Some(message) = self.list_failure.borrow().as_ref() {
+            return Err(StartupError::ProcessError(message.clone()));
+        }
+        Ok(self.processes.borrow().clone())
+    }
+
+    fn kill_process(&self, pid: u32) -> Result<()> {
+        if let Some(message) = self.kill_failures.borrow().get(&pid) {
+            return Err(StartupError::ProcessError(message.clone()));
+        }
+
+        self.take_process(pid)?;
+        self.killed.borrow_mut().push(pid);
+        Ok(())
+    }
+
+    fn request_close(&self, pid: u32) -> Result<()> {
+        if !self.processes.borrow().iter().any(|p| p.pid == pid) {
+            return Err(StartupError::ProcessError(format!(
+                "Process {} not found",
+                pid
+            )));
+        }
+        if self.ignored_close.borrow().contains(&pid) {
+            return Ok(());
+        }
+
+        self.take_process(pid)?;
+        self.closed.borrow_mut().push(pid);
+        Ok(())
+    }
+}
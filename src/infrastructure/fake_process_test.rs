@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use crate::domain::{ProcessInfo, ProcessProvider};
+    use crate::shared::error::StartupError;
+
+    fn sample() -> FakeProcessProvider {
+        FakeProcessProvider::with_processes(vec![
+            ProcessInfo::new(10, "bun.exe", "bun run dev"),
+            ProcessInfo::new(11, "node.exe", "node server.js"),
+            ProcessInfo::new(12, "BUN.EXE", "bun run build"),
+        ])
+    }
+
+    #[test]
+    fn test_list_returns_seeded_processes() {
+        let provider = sample();
+
+        let pids: Vec<_> = provider
+            .list_processes()
+            .unwrap()
+            .iter()
+            .map(|p| p.pid)
+            .collect();
+        assert_eq!(pids, vec![10, 11, 12]);
+    }
+
+    #[test]
+    fn test_kill_removes_process_and_records_pid() {
+        let provider = sample();
+
+        provider.kill_process(11).unwrap();
+
+        assert_eq!(provider.killed(), vec![11]);
+        assert!(provider.processes().iter().all(|p| p.pid != 11));
+    }
+
+    #[test]
+    fn test_kill_unknown_pid_fails() {
+        let provider = sample();
+
+        let result = provider.kill_process(99);
+
+        assert!(matches!(result, Err(StartupError::ProcessError(_))));
+        assert!(provider.killed().is_empty());
+    }
+
+    #[test]
+    fn test_kill_by_name_counts_matching_processes() {
+        let provider = sample();
+
+        let count = provider.kill_processes_by_name("bun.exe").unwrap();
+
+        assert_eq!(count, 2);
+        assert_eq!(provider.killed(), vec![10, 12]);
+    }
+
+    #[test]
+    fn test_kill_by_name_skips_processes_that_fail() {
+        let provider = sample();
+        provider.fail_kill(10, "access denied");
+
+        let count = provider.kill_processes_by_name("bun").unwrap();
+
+        assert_eq!(count, 1);
+        assert_eq!(provider.killed(), vec![12]);
+    }
+
+    #[test]
+    fn test_list_failure_injection() {
+        let provider = sample();
+        provider.fail_list("snapshot failed");
+
+        assert!(provider.list_processes().is_err());
+        assert!(provider.kill_processes_by_name("bun").is_err());
+
+        provider.clear_failures();
+        assert!(provider.list_processes().is_ok());
+    }
+
+    #[test]
+    fn test_request_close_stops_cooperative_process() {
+        let provider = sample();
+
+        provider.request_close(10).unwrap();
+
+        assert_eq!(provider.closed(), vec![10]);
+        assert!(!provider.is_running(10).unwrap());
+    }
+
+    #[test]
+    fn test_request_close_ignored_process_keeps_running() {
+        let provider = sample();
+        provider.ignore_close(10);
+
+        provider.request_close(10).unwrap();
+
+        assert!(provider.closed().is_empty());
+        assert!(provider.is_running(10).unwrap());
+    }
+}
@@ -0,0 +1,98 @@
+use crate::domain::{History, HistoryStore, LAUNCHER_DIR_NAME};
+use crate::shared::error::{Result, StartupError};
+use std::cell::RefCell;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// File the history is kept in, next to the launchers.
+pub const HISTORY_FILE: &str = "history.json";
+
+/// Filesystem implementation of the HistoryStore trait.
+/// The history is a single JSON file, replaced on every save.
+#[derive(Debug, Clone)]
+pub struct FileSystemHistoryStore {
+    path: PathBuf,
+}
+
+impl FileSystemHistoryStore {
+    /// Creates a store keeping the history in the file `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Creates the store in %APPDATA%\windows_startup_manager\history.json.
+    pub fn from_appdata() -> Result<Self> {
+        let appdata = std::env::var("APPDATA").map_err(|_| {
+            StartupError::RegistryError("Failed to get APPDATA environment variable".to_string())
+        })?;
+
+        Ok(Self::new(
+            PathBuf::from(appdata)
+                .join(LAUNCHER_DIR_NAME)
+                .join(HISTORY_FILE),
+        ))
+    }
+
+    /// Returns the file the history is written to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl HistoryStore for FileSystemHistoryStore {
+    fn load(&self) -> Result<History> {
+        match fs::read_to_string(&self.path) {
+            Ok(json) => History::from_json(&json),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(History::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn save(&self, history: &History) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        // Replace the history in one step so readers never see a partial file
+        static WRITES: AtomicUsize = AtomicUsize::new(0);
+        let temp_path = self.path.with_extension(format!(
+            "{}-{}.tmp",
+            std::process::id(),
+            WRITES.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&temp_path, history.to_json())?;
+        fs::rename(&temp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+/// In-memory implementation of the HistoryStore trait.
+#[derive(Debug, Default)]
+pub struct InMemoryHistoryStore {
+    history: RefCell<History>,
+}
+
+impl InMemoryHistoryStore {
+    /// Creates a store with an empty history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a copy of the history.
+    pub fn history(&self) -> History {
+        self.history.borrow().clone()
+    }
+}
+
+impl HistoryStore for InMemoryHistoryStore {
+    fn load(&self) -> Result<History> {
+        Ok(self.history())
+    }
+
+    fn save(&self, history: &History) -> Result<()> {
+        *self.history.borrow_mut() = history.clone();
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use crate::domain::{History, HistoryStore};
+    use crate::shared::error::StartupError;
+    use chrono::{TimeZone, Utc};
+    use std::fs;
+
+    fn temp_file(name: &str) -> std::path::PathBuf {
+        let root = std::env::temp_dir().join(format!("wsm_history_{}", name));
+        fs::remove_dir_all(&root).ok();
+        root.join(HISTORY_FILE)
+    }
+
+    #[test]
+    fn test_file_system_store_round_trip() {
+        let path = temp_file("round_trip");
+        let store = FileSystemHistoryStore::new(&path);
+        let mut history = History::new();
+        let at = Utc.with_ymd_and_hms(2026, 3, 1, 8, 30, 0).unwrap();
+        history.record("remove Dev", at, Vec::new(), Vec::new());
+
+        assert_eq!(store.load().unwrap(), History::new());
+        store.save(&history).unwrap();
+
+        assert_eq!(store.load().unwrap(), history);
+        fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn test_file_system_store_rejects_corrupt_history() {
+        let path = temp_file("corrupt");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "[").unwrap();
+
+        let error = FileSystemHistoryStore::new(&path).load().unwrap_err();
+
+        assert!(matches!(error, StartupError::InvalidFormat(_)), "{}", error);
+        fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+}
@@ -0,0 +1,214 @@
+use crate::domain::{LAUNCHER_DIR_NAME, LauncherManifest, LauncherStore};
+use crate::shared::error::{Result, StartupError};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// File in the launcher directory recording which entry owns which launcher.
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Filesystem implementation of the LauncherStore trait.
+/// Launchers are files in a root directory, next to a `manifest.json`.
+#[derive(Debug, Clone)]
+pub struct FileSystemLauncherStore {
+    root: PathBuf,
+    location: String,
+}
+
+impl FileSystemLauncherStore {
+    /// Creates a store keeping launchers in `root`.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        let root = root.into();
+        let location = root.to_string_lossy().into_owned();
+        Self { root, location }
+    }
+
+    /// Creates the store in %APPDATA%\windows_startup_manager\.
+    /// Registry values keep the unexpanded `%APPDATA%` form, so they follow
+    /// roaming profiles.
+    pub fn from_appdata() -> Result<Self> {
+        let appdata = std::env::var("APPDATA").map_err(|_| {
+            StartupError::RegistryError("Failed to get APPDATA environment variable".to_string())
+        })?;
+
+        Ok(Self {
+            root: PathBuf::from(appdata).join(LAUNCHER_DIR_NAME),
+            location: format!("%APPDATA%\\{}", LAUNCHER_DIR_NAME),
+        })
+    }
+
+    /// Returns the directory the launchers are written to.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn invalid_manifest(error: serde_json::Error) -> StartupError {
+        StartupError::IoError(std::io::Error::new(
+            ErrorKind::InvalidData,
+            format!("Invalid launcher manifest: {}", error),
+        ))
+    }
+}
+
+impl LauncherStore for FileSystemLauncherStore {
+    fn location(&self) -> String {
+        self.location.clone()
+    }
+
+    fn read(&self, filename: &str) -> Result<Option<String>> {
+        match fs::read_to_string(self.root.join(filename)) {
+            Ok(content) => Ok(Some(content)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn write(&self, filename: &str, content: &str) -> Result<()> {
+        fs::create_dir_all(&self.root)?;
+        fs::write(self.root.join(filename), content)?;
+        Ok(())
+    }
+
+    fn delete(&self, filename: &str) -> Result<bool> {
+        match fs::remove_file(self.root.join(filename)) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn list(&self) -> Result<Vec<String>> {
+        let dir = match fs::read_dir(&self.root) {
+            Ok(dir) => dir,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut launchers = Vec::new();
+        for file in dir {
+            let file = file?;
+            let name = file.file_name().to_string_lossy().into_owned();
+            if file.file_type()?.is_file() && name.to_lowercase().ends_with(".vbs") {
+                launchers.push(name);
+            }
+        }
+        launchers.sort();
+        Ok(launchers)
+    }
+
+    fn load_manifest(&self) -> Result<LauncherManifest> {
+        match fs::read_to_string(self.root.join(MANIFEST_FILE)) {
+            Ok(json) => LauncherManifest::from_json(&json).map_err(Self::invalid_manifest),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(LauncherManifest::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn save_manifest(&self, manifest: &LauncherManifest) -> Result<()> {
+        fs::create_dir_all(&self.root)?;
+
+        // Replace the manifest in one step so readers never see a partial file
+        static WRITES: AtomicUsize = AtomicUsize::new(0);
+        let path = self.root.join(MANIFEST_FILE);
+        let temp_path = path.with_extension(format!(
+            "{}-{}.tmp",
+            std::process::id(),
+            WRITES.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&temp_path, manifest.to_json())?;
+        fs::rename(&temp_path, &path)?;
+        Ok(())
+    }
+
+    fn filename_of(&self, path: &str) -> Option<String> {
+        // Values may also spell out the expanded directory
+        let (dir, filename) = path.rsplit_once(['\\', '/'])?;
+        let matches = |location: &str| crate::domain::launcher_store::same_directory(dir, location);
+        (matches(&self.location) || matches(&self.root.to_string_lossy()))
+            .then(|| filename.to_string())
+    }
+}
+
+/// In-memory implementation of the LauncherStore trait.
+/// Registry values reference it like the %APPDATA% store by default, so
+/// rendered values look the same as in production.
+#[derive(Debug)]
+pub struct InMemoryLauncherStore {
+    location: String,
+    files: RefCell<BTreeMap<String, String>>,
+    manifest: RefCell<LauncherManifest>,
+}
+
+impl Default for InMemoryLauncherStore {
+    fn default() -> Self {
+        Self::with_location(format!("%APPDATA%\\{}", LAUNCHER_DIR_NAME))
+    }
+}
+
+impl InMemoryLauncherStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty store that registry values reference as `location`.
+    pub fn with_location(location: impl Into<String>) -> Self {
+        Self {
+            location: location.into(),
+            files: RefCell::new(BTreeMap::new()),
+            manifest: RefCell::new(LauncherManifest::new()),
+        }
+    }
+
+    /// Returns a launcher's content.
+    pub fn file(&self, filename: &str) -> Option<String> {
+        self.files.borrow().get(filename).cloned()
+    }
+
+    /// Returns the file names of all launchers.
+    pub fn filenames(&self) -> Vec<String> {
+        self.files.borrow().keys().cloned().collect()
+    }
+
+    /// Returns a copy of the manifest.
+    pub fn manifest(&self) -> LauncherManifest {
+        self.manifest.borrow().clone()
+    }
+}
+
+impl LauncherStore for InMemoryLauncherStore {
+    fn location(&self) -> String {
+        self.location.clone()
+    }
+
+    fn read(&self, filename: &str) -> Result<Option<String>> {
+        Ok(self.file(filename))
+    }
+
+    fn write(&self, filename: &str, content: &str) -> Result<()> {
+        self.files
+            .borrow_mut()
+            .insert(filename.to_string(), content.to_string());
+        Ok(())
+    }
+
+    fn delete(&self, filename: &str) -> Result<bool> {
+        Ok(self.files.borrow_mut().remove(filename).is_some())
+    }
+
+    fn list(&self) -> Result<Vec<String>> {
+        Ok(self.filenames())
+    }
+
+    fn load_manifest(&self) -> Result<LauncherManifest> {
+        Ok(self.manifest())
+    }
+
+    fn save_manifest(&self, manifest: &LauncherManifest) -> Result<()> {
+        *self.manifest.borrow_mut() = manifest.clone();
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use crate::domain::{LauncherManifest, LauncherStore};
+    use std::fs;
+
+    fn temp_root(name: &str) -> std::path::PathBuf {
+        let root = std::env::temp_dir().join(format!("wsm_launchers_{}", name));
+        fs::remove_dir_all(&root).ok();
+        root
+    }
+
+    #[test]
+    fn test_file_system_store_round_trip() {
+        let root = temp_root("round_trip");
+        let store = FileSystemLauncherStore::new(&root);
+
+        store.write("web_1.vbs", "WScript.Quit").unwrap();
+        fs::write(root.join("notes.txt"), "not a launcher").unwrap();
+
+        assert_eq!(
+            store.read("web_1.vbs").unwrap().as_deref(),
+            Some("WScript.Quit")
+        );
+        assert_eq!(store.read("missing.vbs").unwrap(), None);
+        assert_eq!(store.list().unwrap(), vec!["web_1.vbs"]);
+        assert!(store.delete("web_1.vbs").unwrap());
+        assert!(!store.delete("web_1.vbs").unwrap());
+        assert!(store.list().unwrap().is_empty());
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_file_system_store_missing_root_is_empty() {
+        let store = FileSystemLauncherStore::new(temp_root("missing"));
+
+        assert!(store.list().unwrap().is_empty());
+        assert_eq!(store.load_manifest().unwrap(), LauncherManifest::new());
+    }
+
+    #[test]
+    fn test_file_system_store_manifest() {
+        let root = temp_root("manifest");
+        let store = FileSystemLauncherStore::new(&root);
+        let mut manifest = LauncherManifest::new();
+        manifest.record("Web", "web_1.vbs");
+
+        store.save_manifest(&manifest).unwrap();
+
+        assert_eq!(store.load_manifest().unwrap(), manifest);
+        assert!(root.join(MANIFEST_FILE).exists());
+        fs::write(root.join(MANIFEST_FILE), "{").unwrap();
+        assert!(store.load_manifest().is_err());
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_file_system_store_paths_follow_root() {
+        let root = temp_root("paths");
+        let store = FileSystemLauncherStore::new(&root);
+
+        let path = store.path_of("web_1.vbs");
+
+        assert!(path.starts_with(&*root.to_string_lossy()));
+        assert_eq!(store.filename_of(&path).as_deref(), Some("web_1.vbs"));
+        assert_eq!(store.filename_of("C:\\elsewhere\\web_1.vbs"), None);
+    }
+
+    #[test]
+    fn test_in_memory_store_references_appdata_by_default() {
+        let store = InMemoryLauncherStore::new();
+
+        assert_eq!(
+            store.path_of("web_1.vbs"),
+            "%APPDATA%\\windows_startup_manager\\web_1.vbs"
+        );
+        assert_eq!(
+            store.referenced_by(
+                "wscript.exe //B //Nologo \"%appdata%\\Windows_Startup_Manager\\web_1.vbs\""
+            ),
+            Some("web_1.vbs".to_string())
+        );
+        assert_eq!(
+            store.referenced_by("wscript.exe \"C:\\scripts\\tray.vbs\""),
+            None
+        );
+    }
+
+    #[test]
+    fn test_in_memory_store_round_trip() {
+        let store = InMemoryLauncherStore::with_location("C:\\launchers");
+
+        store.write("b.vbs", "b").unwrap();
+        store.write("a.vbs", "a").unwrap();
+
+        assert_eq!(store.path_of("a.vbs"), "C:\\launchers\\a.vbs");
+        assert_eq!(store.list().unwrap(), vec!["a.vbs", "b.vbs"]);
+        assert_eq!(store.file("b.vbs").as_deref(), Some("b"));
+        assert!(store.delete("a.vbs").unwrap());
+        assert_eq!(store.filenames(), vec!["b.vbs"]);
+    }
+}
@@ -0,0 +1,194 @@
+use crate::domain::{StartupApproval, StartupEntry, StartupLocation, StartupRepository};
+use crate::shared::error::{Result, StartupError};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// Operations exposed by the StartupRepository trait.
+/// Used to target failure injection on an InMemoryRepository.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RepositoryOperation {
+    Add,
+    Remove,
+    List,
+    Exists,
+    SetApproval,
+}
+
+/// A call recorded by an InMemoryRepository, in the order it was made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RepositoryCall {
+    Add(StartupEntry),
+    Remove(String),
+    List,
+    Exists(String),
+    SetApproval(String, StartupApproval),
+}
+
+impl RepositoryCall {
+    /// Returns the repository operation this call corresponds to.
+    pub fn operation(&self) -> RepositoryOperation {
+        match self {
+            RepositoryCall::Add(_) => RepositoryOperation::Add,
+            RepositoryCall::Remove(_) => RepositoryOperation::Remove,
+            RepositoryCall::List => RepositoryOperation::List,
+            RepositoryCall::Exists(_) => RepositoryOperation::Exists,
+            RepositoryCall::SetApproval(..) => RepositoryOperation::SetApproval,
+        }
+    }
+}
+
+/// In-memory implementation of the StartupRepository trait.
+/// Entries keep their insertion order, and their names compare without
+/// regard to case, like values under a registry key.
+/// Supports seeding, failure injection and call recording, so it can be
+/// shared by tests and by tools that need a repository without a registry.
+#[derive(Debug, Default)]
+pub struct InMemoryRepository {
+    entries: RefCell<Vec<StartupEntry>>,
+    failures: RefCell<HashMap<RepositoryOperation, String>>,
+    calls: RefCell<Vec<RepositoryCall>>,
+    without_approvals: bool,
+}
+
+impl InMemoryRepository {
+    /// Creates an empty repository.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a repository seeded with `(name, command)` pairs.
+    pub fn with_entries<I, N, C>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = (N, C)>,
+        N: Into<String>,
+        C: Into<String>,
+    {
+        let repository = Self::new();
+        for (name, command) in entries {
+            repository.seed(StartupEntry::new(name, command));
+        }
+        repository
+    }
+
+    /// Makes the repository hold entries that cannot be enabled or
+    /// disabled, like a RunOnce key.
+    pub fn without_approvals(mut self) -> Self {
+        self.without_approvals = true;
+        self
+    }
+
+    /// Inserts or replaces an entry without recording a call.
+    pub fn seed(&self, entry: StartupEntry) {
+        Self::upsert(&mut self.entries.borrow_mut(), entry);
+    }
+
+    /// Returns a snapshot of the stored entries without recording a call.
+    pub fn entries(&self) -> Vec<StartupEntry> {
+        self.entries.borrow().clone()
+    }
+
+    /// Makes every subsequent call of `operation` fail with a registry error
+    /// carrying `message`, until cleared.
+    pub fn fail_on(&self, operation: RepositoryOperation, message: impl Into<String>) {
+        self.failures.borrow_mut().insert(operation, message.into());
+    }
+
+    /// Removes all injected failures.
+    pub fn clear_failures(&self) {
+        self.failures.borrow_mut().clear();
+    }
+
+    /// Returns every call made through the StartupRepository trait so far.
+    pub fn calls(&self) -> Vec<RepositoryCall> {
+        self.calls.borrow().clone()
+    }
+
+    /// Forgets all recorded calls.
+    pub fn clear_calls(&self) {
+        self.calls.borrow_mut().clear();
+    }
+
+    /// Records a call and returns the injected failure for it, if any.
+    fn record(&self, call: RepositoryCall) -> Result<()> {
+        let operation = call.operation();
+        self.calls.borrow_mut().push(call);
+
+        match self.failures.borrow().get(&operation) {
+            Some(message) => Err(StartupError::RegistryError(message.clone())),
+            None => Ok(()),
+        }
+    }
+
+    fn upsert(entries: &mut Vec<StartupEntry>, entry: StartupEntry) {
+        match entries
+            .iter_mut()
+            .find(|e| e.name.eq_ignore_ascii_case(&entry.name))
+        {
+            Some(existing) => *existing = entry,
+            None => entries.push(entry),
+        }
+    }
+}
+
+impl StartupRepository for InMemoryRepository {
+    /// Replacing an entry keeps its enabled state, like the registry does.
+    fn add(&self, entry: &StartupEntry) -> Result<()> {
+        self.record(RepositoryCall::Add(entry.clone()))?;
+
+        let mut entries = self.entries.borrow_mut();
+        let approval = entries
+            .iter()
+            .find(|e| e.name.eq_ignore_ascii_case(&entry.name))
+            .map_or(entry.approval(), StartupEntry::approval);
+        Self::upsert(&mut entries, entry.clone().with_approval(approval));
+        Ok(())
+    }
+
+    fn remove(&self, name: &str) -> Result<()> {
+        self.record(RepositoryCall::Remove(name.to_string()))?;
+
+        let mut entries = self.entries.borrow_mut();
+        let index = entries
+            .iter()
+            .position(|e| e.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| StartupError::EntryNotFound(name.to_string()))?;
+        entries.remove(index);
+        Ok(())
+    }
+
+    fn list(&self) -> Result<Vec<StartupEntry>> {
+        self.record(RepositoryCall::List)?;
+        Ok(self.entries.borrow().clone())
+    }
+
+    fn exists(&self, name: &str) -> Result<bool> {
+        self.record(RepositoryCall::Exists(name.to_string()))?;
+        Ok(self
+            .entries
+            .borrow()
+            .iter()
+            .any(|e| e.name.eq_ignore_ascii_case(name)))
+    }
+
+    fn set_approval(&self, name: &str, approval: StartupApproval) -> Result<()> {
+        self.record(RepositoryCall::SetApproval(name.to_string(), approval))?;
+        if self.without_approvals {
+            return Err(StartupError::Unsupported(format!(
+                "entry '{}' cannot be enabled or disabled in this location",
+                name
+            )));
+        }
+
+        let mut entries = self.entries.borrow_mut();
+        let entry = entries
+            .iter_mut()
+            .find(|e| e.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| StartupError::EntryNotFound(name.to_string()))?;
+        *entry = entry.clone().with_approval(approval);
+        Ok(())
+    }
+
+    fn supports_approval(&self, _location: StartupLocation) -> bool {
+        !self.without_approvals
+    }
+}
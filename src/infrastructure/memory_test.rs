@@ -0,0 +1,147 @@
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use crate::domain::{StartupApproval, StartupEntry, StartupRepository};
+    use crate::shared::error::StartupError;
+
+    #[test]
+    fn test_new_repository_is_empty() {
+        let repo = InMemoryRepository::new();
+
+        assert!(repo.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_with_entries_preserves_order() {
+        let repo = InMemoryRepository::with_entries(vec![
+            ("Zeta", "C:\\zeta.exe"),
+            ("Alpha", "C:\\alpha.exe"),
+        ]);
+
+        let names: Vec<_> = repo.list().unwrap().into_iter().map(|e| e.name).collect();
+        assert_eq!(names, vec!["Zeta", "Alpha"]);
+    }
+
+    #[test]
+    fn test_add_replaces_existing_entry_in_place() {
+        let repo = InMemoryRepository::with_entries(vec![("A", "a.exe"), ("B", "b.exe")]);
+
+        repo.add(&StartupEntry::new("A", "new.exe")).unwrap();
+
+        let entries = repo.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0], StartupEntry::new("A", "new.exe"));
+    }
+
+    #[test]
+    fn test_remove_missing_entry_returns_not_found() {
+        let repo = InMemoryRepository::new();
+
+        let result = repo.remove("Missing");
+
+        assert!(matches!(result, Err(StartupError::EntryNotFound(name)) if name == "Missing"));
+    }
+
+    #[test]
+    fn test_exists() {
+        let repo = InMemoryRepository::with_entries(vec![("App", "app.exe")]);
+
+        assert!(repo.exists("App").unwrap());
+        assert!(!repo.exists("Other").unwrap());
+    }
+
+    #[test]
+    fn test_names_compare_without_case() {
+        let repo = InMemoryRepository::with_entries(vec![("MyApp", "app.exe")]);
+
+        assert!(repo.exists("myapp").unwrap());
+        repo.add(&StartupEntry::new("MYAPP", "new.exe")).unwrap();
+        assert_eq!(repo.entries(), vec![StartupEntry::new("MYAPP", "new.exe")]);
+
+        repo.set_approval("myApp", StartupApproval::disabled_since(chrono::Utc::now()))
+            .unwrap();
+        assert!(!repo.entries()[0].enabled);
+
+        repo.remove("myapp").unwrap();
+        assert!(repo.entries().is_empty());
+    }
+
+    #[test]
+    fn test_calls_are_recorded_in_order() {
+        let repo = InMemoryRepository::new();
+        repo.seed(StartupEntry::new("Seeded", "seeded.exe"));
+
+        repo.add(&StartupEntry::new("App", "app.exe")).unwrap();
+        repo.exists("App").unwrap();
+        repo.list().unwrap();
+        repo.remove("App").unwrap();
+
+        assert_eq!(
+            repo.calls(),
+            vec![
+                RepositoryCall::Add(StartupEntry::new("App", "app.exe")),
+                RepositoryCall::Exists("App".to_string()),
+                RepositoryCall::List,
+                RepositoryCall::Remove("App".to_string()),
+            ]
+        );
+
+        repo.clear_calls();
+        assert!(repo.calls().is_empty());
+    }
+
+    #[test]
+    fn test_injected_failure_leaves_state_untouched() {
+        let repo = InMemoryRepository::new();
+        repo.fail_on(RepositoryOperation::Add, "access denied");
+
+        let result = repo.add(&StartupEntry::new("App", "app.exe"));
+
+        assert!(matches!(result, Err(StartupError::RegistryError(msg)) if msg == "access denied"));
+        assert!(repo.entries().is_empty());
+        assert_eq!(repo.calls().len(), 1);
+
+        repo.clear_failures();
+        assert!(repo.add(&StartupEntry::new("App", "app.exe")).is_ok());
+    }
+
+    #[test]
+    fn test_injected_failure_targets_single_operation() {
+        let repo = InMemoryRepository::with_entries(vec![("App", "app.exe")]);
+        repo.fail_on(RepositoryOperation::List, "boom");
+
+        assert!(repo.list().is_err());
+        assert!(repo.exists("App").unwrap());
+    }
+
+    #[test]
+    fn test_set_approval_updates_entry() {
+        let repo = InMemoryRepository::with_entries(vec![("App", "app.exe")]);
+        let disabled = StartupApproval::disabled_since(chrono::Utc::now());
+
+        repo.set_approval("App", disabled).unwrap();
+
+        assert_eq!(repo.entries()[0].approval(), disabled);
+        assert_eq!(
+            repo.calls(),
+            vec![RepositoryCall::SetApproval("App".to_string(), disabled)]
+        );
+        assert!(matches!(
+            repo.set_approval("Missing", disabled),
+            Err(StartupError::EntryNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_add_keeps_approval_of_replaced_entry() {
+        let repo = InMemoryRepository::with_entries(vec![("App", "app.exe")]);
+        repo.set_approval("App", StartupApproval::disabled_since(chrono::Utc::now()))
+            .unwrap();
+
+        repo.add(&StartupEntry::new("App", "new.exe")).unwrap();
+
+        let entry = &repo.entries()[0];
+        assert_eq!(entry.command, "new.exe");
+        assert!(!entry.enabled);
+    }
+}
@@ -1,8 +1,83 @@
-pub mod process;
+pub mod approval_store;
+pub mod audit_log;
+pub mod composite;
+pub mod csv;
+pub mod dry_run;
+pub mod fake_process;
+pub mod history_store;
+pub mod launcher_store;
+pub mod memory;
+#[cfg(unix)]
+pub mod procfs;
+#[cfg(windows)]
 pub mod registry;
+pub mod scheduled_task;
+pub mod snapshot;
+pub mod startup_folder;
+pub mod task_scheduler;
+#[cfg(windows)]
+pub mod win32_process;
 
 #[cfg(test)]
-mod process_test;
+mod approval_store_test;
+#[cfg(test)]
+mod audit_log_test;
+#[cfg(test)]
+mod composite_test;
+#[cfg(test)]
+mod csv_test;
+#[cfg(test)]
+mod dry_run_test;
+#[cfg(test)]
+mod fake_process_test;
+#[cfg(test)]
+mod history_store_test;
+#[cfg(test)]
+mod launcher_store_test;
+#[cfg(test)]
+mod memory_test;
+#[cfg(all(test, unix))]
+mod procfs_test;
+#[cfg(test)]
+mod scheduled_task_test;
+#[cfg(test)]
+mod snapshot_test;
+#[cfg(test)]
+mod startup_folder_test;
+#[cfg(test)]
+mod task_scheduler_test;
 
-pub use process::ProcessManager;
+pub use approval_store::InMemoryApprovalStore;
+#[cfg(windows)]
+pub use approval_store::RegistryApprovalStore;
+pub use audit_log::{
+    AUDIT_LOG_ENV, AUDIT_LOG_FILE, DEFAULT_KEPT_LOGS, DEFAULT_MAX_LOG_BYTES, FileSystemAuditLog,
+    InMemoryAuditLog, current_actor,
+};
+pub use composite::CompositeRepository;
+pub use dry_run::{
+    DryRunLauncherStore, DryRunProcessProvider, DryRunRepository, StagedChange, StagedLauncher,
+};
+pub use fake_process::FakeProcessProvider;
+pub use history_store::{FileSystemHistoryStore, HISTORY_FILE, InMemoryHistoryStore};
+pub use launcher_store::{FileSystemLauncherStore, InMemoryLauncherStore, MANIFEST_FILE};
+pub use memory::{InMemoryRepository, RepositoryCall, RepositoryOperation};
+#[cfg(unix)]
+pub use procfs::ProcFsProcessProvider;
+#[cfg(windows)]
 pub use registry::WindowsRegistryRepository;
+pub use scheduled_task::ScheduledTaskRepository;
+pub use snapshot::SnapshotRepository;
+pub use startup_folder::StartupFolderRepository;
+pub use task_scheduler::InMemoryTaskScheduler;
+#[cfg(windows)]
+pub use task_scheduler::SchtasksTaskScheduler;
+#[cfg(windows)]
+pub use win32_process::Win32ProcessProvider;
+
+/// The process provider backed by the current operating system.
+#[cfg(windows)]
+pub type NativeProcessProvider = Win32ProcessProvider;
+/// The process provider backed by the current operating system.
+#[cfg(unix)]
+pub type NativeProcessProvider = ProcFsProcessProvider;
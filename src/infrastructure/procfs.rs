@@ -0,0 +1,167 @@
+use crate::domain::{ProcessInfo, ProcessProvider};
+use crate::shared::error::{Result, StartupError};
+use chrono::{DateTime, Utc};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Fields of `/proc/<pid>/stat` used by the provider.
+struct Stat {
+    state: char,
+    parent_pid: u32,
+    /// Start time in clock ticks after boot.
+    start_ticks: Option<u64>,
+}
+
+/// `/proc`-based implementation of the ProcessProvider trait.
+/// Reads the process table from procfs and terminates processes with signals.
+pub struct ProcFsProcessProvider {
+    root: PathBuf,
+}
+
+impl ProcFsProcessProvider {
+    /// Creates a provider reading the system's `/proc`.
+    pub fn new() -> Self {
+        Self::with_root("/proc")
+    }
+
+    /// Creates a provider reading a procfs-like tree rooted at `root`.
+    pub fn with_root(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Reads a single process directory.
+    /// Returns `None` when the process exited or is not readable.
+    /// `boot_time` is used to turn the start tick count into a timestamp.
+    fn read_process(dir: &Path, pid: u32, boot_time: Option<i64>) -> Option<ProcessInfo> {
+        let raw_cmdline = fs::read(dir.join("cmdline")).ok()?;
+        let command_line = raw_cmdline
+            .split(|&b| b == 0)
+            .filter(|arg| !arg.is_empty())
+            .map(|arg| String::from_utf8_lossy(arg).into_owned())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        // `comm` is truncated to 15 bytes, so prefer the executable's file name
+        let exe = fs::read_link(dir.join("exe")).ok();
+        let name = exe
+            .as_ref()
+            .and_then(|exe| exe.file_name().map(|n| n.to_string_lossy().into_owned()))
+            .or_else(|| {
+                fs::read_to_string(dir.join("comm"))
+                    .ok()
+                    .map(|comm| comm.trim_end().to_string())
+            })?;
+
+        let mut info = ProcessInfo::new(pid, name, command_line);
+        if let Some(exe) = exe {
+            info = info.with_executable_path(exe.to_string_lossy());
+        }
+        if let Some(stat) = Self::read_stat(dir) {
+            info = info.with_parent(stat.parent_pid);
+            if let Some(start) = stat
+                .start_ticks
+                .zip(boot_time)
+                .and_then(|(ticks, boot)| Self::start_time(ticks, boot))
+            {
+                info = info.with_start_time(start);
+            }
+        }
+        if let Ok(cwd) = fs::read_link(dir.join("cwd")) {
+            info = info.with_workdir(cwd.to_string_lossy());
+        }
+        Some(info)
+    }
+
+    /// Reads the state, parent PID and start time from `stat`.
+    /// The command name may contain spaces and parentheses, so fields are
+    /// located after its closing parenthesis.
+    fn read_stat(dir: &Path) -> Option<Stat> {
+        let stat = fs::read_to_string(dir.join("stat")).ok()?;
+        let fields: Vec<&str> = stat[stat.rfind(')')? + 1..].split_whitespace().collect();
+        Some(Stat {
+            state: fields.first()?.chars().next()?,
+            parent_pid: fields.get(1)?.parse().ok()?,
+            // `starttime` is field 22 of the whole line, the 20th after the name
+            start_ticks: fields.get(19).and_then(|t| t.parse().ok()),
+        })
+    }
+
+    /// Reads the boot time, in seconds since the Unix epoch, from `stat`.
+    fn read_boot_time(&self) -> Option<i64> {
+        fs::read_to_string(self.root.join("stat"))
+            .ok()?
+            .lines()
+            .find_map(|line| line.strip_prefix("btime "))?
+            .trim()
+            .parse()
+            .ok()
+    }
+
+    fn start_time(ticks: u64, boot_time: i64) -> Option<DateTime<Utc>> {
+        // SAFETY: sysconf has no memory-safety requirements.
+        let ticks_per_second = u64::try_from(unsafe { libc::sysconf(libc::_SC_CLK_TCK) })
+            .ok()
+            .filter(|&t| t > 0)?;
+        let millis = ticks * 1000 / ticks_per_second;
+        DateTime::from_timestamp_millis(boot_time * 1000 + i64::try_from(millis).ok()?)
+    }
+
+    /// Sends `signal` to `pid`.
+    fn signal(pid: u32, signal: libc::c_int) -> Result<()> {
+        let pid = libc::pid_t::try_from(pid)
+            .map_err(|_| StartupError::ProcessError(format!("Invalid process id {}", pid)))?;
+
+        // SAFETY: kill(2) has no memory-safety requirements.
+        if unsafe { libc::kill(pid, signal) } != 0 {
+            return Err(StartupError::ProcessError(format!(
+                "Failed to signal process {}: {}",
+                pid,
+                std::io::Error::last_os_error()
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Default for ProcFsProcessProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProcessProvider for ProcFsProcessProvider {
+    fn list_processes(&self) -> Result<Vec<ProcessInfo>> {
+        let dir = fs::read_dir(&self.root).map_err(|e| {
+            StartupError::ProcessError(format!(
+                "Failed to read process table '{}': {}",
+                self.root.display(),
+                e
+            ))
+        })?;
+
+        let boot_time = self.read_boot_time();
+        let mut processes: Vec<ProcessInfo> = dir
+            .filter_map(|item| item.ok())
+            .filter_map(|item| {
+                let pid = item.file_name().to_str()?.parse::<u32>().ok()?;
+                Self::read_process(&item.path(), pid, boot_time)
+            })
+            .collect();
+        processes.sort_by_key(|p| p.pid);
+
+        Ok(processes)
+    }
+
+    fn kill_process(&self, pid: u32) -> Result<()> {
+        Self::signal(pid, libc::SIGKILL)
+    }
+
+    fn request_close(&self, pid: u32) -> Result<()> {
+        Self::signal(pid, libc::SIGTERM)
+    }
+
+    fn is_running(&self, pid: u32) -> Result<bool> {
+        // Zombies have exited and only wait to be reaped
+        Ok(Self::read_stat(&self.root.join(pid.to_string())).is_some_and(|stat| stat.state != 'Z'))
+    }
+}
@@ -0,0 +1,160 @@
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use crate::domain::ProcessProvider;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    /// Builds a procfs-like fixture tree in a fresh temporary directory.
+    fn fixture(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("wsm_procfs_{}", name));
+        fs::remove_dir_all(&root).ok();
+        fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    fn add_process(root: &Path, pid: u32, comm: &str, argv: &[&str]) -> PathBuf {
+        let dir = root.join(pid.to_string());
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("comm"), format!("{}\n", comm)).unwrap();
+        let mut cmdline = argv.join("\0");
+        cmdline.push('\0');
+        fs::write(dir.join("cmdline"), cmdline).unwrap();
+        fs::write(
+            dir.join("stat"),
+            format!("{} ({}) S 1 {} 0 0", pid, comm, pid),
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_list_reads_comm_and_cmdline() {
+        let root = fixture("basic");
+        add_process(&root, 42, "bun", &["bun", "run", "dev"]);
+        add_process(&root, 7, "node", &["node", "server.js"]);
+        fs::create_dir_all(root.join("self")).unwrap();
+        fs::write(root.join("uptime"), "1.0 1.0").unwrap();
+
+        let processes = ProcFsProcessProvider::with_root(&root)
+            .list_processes()
+            .unwrap();
+
+        fs::remove_dir_all(&root).ok();
+        assert_eq!(processes.len(), 2);
+        assert_eq!(processes[0].pid, 7);
+        assert_eq!(processes[0].name, "node");
+        assert_eq!(processes[1].command_line, "bun run dev");
+    }
+
+    #[test]
+    fn test_list_prefers_exe_file_name_over_truncated_comm() {
+        let root = fixture("exe");
+        let dir = add_process(&root, 5, "very-long-proce", &["very-long-process-name"]);
+        std::os::unix::fs::symlink("/usr/bin/very-long-process-name", dir.join("exe")).unwrap();
+
+        let processes = ProcFsProcessProvider::with_root(&root)
+            .list_processes()
+            .unwrap();
+
+        fs::remove_dir_all(&root).ok();
+        assert_eq!(processes[0].name, "very-long-process-name");
+    }
+
+    #[test]
+    fn test_list_skips_unreadable_processes() {
+        let root = fixture("partial");
+        add_process(&root, 1, "init", &["/sbin/init"]);
+        fs::create_dir_all(root.join("2")).unwrap();
+
+        let processes = ProcFsProcessProvider::with_root(&root)
+            .list_processes()
+            .unwrap();
+
+        fs::remove_dir_all(&root).ok();
+        assert_eq!(processes.len(), 1);
+    }
+
+    #[test]
+    fn test_list_missing_root_fails() {
+        let provider = ProcFsProcessProvider::with_root("/nonexistent/wsm/proc");
+
+        assert!(provider.list_processes().is_err());
+    }
+
+    #[test]
+    fn test_list_real_proc_includes_current_process() {
+        if !Path::new("/proc/self").exists() {
+            return;
+        }
+        let processes = ProcFsProcessProvider::new().list_processes().unwrap();
+
+        assert!(processes.iter().any(|p| p.pid == std::process::id()));
+    }
+
+    #[test]
+    fn test_list_reads_parent_pid_from_stat() {
+        let root = fixture("stat");
+        let dir = add_process(&root, 30, "x", &["x"]);
+        fs::write(dir.join("stat"), "30 (evil) S 9 (name) R 17 30 0").unwrap();
+
+        let processes = ProcFsProcessProvider::with_root(&root)
+            .list_processes()
+            .unwrap();
+
+        fs::remove_dir_all(&root).ok();
+        assert_eq!(processes[0].parent_pid, Some(17));
+    }
+
+    #[test]
+    fn test_is_running_treats_zombies_as_exited() {
+        let root = fixture("zombie");
+        add_process(&root, 40, "alive", &["alive"]);
+        let zombie = add_process(&root, 41, "zombie", &["zombie"]);
+        fs::write(zombie.join("stat"), "41 (zombie) Z 1 41 0").unwrap();
+        let provider = ProcFsProcessProvider::with_root(&root);
+
+        let alive = provider.is_running(40).unwrap();
+        let dead = provider.is_running(41).unwrap();
+        let missing = provider.is_running(42).unwrap();
+
+        fs::remove_dir_all(&root).ok();
+        assert!(alive);
+        assert!(!dead);
+        assert!(!missing);
+    }
+
+    #[test]
+    fn test_list_reads_executable_path_and_start_time() {
+        let root = fixture("start");
+        let dir = add_process(&root, 50, "bun", &["bun", "run", "dev"]);
+        std::os::unix::fs::symlink("/usr/local/bin/bun", dir.join("exe")).unwrap();
+        // Started 0 ticks after boot, so the start time is the boot time
+        fs::write(
+            dir.join("stat"),
+            "50 (bun) S 1 50 50 0 -1 0 0 0 0 0 0 0 0 0 20 0 1 0 0 1000 10",
+        )
+        .unwrap();
+        fs::write(
+            root.join("stat"),
+            "cpu  1 2 3
+btime 1700000000
+",
+        )
+        .unwrap();
+
+        let processes = ProcFsProcessProvider::with_root(&root)
+            .list_processes()
+            .unwrap();
+
+        fs::remove_dir_all(&root).ok();
+        assert_eq!(
+            processes[0].executable_path.as_deref(),
+            Some("/usr/local/bin/bun")
+        );
+        assert_eq!(
+            processes[0].start_time,
+            chrono::DateTime::from_timestamp(1_700_000_000, 0)
+        );
+    }
+}
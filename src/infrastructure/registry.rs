@@ -1,79 +1,179 @@
-use crate::domain::{StartupEntry, StartupRepository};
+use super::approval_store::RegistryApprovalStore;
+use crate::domain::{
+    ApprovalStore, StartupApproval, StartupEntry, StartupLocation, StartupRepository,
+};
 use crate::shared::error::{Result, StartupError};
 use std::ffi::OsString;
+use std::io::{self, ErrorKind};
 use winreg::RegKey;
 use winreg::enums::*;
 
 /// Windows Registry implementation of the StartupRepository trait.
 /// This follows the Dependency Inversion Principle by implementing the domain trait.
+///
+/// The key is opened for each operation with only the access it needs, so
+/// keys of all users can be listed without administrator rights.
 pub struct WindowsRegistryRepository {
-    key: RegKey,
+    location: StartupLocation,
+    hive: RegKey,
+    path: &'static str,
+    /// The StartupApproved values of the key's entries.
+    approvals: Option<RegistryApprovalStore>,
 }
 
 impl WindowsRegistryRepository {
     /// Creates a new Windows Registry repository.
     /// Opens the registry key for startup programs for the current user.
     pub fn new() -> Result<Self> {
-        let path = r"SOFTWARE\Microsoft\Windows\CurrentVersion\Run";
-        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
-        let key = hkcu
-            .open_subkey_with_flags(path, KEY_ALL_ACCESS)
-            .map_err(|e| {
-                StartupError::RegistryError(format!(
-                    "Failed to open startup registry key '{}': {}",
-                    path, e
-                ))
+        Self::open(StartupLocation::CurrentUserRun)
+    }
+
+    /// Creates a repository for the registry key of `location`.
+    pub fn open(location: StartupLocation) -> Result<Self> {
+        let (hive, path) = location
+            .registry_key()
+            .and_then(|key| key.split_once('\\'))
+            .ok_or_else(|| {
+                StartupError::Unsupported(format!("{} is not a registry key", location))
             })?;
-        Ok(Self { key })
+        let hive = match hive {
+            "HKLM" => HKEY_LOCAL_MACHINE,
+            _ => HKEY_CURRENT_USER,
+        };
+        Ok(Self {
+            location,
+            hive: RegKey::predef(hive),
+            path,
+            approvals: RegistryApprovalStore::open(location),
+        })
+    }
+
+    /// Opens the key; `WOW6432Node` paths address the 32-bit view, so the
+    /// 64-bit view is requested even from 32-bit builds.
+    fn open_key(&self, access: u32) -> io::Result<RegKey> {
+        self.hive
+            .open_subkey_with_flags(self.path, access | KEY_WOW64_64KEY)
+    }
+
+    /// Reads the enabled state of an entry; entries without a
+    /// StartupApproved value are enabled.
+    fn approval(&self, name: &str) -> Result<StartupApproval> {
+        let approval = match &self.approvals {
+            Some(approvals) => approvals.get(name)?,
+            None => None,
+        };
+        Ok(approval.unwrap_or_default())
+    }
+
+    fn error(&self, e: io::Error, action: String) -> StartupError {
+        if e.kind() == ErrorKind::PermissionDenied {
+            StartupError::ElevationRequired(self.location.to_string())
+        } else {
+            StartupError::RegistryError(format!("{}: {}", action, e))
+        }
     }
 }
 
 impl StartupRepository for WindowsRegistryRepository {
     fn add(&self, entry: &StartupEntry) -> Result<()> {
-        self.key
-            .set_value(&entry.name, &OsString::from(&entry.command))
+        // RunOnce keys only exist while they hold a value
+        let (key, _) = self
+            .hive
+            .create_subkey_with_flags(self.path, KEY_SET_VALUE | KEY_WOW64_64KEY)
             .map_err(|e| {
-                StartupError::RegistryError(format!(
-                    "Failed to add entry '{}' to registry: {}",
-                    entry.name, e
-                ))
+                self.error(
+                    e,
+                    format!("Failed to open startup registry key '{}'", self.location),
+                )
+            })?;
+        key.set_value(&entry.name, &OsString::from(&entry.command))
+            .map_err(|e| {
+                self.error(
+                    e,
+                    format!("Failed to add entry '{}' to registry", entry.name),
+                )
             })?;
         Ok(())
     }
 
     fn remove(&self, name: &str) -> Result<()> {
-        self.key.delete_value(name).map_err(|e| {
-            StartupError::RegistryError(format!(
-                "Failed to remove entry '{}' from registry: {}",
-                name, e
-            ))
-        })?;
-        Ok(())
+        let result = self
+            .open_key(KEY_SET_VALUE)
+            .and_then(|key| key.delete_value(name));
+        match result {
+            Ok(()) => {
+                // Forget the enabled state too, so a new entry with this
+                // name starts enabled
+                if let Some(approvals) = &self.approvals {
+                    approvals.delete(name).ok();
+                }
+                Ok(())
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                Err(StartupError::EntryNotFound(name.to_string()))
+            }
+            Err(e) => Err(self.error(
+                e,
+                format!("Failed to remove entry '{}' from registry", name),
+            )),
+        }
     }
 
     fn list(&self) -> Result<Vec<StartupEntry>> {
-        let mut entries = Vec::new();
+        let key = match self.open_key(KEY_READ) {
+            Ok(key) => key,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(self.error(
+                    e,
+                    format!("Failed to open startup registry key '{}'", self.location),
+                ));
+            }
+        };
 
-        for item in self.key.enum_values() {
+        let mut entries = Vec::new();
+        for item in key.enum_values() {
             let (name, value) = item.map_err(|e| {
                 StartupError::RegistryError(format!("Failed to enumerate registry values: {}", e))
             })?;
 
             let command = value.to_string();
-            entries.push(StartupEntry::new(name, command));
+            let approval = self.approval(&name)?;
+            entries.push(
+                StartupEntry::new(name, command)
+                    .with_location(self.location)
+                    .with_approval(approval),
+            );
         }
 
         Ok(entries)
     }
 
     fn exists(&self, name: &str) -> Result<bool> {
-        match self.key.get_value::<String, _>(name) {
+        let result = self
+            .open_key(KEY_READ)
+            .and_then(|key| key.get_raw_value(name));
+        match result {
             Ok(_) => Ok(true),
-            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
-            Err(e) => Err(StartupError::RegistryError(format!(
-                "Failed to check if entry '{}' exists: {}",
-                name, e
-            ))),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(self.error(e, format!("Failed to check if entry '{}' exists", name))),
+        }
+    }
+
+    fn set_approval(&self, name: &str, approval: StartupApproval) -> Result<()> {
+        let approvals = self.approvals.as_ref().ok_or_else(|| {
+            StartupError::Unsupported(format!(
+                "entries in {} run once and cannot be enabled or disabled",
+                self.location
+            ))
+        })?;
+        if !self.exists(name)? {
+            return Err(StartupError::EntryNotFound(name.to_string()));
         }
+        approvals.set(name, approval)
+    }
+
+    fn supports_approval(&self, _location: StartupLocation) -> bool {
+        self.approvals.is_some()
     }
 }
@@ -0,0 +1,118 @@
+use crate::domain::{
+    StartupApproval, StartupEntry, StartupLocation, StartupRepository, TaskDefinition,
+    TaskScheduler,
+};
+use crate::shared::error::{Result, StartupError};
+
+/// Characters Task Scheduler does not allow in task names.
+const INVALID_TASK_NAME_CHARACTERS: &[char] = &['\\', '/', ':', '*', '?', '"', '<', '>', '|'];
+
+/// Task Scheduler implementation of the StartupRepository trait.
+/// Every task in the tool's folder is an entry named after the task, whose
+/// command is the task's XML definition. Only commands in the scheduled
+/// task mode can be added; tasks are enabled and disabled in Task Scheduler
+/// itself.
+pub struct ScheduledTaskRepository<S: TaskScheduler> {
+    scheduler: S,
+    user_id: Option<String>,
+}
+
+impl<S: TaskScheduler> ScheduledTaskRepository<S> {
+    /// Creates a repository registering tasks with `scheduler`.
+    pub fn new(scheduler: S) -> Self {
+        Self {
+            scheduler,
+            user_id: None,
+        }
+    }
+
+    /// Starts added tasks at the logon of `user_id` (`DOMAIN\user`) unless
+    /// their definition names a user.
+    pub fn with_user(mut self, user_id: impl Into<String>) -> Self {
+        self.user_id = Some(user_id.into());
+        self
+    }
+
+    /// Returns the scheduler the tasks are registered with.
+    pub fn scheduler(&self) -> &S {
+        &self.scheduler
+    }
+
+    /// The name of the task `name` refers to, ignoring case.
+    fn find(&self, name: &str) -> Result<Option<String>> {
+        Ok(self
+            .scheduler
+            .list()?
+            .into_iter()
+            .map(|(task, _)| task)
+            .find(|task| task.eq_ignore_ascii_case(name)))
+    }
+}
+
+impl<S: TaskScheduler> StartupRepository for ScheduledTaskRepository<S> {
+    fn add(&self, entry: &StartupEntry) -> Result<()> {
+        if entry.name.contains(INVALID_TASK_NAME_CHARACTERS) {
+            return Err(StartupError::InvalidName(format!(
+                "Scheduled task names cannot contain any of {}",
+                INVALID_TASK_NAME_CHARACTERS.iter().collect::<String>()
+            )));
+        }
+
+        let task = TaskDefinition::from_xml(&entry.command).map_err(|_| {
+            StartupError::Unsupported(format!(
+                "{} only holds commands added in the scheduled task mode",
+                StartupLocation::TaskScheduler
+            ))
+        })?;
+        let task = TaskDefinition {
+            user_id: task.user_id.or_else(|| self.user_id.clone()),
+            enabled: entry.enabled,
+            ..task
+        };
+
+        let name = self
+            .find(&entry.name)?
+            .unwrap_or_else(|| entry.name.clone());
+        self.scheduler.register(&name, &task.to_xml())
+    }
+
+    fn remove(&self, name: &str) -> Result<()> {
+        let task = self
+            .find(name)?
+            .ok_or_else(|| StartupError::EntryNotFound(name.to_string()))?;
+        self.scheduler.delete(&task).map(|_| ())
+    }
+
+    fn list(&self) -> Result<Vec<StartupEntry>> {
+        Ok(self
+            .scheduler
+            .list()?
+            .into_iter()
+            .map(|(name, xml)| {
+                // Tasks the tool cannot read still run, so they show as enabled
+                let enabled = TaskDefinition::from_xml(&xml).map_or(true, |task| task.enabled);
+                StartupEntry::new(name, xml)
+                    .with_location(StartupLocation::TaskScheduler)
+                    .with_approval(StartupApproval {
+                        enabled,
+                        disabled_at: None,
+                    })
+            })
+            .collect())
+    }
+
+    fn exists(&self, name: &str) -> Result<bool> {
+        Ok(self.find(name)?.is_some())
+    }
+
+    fn set_approval(&self, name: &str, approval: StartupApproval) -> Result<()> {
+        let task = self
+            .find(name)?
+            .ok_or_else(|| StartupError::EntryNotFound(name.to_string()))?;
+        self.scheduler.set_enabled(&task, approval.enabled)
+    }
+
+    fn supports_approval(&self, _location: StartupLocation) -> bool {
+        true
+    }
+}
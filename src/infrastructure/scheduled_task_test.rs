@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use crate::domain::{
+        StartupApproval, StartupEntry, StartupLocation, StartupRepository, TaskDefinition,
+        TaskScheduler,
+    };
+    use crate::shared::error::StartupError;
+
+    fn repository() -> ScheduledTaskRepository<InMemoryTaskScheduler> {
+        ScheduledTaskRepository::new(InMemoryTaskScheduler::new()).with_user(r"PC\dev")
+    }
+
+    fn task_entry(name: &str) -> StartupEntry {
+        StartupEntry::new(name, TaskDefinition::new(r"C:\app.exe").to_xml())
+    }
+
+    #[test]
+    fn test_add_registers_task_for_the_user() {
+        let repo = repository();
+
+        repo.add(&task_entry("App")).unwrap();
+
+        let task = TaskDefinition::from_xml(&repo.scheduler().task("App").unwrap()).unwrap();
+        assert_eq!(task.command, r"C:\app.exe");
+        assert_eq!(task.user_id.as_deref(), Some(r"PC\dev"));
+    }
+
+    #[test]
+    fn test_add_keeps_user_named_by_the_task() {
+        let repo = repository();
+        let task = TaskDefinition {
+            user_id: Some(r"PC\other".to_string()),
+            ..TaskDefinition::new("app.exe")
+        };
+
+        repo.add(&StartupEntry::new("App", task.to_xml())).unwrap();
+
+        let task = TaskDefinition::from_xml(&repo.scheduler().task("App").unwrap()).unwrap();
+        assert_eq!(task.user_id.as_deref(), Some(r"PC\other"));
+    }
+
+    #[test]
+    fn test_add_rejects_commands_that_are_not_tasks() {
+        let repo = repository();
+
+        let result = repo.add(&StartupEntry::new("App", r"C:\app.exe"));
+
+        assert!(matches!(result, Err(StartupError::Unsupported(_))));
+        assert!(repo.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_add_rejects_names_that_are_not_task_names() {
+        let result = repository().add(&task_entry(r"a\b"));
+
+        assert!(matches!(result, Err(StartupError::InvalidName(_))));
+    }
+
+    #[test]
+    fn test_add_replaces_task_ignoring_case() {
+        let repo = repository();
+        repo.add(&task_entry("App")).unwrap();
+
+        repo.add(&task_entry("APP")).unwrap();
+
+        let entries = repo.list().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "App");
+    }
+
+    #[test]
+    fn test_list_tags_entries_and_reads_enabled_state() {
+        let repo = repository();
+        repo.add(&task_entry("App")).unwrap();
+        repo.scheduler().set_enabled("App", false).unwrap();
+        repo.scheduler().register("Broken", "not xml").unwrap();
+
+        let entries = repo.list().unwrap();
+
+        assert_eq!(entries[0].name, "App");
+        assert_eq!(entries[0].location, StartupLocation::TaskScheduler);
+        assert!(!entries[0].enabled);
+        assert_eq!(entries[1].name, "Broken");
+        assert!(entries[1].enabled);
+    }
+
+    #[test]
+    fn test_remove_and_exists() {
+        let repo = repository();
+        repo.add(&task_entry("App")).unwrap();
+
+        assert!(repo.exists("app").unwrap());
+        repo.remove("app").unwrap();
+
+        assert!(!repo.exists("App").unwrap());
+        assert!(matches!(
+            repo.remove("App"),
+            Err(StartupError::EntryNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_set_approval_toggles_task() {
+        let repo = repository();
+        repo.add(&task_entry("App")).unwrap();
+
+        repo.set_approval("app", StartupApproval::disabled_since(chrono::Utc::now()))
+            .unwrap();
+        assert!(!repo.list().unwrap()[0].enabled);
+
+        repo.set_approval("App", StartupApproval::ENABLED).unwrap();
+        assert!(repo.list().unwrap()[0].enabled);
+        assert!(matches!(
+            repo.set_approval("Missing", StartupApproval::ENABLED),
+            Err(StartupError::EntryNotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,122 @@
+use crate::domain::{
+    EntrySnapshot, LauncherStore, SnapshotChange, StartupApproval, StartupEntry, StartupLocation,
+    StartupRepository,
+};
+use crate::shared::error::Result;
+use std::cell::RefCell;
+
+/// StartupRepository recording how every change made through it affected
+/// the entries of the wrapped repository.
+///
+/// Each operation captures the entries named like the one it changes before
+/// and after it runs, with the content of the launchers they run. Changes to
+/// the same entry in the same location are merged, so a command that
+/// replaces an entry and then disables it records a single change.
+pub struct SnapshotRepository<'a, R: StartupRepository, L: LauncherStore> {
+    repository: &'a R,
+    launchers: &'a L,
+    changes: RefCell<Vec<SnapshotChange>>,
+}
+
+impl<'a, R: StartupRepository, L: LauncherStore> SnapshotRepository<'a, R, L> {
+    pub fn new(repository: &'a R, launchers: &'a L) -> Self {
+        Self {
+            repository,
+            launchers,
+            changes: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Returns the changes made so far, leaving out entries that ended up
+    /// as they were.
+    pub fn changes(&self) -> Vec<SnapshotChange> {
+        self.changes
+            .borrow()
+            .iter()
+            .filter(|change| !change.is_empty())
+            .cloned()
+            .collect()
+    }
+
+    /// Runs `operation` and records how it changed the entries named `name`.
+    /// Changes are recorded even when the operation fails partway.
+    fn track(&self, name: &str, operation: impl FnOnce() -> Result<()>) -> Result<()> {
+        let before = self.snapshots(name)?;
+        let result = operation();
+        let after = match self.snapshots(name) {
+            Ok(after) => after,
+            Err(e) => return result.and(Err(e)),
+        };
+
+        let mut changes = self.changes.borrow_mut();
+        let mut locations: Vec<_> = before.iter().chain(&after).map(|s| s.location).collect();
+        locations.sort();
+        locations.dedup();
+        for location in locations {
+            let find = |snapshots: &[EntrySnapshot]| {
+                snapshots.iter().find(|s| s.location == location).cloned()
+            };
+            let (was, is) = (find(&before), find(&after));
+            if was == is {
+                continue;
+            }
+            let recorded = changes.iter_mut().find(|change| {
+                change.location() == location && change.name().eq_ignore_ascii_case(name)
+            });
+            match recorded {
+                Some(change) => change.after = is,
+                None => changes.push(SnapshotChange {
+                    before: was,
+                    after: is,
+                }),
+            }
+        }
+        result
+    }
+
+    fn snapshots(&self, name: &str) -> Result<Vec<EntrySnapshot>> {
+        self.repository
+            .list()?
+            .iter()
+            .filter(|entry| entry.name.eq_ignore_ascii_case(name))
+            .map(|entry| EntrySnapshot::capture(entry, self.launchers))
+            .collect()
+    }
+}
+
+impl<R: StartupRepository, L: LauncherStore> StartupRepository for SnapshotRepository<'_, R, L> {
+    fn add(&self, entry: &StartupEntry) -> Result<()> {
+        self.track(&entry.name, || self.repository.add(entry))
+    }
+
+    fn remove(&self, name: &str) -> Result<()> {
+        self.track(name, || self.repository.remove(name))
+    }
+
+    fn list(&self) -> Result<Vec<StartupEntry>> {
+        self.repository.list()
+    }
+
+    fn exists(&self, name: &str) -> Result<bool> {
+        self.repository.exists(name)
+    }
+
+    fn set_approval(&self, name: &str, approval: StartupApproval) -> Result<()> {
+        self.track(name, || self.repository.set_approval(name, approval))
+    }
+
+    fn supports_approval(&self, location: StartupLocation) -> bool {
+        self.repository.supports_approval(location)
+    }
+
+    fn update(&self, entry: &StartupEntry) -> Result<()> {
+        self.track(&entry.name, || self.repository.update(entry))
+    }
+
+    /// Records the entry leaving its old name and arriving at its new one.
+    fn rename(&self, name: &str, new_name: &str) -> Result<()> {
+        self.track(name, || {
+            self.track(new_name, || self.repository.rename(name, new_name))
+        })
+    }
+}
@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use crate::domain::{
+        BackupLauncher, LauncherStore, StartupApproval, StartupEntry, StartupLocation,
+        StartupRepository,
+    };
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_records_replaced_values_with_their_launchers() {
+        let repo = InMemoryRepository::new();
+        let launchers = InMemoryLauncherStore::new();
+        launchers.write("dev_1.vbs", "old").unwrap();
+        repo.seed(StartupEntry::new(
+            "Dev",
+            format!(
+                "wscript.exe //B //Nologo \"{}\"",
+                launchers.path_of("dev_1.vbs")
+            ),
+        ));
+        let recorder = SnapshotRepository::new(&repo, &launchers);
+
+        recorder.add(&StartupEntry::new("Dev", "dev.exe")).unwrap();
+
+        let changes = recorder.changes();
+        assert_eq!(changes.len(), 1);
+        let before = changes[0].before.as_ref().unwrap();
+        assert_eq!(
+            before.launcher,
+            Some(BackupLauncher {
+                filename: "dev_1.vbs".to_string(),
+                content: "old".to_string(),
+            })
+        );
+        assert_eq!(changes[0].after.as_ref().unwrap().value, "dev.exe");
+    }
+
+    #[test]
+    fn test_merges_changes_to_the_same_entry() {
+        let repo = InMemoryRepository::with_entries([("Dev", "old.exe")]);
+        let launchers = InMemoryLauncherStore::new();
+        let recorder = SnapshotRepository::new(&repo, &launchers);
+        let disabled_at = Utc.with_ymd_and_hms(2026, 3, 1, 8, 30, 0).unwrap();
+
+        recorder.add(&StartupEntry::new("Dev", "new.exe")).unwrap();
+        recorder
+            .set_approval("Dev", StartupApproval::disabled_since(disabled_at))
+            .unwrap();
+        recorder.add(&StartupEntry::new("Tmp", "tmp.exe")).unwrap();
+        recorder.remove("Tmp").unwrap();
+
+        let changes = recorder.changes();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].before.as_ref().unwrap().value, "old.exe");
+        let after = changes[0].after.as_ref().unwrap();
+        assert_eq!((after.value.as_str(), after.enabled), ("new.exe", false));
+    }
+
+    #[test]
+    fn test_records_each_location_of_a_moved_entry() {
+        let repo = CompositeRepository::new()
+            .with(
+                StartupLocation::CurrentUserRun,
+                InMemoryRepository::with_entries([("Dev", "dev.exe")]),
+            )
+            .with(StartupLocation::LocalMachineRun, InMemoryRepository::new());
+        let launchers = InMemoryLauncherStore::new();
+        let recorder = SnapshotRepository::new(&repo, &launchers);
+
+        recorder.remove("Dev").unwrap();
+        recorder
+            .add(
+                &StartupEntry::new("Dev", "dev.exe")
+                    .with_location(StartupLocation::LocalMachineRun),
+            )
+            .unwrap();
+
+        let changes: Vec<_> = recorder
+            .changes()
+            .iter()
+            .map(|c| (c.location(), c.before.is_some(), c.after.is_some()))
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                (StartupLocation::CurrentUserRun, true, false),
+                (StartupLocation::LocalMachineRun, false, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_records_changes_of_failed_operations_only_when_they_happened() {
+        let repo = InMemoryRepository::new();
+        repo.fail_on(RepositoryOperation::Add, "access denied");
+        let launchers = InMemoryLauncherStore::new();
+        let recorder = SnapshotRepository::new(&repo, &launchers);
+
+        assert!(recorder.add(&StartupEntry::new("Dev", "dev.exe")).is_err());
+        assert!(recorder.changes().is_empty());
+    }
+}
@@ -0,0 +1,260 @@
+#[cfg(windows)]
+use super::approval_store::RegistryApprovalStore;
+use crate::domain::models::expand_environment;
+use crate::domain::{
+    ApprovalStore, ExecutionMode, ShellLink, ShowCommand, StartupApproval, StartupCommand,
+    StartupEntry, StartupLocation, StartupRepository, WindowStyle, cmdline,
+};
+use crate::shared::error::{Result, StartupError};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// Characters Windows does not allow in file names.
+const INVALID_FILE_NAME_CHARACTERS: &[char] = &['\\', '/', ':', '*', '?', '"', '<', '>', '|'];
+
+/// Full paths of the programs the launchers of other execution modes run.
+const SYSTEM_PROGRAMS: &[(&str, &str)] = &[
+    ("wscript.exe", r"%SystemRoot%\System32\wscript.exe"),
+    ("cmd.exe", r"%SystemRoot%\System32\cmd.exe"),
+    (
+        "powershell.exe",
+        r"%SystemRoot%\System32\WindowsPowerShell\v1.0\powershell.exe",
+    ),
+];
+
+/// Startup folder implementation of the StartupRepository trait.
+/// Every file in the folder is an entry named after the file without its
+/// extension. Shortcuts (`.lnk`) run their target with its arguments; any
+/// other file is opened by Explorer, so its command is the file's path.
+/// Entries are added as shortcuts. Their enabled state is kept in an
+/// approval store, under the file's name.
+pub struct StartupFolderRepository {
+    dir: PathBuf,
+    location: StartupLocation,
+    approvals: Option<Box<dyn ApprovalStore>>,
+}
+
+impl StartupFolderRepository {
+    /// Creates a repository for the folder `dir`, whose entries are tagged
+    /// with `location`.
+    pub fn new(dir: impl Into<PathBuf>, location: StartupLocation) -> Self {
+        Self {
+            dir: dir.into(),
+            location,
+            approvals: None,
+        }
+    }
+
+    /// Keeps the enabled state of the entries in `approvals`. Without a
+    /// store, entries are always enabled.
+    pub fn with_approvals(mut self, approvals: impl ApprovalStore + 'static) -> Self {
+        self.approvals = Some(Box::new(approvals));
+        self
+    }
+
+    /// Opens the startup folder of `location`, with the StartupApproved
+    /// values Task Manager keeps for it.
+    pub fn open(location: StartupLocation) -> Result<Self> {
+        let folder = location.startup_folder().ok_or_else(|| {
+            StartupError::Unsupported(format!("{} is not a startup folder", location))
+        })?;
+        let repository = Self::new(expand_environment(folder), location);
+        #[cfg(windows)]
+        let repository = match RegistryApprovalStore::open(location) {
+            Some(approvals) => repository.with_approvals(approvals),
+            None => repository,
+        };
+        Ok(repository)
+    }
+
+    /// Returns the folder this repository manages.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Lists the entry files as `(name, path)` pairs, sorted by name.
+    fn files(&self) -> Result<Vec<(String, PathBuf)>> {
+        let read_dir = match fs::read_dir(&self.dir) {
+            Ok(read_dir) => read_dir,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut files = Vec::new();
+        for item in read_dir {
+            let path = item?.path();
+            // Explorer keeps the folder's display settings in desktop.ini
+            let is_settings = path
+                .file_name()
+                .is_some_and(|f| f.eq_ignore_ascii_case("desktop.ini"));
+            if !path.is_file() || is_settings {
+                continue;
+            }
+            if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                files.push((stem.to_string(), path));
+            }
+        }
+        files.sort_by_key(|(name, _)| name.to_lowercase());
+        Ok(files)
+    }
+
+    fn find(&self, name: &str) -> Result<Option<PathBuf>> {
+        Ok(self
+            .files()?
+            .into_iter()
+            .find(|(stem, _)| stem.eq_ignore_ascii_case(name))
+            .map(|(_, path)| path))
+    }
+
+    /// Reads the enabled state of an entry file; files without a
+    /// StartupApproved value are enabled.
+    fn approval(&self, path: &Path) -> Result<StartupApproval> {
+        let approval = match &self.approvals {
+            Some(approvals) => approvals.get(&Self::file_name(path))?,
+            None => None,
+        };
+        Ok(approval.unwrap_or_default())
+    }
+
+    /// The name StartupApproved values of `path` are stored under.
+    fn file_name(path: &Path) -> String {
+        path.file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    /// Returns the command an entry file runs.
+    fn command_of(path: &Path) -> String {
+        let is_link = path
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case("lnk"));
+        if is_link
+            && let Ok(link) = fs::read(path)
+                .map_err(StartupError::from)
+                .and_then(|bytes| ShellLink::from_bytes(&bytes))
+        {
+            return link.command_line();
+        }
+        path.to_string_lossy().into_owned()
+    }
+
+    /// Builds the shortcut running a registry-style command line.
+    fn link_for(command: &str) -> ShellLink {
+        let (program, arguments) = cmdline::split_program(command);
+        // Shortcuts need a full path where the Run key searches the PATH
+        let target = SYSTEM_PROGRAMS
+            .iter()
+            .find(|(name, _)| program.eq_ignore_ascii_case(name))
+            .map_or(program, |(_, path)| path.to_string());
+        let decoded = StartupCommand::parse(command);
+        ShellLink {
+            arguments: arguments.to_string(),
+            working_dir: decoded
+                .as_ref()
+                .and_then(|c| c.workdir())
+                .map(str::to_string),
+            // Keeps the PowerShell window out of the way until it hides itself
+            show_command: match decoded.and_then(|c| c.mode()) {
+                Some(ExecutionMode::PowerShell(options))
+                    if options.window != WindowStyle::Normal =>
+                {
+                    ShowCommand::Minimized
+                }
+                _ => ShowCommand::Normal,
+            },
+            ..ShellLink::new(target)
+        }
+    }
+
+    fn error(&self, e: std::io::Error) -> StartupError {
+        match e.kind() {
+            ErrorKind::PermissionDenied => {
+                StartupError::ElevationRequired(self.location.to_string())
+            }
+            _ => StartupError::IoError(e),
+        }
+    }
+}
+
+impl StartupRepository for StartupFolderRepository {
+    fn add(&self, entry: &StartupEntry) -> Result<()> {
+        if entry.name.contains(INVALID_FILE_NAME_CHARACTERS) {
+            return Err(StartupError::InvalidName(format!(
+                "Startup folder entries cannot contain any of {}",
+                INVALID_FILE_NAME_CHARACTERS.iter().collect::<String>()
+            )));
+        }
+
+        let previous = self.find(&entry.name)?;
+        let path = self.dir.join(format!("{}.lnk", entry.name));
+        let link = Self::link_for(&entry.command);
+
+        fs::create_dir_all(&self.dir).map_err(|e| self.error(e))?;
+        fs::write(&path, link.to_bytes()).map_err(|e| self.error(e))?;
+
+        // Replace an entry stored in a file of another type, keeping its
+        // enabled state
+        if let Some(previous) = previous
+            && !previous
+                .to_string_lossy()
+                .eq_ignore_ascii_case(&path.to_string_lossy())
+        {
+            fs::remove_file(&previous).map_err(|e| self.error(e))?;
+            if let Some(approvals) = &self.approvals
+                && let Some(approval) = approvals.get(&Self::file_name(&previous))?
+            {
+                approvals.set(&Self::file_name(&path), approval)?;
+                approvals.delete(&Self::file_name(&previous)).ok();
+            }
+        }
+        Ok(())
+    }
+
+    fn remove(&self, name: &str) -> Result<()> {
+        let path = self
+            .find(name)?
+            .ok_or_else(|| StartupError::EntryNotFound(name.to_string()))?;
+        fs::remove_file(&path).map_err(|e| self.error(e))?;
+
+        // Forget the enabled state too, so a new entry with this name
+        // starts enabled
+        if let Some(approvals) = &self.approvals {
+            approvals.delete(&Self::file_name(&path)).ok();
+        }
+        Ok(())
+    }
+
+    fn list(&self) -> Result<Vec<StartupEntry>> {
+        self.files()?
+            .into_iter()
+            .map(|(name, path)| {
+                Ok(StartupEntry::new(name, Self::command_of(&path))
+                    .with_location(self.location)
+                    .with_approval(self.approval(&path)?))
+            })
+            .collect()
+    }
+
+    fn exists(&self, name: &str) -> Result<bool> {
+        Ok(self.find(name)?.is_some())
+    }
+
+    fn set_approval(&self, name: &str, approval: StartupApproval) -> Result<()> {
+        let approvals = self.approvals.as_ref().ok_or_else(|| {
+            StartupError::Unsupported(format!(
+                "entries in {} cannot be enabled or disabled on this system",
+                self.location
+            ))
+        })?;
+        let path = self
+            .find(name)?
+            .ok_or_else(|| StartupError::EntryNotFound(name.to_string()))?;
+
+        approvals.set(&Self::file_name(&path), approval)
+    }
+
+    fn supports_approval(&self, _location: StartupLocation) -> bool {
+        self.approvals.is_some()
+    }
+}
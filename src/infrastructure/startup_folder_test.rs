@@ -0,0 +1,218 @@
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use crate::domain::{
+        ApprovalStore, ShellLink, StartupApproval, StartupEntry, StartupLocation, StartupRepository,
+    };
+    use crate::shared::error::StartupError;
+    use std::fs;
+
+    fn temp_folder(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("wsm_startup_folder_{}", name));
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_list_names_entries_after_files() {
+        let dir = temp_folder("list");
+        fs::write(dir.join("Zeta.lnk"), "").unwrap();
+        fs::write(dir.join("alpha.bat"), "").unwrap();
+        fs::write(dir.join("desktop.ini"), "").unwrap();
+        fs::create_dir(dir.join("nested")).unwrap();
+        let repo = StartupFolderRepository::new(&dir, StartupLocation::UserStartupFolder);
+
+        let entries = repo.list().unwrap();
+
+        fs::remove_dir_all(&dir).ok();
+        let names: Vec<_> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["alpha", "Zeta"]);
+        assert!(entries[0].command.ends_with("alpha.bat"));
+        assert!(
+            entries
+                .iter()
+                .all(|e| e.location == StartupLocation::UserStartupFolder)
+        );
+    }
+
+    #[test]
+    fn test_missing_folder_is_empty() {
+        let dir = std::env::temp_dir().join("wsm_startup_folder_missing");
+        fs::remove_dir_all(&dir).ok();
+        let repo = StartupFolderRepository::new(&dir, StartupLocation::CommonStartupFolder);
+
+        assert!(repo.list().unwrap().is_empty());
+        assert!(!repo.exists("App").unwrap());
+    }
+
+    #[test]
+    fn test_remove_deletes_file_ignoring_case() {
+        let dir = temp_folder("remove");
+        fs::write(dir.join("App.lnk"), "").unwrap();
+        let repo = StartupFolderRepository::new(&dir, StartupLocation::UserStartupFolder);
+
+        repo.remove("app").unwrap();
+        let missing = repo.remove("app");
+
+        let left = dir.join("App.lnk").exists();
+        fs::remove_dir_all(&dir).ok();
+        assert!(!left);
+        assert!(matches!(missing, Err(StartupError::EntryNotFound(_))));
+    }
+
+    #[test]
+    fn test_open_rejects_registry_locations() {
+        let result = StartupFolderRepository::open(StartupLocation::CurrentUserRun);
+
+        assert!(matches!(result, Err(StartupError::Unsupported(_))));
+    }
+
+    #[test]
+    fn test_add_writes_shortcut_listed_with_its_command() {
+        let dir = temp_folder("add");
+        let repo = StartupFolderRepository::new(&dir, StartupLocation::UserStartupFolder);
+
+        repo.add(&StartupEntry::new(
+            "App",
+            r#""C:\Program Files\App\app.exe" --name "a b""#,
+        ))
+        .unwrap();
+
+        let entries = repo.list().unwrap();
+        let link = ShellLink::from_bytes(&fs::read(dir.join("App.lnk")).unwrap()).unwrap();
+        fs::remove_dir_all(&dir).ok();
+        assert_eq!(
+            entries[0].command,
+            r#""C:\Program Files\App\app.exe" --name "a b""#
+        );
+        assert_eq!(link.target, r"C:\Program Files\App\app.exe");
+        assert_eq!(link.arguments, r#"--name "a b""#);
+    }
+
+    #[test]
+    fn test_add_resolves_launcher_programs() {
+        let dir = temp_folder("add_launcher");
+        let repo = StartupFolderRepository::new(&dir, StartupLocation::UserStartupFolder);
+
+        repo.add(&StartupEntry::new(
+            "Dev",
+            r#"cmd.exe /c "cd /d "C:\dev" && bun run dev""#,
+        ))
+        .unwrap();
+
+        let link = ShellLink::from_bytes(&fs::read(dir.join("Dev.lnk")).unwrap()).unwrap();
+        fs::remove_dir_all(&dir).ok();
+        assert_eq!(link.target, r"%SystemRoot%\System32\cmd.exe");
+        assert_eq!(link.arguments, r#"/c "cd /d "C:\dev" && bun run dev""#);
+        assert_eq!(link.working_dir.as_deref(), Some(r"C:\dev"));
+    }
+
+    #[test]
+    fn test_add_replaces_file_of_another_type() {
+        let dir = temp_folder("replace");
+        fs::write(dir.join("app.bat"), "@echo off").unwrap();
+        let repo = StartupFolderRepository::new(&dir, StartupLocation::UserStartupFolder);
+
+        repo.add(&StartupEntry::new("App", r"C:\app.exe")).unwrap();
+
+        let entries = repo.list().unwrap();
+        let bat_left = dir.join("app.bat").exists();
+        fs::remove_dir_all(&dir).ok();
+        assert!(!bat_left);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].command, r"C:\app.exe");
+    }
+
+    #[test]
+    fn test_add_rejects_names_that_are_not_file_names() {
+        let dir = temp_folder("invalid_name");
+        let repo = StartupFolderRepository::new(&dir, StartupLocation::UserStartupFolder);
+
+        let result = repo.add(&StartupEntry::new("a/b", r"C:\app.exe"));
+
+        fs::remove_dir_all(&dir).ok();
+        assert!(matches!(result, Err(StartupError::InvalidName(_))));
+    }
+
+    #[test]
+    fn test_list_reads_shortcuts_of_other_tools() {
+        let dir = temp_folder("fixture");
+        fs::write(
+            dir.join("Example.lnk"),
+            include_bytes!("../../tests/fixtures/shortcut_idlist.lnk"),
+        )
+        .unwrap();
+        let repo = StartupFolderRepository::new(&dir, StartupLocation::UserStartupFolder);
+
+        let entries = repo.list().unwrap();
+
+        fs::remove_dir_all(&dir).ok();
+        assert_eq!(
+            entries[0].command,
+            r#""C:\Program Files\Example App\example.exe" -q"#
+        );
+    }
+
+    #[test]
+    fn test_enabled_state_is_kept_under_the_file_name() {
+        let dir = temp_folder("approval");
+        fs::write(dir.join("Tray.exe"), "").unwrap();
+        let approvals = InMemoryApprovalStore::new();
+        approvals
+            .set(
+                "Tray.exe",
+                StartupApproval::disabled_since(chrono::Utc::now()),
+            )
+            .unwrap();
+        let repo = StartupFolderRepository::new(&dir, StartupLocation::UserStartupFolder)
+            .with_approvals(approvals);
+
+        let listed = repo.list().unwrap();
+        repo.add(&StartupEntry::new("App", r"C:pp.exe")).unwrap();
+        repo.set_approval("app", StartupApproval::disabled_since(chrono::Utc::now()))
+            .unwrap();
+        let disabled = repo.list().unwrap();
+        repo.remove("App").unwrap();
+        repo.add(&StartupEntry::new("App", r"C:pp.exe")).unwrap();
+        let readded = repo.list().unwrap();
+
+        fs::remove_dir_all(&dir).ok();
+        assert!(!listed[0].enabled);
+        assert!(disabled.iter().all(|e| !e.enabled));
+        assert!(readded.iter().find(|e| e.name == "App").unwrap().enabled);
+    }
+
+    #[test]
+    fn test_replacing_a_file_keeps_its_enabled_state() {
+        let dir = temp_folder("approval_replace");
+        fs::write(dir.join("app.bat"), "@echo off").unwrap();
+        let approvals = InMemoryApprovalStore::new();
+        approvals
+            .set(
+                "app.bat",
+                StartupApproval::disabled_since(chrono::Utc::now()),
+            )
+            .unwrap();
+        let repo = StartupFolderRepository::new(&dir, StartupLocation::UserStartupFolder)
+            .with_approvals(approvals);
+
+        repo.add(&StartupEntry::new("App", r"C:pp.exe")).unwrap();
+
+        let entries = repo.list().unwrap();
+        fs::remove_dir_all(&dir).ok();
+        assert!(!entries[0].enabled);
+    }
+
+    #[test]
+    fn test_set_approval_without_store_is_unsupported() {
+        let dir = temp_folder("approval_unsupported");
+        fs::write(dir.join("App.lnk"), "").unwrap();
+        let repo = StartupFolderRepository::new(&dir, StartupLocation::UserStartupFolder);
+
+        let result = repo.set_approval("App", StartupApproval::ENABLED);
+
+        fs::remove_dir_all(&dir).ok();
+        assert!(matches!(result, Err(StartupError::Unsupported(_))));
+    }
+}
@@ -0,0 +1,245 @@
+use super::csv::{decode_output, parse_csv};
+#[cfg(windows)]
+use crate::domain::TASK_FOLDER;
+use crate::domain::{TaskDefinition, TaskScheduler};
+use crate::shared::error::{Result, StartupError};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+/// Extracts the names of the tasks in `folder` from the output of
+/// `schtasks /Query /FO CSV /NH`, whose first column is the full task path.
+/// Tasks with several triggers are listed once per trigger; names are
+/// returned once each, sorted.
+pub fn parse_task_listing(output: &[u8], folder: &str) -> Vec<String> {
+    let mut names: Vec<String> = parse_csv(&decode_output(output))
+        .into_iter()
+        .filter_map(|record| {
+            let path = record.into_iter().next()?;
+            let prefix = path.get(..folder.len())?;
+            let name = &path[folder.len()..];
+            (prefix.eq_ignore_ascii_case(folder) && !name.is_empty() && !name.contains('\\'))
+                .then(|| name.to_string())
+        })
+        .collect();
+    names.sort_by_key(|name| name.to_lowercase());
+    names.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    names
+}
+
+/// Encodes an XML document the way `schtasks /Create /XML` expects it:
+/// UTF-16LE with a byte order mark.
+pub fn encode_task_file(xml: &str) -> Vec<u8> {
+    let mut bytes = vec![0xFF, 0xFE];
+    bytes.extend(xml.encode_utf16().flat_map(u16::to_le_bytes));
+    bytes
+}
+
+/// In-memory implementation of the TaskScheduler trait.
+#[derive(Debug, Default)]
+pub struct InMemoryTaskScheduler {
+    tasks: RefCell<BTreeMap<String, String>>,
+}
+
+impl InMemoryTaskScheduler {
+    /// Creates a scheduler without tasks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the XML definition of a task.
+    pub fn task(&self, name: &str) -> Option<String> {
+        self.key(name)
+            .and_then(|key| self.tasks.borrow().get(&key).cloned())
+    }
+
+    /// The stored name matching `name`, ignoring case.
+    fn key(&self, name: &str) -> Option<String> {
+        self.tasks
+            .borrow()
+            .keys()
+            .find(|key| key.eq_ignore_ascii_case(name))
+            .cloned()
+    }
+}
+
+impl TaskScheduler for InMemoryTaskScheduler {
+    fn list(&self) -> Result<Vec<(String, String)>> {
+        let mut tasks: Vec<_> = self
+            .tasks
+            .borrow()
+            .iter()
+            .map(|(name, xml)| (name.clone(), xml.clone()))
+            .collect();
+        tasks.sort_by_key(|(name, _)| name.to_lowercase());
+        Ok(tasks)
+    }
+
+    fn register(&self, name: &str, xml: &str) -> Result<()> {
+        let key = self.key(name).unwrap_or_else(|| name.to_string());
+        self.tasks.borrow_mut().insert(key, xml.to_string());
+        Ok(())
+    }
+
+    fn delete(&self, name: &str) -> Result<bool> {
+        Ok(match self.key(name) {
+            Some(key) => self.tasks.borrow_mut().remove(&key).is_some(),
+            None => false,
+        })
+    }
+
+    fn set_enabled(&self, name: &str, enabled: bool) -> Result<()> {
+        let key = self
+            .key(name)
+            .ok_or_else(|| StartupError::EntryNotFound(name.to_string()))?;
+        let mut tasks = self.tasks.borrow_mut();
+        let task = TaskDefinition {
+            enabled,
+            ..TaskDefinition::from_xml(&tasks[&key])?
+        };
+        tasks.insert(key, task.to_xml());
+        Ok(())
+    }
+}
+
+/// `schtasks.exe` implementation of the TaskScheduler trait, managing the
+/// tasks in [`TASK_FOLDER`].
+/// Listing takes one `schtasks` run per task, so the listing is kept until
+/// the scheduler changes a task.
+#[cfg(windows)]
+#[derive(Debug, Default)]
+pub struct SchtasksTaskScheduler {
+    tasks: RefCell<Option<Vec<(String, String)>>>,
+}
+
+#[cfg(windows)]
+impl SchtasksTaskScheduler {
+    /// Creates a scheduler backed by `schtasks.exe`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn path(name: &str) -> String {
+        format!("{}{}", TASK_FOLDER, name)
+    }
+
+    fn run(args: &[&str]) -> Result<Vec<u8>> {
+        let output = std::process::Command::new("schtasks.exe")
+            .args(args)
+            .output()
+            .map_err(|e| {
+                StartupError::TaskSchedulerError(format!("Failed to run schtasks: {}", e))
+            })?;
+
+        if !output.status.success() {
+            let message = decode_console_output(&output.stderr).trim().to_string();
+            if is_access_denied(&message) {
+                return Err(StartupError::ElevationRequired(
+                    crate::domain::StartupLocation::TaskScheduler.to_string(),
+                ));
+            }
+            return Err(StartupError::TaskSchedulerError(message));
+        }
+        Ok(output.stdout)
+    }
+
+    fn names(&self) -> Result<Vec<String>> {
+        // Listing every folder avoids the error schtasks reports for a
+        // folder that doesn't exist yet
+        let output = Self::run(&["/Query", "/FO", "CSV", "/NH"])?;
+        Ok(parse_task_listing(&output, TASK_FOLDER))
+    }
+
+    /// Runs a command changing a task, then drops the cached listing.
+    fn change(&self, args: &[&str]) -> Result<Vec<u8>> {
+        let result = Self::run(args);
+        self.tasks.borrow_mut().take();
+        result
+    }
+}
+
+/// Decodes error output of `schtasks`, which is written in the console's
+/// OEM code page when redirected.
+#[cfg(windows)]
+fn decode_console_output(bytes: &[u8]) -> String {
+    use windows_sys::Win32::Globalization::{CP_OEMCP, MultiByteToWideChar};
+
+    let Ok(len) = i32::try_from(bytes.len()) else {
+        return decode_output(bytes);
+    };
+    // SAFETY: a null output buffer asks for the required length.
+    let units =
+        unsafe { MultiByteToWideChar(CP_OEMCP, 0, bytes.as_ptr(), len, std::ptr::null_mut(), 0) };
+    if units <= 0 {
+        return decode_output(bytes);
+    }
+    let mut buffer = vec![0u16; units as usize];
+    // SAFETY: the buffer holds the `units` UTF-16 units asked for above.
+    let written = unsafe {
+        MultiByteToWideChar(CP_OEMCP, 0, bytes.as_ptr(), len, buffer.as_mut_ptr(), units)
+    };
+    String::from_utf16_lossy(&buffer[..written.max(0) as usize])
+}
+
+/// Checks whether `schtasks` failed because access was denied. It exits
+/// with 1 for every error, but prints the system's own text of the error
+/// in the user's language, so that text is looked up the same way.
+#[cfg(windows)]
+fn is_access_denied(message: &str) -> bool {
+    use windows_sys::Win32::Foundation::ERROR_ACCESS_DENIED;
+
+    let text = std::io::Error::from_raw_os_error(ERROR_ACCESS_DENIED as i32).to_string();
+    let text = text.split(" (os error").next().unwrap_or_default().trim();
+    !text.is_empty() && message.contains(text)
+}
+
+#[cfg(windows)]
+impl TaskScheduler for SchtasksTaskScheduler {
+    fn list(&self) -> Result<Vec<(String, String)>> {
+        if let Some(tasks) = self.tasks.borrow().as_ref() {
+            return Ok(tasks.clone());
+        }
+        let tasks = self
+            .names()?
+            .into_iter()
+            .map(|name| {
+                let xml = Self::run(&["/Query", "/TN", &Self::path(&name), "/XML"])?;
+                Ok((name, decode_output(&xml)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        *self.tasks.borrow_mut() = Some(tasks.clone());
+        Ok(tasks)
+    }
+
+    fn register(&self, name: &str, xml: &str) -> Result<()> {
+        let file = std::env::temp_dir().join(format!("wsm-task-{}.xml", std::process::id()));
+        std::fs::write(&file, encode_task_file(xml))?;
+        let result = self.change(&[
+            "/Create",
+            "/TN",
+            &Self::path(name),
+            "/XML",
+            &file.to_string_lossy(),
+            "/F",
+        ]);
+        std::fs::remove_file(&file).ok();
+        result.map(|_| ())
+    }
+
+    fn delete(&self, name: &str) -> Result<bool> {
+        if !self
+            .list()?
+            .iter()
+            .any(|(n, _)| n.eq_ignore_ascii_case(name))
+        {
+            return Ok(false);
+        }
+        self.change(&["/Delete", "/TN", &Self::path(name), "/F"])
+            .map(|_| true)
+    }
+
+    fn set_enabled(&self, name: &str, enabled: bool) -> Result<()> {
+        let switch = if enabled { "/ENABLE" } else { "/DISABLE" };
+        self.change(&["/Change", "/TN", &Self::path(name), switch])
+            .map(|_| ())
+    }
+}
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use super::super::task_scheduler::*;
+    use crate::domain::{TASK_FOLDER, TaskDefinition, TaskScheduler};
+    use crate::shared::error::StartupError;
+
+    #[test]
+    fn test_listing_keeps_tasks_of_the_folder_once() {
+        let output = "\"\\Microsoft\\Windows\\Defrag\\ScheduledDefrag\",\"N/A\",\"Ready\"\r\n\
+                      \"\\windows_startup_manager\\Sync\",\"N/A\",\"Ready\"\r\n\
+                      \"\\windows_startup_manager\\Sync\",\"N/A\",\"Ready\"\r\n\
+                      \"\\Windows_Startup_Manager\\dev server\",\"N/A\",\"Disabled\"\r\n\
+                      \"\\windows_startup_manager\\nested\\Other\",\"N/A\",\"Ready\"\r\n\
+                      \"\\TopLevel\",\"N/A\",\"Ready\"\r\n";
+
+        assert_eq!(
+            parse_task_listing(output.as_bytes(), TASK_FOLDER),
+            vec!["dev server", "Sync"]
+        );
+    }
+
+    #[test]
+    fn test_listing_reads_utf16_output() {
+        let output =
+            encode_task_file("\"\\windows_startup_manager\\Ünïcode\",\"N/A\",\"Ready\"\r\n");
+
+        assert_eq!(parse_task_listing(&output, TASK_FOLDER), vec!["Ünïcode"]);
+    }
+
+    #[test]
+    fn test_task_file_is_utf16le_with_bom() {
+        assert_eq!(encode_task_file("<a/>"), b"\xFF\xFE<\0a\0/\0>\0");
+    }
+
+    #[test]
+    fn test_in_memory_scheduler_ignores_case() {
+        let scheduler = InMemoryTaskScheduler::new();
+        let xml = TaskDefinition::new("app.exe").to_xml();
+
+        scheduler.register("App", &xml).unwrap();
+        scheduler.register("APP", &xml).unwrap();
+
+        assert_eq!(scheduler.list().unwrap().len(), 1);
+        assert_eq!(scheduler.list().unwrap()[0].0, "App");
+        assert!(scheduler.delete("app").unwrap());
+        assert!(!scheduler.delete("app").unwrap());
+    }
+
+    #[test]
+    fn test_in_memory_scheduler_rewrites_enabled_state() {
+        let scheduler = InMemoryTaskScheduler::new();
+        scheduler
+            .register("App", &TaskDefinition::new("app.exe").to_xml())
+            .unwrap();
+
+        scheduler.set_enabled("app", false).unwrap();
+
+        let task = TaskDefinition::from_xml(&scheduler.task("App").unwrap()).unwrap();
+        assert!(!task.enabled);
+        assert!(matches!(
+            scheduler.set_enabled("missing", true),
+            Err(StartupError::EntryNotFound(_))
+        ));
+    }
+}
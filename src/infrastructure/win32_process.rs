@@ -0,0 +1,565 @@
+use crate::domain::{ProcessInfo, ProcessProvider};
+use crate::shared::error::{Result, StartupError};
+use crate::shared::time::from_filetime;
+use chrono::{DateTime, Utc};
+use std::ffi::c_void;
+use std::mem;
+use std::ptr;
+use windows_sys::Wdk::System::Threading::{NtQueryInformationProcess, ProcessBasicInformation};
+use windows_sys::Win32::Foundation::{
+    BOOL, CloseHandle, ERROR_INVALID_PARAMETER, FILETIME, GENERIC_READ, GENERIC_WRITE, HANDLE,
+    HWND, INVALID_HANDLE_VALUE, LPARAM, STILL_ACTIVE,
+};
+use windows_sys::Win32::Storage::FileSystem::{
+    CreateFileW, FILE_SHARE_READ, FILE_SHARE_WRITE, OPEN_EXISTING,
+};
+use windows_sys::Win32::System::Console::{
+    AttachConsole, CTRL_BREAK_EVENT, FreeConsole, GenerateConsoleCtrlEvent, GetConsoleMode,
+    GetConsoleProcessList, GetStdHandle, STD_ERROR_HANDLE, STD_HANDLE, STD_INPUT_HANDLE,
+    STD_OUTPUT_HANDLE, SetConsoleCtrlHandler, SetStdHandle,
+};
+use windows_sys::Win32::System::Diagnostics::Debug::ReadProcessMemory;
+use windows_sys::Win32::System::Diagnostics::ToolHelp::{
+    CreateToolhelp32Snapshot, PROCESSENTRY32W, Process32FirstW, Process32NextW, TH32CS_SNAPPROCESS,
+};
+use windows_sys::Win32::System::Threading::{
+    GetCurrentProcessId, GetExitCodeProcess, GetProcessTimes, OpenProcess,
+    PROCESS_BASIC_INFORMATION, PROCESS_NAME_WIN32, PROCESS_QUERY_INFORMATION,
+    PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_TERMINATE, PROCESS_VM_READ,
+    QueryFullProcessImageNameW, TerminateProcess,
+};
+use windows_sys::Win32::UI::WindowsAndMessaging::{
+    EnumWindows, GetWindowThreadProcessId, PostMessageW, WM_CLOSE,
+};
+
+/// Layout of `UNICODE_STRING` in the target process.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[allow(dead_code)]
+struct UnicodeString {
+    length: u16,
+    maximum_length: u16,
+    buffer: *const u16,
+}
+
+/// Leading fields of the `PEB`, up to `ProcessParameters`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[allow(dead_code)]
+struct Peb {
+    reserved1: [u8; 2],
+    being_debugged: u8,
+    reserved2: [u8; 1],
+    reserved3: [*const c_void; 2],
+    ldr: *const c_void,
+    process_parameters: *const c_void,
+}
+
+/// Leading fields of `RTL_USER_PROCESS_PARAMETERS`, up to `CommandLine`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[allow(dead_code)]
+struct ProcessParameters {
+    maximum_length: u32,
+    length: u32,
+    flags: u32,
+    debug_flags: u32,
+    console_handle: *const c_void,
+    console_flags: u32,
+    standard_input: *const c_void,
+    standard_output: *const c_void,
+    standard_error: *const c_void,
+    current_directory_path: UnicodeString,
+    current_directory_handle: *const c_void,
+    dll_path: UnicodeString,
+    image_path_name: UnicodeString,
+    command_line: UnicodeString,
+}
+
+/// Layout of `RTL_DRIVE_LETTER_CURDIR`; its `DosPath` is a `STRING`, which
+/// has the same layout as a `UNICODE_STRING`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[allow(dead_code)]
+struct DriveLetterCurDir {
+    flags: u16,
+    length: u16,
+    time_stamp: u32,
+    dos_path: UnicodeString,
+}
+
+/// Fields of `RTL_USER_PROCESS_PARAMETERS` following `CommandLine`, up to
+/// `ProcessGroupId`, which Windows 10 and later fill in.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[allow(dead_code)]
+struct ProcessParametersTail {
+    environment: *const c_void,
+    window: [u32; 9],
+    window_title: UnicodeString,
+    desktop_info: UnicodeString,
+    shell_info: UnicodeString,
+    runtime_data: UnicodeString,
+    current_directories: [DriveLetterCurDir; 32],
+    environment_size: usize,
+    environment_version: usize,
+    package_dependency_data: *const c_void,
+    process_group_id: u32,
+}
+
+/// Process handle that is closed when dropped.
+struct OwnedHandle(HANDLE);
+
+impl OwnedHandle {
+    fn open(access: u32, pid: u32) -> Option<Self> {
+        // SAFETY: OpenProcess has no pointer arguments.
+        let handle = unsafe { OpenProcess(access, 0, pid) };
+        (!handle.is_null()).then_some(Self(handle))
+    }
+}
+
+impl Drop for OwnedHandle {
+    fn drop(&mut self) {
+        // SAFETY: the handle is owned and closed exactly once.
+        unsafe { CloseHandle(self.0) };
+    }
+}
+
+/// Console control handler that swallows Ctrl-Break, so the event sent
+/// to another process group doesn't stop this process.
+unsafe extern "system" fn ignore_ctrl_break(ctrl_type: u32) -> BOOL {
+    BOOL::from(ctrl_type == CTRL_BREAK_EVENT)
+}
+
+/// Native Win32 implementation of the ProcessProvider trait.
+/// Enumerates processes with a Toolhelp snapshot and reads each command line
+/// and working directory from the target's PEB via `NtQueryInformationProcess`.
+/// Details of protected processes, or of 64-bit processes when built
+/// for 32-bit, cannot be read and are left empty.
+#[derive(Debug, Default)]
+pub struct Win32ProcessProvider;
+
+impl Win32ProcessProvider {
+    /// Creates a new Win32 process provider.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Takes a Toolhelp snapshot of all processes.
+    fn snapshot() -> Result<Vec<PROCESSENTRY32W>> {
+        // SAFETY: the snapshot handle is checked and owned below.
+        let handle = unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) };
+        if handle == INVALID_HANDLE_VALUE {
+            return Err(StartupError::ProcessError(format!(
+                "Failed to snapshot processes: {}",
+                std::io::Error::last_os_error()
+            )));
+        }
+        let snapshot = OwnedHandle(handle);
+
+        let mut entries = Vec::new();
+        // SAFETY: PROCESSENTRY32W is plain data; dwSize is set before use.
+        let mut entry: PROCESSENTRY32W = unsafe { mem::zeroed() };
+        entry.dwSize = mem::size_of::<PROCESSENTRY32W>() as u32;
+
+        // SAFETY: `entry` is a valid, correctly sized PROCESSENTRY32W.
+        let mut ok = unsafe { Process32FirstW(snapshot.0, &mut entry) };
+        while ok != 0 {
+            entries.push(entry);
+            // SAFETY: as above.
+            ok = unsafe { Process32NextW(snapshot.0, &mut entry) };
+        }
+
+        Ok(entries)
+    }
+
+    /// Reads a `T` from the address space of another process.
+    fn read_struct<T: Copy>(process: &OwnedHandle, address: *const c_void) -> Option<T> {
+        if address.is_null() {
+            return None;
+        }
+        let mut value = mem::MaybeUninit::<T>::uninit();
+        let mut read = 0usize;
+        // SAFETY: the buffer is exactly size_of::<T>() bytes.
+        let ok = unsafe {
+            ReadProcessMemory(
+                process.0,
+                address,
+                value.as_mut_ptr() as *mut c_void,
+                mem::size_of::<T>(),
+                &mut read,
+            )
+        };
+        // SAFETY: the value is fully initialized when the whole struct was read.
+        (ok != 0 && read == mem::size_of::<T>()).then(|| unsafe { value.assume_init() })
+    }
+
+    /// Reads the text of a `UNICODE_STRING` living in another process.
+    fn read_unicode_string(process: &OwnedHandle, string: &UnicodeString) -> Option<String> {
+        let len = usize::from(string.length) / 2;
+        if len == 0 || string.buffer.is_null() {
+            return Some(String::new());
+        }
+        let mut buffer = vec![0u16; len];
+        let mut read = 0usize;
+        // SAFETY: the buffer holds `len` UTF-16 units, i.e. `string.length` bytes.
+        let ok = unsafe {
+            ReadProcessMemory(
+                process.0,
+                string.buffer as *const c_void,
+                buffer.as_mut_ptr() as *mut c_void,
+                len * 2,
+                &mut read,
+            )
+        };
+        (ok != 0).then(|| String::from_utf16_lossy(&buffer[..read / 2]))
+    }
+
+    /// Reads the process parameters block of `pid`, with its address.
+    fn read_parameters(pid: u32) -> Option<(OwnedHandle, ProcessParameters, *const c_void)> {
+        let process = OwnedHandle::open(PROCESS_QUERY_INFORMATION | PROCESS_VM_READ, pid)?;
+
+        // SAFETY: PROCESS_BASIC_INFORMATION is plain data filled by the call.
+        let mut info: PROCESS_BASIC_INFORMATION = unsafe { mem::zeroed() };
+        // SAFETY: the output buffer matches the requested information class.
+        let status = unsafe {
+            NtQueryInformationProcess(
+                process.0,
+                ProcessBasicInformation,
+                &mut info as *mut _ as *mut c_void,
+                mem::size_of::<PROCESS_BASIC_INFORMATION>() as u32,
+                ptr::null_mut(),
+            )
+        };
+        if status < 0 {
+            return None;
+        }
+
+        let peb: Peb = Self::read_struct(&process, info.PebBaseAddress as *const c_void)?;
+        let parameters: ProcessParameters = Self::read_struct(&process, peb.process_parameters)?;
+        Some((process, parameters, peb.process_parameters))
+    }
+
+    /// Reads the command line and current directory of `pid`, if accessible.
+    fn read_details(pid: u32) -> (Option<String>, Option<String>) {
+        let Some((process, parameters, _)) = Self::read_parameters(pid) else {
+            return (None, None);
+        };
+        let command_line = Self::read_unicode_string(&process, &parameters.command_line);
+        let workdir = Self::read_unicode_string(&process, &parameters.current_directory_path)
+            .filter(|dir| !dir.is_empty());
+        (command_line, workdir)
+    }
+
+    /// Reads the ID of the console process group `pid` belongs to.
+    /// Returns `None` when the parameters block cannot be read or is too
+    /// short to hold the field, as before Windows 10.
+    fn read_process_group(pid: u32) -> Option<u32> {
+        let (process, parameters, address) = Self::read_parameters(pid)?;
+        let offset = mem::size_of::<ProcessParameters>()
+            + mem::offset_of!(ProcessParametersTail, process_group_id);
+        if (parameters.length as usize) < offset + mem::size_of::<u32>() {
+            return None;
+        }
+        Self::read_struct(&process, address.wrapping_byte_add(offset))
+    }
+
+    /// Reads the executable path and start time of `pid`.
+    /// Only needs limited query rights, so it works for more processes than
+    /// reading the PEB does.
+    fn read_image(pid: u32) -> (Option<String>, Option<DateTime<Utc>>) {
+        let Some(process) = OwnedHandle::open(PROCESS_QUERY_LIMITED_INFORMATION, pid) else {
+            return (None, None);
+        };
+
+        let mut buffer = vec![0u16; 32 * 1024];
+        let mut size = buffer.len() as u32;
+        // SAFETY: `size` holds the buffer capacity in UTF-16 units.
+        let path = (unsafe {
+            QueryFullProcessImageNameW(
+                process.0,
+                PROCESS_NAME_WIN32,
+                buffer.as_mut_ptr(),
+                &mut size,
+            )
+        } != 0)
+            .then(|| String::from_utf16_lossy(&buffer[..size as usize]));
+
+        let mut times = [FILETIME {
+            dwLowDateTime: 0,
+            dwHighDateTime: 0,
+        }; 4];
+        let [creation, exit, kernel, user] = &mut times;
+        // SAFETY: all four out pointers are valid FILETIMEs.
+        let ok = unsafe { GetProcessTimes(process.0, creation, exit, kernel, user) };
+        let start_time = (ok != 0)
+            .then(|| {
+                let creation = times[0];
+                from_filetime(
+                    (u64::from(creation.dwHighDateTime) << 32) | u64::from(creation.dwLowDateTime),
+                )
+            })
+            .flatten();
+
+        (path, start_time)
+    }
+
+    /// Posts `WM_CLOSE` to every top-level window owned by `pid`.
+    /// Returns the number of windows that received the message.
+    fn close_windows(pid: u32) -> usize {
+        struct Search {
+            pid: u32,
+            posted: usize,
+        }
+
+        unsafe extern "system" fn visit(hwnd: HWND, lparam: LPARAM) -> BOOL {
+            // SAFETY: `lparam` is the `Search` passed to EnumWindows below.
+            let search = unsafe { &mut *(lparam as *mut Search) };
+            let mut owner = 0u32;
+            // SAFETY: `hwnd` comes from EnumWindows and `owner` is a valid out pointer.
+            unsafe { GetWindowThreadProcessId(hwnd, &mut owner) };
+            // SAFETY: posting a message has no memory-safety requirements.
+            if owner == search.pid && unsafe { PostMessageW(hwnd, WM_CLOSE, 0, 0) } != 0 {
+                search.posted += 1;
+            }
+            1
+        }
+
+        let mut search = Search { pid, posted: 0 };
+        // SAFETY: `search` outlives the synchronous enumeration.
+        unsafe { EnumWindows(Some(visit), &mut search as *mut Search as LPARAM) };
+        search.posted
+    }
+
+    /// Sends Ctrl-Break to the process group led by `pid`.
+    /// Hidden console programs own no windows, so this is their only
+    /// graceful signal. This process briefly joins their console and
+    /// ignores the event itself. The event reaches a whole group, so it is
+    /// only sent to group leaders; other processes get force-killed.
+    ///
+    /// A console can only be joined again through a process attached to
+    /// it, so this process returns to its own console through another one
+    /// sharing it, and doesn't leave a console it alone holds, which would
+    /// close it. The standard handles that pointed at the console are
+    /// reopened on the way back, so later output still reaches it.
+    fn send_ctrl_break(pid: u32) -> Result<()> {
+        if Self::read_process_group(pid) != Some(pid) {
+            return Err(StartupError::ProcessError(format!(
+                "Process {} does not lead a console process group",
+                pid
+            )));
+        }
+        let host = match Self::console_sharers() {
+            None => None,
+            Some(sharers) => match sharers.first() {
+                Some(&host) => Some(host),
+                None => {
+                    return Err(StartupError::ProcessError(format!(
+                        "Sending Ctrl-Break to process {} would close the console of this program",
+                        pid
+                    )));
+                }
+            },
+        };
+        let std_handles = Self::console_std_handles();
+        let restore = || {
+            // SAFETY: console attachment calls have no pointer arguments.
+            unsafe {
+                FreeConsole();
+                if let Some(host) = host {
+                    AttachConsole(host);
+                }
+            }
+            Self::reopen_std_handles(&std_handles);
+        };
+
+        // SAFETY: console attachment calls have no pointer arguments, and
+        // the handler is a plain function that stays valid.
+        unsafe {
+            // The handler stays installed: the event reaches this process
+            // asynchronously, possibly after it has left the console
+            SetConsoleCtrlHandler(Some(ignore_ctrl_break), 1);
+            FreeConsole();
+            if AttachConsole(pid) == 0 {
+                let error = std::io::Error::last_os_error();
+                restore();
+                return Err(StartupError::ProcessError(format!(
+                    "Failed to attach to the console of process {}: {}",
+                    pid, error
+                )));
+            }
+            let sent = GenerateConsoleCtrlEvent(CTRL_BREAK_EVENT, pid);
+            let error = std::io::Error::last_os_error();
+            restore();
+
+            if sent == 0 {
+                return Err(StartupError::ProcessError(format!(
+                    "Failed to send Ctrl-Break to process {}: {}",
+                    pid, error
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the other processes attached to the console of this one, or
+    /// `None` when it has no console.
+    fn console_sharers() -> Option<Vec<u32>> {
+        let mut pids = vec![0u32; 16];
+        loop {
+            // SAFETY: `pids` holds as many elements as the length passed.
+            let count =
+                unsafe { GetConsoleProcessList(pids.as_mut_ptr(), pids.len() as u32) } as usize;
+            if count == 0 {
+                return None;
+            }
+            // A short buffer is left empty and the needed length returned
+            if count <= pids.len() {
+                pids.truncate(count);
+                break;
+            }
+            pids.resize(count, 0);
+        }
+        // SAFETY: GetCurrentProcessId has no preconditions.
+        let own = unsafe { GetCurrentProcessId() };
+        pids.retain(|&p| p != own);
+        Some(pids)
+    }
+
+    /// Returns the standard handles that are console handles, which leaving
+    /// the console invalidates.
+    fn console_std_handles() -> Vec<STD_HANDLE> {
+        [STD_INPUT_HANDLE, STD_OUTPUT_HANDLE, STD_ERROR_HANDLE]
+            .into_iter()
+            .filter(|&id| {
+                let mut mode = 0;
+                // SAFETY: `mode` is a valid location for the console mode.
+                unsafe { GetConsoleMode(GetStdHandle(id), &mut mode) != 0 }
+            })
+            .collect()
+    }
+
+    /// Points the standard handles `ids` at the console this process is
+    /// attached to.
+    fn reopen_std_handles(ids: &[STD_HANDLE]) {
+        for &id in ids {
+            let name = if id == STD_INPUT_HANDLE {
+                "CONIN$"
+            } else {
+                "CONOUT$"
+            };
+            let name: Vec<u16> = name.encode_utf16().chain([0]).collect();
+            // SAFETY: `name` is NUL-terminated and outlives the call, and no
+            // security attributes or template are passed.
+            unsafe {
+                let handle = CreateFileW(
+                    name.as_ptr(),
+                    GENERIC_READ | GENERIC_WRITE,
+                    FILE_SHARE_READ | FILE_SHARE_WRITE,
+                    ptr::null(),
+                    OPEN_EXISTING,
+                    0,
+                    ptr::null_mut(),
+                );
+                if handle != INVALID_HANDLE_VALUE {
+                    SetStdHandle(id, handle);
+                }
+            }
+        }
+    }
+
+    fn entry_name(entry: &PROCESSENTRY32W) -> String {
+        let len = entry
+            .szExeFile
+            .iter()
+            .position(|&c| c == 0)
+            .unwrap_or(entry.szExeFile.len());
+        String::from_utf16_lossy(&entry.szExeFile[..len])
+    }
+}
+
+impl ProcessProvider for Win32ProcessProvider {
+    fn list_processes(&self) -> Result<Vec<ProcessInfo>> {
+        let processes = Self::snapshot()?
+            .iter()
+            .filter(|entry| entry.th32ProcessID != 0)
+            .map(|entry| {
+                let pid = entry.th32ProcessID;
+                let (command_line, workdir) = Self::read_details(pid);
+                let info = ProcessInfo::new(
+                    pid,
+                    Self::entry_name(entry),
+                    command_line.unwrap_or_default(),
+                )
+                .with_parent(entry.th32ParentProcessID);
+                let info = match workdir {
+                    Some(dir) => info.with_workdir(dir),
+                    None => info,
+                };
+                let (path, start_time) = Self::read_image(pid);
+                let info = match path {
+                    Some(path) => info.with_executable_path(path),
+                    None => info,
+                };
+                match start_time {
+                    Some(start) => info.with_start_time(start),
+                    None => info,
+                }
+            })
+            .collect();
+
+        Ok(processes)
+    }
+
+    fn kill_process(&self, pid: u32) -> Result<()> {
+        let process = OwnedHandle::open(PROCESS_TERMINATE, pid).ok_or_else(|| {
+            StartupError::ProcessError(format!(
+                "Failed to open process {}: {}",
+                pid,
+                std::io::Error::last_os_error()
+            ))
+        })?;
+
+        // SAFETY: the handle was opened with PROCESS_TERMINATE.
+        if unsafe { TerminateProcess(process.0, 1) } == 0 {
+            return Err(StartupError::ProcessError(format!(
+                "Failed to kill process {}: {}",
+                pid,
+                std::io::Error::last_os_error()
+            )));
+        }
+        Ok(())
+    }
+
+    fn request_close(&self, pid: u32) -> Result<()> {
+        if Self::close_windows(pid) > 0 {
+            return Ok(());
+        }
+        Self::send_ctrl_break(pid)
+    }
+
+    fn is_running(&self, pid: u32) -> Result<bool> {
+        let Some(process) = OwnedHandle::open(PROCESS_QUERY_LIMITED_INFORMATION, pid) else {
+            // Only an unknown PID means the process is gone; access denied
+            // means it runs with rights this process lacks
+            let error = std::io::Error::last_os_error();
+            if error.raw_os_error() == Some(ERROR_INVALID_PARAMETER as i32) {
+                return Ok(false);
+            }
+            return Err(StartupError::ProcessError(format!(
+                "Failed to open process {}: {}",
+                pid, error
+            )));
+        };
+        let mut exit_code = 0u32;
+        // SAFETY: `exit_code` is a valid out pointer.
+        if unsafe { GetExitCodeProcess(process.0, &mut exit_code) } == 0 {
+            return Err(StartupError::ProcessError(format!(
+                "Failed to query process {}: {}",
+                pid,
+                std::io::Error::last_os_error()
+            )));
+        }
+        Ok(exit_code == STILL_ACTIVE as u32)
+    }
+}
@@ -1,4 +1,10 @@
-use clap::{Parser, Subcommand};
+use crate::application::ConflictStrategy;
+use crate::domain::scheduled_task;
+use crate::domain::{AuditAction, ExecutionMode, ModeName, ModeSpec, StartupLocation, WindowStyle};
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+use std::time::Duration;
 
 /// A simple command-line tool to manage Windows startup programs via the registry.
 #[derive(Parser)]
@@ -7,6 +13,17 @@ use clap::{Parser, Subcommand};
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+    /// How to print results: table (default), plain, json or yaml.
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    pub output: OutputFormat,
+    /// When to color the table output: auto (default), always or never.
+    /// `auto` colors a terminal unless NO_COLOR is set.
+    #[arg(long, global = true, value_enum, default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+    /// Runs every check and prints the registry values, launchers and
+    /// processes the command would change, without changing them.
+    #[arg(long, global = true)]
+    pub dry_run: bool,
 }
 
 #[derive(Subcommand)]
@@ -17,6 +34,14 @@ pub enum Commands {
         name: String,
         /// The full path to the executable file to run.
         path: String,
+        /// Where to store the entry: hkcu-run (default), hkcu-run-once,
+        /// hklm-run, hklm-run-once, hklm-run32, hklm-run-once32,
+        /// startup-folder or common-startup-folder.
+        #[arg(long)]
+        location: Option<StartupLocation>,
+        /// Replaces an existing entry of the same name instead of failing.
+        #[arg(long)]
+        force: bool,
     },
     /// Adds a command with arguments to the startup list (e.g., "bun run dev").
     AddCommand {
@@ -31,19 +56,432 @@ pub enum Commands {
         /// If not specified, uses the current working directory.
         #[arg(short = 'd', long)]
         workdir: Option<String>,
+        /// Where to store the entry: hkcu-run (default), hkcu-run-once,
+        /// hklm-run, hklm-run-once, hklm-run32, hklm-run-once32,
+        /// startup-folder or common-startup-folder. Scheduled tasks are
+        /// always stored in task-scheduler.
+        #[arg(long)]
+        location: Option<StartupLocation>,
+        /// Replaces an existing entry of the same name instead of failing.
+        #[arg(long)]
+        force: bool,
+        #[command(flatten)]
+        mode: ModeOptions,
+    },
+    /// Changes the command, arguments, working directory or mode of an
+    /// entry, keeping its name, location and enabled state.
+    Edit {
+        /// The name of the entry to change.
+        name: String,
+        /// The program to run instead.
+        #[arg(long)]
+        command: Option<String>,
+        /// The arguments to run the program with instead. Takes every
+        /// argument after it, so it goes last; none clears the arguments.
+        #[arg(long, num_args = 0.., allow_hyphen_values = true)]
+        args: Option<Vec<String>>,
+        /// The working directory to run the command in instead.
+        #[arg(short = 'd', long)]
+        workdir: Option<String>,
+        /// Runs the command without a working directory of its own.
+        #[arg(long, conflicts_with = "workdir")]
+        no_workdir: bool,
+        /// The location of the entry. Required when several locations hold
+        /// an entry with this name.
+        #[arg(long)]
+        location: Option<StartupLocation>,
+        /// Mode options replace every option of the current mode, and need
+        /// --mode.
+        #[command(flatten)]
+        mode: ModeOptions,
+    },
+    /// Renames an entry, moving its VBScript launcher along with it.
+    Rename {
+        /// The name of the entry to rename.
+        name: String,
+        /// The new name of the entry.
+        new_name: String,
+        /// The location of the entry. Required when several locations hold
+        /// an entry with this name.
+        #[arg(long)]
+        location: Option<StartupLocation>,
     },
     /// Removes a program from the startup list.
     Remove {
         /// The name of the entry to remove from the startup registry.
         name: String,
+        /// The location to remove the entry from. Required when several
+        /// locations hold an entry with this name.
+        #[arg(long)]
+        location: Option<StartupLocation>,
+    },
+    /// Enables a startup entry that was disabled.
+    Enable {
+        /// The name of the entry to enable.
+        name: String,
+        /// The location of the entry. Required when several locations hold
+        /// an entry with this name.
+        #[arg(long)]
+        location: Option<StartupLocation>,
+    },
+    /// Disables a startup entry without removing it, like Task Manager does.
+    Disable {
+        /// The name of the entry to disable.
+        name: String,
+        /// The location of the entry. Required when several locations hold
+        /// an entry with this name.
+        #[arg(long)]
+        location: Option<StartupLocation>,
     },
     /// Lists all programs currently in the startup list.
     List,
-    /// Kills a running process associated with a startup entry.
+    /// Shows the changes `apply` would make to match a TOML or YAML manifest.
+    Plan {
+        /// The manifest file (.toml, .yaml or .yml).
+        file: PathBuf,
+        /// Also remove the entries the manifest doesn't list from the
+        /// locations it uses.
+        #[arg(long)]
+        prune: bool,
+    },
+    /// Adds, changes and removes entries to match a TOML or YAML manifest.
+    /// If a change fails, the changes already made are reverted.
+    Apply {
+        /// The manifest file (.toml, .yaml or .yml).
+        file: PathBuf,
+        /// Also remove the entries the manifest doesn't list from the
+        /// locations it uses.
+        #[arg(long)]
+        prune: bool,
+    },
+    /// Writes every entry, with its decoded command, enabled state and
+    /// launcher, to a JSON backup, or the entries of the Run keys to a
+    /// .reg file.
+    Export {
+        /// The file to write. Writes to standard output when omitted.
+        file: Option<PathBuf>,
+        /// The format to write.
+        #[arg(long, value_enum, default_value_t = ExportFormat::Json)]
+        format: ExportFormat,
+    },
+    /// Restores the entries of a JSON backup written by `export`, or the
+    /// Run-key values of a .reg file. Every entry is checked before
+    /// anything is written.
+    Import {
+        /// The backup file; files ending in .reg are read as .reg files.
+        file: PathBuf,
+        /// What to do with entries whose name is already used.
+        #[arg(long, value_enum, default_value_t = ConflictArg::Skip)]
+        on_conflict: ConflictArg,
+    },
+    /// Shows the changes recorded for the commands that added, changed or
+    /// removed entries, most recent first.
+    History {
+        /// The number of commands to show.
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Puts back the entries and launchers the last commands changed, as
+    /// they were before.
+    Undo {
+        /// The number of commands to revert.
+        #[arg(default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        count: u32,
+    },
+    /// Shows the audit log of the commands that changed entries or stopped
+    /// their processes, oldest first.
+    Log {
+        /// Only events about this entry.
+        #[arg(long)]
+        entry: Option<String>,
+        /// Only events of this kind of command.
+        #[arg(long, value_enum)]
+        action: Option<ActionArg>,
+        /// Only events of this user (e.g. CORP\builder).
+        #[arg(long)]
+        user: Option<String>,
+        /// Only events at or after this date (YYYY-MM-DD, local time) or
+        /// RFC 3339 time.
+        #[arg(long, value_parser = parse_time)]
+        since: Option<DateTime<Utc>>,
+        /// Only events before this date (YYYY-MM-DD, local time) or
+        /// RFC 3339 time.
+        #[arg(long, value_parser = parse_time)]
+        until: Option<DateTime<Utc>>,
+        /// Only events of commands that failed.
+        #[arg(long)]
+        failed: bool,
+        /// The number of events to show, counting from the most recent.
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+    },
+    /// Deletes VBScript launchers that no startup entry runs anymore.
+    /// Use `--dry-run` to only report the launchers that would be deleted.
+    Gc,
+    /// Stops the running processes (and their children) of a startup entry.
     Kill {
         /// The name of the startup entry whose process to kill.
         name: String,
+        /// Kill every process with the entry's executable name instead of
+        /// only the processes launched by the entry.
+        #[arg(long)]
+        by_name: bool,
+        /// Seconds to wait for processes to exit after a close request
+        /// before force-killing them. Use 0 to kill immediately.
+        #[arg(long, default_value_t = 5)]
+        timeout: u64,
     },
-    /// Kills all running processes associated with startup entries.
-    KillAll,
+    /// Stops all running processes (and their children) of startup entries.
+    KillAll {
+        /// Kill every process with each entry's executable name instead of
+        /// only the processes launched by the entries.
+        #[arg(long)]
+        by_name: bool,
+        /// Seconds to wait for processes to exit after a close request
+        /// before force-killing them. Use 0 to kill immediately.
+        #[arg(long, default_value_t = 5)]
+        timeout: u64,
+    },
+}
+
+/// How `add-command` runs the command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ModeArg {
+    /// Silently, through a VBScript launcher.
+    #[default]
+    Vbscript,
+    /// Through PowerShell, with a hidden window unless --window says otherwise.
+    Powershell,
+    /// In a visible console window.
+    Visible,
+    /// As a Task Scheduler logon task.
+    ScheduledTask,
+}
+
+impl From<ModeArg> for ModeName {
+    fn from(mode: ModeArg) -> Self {
+        match mode {
+            ModeArg::Vbscript => ModeName::Vbscript,
+            ModeArg::Powershell => ModeName::Powershell,
+            ModeArg::Visible => ModeName::Visible,
+            ModeArg::ScheduledTask => ModeName::ScheduledTask,
+        }
+    }
+}
+
+/// Window style of the PowerShell mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum WindowArg {
+    Hidden,
+    Minimized,
+    Normal,
+}
+
+impl From<WindowArg> for WindowStyle {
+    fn from(window: WindowArg) -> Self {
+        match window {
+            WindowArg::Hidden => WindowStyle::Hidden,
+            WindowArg::Minimized => WindowStyle::Minimized,
+            WindowArg::Normal => WindowStyle::Normal,
+        }
+    }
+}
+
+/// File format of `export`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ExportFormat {
+    /// A JSON backup of every entry.
+    #[default]
+    Json,
+    /// A Registry Editor file of the Run-key entries, encoded as UTF-16LE.
+    Reg,
+}
+
+/// How results are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Colored text for people.
+    #[default]
+    Table,
+    /// Uncolored, tab-separated lines without headers.
+    Plain,
+    /// Versioned JSON documents.
+    Json,
+    /// Versioned YAML documents.
+    Yaml,
+}
+
+/// When `--output table` is colored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ColorChoice {
+    /// Color a terminal unless NO_COLOR is set to a non-empty value.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Whether to color the output, given the value of NO_COLOR and whether
+    /// standard output is a terminal.
+    pub fn enabled(self, no_color: Option<&str>, terminal: bool) -> bool {
+        match self {
+            ColorChoice::Auto => terminal && no_color.is_none_or(str::is_empty),
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+}
+
+/// What `import` does with entries whose name is already used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ConflictArg {
+    /// Keep the existing entry.
+    #[default]
+    Skip,
+    /// Replace the existing entry.
+    Overwrite,
+    /// Import the entry under a free name, like "Name (2)".
+    Rename,
+}
+
+impl From<ConflictArg> for ConflictStrategy {
+    fn from(conflict: ConflictArg) -> Self {
+        match conflict {
+            ConflictArg::Skip => ConflictStrategy::Skip,
+            ConflictArg::Overwrite => ConflictStrategy::Overwrite,
+            ConflictArg::Rename => ConflictStrategy::Rename,
+        }
+    }
+}
+
+/// Kind of command `log` shows events of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ActionArg {
+    Add,
+    Edit,
+    Rename,
+    Remove,
+    Enable,
+    Disable,
+    Kill,
+    Apply,
+    Import,
+    Undo,
+}
+
+impl From<ActionArg> for AuditAction {
+    fn from(action: ActionArg) -> Self {
+        match action {
+            ActionArg::Add => AuditAction::Add,
+            ActionArg::Edit => AuditAction::Edit,
+            ActionArg::Rename => AuditAction::Rename,
+            ActionArg::Remove => AuditAction::Remove,
+            ActionArg::Enable => AuditAction::Enable,
+            ActionArg::Disable => AuditAction::Disable,
+            ActionArg::Kill => AuditAction::Kill,
+            ActionArg::Apply => AuditAction::Apply,
+            ActionArg::Import => AuditAction::Import,
+            ActionArg::Undo => AuditAction::Undo,
+        }
+    }
+}
+
+/// Execution mode of `add-command` and `edit` and the options of each mode.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Args)]
+pub struct ModeOptions {
+    /// How to run the command. `add-command` defaults to vbscript, `edit`
+    /// keeps the current mode.
+    #[arg(long, value_enum)]
+    pub mode: Option<ModeArg>,
+    /// Window style of the powershell mode.
+    #[arg(long, value_enum)]
+    pub window: Option<WindowArg>,
+    /// Keeps the powershell or visible window open after the command
+    /// exits, to read its output when debugging.
+    #[arg(long)]
+    pub no_exit: bool,
+    /// Time to wait after logon before starting a scheduled task
+    /// (e.g. 30s, 1m30s, PT2M).
+    #[arg(long, value_parser = parse_duration)]
+    pub delay: Option<Duration>,
+    /// Restarts a failing scheduled task up to this many times.
+    #[arg(long)]
+    pub restart_count: Option<u32>,
+    /// Time between restarts of a scheduled task (default: 1m).
+    #[arg(long, value_parser = parse_duration)]
+    pub restart_interval: Option<Duration>,
+    /// Runs a scheduled task without a visible window, in a
+    /// non-interactive session.
+    #[arg(long)]
+    pub hidden: bool,
+    /// Runs a scheduled task with the highest privileges of the user.
+    #[arg(long)]
+    pub highest: bool,
+}
+
+impl ModeOptions {
+    /// Builds the execution mode, rejecting options the mode doesn't take.
+    pub fn execution_mode(&self) -> Result<ExecutionMode, String> {
+        let spec = ModeSpec {
+            mode: self.mode.unwrap_or_default().into(),
+            window: self.window.map(WindowStyle::from),
+            no_exit: self.no_exit,
+            delay: self.delay,
+            restart_count: self.restart_count,
+            restart_interval: self.restart_interval,
+            hidden: self.hidden,
+            highest: self.highest,
+        };
+        let unused = spec.unused_options();
+        if !unused.is_empty() {
+            let flags: Vec<_> = unused.iter().map(|name| format!("--{}", name)).collect();
+            return Err(format!(
+                "{} cannot be used with --mode {}",
+                flags.join(", "),
+                spec.mode.as_str()
+            ));
+        }
+        spec.execution_mode()
+    }
+
+    /// Builds the mode `edit` switches to, or `None` when no mode option is
+    /// given. Mode options without `--mode` are rejected.
+    pub fn changed_mode(&self) -> Result<Option<ExecutionMode>, String> {
+        match self.mode {
+            Some(_) => self.execution_mode().map(Some),
+            None if *self == Self::default() => Ok(None),
+            None => Err("mode options cannot be used without --mode".to_string()),
+        }
+    }
+}
+
+/// Parses a duration such as `90s`, `1m30s`, `2h` or `1d`, or an ISO 8601
+/// duration such as `PT1M30S`. A bare number is a number of seconds.
+pub fn parse_duration(text: &str) -> Result<Duration, String> {
+    scheduled_task::parse_short_duration(text).ok_or_else(|| {
+        format!(
+            "invalid duration '{}' (expected e.g. 30s, 1m30s or PT1M)",
+            text.trim()
+        )
+    })
+}
+
+/// Parses an RFC 3339 time, or a date meaning local midnight.
+pub fn parse_time(text: &str) -> Result<DateTime<Utc>, String> {
+    let text = text.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(text) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .and_then(|midnight| midnight.and_local_timezone(Local).earliest())
+        .map(|time| time.with_timezone(&Utc))
+        .ok_or_else(|| {
+            format!(
+                "invalid time '{}' (expected e.g. 2026-03-01 or 2026-03-01T08:30:00Z)",
+                text
+            )
+        })
 }
@@ -0,0 +1,283 @@
+#[cfg(test)]
+mod tests {
+    use super::super::cli::*;
+    use crate::application::ConflictStrategy;
+    use crate::domain::{
+        AuditAction, ConsoleOptions, ExecutionMode, PowerShellOptions, RestartPolicy, TaskSettings,
+        WindowStyle,
+    };
+    use chrono::{TimeZone, Utc};
+    use clap::Parser;
+    use std::time::Duration;
+
+    fn mode_of(args: &[&str]) -> Result<ExecutionMode, String> {
+        let cli = Cli::try_parse_from(
+            ["startup", "add-command", "Dev", "bun"]
+                .iter()
+                .chain(args)
+                .chain(&["--", "run", "dev"]),
+        )
+        .unwrap();
+        match cli.command {
+            Commands::AddCommand { mode, .. } => mode.execution_mode(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_mode_defaults_to_vbscript() {
+        assert_eq!(mode_of(&[]), Ok(ExecutionMode::VBScript));
+    }
+
+    #[test]
+    fn test_powershell_and_visible_options() {
+        assert_eq!(
+            mode_of(&["--mode", "powershell", "--window", "minimized", "--no-exit"]),
+            Ok(ExecutionMode::PowerShell(PowerShellOptions {
+                window: WindowStyle::Minimized,
+                no_exit: true,
+            }))
+        );
+        assert_eq!(
+            mode_of(&["--mode", "visible", "--no-exit"]),
+            Ok(ExecutionMode::Visible(ConsoleOptions { no_exit: true }))
+        );
+    }
+
+    #[test]
+    fn test_scheduled_task_options() {
+        assert_eq!(
+            mode_of(&[
+                "--mode",
+                "scheduled-task",
+                "--delay",
+                "1m30s",
+                "--restart-count",
+                "3",
+                "--highest",
+            ]),
+            Ok(ExecutionMode::ScheduledTask(TaskSettings {
+                delay: Some(Duration::from_secs(90)),
+                restart: Some(RestartPolicy {
+                    count: 3,
+                    interval: Duration::from_secs(60),
+                }),
+                hidden: false,
+                highest_privileges: true,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_rejects_options_of_other_modes() {
+        let error = mode_of(&["--window", "normal", "--delay", "30s"]).unwrap_err();
+        assert!(error.contains("--window, --delay"));
+        assert!(error.contains("--mode vbscript"));
+
+        assert!(mode_of(&["--mode", "scheduled-task", "--no-exit"]).is_err());
+        assert!(mode_of(&["--mode", "scheduled-task", "--restart-interval", "5m"]).is_err());
+    }
+
+    #[test]
+    fn test_adds_only_replace_with_force() {
+        let forced = Cli::try_parse_from(["startup", "add", "App", "app.exe", "--force"]).unwrap();
+        let command = Cli::try_parse_from(["startup", "add-command", "Dev", "bun"]).unwrap();
+
+        assert!(matches!(forced.command, Commands::Add { force: true, .. }));
+        assert!(matches!(
+            command.command,
+            Commands::AddCommand { force: false, .. }
+        ));
+    }
+
+    #[test]
+    fn test_edit_changes_only_the_given_parts() {
+        let parse = |args: &[&str]| {
+            let cli = Cli::try_parse_from(["startup", "edit", "Dev"].iter().chain(args)).unwrap();
+            match cli.command {
+                Commands::Edit {
+                    command,
+                    args,
+                    workdir,
+                    no_workdir,
+                    mode,
+                    ..
+                } => (command, args, (workdir, no_workdir), mode.changed_mode()),
+                _ => unreachable!(),
+            }
+        };
+
+        assert_eq!(
+            parse(&["-d", "C:\\app", "--args", "run", "--port", "3000"]),
+            (
+                None,
+                Some(vec![
+                    "run".to_string(),
+                    "--port".to_string(),
+                    "3000".to_string()
+                ]),
+                (Some("C:\\app".to_string()), false),
+                Ok(None)
+            )
+        );
+        assert_eq!(parse(&["--args"]).1, Some(Vec::new()));
+        assert_eq!(parse(&["--no-workdir"]).2, (None, true));
+        assert!(
+            Cli::try_parse_from(["startup", "edit", "Dev", "-d", "C:\\app", "--no-workdir"])
+                .is_err()
+        );
+        assert_eq!(parse(&["--command", "node"]).0, Some("node".to_string()));
+        assert_eq!(
+            parse(&["--mode", "visible", "--no-exit"]).3,
+            Ok(Some(ExecutionMode::Visible(ConsoleOptions {
+                no_exit: true
+            })))
+        );
+        assert!(parse(&["--window", "minimized"]).3.is_err());
+    }
+
+    #[test]
+    fn test_rename_takes_old_and_new_name() {
+        let cli = Cli::try_parse_from(["startup", "rename", "Dev", "Server"]).unwrap();
+
+        assert!(matches!(
+            cli.command,
+            Commands::Rename { name, new_name, location: None } if name == "Dev" && new_name == "Server"
+        ));
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("1m30s"), Ok(Duration::from_secs(90)));
+        assert!(parse_duration("soon").unwrap_err().contains("'soon'"));
+    }
+
+    #[test]
+    fn test_import_skips_conflicts_by_default() {
+        let on_conflict = |args: &[&str]| {
+            let cli = Cli::try_parse_from(["startup", "import", "backup.json"].iter().chain(args))
+                .unwrap();
+            match cli.command {
+                Commands::Import { on_conflict, .. } => ConflictStrategy::from(on_conflict),
+                _ => unreachable!(),
+            }
+        };
+
+        assert_eq!(on_conflict(&[]), ConflictStrategy::Skip);
+        assert_eq!(
+            on_conflict(&["--on-conflict", "rename"]),
+            ConflictStrategy::Rename
+        );
+        assert!(
+            Cli::try_parse_from(["startup", "import", "b.json", "--on-conflict", "merge"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_export_writes_json_by_default() {
+        let format = |args: &[&str]| {
+            let cli = Cli::try_parse_from(["startup", "export"].iter().chain(args)).unwrap();
+            match cli.command {
+                Commands::Export { format, .. } => format,
+                _ => unreachable!(),
+            }
+        };
+
+        assert_eq!(format(&[]), ExportFormat::Json);
+        assert_eq!(
+            format(&["--format", "reg", "startup.reg"]),
+            ExportFormat::Reg
+        );
+    }
+
+    #[test]
+    fn test_undo_reverts_one_command_by_default() {
+        let count = |args: &[&str]| {
+            let cli = Cli::try_parse_from(["startup", "undo"].iter().chain(args)).unwrap();
+            match cli.command {
+                Commands::Undo { count } => count,
+                _ => unreachable!(),
+            }
+        };
+
+        assert_eq!(count(&[]), 1);
+        assert_eq!(count(&["3"]), 3);
+        assert!(Cli::try_parse_from(["startup", "undo", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_log_filters() {
+        let cli = Cli::try_parse_from([
+            "startup",
+            "log",
+            "--entry",
+            "Dev",
+            "--action",
+            "kill",
+            "--since",
+            "2026-03-01T08:30:00Z",
+            "--failed",
+        ])
+        .unwrap();
+
+        let Commands::Log {
+            entry,
+            action,
+            since,
+            until,
+            failed,
+            limit,
+            ..
+        } = cli.command
+        else {
+            unreachable!()
+        };
+        assert_eq!(entry.as_deref(), Some("Dev"));
+        assert_eq!(action.map(AuditAction::from), Some(AuditAction::Kill));
+        assert_eq!(since, Utc.with_ymd_and_hms(2026, 3, 1, 8, 30, 0).single());
+        assert_eq!(until, None);
+        assert!(failed);
+        assert_eq!(limit, 50);
+        assert!(parse_time("2026-03-01").is_ok());
+        assert!(parse_time("March 1st").is_err());
+    }
+
+    #[test]
+    fn test_output_is_a_global_flag() {
+        let default = Cli::try_parse_from(["startup", "list"]).unwrap();
+        let json = Cli::try_parse_from(["startup", "list", "--output", "json"]).unwrap();
+        let plain = Cli::try_parse_from(["startup", "--output", "plain", "kill-all"]).unwrap();
+
+        assert_eq!(default.output, OutputFormat::Table);
+        assert_eq!(json.output, OutputFormat::Json);
+        assert_eq!(plain.output, OutputFormat::Plain);
+        assert!(Cli::try_parse_from(["startup", "list", "--output", "xml"]).is_err());
+    }
+
+    #[test]
+    fn test_color_choice() {
+        let auto = Cli::try_parse_from(["startup", "list"]).unwrap().color;
+        let never = Cli::try_parse_from(["startup", "list", "--color", "never"]).unwrap();
+
+        assert_eq!(auto, ColorChoice::Auto);
+        assert_eq!(never.color, ColorChoice::Never);
+        assert!(auto.enabled(None, true));
+        assert!(auto.enabled(Some(""), true));
+        assert!(!auto.enabled(Some("1"), true));
+        assert!(!auto.enabled(None, false));
+        assert!(ColorChoice::Always.enabled(Some("1"), false));
+        assert!(!ColorChoice::Never.enabled(None, true));
+    }
+
+    #[test]
+    fn test_dry_run_is_a_global_flag() {
+        let add = Cli::try_parse_from(["startup", "--dry-run", "add", "App", "app.exe"]).unwrap();
+        let gc = Cli::try_parse_from(["startup", "gc", "--dry-run"]).unwrap();
+        let kill = Cli::try_parse_from(["startup", "kill-all"]).unwrap();
+
+        assert!(add.dry_run);
+        assert!(matches!(gc.command, Commands::Gc));
+        assert!(gc.dry_run);
+        assert!(!kill.dry_run);
+    }
+}
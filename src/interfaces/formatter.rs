@@ -0,0 +1,798 @@
+use super::cli::OutputFormat;
+use super::presenter::{ConsolePresenter, Presenter};
+use super::schema::*;
+use crate::application::{EditedEntry, ImportOutcome, Plan, ProcessKillResult};
+use crate::domain::{AuditEvent, AuditOutcome, HistoryRecord, StartupEntry};
+use crate::shared::error::StartupError;
+use serde::Serialize;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Prints the results of commands in one `--output` format: results to
+/// standard output and errors to standard error.
+pub trait OutputFormatter {
+    fn added(&self, name: &str, replaced: Option<&StartupEntry>) -> io::Result<()>;
+    fn added_command(
+        &self,
+        name: &str,
+        command: &str,
+        workdir: Option<&str>,
+        replaced: Option<&StartupEntry>,
+    ) -> io::Result<()>;
+    fn edited(&self, edited: &EditedEntry) -> io::Result<()>;
+    fn renamed(&self, name: &str, renamed: &StartupEntry) -> io::Result<()>;
+    fn removed(&self, name: &str, deleted_launchers: &[String]) -> io::Result<()>;
+    fn set_enabled(&self, name: &str, enabled: bool, changed: bool) -> io::Result<()>;
+    fn entries(&self, entries: &[EntryView]) -> io::Result<()>;
+    fn gc(&self, orphans: &[String], dry_run: bool) -> io::Result<()>;
+    fn plan(&self, plan: &Plan) -> io::Result<()>;
+    /// Called with the plan `apply` is about to carry out.
+    fn applying(&self, plan: &Plan) -> io::Result<()>;
+    fn applied(&self, plan: &Plan, deleted_launchers: &[String]) -> io::Result<()>;
+    fn exported(&self, count: usize, skipped: usize, file: &Path) -> io::Result<()>;
+    fn imported(&self, outcomes: &[ImportOutcome]) -> io::Result<()>;
+    fn history(&self, records: &[HistoryRecord]) -> io::Result<()>;
+    fn undone(&self, records: &[HistoryRecord]) -> io::Result<()>;
+    fn audit_events(&self, events: &[AuditEvent]) -> io::Result<()>;
+    fn killed(&self, name: &str, results: &[ProcessKillResult]) -> io::Result<()>;
+    fn killed_all(&self, results: &[(String, Vec<ProcessKillResult>)]) -> io::Result<()>;
+    fn dry_run(&self, report: &DryRunView) -> io::Result<()>;
+    /// Reports a failed command. Errors writing the report are ignored, as
+    /// there is nowhere left to report them.
+    fn error(&self, error: &StartupError);
+}
+
+impl<P: Presenter> OutputFormatter for P {
+    fn added(&self, name: &str, replaced: Option<&StartupEntry>) -> io::Result<()> {
+        self.show_success_add(&mut io::stdout().lock(), name, replaced)
+    }
+
+    fn added_command(
+        &self,
+        name: &str,
+        command: &str,
+        workdir: Option<&str>,
+        replaced: Option<&StartupEntry>,
+    ) -> io::Result<()> {
+        self.show_success_add_command(&mut io::stdout().lock(), name, command, workdir, replaced)
+    }
+
+    fn edited(&self, edited: &EditedEntry) -> io::Result<()> {
+        self.show_success_edit(&mut io::stdout().lock(), edited)
+    }
+
+    fn renamed(&self, name: &str, renamed: &StartupEntry) -> io::Result<()> {
+        self.show_success_rename(&mut io::stdout().lock(), name, renamed)
+    }
+
+    fn removed(&self, name: &str, deleted_launchers: &[String]) -> io::Result<()> {
+        self.show_success_remove(&mut io::stdout().lock(), name, deleted_launchers)
+    }
+
+    fn set_enabled(&self, name: &str, enabled: bool, changed: bool) -> io::Result<()> {
+        self.show_success_set_enabled(&mut io::stdout().lock(), name, enabled, changed)
+    }
+
+    fn entries(&self, entries: &[EntryView]) -> io::Result<()> {
+        self.show_entries(&mut io::stdout().lock(), entries)
+    }
+
+    fn gc(&self, orphans: &[String], dry_run: bool) -> io::Result<()> {
+        self.show_gc_result(&mut io::stdout().lock(), orphans, dry_run)
+    }
+
+    fn plan(&self, plan: &Plan) -> io::Result<()> {
+        self.show_plan(&mut io::stdout().lock(), plan)
+    }
+
+    fn applying(&self, plan: &Plan) -> io::Result<()> {
+        self.show_apply_plan(&mut io::stdout().lock(), plan)
+    }
+
+    fn applied(&self, plan: &Plan, deleted_launchers: &[String]) -> io::Result<()> {
+        self.show_apply_result(&mut io::stdout().lock(), plan, deleted_launchers)
+    }
+
+    fn exported(&self, count: usize, skipped: usize, file: &Path) -> io::Result<()> {
+        self.show_export_success(&mut io::stdout().lock(), count, skipped, file)
+    }
+
+    fn imported(&self, outcomes: &[ImportOutcome]) -> io::Result<()> {
+        self.show_import_result(&mut io::stdout().lock(), outcomes)
+    }
+
+    fn history(&self, records: &[HistoryRecord]) -> io::Result<()> {
+        self.show_history(&mut io::stdout().lock(), records)
+    }
+
+    fn undone(&self, records: &[HistoryRecord]) -> io::Result<()> {
+        self.show_undo_result(&mut io::stdout().lock(), records)
+    }
+
+    fn audit_events(&self, events: &[AuditEvent]) -> io::Result<()> {
+        self.show_audit_events(&mut io::stdout().lock(), events)
+    }
+
+    fn killed(&self, name: &str, results: &[ProcessKillResult]) -> io::Result<()> {
+        self.show_kill_success(&mut io::stdout().lock(), name, results)
+    }
+
+    fn killed_all(&self, results: &[(String, Vec<ProcessKillResult>)]) -> io::Result<()> {
+        self.show_kill_all_success(&mut io::stdout().lock(), results)
+    }
+
+    fn dry_run(&self, report: &DryRunView) -> io::Result<()> {
+        self.show_dry_run(&mut io::stdout().lock(), report)
+    }
+
+    fn error(&self, error: &StartupError) {
+        self.show_error(&mut io::stderr().lock(), error).ok();
+    }
+}
+
+/// Returns the formatter of `format`. `color` only applies to `table`.
+pub fn formatter(format: OutputFormat, color: bool) -> Box<dyn OutputFormatter> {
+    match format {
+        OutputFormat::Table => Box::new(ConsolePresenter::new(color)),
+        OutputFormat::Plain => Box::new(PlainFormatter),
+        OutputFormat::Json => Box::new(StructuredFormatter::Json),
+        OutputFormat::Yaml => Box::new(StructuredFormatter::Yaml),
+    }
+}
+
+/// Formatter of commands run with `--dry-run`. Their results describe
+/// changes that were never made, so only the dry-run report and errors are
+/// passed on to the wrapped formatter.
+pub struct DryRunFormatter<'a>(pub &'a dyn OutputFormatter);
+
+impl OutputFormatter for DryRunFormatter<'_> {
+    fn added(&self, _name: &str, _replaced: Option<&StartupEntry>) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn added_command(
+        &self,
+        _name: &str,
+        _command: &str,
+        _workdir: Option<&str>,
+        _replaced: Option<&StartupEntry>,
+    ) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn edited(&self, _edited: &EditedEntry) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn renamed(&self, _name: &str, _renamed: &StartupEntry) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn removed(&self, _name: &str, _deleted_launchers: &[String]) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn set_enabled(&self, _name: &str, _enabled: bool, _changed: bool) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn entries(&self, entries: &[EntryView]) -> io::Result<()> {
+        self.0.entries(entries)
+    }
+
+    fn gc(&self, orphans: &[String], dry_run: bool) -> io::Result<()> {
+        self.0.gc(orphans, dry_run)
+    }
+
+    fn plan(&self, plan: &Plan) -> io::Result<()> {
+        self.0.plan(plan)
+    }
+
+    fn applying(&self, _plan: &Plan) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn applied(&self, _plan: &Plan, _deleted_launchers: &[String]) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn exported(&self, count: usize, skipped: usize, file: &Path) -> io::Result<()> {
+        self.0.exported(count, skipped, file)
+    }
+
+    fn imported(&self, _outcomes: &[ImportOutcome]) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn history(&self, records: &[HistoryRecord]) -> io::Result<()> {
+        self.0.history(records)
+    }
+
+    fn undone(&self, _records: &[HistoryRecord]) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn audit_events(&self, events: &[AuditEvent]) -> io::Result<()> {
+        self.0.audit_events(events)
+    }
+
+    fn killed(&self, _name: &str, _results: &[ProcessKillResult]) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn killed_all(&self, _results: &[(String, Vec<ProcessKillResult>)]) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn dry_run(&self, report: &DryRunView) -> io::Result<()> {
+        self.0.dry_run(report)
+    }
+
+    fn error(&self, error: &StartupError) {
+        self.0.error(error)
+    }
+}
+
+/// Prints every result as a versioned JSON or YAML document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructuredFormatter {
+    Json,
+    Yaml,
+}
+
+impl StructuredFormatter {
+    pub fn render<T: Serialize>(&self, document: &Document<T>) -> String {
+        match self {
+            StructuredFormatter::Json => {
+                serde_json::to_string_pretty(document).unwrap_or_default() + "\n"
+            }
+            StructuredFormatter::Yaml => serde_yaml::to_string(document).unwrap_or_default(),
+        }
+    }
+
+    fn write<T: Serialize>(
+        &self,
+        out: &mut dyn Write,
+        schema: &'static str,
+        data: T,
+    ) -> io::Result<()> {
+        out.write_all(self.render(&Document::new(schema, data)).as_bytes())
+    }
+}
+
+impl Presenter for StructuredFormatter {
+    fn show_success_add(
+        &self,
+        out: &mut dyn Write,
+        name: &str,
+        replaced: Option<&StartupEntry>,
+    ) -> io::Result<()> {
+        self.write(
+            out,
+            "add",
+            AddedView {
+                name: name.to_string(),
+                command: None,
+                workdir: None,
+                replaced: replaced.map(|entry| entry.command.clone()),
+            },
+        )
+    }
+
+    fn show_success_add_command(
+        &self,
+        out: &mut dyn Write,
+        name: &str,
+        command: &str,
+        workdir: Option<&str>,
+        replaced: Option<&StartupEntry>,
+    ) -> io::Result<()> {
+        self.write(
+            out,
+            "add",
+            AddedView {
+                name: name.to_string(),
+                command: Some(command.to_string()),
+                workdir: workdir.map(str::to_string),
+                replaced: replaced.map(|entry| entry.command.clone()),
+            },
+        )
+    }
+
+    fn show_success_edit(&self, out: &mut dyn Write, edited: &EditedEntry) -> io::Result<()> {
+        self.write(out, "edit", EditedView::new(edited))
+    }
+
+    fn show_success_rename(
+        &self,
+        out: &mut dyn Write,
+        name: &str,
+        renamed: &StartupEntry,
+    ) -> io::Result<()> {
+        self.write(
+            out,
+            "rename",
+            RenamedView {
+                name: name.to_string(),
+                new_name: renamed.name.clone(),
+                location: renamed.location,
+                value: renamed.command.clone(),
+            },
+        )
+    }
+
+    fn show_success_remove(
+        &self,
+        out: &mut dyn Write,
+        name: &str,
+        deleted_launchers: &[String],
+    ) -> io::Result<()> {
+        self.write(
+            out,
+            "remove",
+            RemovedView {
+                name: name.to_string(),
+                deleted_launchers: deleted_launchers.to_vec(),
+            },
+        )
+    }
+
+    fn show_success_set_enabled(
+        &self,
+        out: &mut dyn Write,
+        name: &str,
+        enabled: bool,
+        changed: bool,
+    ) -> io::Result<()> {
+        self.write(
+            out,
+            "set-enabled",
+            EnabledView {
+                name: name.to_string(),
+                enabled,
+                changed,
+            },
+        )
+    }
+
+    fn show_entries(&self, out: &mut dyn Write, entries: &[EntryView]) -> io::Result<()> {
+        self.write(
+            out,
+            "entries",
+            EntriesView {
+                entries: entries.to_vec(),
+            },
+        )
+    }
+
+    fn show_gc_result(
+        &self,
+        out: &mut dyn Write,
+        orphans: &[String],
+        dry_run: bool,
+    ) -> io::Result<()> {
+        self.write(
+            out,
+            "gc",
+            GcView {
+                dry_run,
+                launchers: orphans.to_vec(),
+            },
+        )
+    }
+
+    fn show_plan(&self, out: &mut dyn Write, plan: &Plan) -> io::Result<()> {
+        self.write(out, "plan", PlanView::new(plan, &[]))
+    }
+
+    fn show_apply_result(
+        &self,
+        out: &mut dyn Write,
+        plan: &Plan,
+        deleted_launchers: &[String],
+    ) -> io::Result<()> {
+        self.write(out, "apply", PlanView::new(plan, deleted_launchers))
+    }
+
+    fn show_export_success(
+        &self,
+        out: &mut dyn Write,
+        count: usize,
+        skipped: usize,
+        file: &Path,
+    ) -> io::Result<()> {
+        self.write(out, "export", ExportView::new(count, skipped, file))
+    }
+
+    fn show_import_result(
+        &self,
+        out: &mut dyn Write,
+        outcomes: &[ImportOutcome],
+    ) -> io::Result<()> {
+        self.write(
+            out,
+            "import",
+            ImportView {
+                outcomes: outcomes.iter().map(ImportOutcomeView::new).collect(),
+            },
+        )
+    }
+
+    fn show_history(&self, out: &mut dyn Write, records: &[HistoryRecord]) -> io::Result<()> {
+        self.write(
+            out,
+            "history",
+            HistoryView {
+                records: records.to_vec(),
+            },
+        )
+    }
+
+    fn show_undo_result(&self, out: &mut dyn Write, records: &[HistoryRecord]) -> io::Result<()> {
+        self.write(
+            out,
+            "undo",
+            HistoryView {
+                records: records.to_vec(),
+            },
+        )
+    }
+
+    fn show_audit_events(&self, out: &mut dyn Write, events: &[AuditEvent]) -> io::Result<()> {
+        self.write(
+            out,
+            "audit-log",
+            AuditView {
+                events: events.to_vec(),
+            },
+        )
+    }
+
+    fn show_kill_success(
+        &self,
+        out: &mut dyn Write,
+        name: &str,
+        results: &[ProcessKillResult],
+    ) -> io::Result<()> {
+        self.write(
+            out,
+            "kill-results",
+            KillResultsView {
+                results: vec![KillView::new(name, results)],
+            },
+        )
+    }
+
+    fn show_kill_all_success(
+        &self,
+        out: &mut dyn Write,
+        results: &[(String, Vec<ProcessKillResult>)],
+    ) -> io::Result<()> {
+        self.write(
+            out,
+            "kill-results",
+            KillResultsView {
+                results: results
+                    .iter()
+                    .map(|(label, processes)| KillView::new(label, processes))
+                    .collect(),
+            },
+        )
+    }
+
+    fn show_dry_run(&self, out: &mut dyn Write, report: &DryRunView) -> io::Result<()> {
+        self.write(out, "dry-run", report)
+    }
+
+    fn show_error(&self, out: &mut dyn Write, error: &StartupError) -> io::Result<()> {
+        self.write(out, "error", ErrorView::new(error))
+    }
+}
+
+/// Prints results as uncolored, tab-separated lines without headers, one
+/// record per line. Missing values are written as `-`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlainFormatter;
+
+impl PlainFormatter {
+    /// `name location enabled|disabled running|stopped|unknown pids value`,
+    /// with the PIDs separated by commas.
+    pub fn entry_line(entry: &EntryView) -> String {
+        let running = match entry.running {
+            Some(true) => "running",
+            Some(false) => "stopped",
+            None => "unknown",
+        };
+        let pids: Vec<String> = entry.processes.iter().map(|p| p.pid.to_string()).collect();
+        Self::line(&[
+            &entry.name,
+            entry.location.as_str(),
+            Self::state(entry.enabled),
+            running,
+            &pids.join(","),
+            &entry.value,
+        ])
+    }
+
+    /// `name replaced-value`, with `-` when nothing was replaced.
+    pub fn added_line(name: &str, replaced: Option<&StartupEntry>) -> String {
+        Self::line(&[name, replaced.map_or("", |entry| entry.command.as_str())])
+    }
+
+    /// `entry pid name outcome`, one line per process.
+    pub fn kill_lines(kill: &KillView) -> Vec<String> {
+        kill.processes
+            .iter()
+            .map(|process| {
+                Self::line(&[
+                    &kill.entry,
+                    &process.pid.to_string(),
+                    &process.name,
+                    process.outcome,
+                ])
+            })
+            .collect()
+    }
+
+    /// `action name location fields`, with the fields separated by commas.
+    pub fn plan_lines(plan: &PlanView) -> Vec<String> {
+        plan.changes
+            .iter()
+            .map(|change| {
+                Self::line(&[
+                    change.action,
+                    &change.name,
+                    change.location.as_str(),
+                    &change.fields.join(","),
+                ])
+            })
+            .collect()
+    }
+
+    /// `entry action name location value`, `launcher action file` and
+    /// `process pid name`, in that order.
+    pub fn dry_run_lines(report: &DryRunView) -> Vec<String> {
+        let entries = report.entries.iter().map(|entry| {
+            Self::line(&[
+                "entry",
+                entry.action,
+                &entry.name,
+                entry.location.as_str(),
+                &entry.value,
+            ])
+        });
+        let launchers = report
+            .launchers
+            .iter()
+            .map(|launcher| Self::line(&["launcher", launcher.action, &launcher.file]));
+        let processes = report
+            .processes
+            .iter()
+            .map(|process| Self::line(&["process", &process.pid.to_string(), &process.name]));
+        entries.chain(launchers).chain(processes).collect()
+    }
+
+    fn write_lines(out: &mut dyn Write, lines: &[String]) -> io::Result<()> {
+        for line in lines {
+            writeln!(out, "{}", line)?;
+        }
+        Ok(())
+    }
+
+    fn write_history(out: &mut dyn Write, records: &[HistoryRecord]) -> io::Result<()> {
+        for record in records {
+            writeln!(
+                out,
+                "{}",
+                Self::line(&[
+                    &record.id.to_string(),
+                    &record.at.to_rfc3339(),
+                    &record.command,
+                ])
+            )?;
+        }
+        Ok(())
+    }
+
+    fn line(fields: &[&str]) -> String {
+        // Tabs and line breaks inside a value would split the record
+        let fields: Vec<String> = fields
+            .iter()
+            .map(|field| {
+                if field.is_empty() {
+                    "-".to_string()
+                } else {
+                    field.replace(['\t', '\r', '\n'], " ")
+                }
+            })
+            .collect();
+        fields.join("\t")
+    }
+
+    fn state(enabled: bool) -> &'static str {
+        if enabled { "enabled" } else { "disabled" }
+    }
+}
+
+impl Presenter for PlainFormatter {
+    fn show_success_add(
+        &self,
+        out: &mut dyn Write,
+        name: &str,
+        replaced: Option<&StartupEntry>,
+    ) -> io::Result<()> {
+        writeln!(out, "{}", Self::added_line(name, replaced))
+    }
+
+    fn show_success_add_command(
+        &self,
+        out: &mut dyn Write,
+        name: &str,
+        _command: &str,
+        _workdir: Option<&str>,
+        replaced: Option<&StartupEntry>,
+    ) -> io::Result<()> {
+        writeln!(out, "{}", Self::added_line(name, replaced))
+    }
+
+    fn show_success_edit(&self, out: &mut dyn Write, edited: &EditedEntry) -> io::Result<()> {
+        writeln!(
+            out,
+            "{}",
+            Self::line(&[&edited.after.name, &edited.after.command])
+        )
+    }
+
+    fn show_success_rename(
+        &self,
+        out: &mut dyn Write,
+        name: &str,
+        renamed: &StartupEntry,
+    ) -> io::Result<()> {
+        writeln!(out, "{}", Self::line(&[name, &renamed.name]))
+    }
+
+    fn show_success_remove(
+        &self,
+        out: &mut dyn Write,
+        name: &str,
+        _deleted_launchers: &[String],
+    ) -> io::Result<()> {
+        writeln!(out, "{}", Self::line(&[name]))
+    }
+
+    fn show_success_set_enabled(
+        &self,
+        out: &mut dyn Write,
+        name: &str,
+        enabled: bool,
+        changed: bool,
+    ) -> io::Result<()> {
+        let changed = if changed { "changed" } else { "unchanged" };
+        writeln!(
+            out,
+            "{}",
+            Self::line(&[name, Self::state(enabled), changed])
+        )
+    }
+
+    fn show_entries(&self, out: &mut dyn Write, entries: &[EntryView]) -> io::Result<()> {
+        let lines: Vec<String> = entries.iter().map(Self::entry_line).collect();
+        Self::write_lines(out, &lines)
+    }
+
+    fn show_gc_result(
+        &self,
+        out: &mut dyn Write,
+        orphans: &[String],
+        _dry_run: bool,
+    ) -> io::Result<()> {
+        let lines: Vec<String> = orphans.iter().map(|o| Self::line(&[o])).collect();
+        Self::write_lines(out, &lines)
+    }
+
+    fn show_plan(&self, out: &mut dyn Write, plan: &Plan) -> io::Result<()> {
+        Self::write_lines(out, &Self::plan_lines(&PlanView::new(plan, &[])))
+    }
+
+    fn show_apply_result(
+        &self,
+        out: &mut dyn Write,
+        plan: &Plan,
+        deleted_launchers: &[String],
+    ) -> io::Result<()> {
+        Self::write_lines(
+            out,
+            &Self::plan_lines(&PlanView::new(plan, deleted_launchers)),
+        )
+    }
+
+    fn show_export_success(
+        &self,
+        out: &mut dyn Write,
+        count: usize,
+        skipped: usize,
+        file: &Path,
+    ) -> io::Result<()> {
+        let file = file.display().to_string();
+        writeln!(
+            out,
+            "{}",
+            Self::line(&[&count.to_string(), &skipped.to_string(), &file])
+        )
+    }
+
+    fn show_import_result(
+        &self,
+        out: &mut dyn Write,
+        outcomes: &[ImportOutcome],
+    ) -> io::Result<()> {
+        for outcome in outcomes.iter().map(ImportOutcomeView::new) {
+            let from = outcome.renamed_from.unwrap_or_default();
+            writeln!(
+                out,
+                "{}",
+                Self::line(&[outcome.outcome, &outcome.name, &from])
+            )?;
+        }
+        Ok(())
+    }
+
+    fn show_history(&self, out: &mut dyn Write, records: &[HistoryRecord]) -> io::Result<()> {
+        Self::write_history(out, records)
+    }
+
+    fn show_undo_result(&self, out: &mut dyn Write, records: &[HistoryRecord]) -> io::Result<()> {
+        Self::write_history(out, records)
+    }
+
+    fn show_audit_events(&self, out: &mut dyn Write, events: &[AuditEvent]) -> io::Result<()> {
+        for event in events {
+            let outcome = match event.outcome {
+                AuditOutcome::Success => "success",
+                AuditOutcome::Failure => "failure",
+            };
+            writeln!(
+                out,
+                "{}",
+                Self::line(&[
+                    &event.at.to_rfc3339(),
+                    &event.user,
+                    &event.host,
+                    event.action.as_str(),
+                    event.entry.as_deref().unwrap_or_default(),
+                    outcome,
+                ])
+            )?;
+        }
+        Ok(())
+    }
+
+    fn show_kill_success(
+        &self,
+        out: &mut dyn Write,
+        name: &str,
+        results: &[ProcessKillResult],
+    ) -> io::Result<()> {
+        Self::write_lines(out, &Self::kill_lines(&KillView::new(name, results)))
+    }
+
+    fn show_kill_all_success(
+        &self,
+        out: &mut dyn Write,
+        results: &[(String, Vec<ProcessKillResult>)],
+    ) -> io::Result<()> {
+        for (label, processes) in results {
+            Self::write_lines(out, &Self::kill_lines(&KillView::new(label, processes)))?;
+        }
+        Ok(())
+    }
+
+    fn show_dry_run(&self, out: &mut dyn Write, report: &DryRunView) -> io::Result<()> {
+        Self::write_lines(out, &Self::dry_run_lines(report))
+    }
+
+    fn show_error(&self, out: &mut dyn Write, error: &StartupError) -> io::Result<()> {
+        writeln!(out, "{}", Self::line(&[error.kind(), &error.to_string()]))
+    }
+}
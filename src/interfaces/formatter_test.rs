@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod tests {
+    use super::super::formatter::*;
+    use super::super::schema::*;
+    use crate::application::{KillOutcome, ProcessKillResult};
+    use crate::domain::{ProcessInfo, StartupEntry};
+
+    fn view() -> GcView {
+        GcView {
+            dry_run: true,
+            launchers: vec!["dev_1.vbs".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_structured_documents_start_with_schema_and_version() {
+        let document = Document::new("gc", view());
+
+        assert_eq!(
+            StructuredFormatter::Json.render(&document),
+            "{\n  \"schema\": \"gc\",\n  \"version\": 1,\n  \"dry-run\": true,\n  \
+             \"launchers\": [\n    \"dev_1.vbs\"\n  ]\n}\n"
+        );
+        assert_eq!(
+            StructuredFormatter::Yaml.render(&document),
+            "schema: gc\nversion: 1\ndry-run: true\nlaunchers:\n- dev_1.vbs\n"
+        );
+    }
+
+    #[test]
+    fn test_plain_entries_are_tab_separated() {
+        let entry = StartupEntry::new("Notes", "notepad.exe\tC:\\notes.txt");
+        let running = [ProcessInfo::new(10, "notepad.exe", "notepad.exe")];
+
+        assert_eq!(
+            PlainFormatter::entry_line(&EntryView::new(&entry, &running)),
+            "Notes\thkcu-run\tenabled\tstopped\t-\tnotepad.exe C:\\notes.txt"
+        );
+    }
+
+    #[test]
+    fn test_plain_kill_results_have_one_line_per_process() {
+        let results = [
+            ProcessKillResult {
+                pid: 10,
+                name: "bun.exe".to_string(),
+                outcome: KillOutcome::Closed,
+            },
+            ProcessKillResult {
+                pid: 11,
+                name: "node.exe".to_string(),
+                outcome: KillOutcome::Failed("access denied".to_string()),
+            },
+        ];
+
+        assert_eq!(
+            PlainFormatter::kill_lines(&KillView::new("Dev", &results)),
+            ["Dev\t10\tbun.exe\tclosed", "Dev\t11\tnode.exe\tfailed"]
+        );
+    }
+}
@@ -1,5 +1,20 @@
 pub mod cli;
+pub mod formatter;
 pub mod presenter;
+pub mod schema;
 
-pub use cli::{Cli, Commands};
-pub use presenter::ConsolePresenter;
+#[cfg(test)]
+mod cli_test;
+#[cfg(test)]
+mod formatter_test;
+#[cfg(test)]
+mod presenter_test;
+#[cfg(test)]
+mod schema_test;
+
+pub use cli::{Cli, ColorChoice, Commands, ExportFormat, OutputFormat};
+pub use formatter::{
+    DryRunFormatter, OutputFormatter, PlainFormatter, StructuredFormatter, formatter,
+};
+pub use presenter::{ConsolePresenter, Presenter};
+pub use schema::{DryRunView, EntryView};
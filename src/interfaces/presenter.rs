@@ -1,136 +1,1067 @@
-use crate::domain::StartupEntry;
-use crate::infrastructure::ProcessManager;
+use super::schema::{DryRunView, EntryView};
+use crate::application::{
+    EditedEntry, ImportOutcome, KillOutcome, Plan, PlanField, PlannedChange, ProcessKillResult,
+};
+use crate::domain::scheduled_task::format_duration;
+use crate::domain::{
+    AuditEvent, AuditOutcome, ConsoleOptions, ExecutionMode, HistoryRecord, SnapshotChange,
+    StartupEntry, StartupLocation, TaskSettings,
+};
+use crate::shared::error::StartupError;
+use chrono::{DateTime, FixedOffset, Local, Utc};
 use colored::*;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Writes the result of each command to `out`.
+pub trait Presenter {
+    /// `replaced` is the entry the new one replaced, if any.
+    fn show_success_add(
+        &self,
+        out: &mut dyn Write,
+        name: &str,
+        replaced: Option<&StartupEntry>,
+    ) -> io::Result<()>;
+    fn show_success_add_command(
+        &self,
+        out: &mut dyn Write,
+        name: &str,
+        command: &str,
+        workdir: Option<&str>,
+        replaced: Option<&StartupEntry>,
+    ) -> io::Result<()>;
+    fn show_success_edit(&self, out: &mut dyn Write, edited: &EditedEntry) -> io::Result<()>;
+    /// `renamed` is the entry under its new name.
+    fn show_success_rename(
+        &self,
+        out: &mut dyn Write,
+        name: &str,
+        renamed: &StartupEntry,
+    ) -> io::Result<()>;
+    fn show_success_remove(
+        &self,
+        out: &mut dyn Write,
+        name: &str,
+        deleted_launchers: &[String],
+    ) -> io::Result<()>;
+    fn show_success_set_enabled(
+        &self,
+        out: &mut dyn Write,
+        name: &str,
+        enabled: bool,
+        changed: bool,
+    ) -> io::Result<()>;
+    fn show_entries(&self, out: &mut dyn Write, entries: &[EntryView]) -> io::Result<()>;
+    fn show_gc_result(
+        &self,
+        out: &mut dyn Write,
+        orphans: &[String],
+        dry_run: bool,
+    ) -> io::Result<()>;
+    fn show_plan(&self, out: &mut dyn Write, plan: &Plan) -> io::Result<()>;
+    /// Shows the plan `apply` is about to carry out.
+    fn show_apply_plan(&self, _out: &mut dyn Write, _plan: &Plan) -> io::Result<()> {
+        Ok(())
+    }
+    fn show_apply_result(
+        &self,
+        out: &mut dyn Write,
+        plan: &Plan,
+        deleted_launchers: &[String],
+    ) -> io::Result<()>;
+    fn show_export_success(
+        &self,
+        out: &mut dyn Write,
+        count: usize,
+        skipped: usize,
+        file: &Path,
+    ) -> io::Result<()>;
+    fn show_import_result(&self, out: &mut dyn Write, outcomes: &[ImportOutcome])
+    -> io::Result<()>;
+    fn show_history(&self, out: &mut dyn Write, records: &[HistoryRecord]) -> io::Result<()>;
+    fn show_undo_result(&self, out: &mut dyn Write, records: &[HistoryRecord]) -> io::Result<()>;
+    fn show_audit_events(&self, out: &mut dyn Write, events: &[AuditEvent]) -> io::Result<()>;
+    fn show_kill_success(
+        &self,
+        out: &mut dyn Write,
+        name: &str,
+        results: &[ProcessKillResult],
+    ) -> io::Result<()>;
+    fn show_kill_all_success(
+        &self,
+        out: &mut dyn Write,
+        results: &[(String, Vec<ProcessKillResult>)],
+    ) -> io::Result<()>;
+    /// Shows what a command run with `--dry-run` would have changed.
+    fn show_dry_run(&self, out: &mut dyn Write, report: &DryRunView) -> io::Result<()>;
+    fn show_error(&self, out: &mut dyn Write, error: &StartupError) -> io::Result<()>;
+}
 
 /// Handles output presentation to the user.
-pub struct ConsolePresenter;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConsolePresenter {
+    color: bool,
+    timezone: Option<FixedOffset>,
+}
 
 impl ConsolePresenter {
-    pub fn show_success_add(name: &str) {
-        println!(
+    /// Creates a presenter that colors its output when `color` is set.
+    pub fn new(color: bool) -> Self {
+        Self {
+            color,
+            timezone: None,
+        }
+    }
+
+    /// Shows times at `offset` instead of in the local time zone.
+    pub fn with_timezone(mut self, offset: FixedOffset) -> Self {
+        self.timezone = Some(offset);
+        self
+    }
+
+    fn paint(&self, text: ColoredString) -> String {
+        if self.color {
+            text.to_string()
+        } else {
+            text.input
+        }
+    }
+
+    fn time(&self, at: DateTime<Utc>, format: &str) -> String {
+        match self.timezone {
+            Some(offset) => at.with_timezone(&offset).format(format).to_string(),
+            None => at.with_timezone(&Local).format(format).to_string(),
+        }
+    }
+
+    fn show_plan_header(
+        &self,
+        out: &mut dyn Write,
+        marker: ColoredString,
+        name: &str,
+        location: StartupLocation,
+    ) -> io::Result<()> {
+        writeln!(
+            out,
+            "  {} {} {}",
+            self.paint(marker),
+            self.paint(name.cyan().bold()),
+            self.paint(format!("({})", location.as_str()).bright_black())
+        )
+    }
+
+    fn show_plan_line(&self, out: &mut dyn Write, label: &str, value: &str) -> io::Result<()> {
+        writeln!(
+            out,
+            "      {} {}",
+            self.paint(format!("{}:", label).dimmed()),
+            self.paint(value.white())
+        )
+    }
+
+    fn show_snapshot_change(&self, out: &mut dyn Write, change: &SnapshotChange) -> io::Result<()> {
+        match (&change.before, &change.after) {
+            (None, _) => {
+                self.show_plan_header(out, "+".green().bold(), change.name(), change.location())
+            }
+            (_, None) => {
+                self.show_plan_header(out, "-".red().bold(), change.name(), change.location())
+            }
+            (Some(before), Some(after)) => {
+                self.show_plan_header(out, "~".yellow().bold(), change.name(), change.location())?;
+                if before.value != after.value {
+                    self.show_plan_line(
+                        out,
+                        "Value",
+                        &format!("{} → {}", before.value, after.value),
+                    )?;
+                }
+                if before.enabled != after.enabled {
+                    let state = |enabled| if enabled { "enabled" } else { "disabled" };
+                    self.show_plan_line(
+                        out,
+                        "Startup",
+                        &format!("{} → {}", state(before.enabled), state(after.enabled)),
+                    )?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn show_replaced(
+        &self,
+        out: &mut dyn Write,
+        replaced: Option<&StartupEntry>,
+    ) -> io::Result<()> {
+        match replaced {
+            Some(entry) => writeln!(
+                out,
+                "  {} {} {}",
+                self.paint("Replaced:".dimmed()),
+                self.paint(entry.command.white()),
+                self.paint(format!("({})", entry.location.as_str()).bright_black())
+            ),
+            None => Ok(()),
+        }
+    }
+
+    fn show_kill_results(
+        &self,
+        out: &mut dyn Write,
+        results: &[ProcessKillResult],
+    ) -> io::Result<()> {
+        for result in results {
+            let outcome = match &result.outcome {
+                KillOutcome::Closed => "closed gracefully".green(),
+                KillOutcome::Killed => "force-killed".yellow(),
+                KillOutcome::Failed(reason) => format!("failed: {}", reason).red(),
+            };
+            writeln!(
+                out,
+                "    {} {} {} {}",
+                self.paint("PID:".dimmed()),
+                self.paint(result.pid.to_string().yellow()),
+                self.paint(result.name.white()),
+                self.paint(outcome)
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Describes an execution mode with the names `add-command --mode` takes.
+    fn describe_mode(mode: &ExecutionMode) -> String {
+        let (name, options) = match mode {
+            ExecutionMode::VBScript => ("vbscript", Vec::new()),
+            ExecutionMode::PowerShell(options) => {
+                let mut parts = vec![options.window.as_str().to_lowercase()];
+                if options.no_exit {
+                    parts.push("no-exit".to_string());
+                }
+                ("powershell", parts)
+            }
+            ExecutionMode::Visible(options) => (
+                "visible",
+                if options.no_exit {
+                    vec!["no-exit".to_string()]
+                } else {
+                    Vec::new()
+                },
+            ),
+            ExecutionMode::ScheduledTask(settings) => {
+                ("scheduled-task", Self::describe_task(settings))
+            }
+        };
+        if options.is_empty() {
+            name.to_string()
+        } else {
+            format!("{} ({})", name, options.join(", "))
+        }
+    }
+
+    /// Summarizes when and how a logon task runs.
+    fn describe_task(settings: &TaskSettings) -> Vec<String> {
+        let mut parts = vec![match settings.delay {
+            Some(delay) => format!("at logon after {}", format_duration(delay)),
+            None => "at logon".to_string(),
+        }];
+        if let Some(restart) = settings.restart {
+            parts.push(format!(
+                "restarts up to {} time(s) every {}",
+                restart.count,
+                format_duration(restart.interval)
+            ));
+        }
+        if settings.hidden {
+            parts.push("hidden".to_string());
+        }
+        if settings.highest_privileges {
+            parts.push("highest privileges".to_string());
+        }
+        parts
+    }
+}
+
+impl Presenter for ConsolePresenter {
+    fn show_success_add(
+        &self,
+        out: &mut dyn Write,
+        name: &str,
+        replaced: Option<&StartupEntry>,
+    ) -> io::Result<()> {
+        writeln!(
+            out,
             "{} Successfully added {} to startup.",
-            "✓".green().bold(),
-            name.cyan().bold()
-        );
+            self.paint("✓".green().bold()),
+            self.paint(name.cyan().bold())
+        )?;
+        self.show_replaced(out, replaced)
     }
 
-    pub fn show_success_add_command(name: &str, command: &str, workdir: Option<&str>) {
-        println!(
+    fn show_success_add_command(
+        &self,
+        out: &mut dyn Write,
+        name: &str,
+        command: &str,
+        workdir: Option<&str>,
+        replaced: Option<&StartupEntry>,
+    ) -> io::Result<()> {
+        writeln!(
+            out,
             "{} Successfully added command {} to startup.",
-            "✓".green().bold(),
-            name.cyan().bold()
-        );
+            self.paint("✓".green().bold()),
+            self.paint(name.cyan().bold())
+        )?;
+        self.show_replaced(out, replaced)?;
         if let Some(dir) = workdir {
-            println!("  {} {}", "Working directory:".dimmed(), dir.yellow());
+            writeln!(
+                out,
+                "  {} {}",
+                self.paint("Working directory:".dimmed()),
+                self.paint(dir.yellow())
+            )?;
+        }
+        writeln!(
+            out,
+            "  {} {}",
+            self.paint("Command:".dimmed()),
+            self.paint(command.white())
+        )
+    }
+
+    fn show_success_edit(&self, out: &mut dyn Write, edited: &EditedEntry) -> io::Result<()> {
+        let name = &edited.after.name;
+        if edited.before == edited.after {
+            return writeln!(
+                out,
+                "{} {} already runs that command.",
+                self.paint("○".bright_black()),
+                self.paint(name.cyan())
+            );
         }
-        println!("  {} {}", "Command:".dimmed(), command.white());
+        writeln!(
+            out,
+            "{} Successfully updated {}.",
+            self.paint("✓".green().bold()),
+            self.paint(name.cyan().bold())
+        )?;
+        if let Some(dir) = edited.command.workdir() {
+            writeln!(
+                out,
+                "  {} {}",
+                self.paint("Working directory:".dimmed()),
+                self.paint(dir.yellow())
+            )?;
+        }
+        writeln!(
+            out,
+            "  {} {}",
+            self.paint("Command:".dimmed()),
+            self.paint(edited.command.command_line().white())
+        )?;
+        if let Some(mode) = edited.command.mode() {
+            writeln!(
+                out,
+                "  {} {}",
+                self.paint("Mode:".dimmed()),
+                self.paint(Self::describe_mode(&mode).white())
+            )?;
+        }
+        Ok(())
+    }
+
+    fn show_success_rename(
+        &self,
+        out: &mut dyn Write,
+        name: &str,
+        renamed: &StartupEntry,
+    ) -> io::Result<()> {
+        writeln!(
+            out,
+            "{} Successfully renamed {} to {}.",
+            self.paint("✓".green().bold()),
+            self.paint(name.cyan()),
+            self.paint(renamed.name.cyan().bold())
+        )
     }
 
-    pub fn show_success_remove(name: &str) {
-        println!(
+    fn show_success_remove(
+        &self,
+        out: &mut dyn Write,
+        name: &str,
+        deleted_launchers: &[String],
+    ) -> io::Result<()> {
+        writeln!(
+            out,
             "{} Successfully removed {} from startup.",
-            "✓".green().bold(),
-            name.cyan().bold()
-        );
+            self.paint("✓".green().bold()),
+            self.paint(name.cyan().bold())
+        )?;
+        for launcher in deleted_launchers {
+            writeln!(
+                out,
+                "  {} {}",
+                self.paint("Deleted launcher:".dimmed()),
+                self.paint(launcher.white())
+            )?;
+        }
+        Ok(())
+    }
+
+    fn show_success_set_enabled(
+        &self,
+        out: &mut dyn Write,
+        name: &str,
+        enabled: bool,
+        changed: bool,
+    ) -> io::Result<()> {
+        let state = if enabled { "enabled" } else { "disabled" };
+        if changed {
+            writeln!(
+                out,
+                "{} Successfully {} {}.",
+                self.paint("✓".green().bold()),
+                state,
+                self.paint(name.cyan().bold())
+            )
+        } else {
+            writeln!(
+                out,
+                "{} {} is already {}.",
+                self.paint("○".bright_black()),
+                self.paint(name.cyan()),
+                state
+            )
+        }
     }
 
-    pub fn show_entries(entries: &[StartupEntry]) {
-        println!("\n{}", "Current startup programs:".bright_blue().bold());
-        println!("{}", "═".repeat(50).bright_black());
+    fn show_entries(&self, out: &mut dyn Write, entries: &[EntryView]) -> io::Result<()> {
+        writeln!(
+            out,
+            "\n{}",
+            self.paint("Current startup programs:".bright_blue().bold())
+        )?;
+        writeln!(out, "{}", self.paint("═".repeat(50).bright_black()))?;
 
         if entries.is_empty() {
-            println!("  {}", "No startup programs found.".yellow());
+            return writeln!(
+                out,
+                "  {}",
+                self.paint("No startup programs found.".yellow())
+            );
+        }
+
+        for (idx, entry) in entries.iter().enumerate() {
+            writeln!(
+                out,
+                "\n{} {} {}",
+                self.paint(format!("[{}]", idx + 1).bright_black()),
+                self.paint(entry.name.cyan().bold()),
+                self.paint(format!("({})", entry.location.as_str()).bright_black())
+            )?;
+            let mode = entry.execution_mode;
+            let task = matches!(mode, Some(ExecutionMode::ScheduledTask(_)));
+            let command = match (&entry.command, task) {
+                // Show the program a task runs rather than its XML definition
+                (Some(command), true) => command,
+                _ => &entry.value,
+            };
+            writeln!(
+                out,
+                "  {} {}",
+                self.paint("Command:".dimmed()),
+                self.paint(command.white())
+            )?;
+            if let Some(mode) = mode {
+                writeln!(
+                    out,
+                    "  {} {}",
+                    self.paint("Mode:".dimmed()),
+                    self.paint(Self::describe_mode(&mode).white())
+                )?;
+            }
+            if !entry.enabled {
+                let since = entry
+                    .disabled_at
+                    .map(|at| format!(" since {}", self.time(at, "%Y-%m-%d %H:%M")))
+                    .unwrap_or_default();
+                writeln!(
+                    out,
+                    "  {} {} {}",
+                    self.paint("Startup:".dimmed()),
+                    self.paint("✗".red()),
+                    self.paint(format!("Disabled{}", since).red())
+                )?;
+            }
+
+            // Show what wrapped commands actually run
+            let wrapped = entry.workdir.is_some()
+                || mode
+                    .is_some_and(|mode| mode != ExecutionMode::Visible(ConsoleOptions::default()));
+            if let Some(command) = &entry.command
+                && wrapped
+                && !task
+            {
+                writeln!(
+                    out,
+                    "  {} {}",
+                    self.paint("Runs:".dimmed()),
+                    self.paint(command.white())
+                )?;
+            }
+            if let Some(dir) = &entry.workdir {
+                writeln!(
+                    out,
+                    "  {} {}",
+                    self.paint("Working directory:".dimmed()),
+                    self.paint(dir.yellow())
+                )?;
+            }
+
+            match entry.running {
+                Some(true) => {
+                    writeln!(
+                        out,
+                        "  {} {} {}",
+                        self.paint("Status:".dimmed()),
+                        self.paint("✓".green().bold()),
+                        self.paint(
+                            format!("Running ({} process(es))", entry.processes.len()).green()
+                        )
+                    )?;
+                    for process in &entry.processes {
+                        writeln!(
+                            out,
+                            "    {} {}",
+                            self.paint("PID:".dimmed()),
+                            self.paint(process.pid.to_string().yellow())
+                        )?;
+                    }
+                }
+                Some(false) => writeln!(
+                    out,
+                    "  {} {} {}",
+                    self.paint("Status:".dimmed()),
+                    self.paint("○".bright_black()),
+                    self.paint("Not running".bright_black())
+                )?,
+                None => {}
+            }
+        }
+        writeln!(out, "\n{}", self.paint("─".repeat(50).bright_black()))?;
+        writeln!(
+            out,
+            "{} {} entries",
+            self.paint("Total:".dimmed()),
+            self.paint(entries.len().to_string().cyan().bold())
+        )
+    }
+
+    fn show_gc_result(
+        &self,
+        out: &mut dyn Write,
+        orphans: &[String],
+        dry_run: bool,
+    ) -> io::Result<()> {
+        if orphans.is_empty() {
+            return writeln!(
+                out,
+                "{} No orphaned launchers found.",
+                self.paint("✓".green().bold())
+            );
+        }
+
+        let (marker, verb) = if dry_run {
+            ("○".bright_black(), "Would delete")
         } else {
-            // Get running processes
-            let running_processes = ProcessManager::list_processes().unwrap_or_default();
-
-            for (idx, entry) in entries.iter().enumerate() {
-                println!(
-                    "\n{} {}",
-                    format!("[{}]", idx + 1).bright_black(),
-                    entry.name.cyan().bold()
-                );
-                println!("  {} {}", "Command:".dimmed(), entry.command.white());
-
-                // Check if process is running
-                if let Some(exe_name) = ProcessManager::extract_executable_name(&entry.command) {
-                    let matching_procs: Vec<_> = running_processes
-                        .iter()
-                        .filter(|p| p.name.eq_ignore_ascii_case(&exe_name))
-                        .collect();
-
-                    if !matching_procs.is_empty() {
-                        println!(
-                            "  {} {} {}",
-                            "Status:".dimmed(),
-                            "✓".green().bold(),
-                            format!("Running ({} process(es))", matching_procs.len()).green()
-                        );
-                        for proc in matching_procs {
-                            println!("    {} {}", "PID:".dimmed(), proc.pid.to_string().yellow());
-                        }
-                    } else {
-                        println!(
-                            "  {} {} {}",
-                            "Status:".dimmed(),
-                            "○".bright_black(),
-                            "Not running".bright_black()
-                        );
+            ("✓".green().bold(), "Deleted")
+        };
+        writeln!(
+            out,
+            "{} {} {}",
+            self.paint(marker),
+            verb,
+            self.paint(format!("{} orphaned launcher(s):", orphans.len()).yellow())
+        )?;
+        for launcher in orphans {
+            writeln!(out, "  {}", self.paint(launcher.white()))?;
+        }
+        Ok(())
+    }
+
+    fn show_plan(&self, out: &mut dyn Write, plan: &Plan) -> io::Result<()> {
+        if plan.is_empty() {
+            return writeln!(
+                out,
+                "{} No changes. Startup entries match the manifest.",
+                self.paint("✓".green().bold())
+            );
+        }
+
+        writeln!(
+            out,
+            "\n{}",
+            self.paint("Planned changes:".bright_blue().bold())
+        )?;
+        for change in &plan.changes {
+            match change {
+                PlannedChange::Add(desired) => {
+                    self.show_plan_header(
+                        out,
+                        "+".green().bold(),
+                        &desired.name,
+                        desired.location,
+                    )?;
+                    self.show_plan_line(out, "command", &desired.command.command_line())?;
+                    if let Some(dir) = desired.command.workdir() {
+                        self.show_plan_line(out, "working directory", dir)?;
+                    }
+                    if let Some(mode) = desired.command.mode() {
+                        self.show_plan_line(out, "mode", &Self::describe_mode(&mode))?;
+                    }
+                    if !desired.enabled {
+                        self.show_plan_line(out, "enabled", "false")?;
+                    }
+                }
+                PlannedChange::Change(change) => {
+                    let (current, desired) = (&change.current, &change.desired);
+                    self.show_plan_header(
+                        out,
+                        "~".yellow().bold(),
+                        &desired.name,
+                        desired.location,
+                    )?;
+                    let decoded = change.decoded.as_ref();
+                    let mode = |mode: Option<ExecutionMode>| {
+                        mode.map_or("none".to_string(), |mode| Self::describe_mode(&mode))
+                    };
+                    for field in &change.fields {
+                        let (label, from, to) = match field {
+                            PlanField::Command => (
+                                "command",
+                                decoded.map_or(current.command.clone(), |c| c.command_line()),
+                                desired.command.command_line(),
+                            ),
+                            PlanField::WorkingDirectory => (
+                                "working directory",
+                                decoded
+                                    .and_then(|c| c.workdir())
+                                    .unwrap_or("none")
+                                    .to_string(),
+                                desired.command.workdir().unwrap_or("none").to_string(),
+                            ),
+                            PlanField::Mode => (
+                                "mode",
+                                mode(decoded.and_then(|c| c.mode())),
+                                mode(desired.command.mode()),
+                            ),
+                            PlanField::Location => (
+                                "location",
+                                current.location.as_str().to_string(),
+                                desired.location.as_str().to_string(),
+                            ),
+                            PlanField::Enabled => (
+                                "enabled",
+                                current.enabled.to_string(),
+                                desired.enabled.to_string(),
+                            ),
+                        };
+                        self.show_plan_line(out, label, &format!("{} → {}", from, to))?;
                     }
                 }
+                PlannedChange::Remove(current) => {
+                    self.show_plan_header(out, "-".red().bold(), &current.name, current.location)?;
+                }
+            }
+        }
+
+        let (add, change, remove) = plan.counts();
+        writeln!(
+            out,
+            "\n{} {} to add, {} to change, {} to remove.",
+            self.paint("Plan:".bold()),
+            self.paint(add.to_string().green()),
+            self.paint(change.to_string().yellow()),
+            self.paint(remove.to_string().red())
+        )
+    }
+
+    fn show_apply_plan(&self, out: &mut dyn Write, plan: &Plan) -> io::Result<()> {
+        self.show_plan(out, plan)
+    }
+
+    fn show_apply_result(
+        &self,
+        out: &mut dyn Write,
+        plan: &Plan,
+        deleted_launchers: &[String],
+    ) -> io::Result<()> {
+        // The plan already said there is nothing to do
+        if plan.is_empty() {
+            return Ok(());
+        }
+
+        let (add, change, remove) = plan.counts();
+        writeln!(
+            out,
+            "{} Applied the manifest: {} added, {} changed, {} removed.",
+            self.paint("✓".green().bold()),
+            add,
+            change,
+            remove
+        )?;
+        for launcher in deleted_launchers {
+            writeln!(
+                out,
+                "  {} {}",
+                self.paint("Deleted launcher:".dimmed()),
+                self.paint(launcher.white())
+            )?;
+        }
+        Ok(())
+    }
+
+    fn show_export_success(
+        &self,
+        out: &mut dyn Write,
+        count: usize,
+        skipped: usize,
+        file: &Path,
+    ) -> io::Result<()> {
+        writeln!(
+            out,
+            "{} Exported {} entries to {}.",
+            self.paint("✓".green().bold()),
+            count,
+            self.paint(file.display().to_string().cyan())
+        )?;
+        if skipped > 0 {
+            writeln!(
+                out,
+                "  {}",
+                self.paint(
+                    format!("Skipped {} entries stored outside the registry.", skipped).yellow()
+                )
+            )?;
+        }
+        Ok(())
+    }
+
+    fn show_import_result(
+        &self,
+        out: &mut dyn Write,
+        outcomes: &[ImportOutcome],
+    ) -> io::Result<()> {
+        if outcomes.is_empty() {
+            return writeln!(out, "{} Nothing to import.", self.paint("✓".green().bold()));
+        }
+
+        let (mut imported, mut removed) = (0, 0);
+        for outcome in outcomes {
+            match outcome {
+                ImportOutcome::Added(name) => writeln!(
+                    out,
+                    "  {} {}",
+                    self.paint("+".green().bold()),
+                    self.paint(name.cyan().bold())
+                )?,
+                ImportOutcome::Replaced(name) => writeln!(
+                    out,
+                    "  {} {} {}",
+                    self.paint("~".yellow().bold()),
+                    self.paint(name.cyan().bold()),
+                    self.paint("(replaced the existing entry)".bright_black())
+                )?,
+                ImportOutcome::Renamed { from, to } => writeln!(
+                    out,
+                    "  {} {} {}",
+                    self.paint("+".green().bold()),
+                    self.paint(to.cyan().bold()),
+                    self.paint(format!("(renamed from {})", from).bright_black())
+                )?,
+                ImportOutcome::Skipped(name) => writeln!(
+                    out,
+                    "  {} {} {}",
+                    self.paint("○".bright_black()),
+                    self.paint(name.white()),
+                    self.paint("(skipped, the name is already used)".bright_black())
+                )?,
+                ImportOutcome::Removed(name) => writeln!(
+                    out,
+                    "  {} {}",
+                    self.paint("-".red().bold()),
+                    self.paint(name.cyan().bold())
+                )?,
+            }
+            match outcome {
+                ImportOutcome::Skipped(_) => {}
+                ImportOutcome::Removed(_) => removed += 1,
+                _ => imported += 1,
             }
-            println!("\n{}", "─".repeat(50).bright_black());
-            println!(
-                "{} {} entries",
-                "Total:".dimmed(),
-                entries.len().to_string().cyan().bold()
+        }
+        let mut summary = format!(
+            "Imported {} of {} entries",
+            imported,
+            outcomes.len() - removed
+        );
+        if removed > 0 {
+            summary.push_str(&format!(", removed {}", removed));
+        }
+        writeln!(out, "{} {}.", self.paint("✓".green().bold()), summary)
+    }
+
+    fn show_history(&self, out: &mut dyn Write, records: &[HistoryRecord]) -> io::Result<()> {
+        if records.is_empty() {
+            return writeln!(
+                out,
+                "{} No changes recorded yet.",
+                self.paint("○".bright_black())
             );
         }
+
+        for record in records {
+            let mut notes = Vec::new();
+            if !record.reverts.is_empty() {
+                let ids: Vec<_> = record.reverts.iter().map(|id| format!("#{}", id)).collect();
+                notes.push(format!("reverted {}", ids.join(", ")));
+            }
+            if record.undone {
+                notes.push("undone".to_string());
+            }
+            let notes = if notes.is_empty() {
+                String::new()
+            } else {
+                format!(" ({})", notes.join("; "))
+            };
+            writeln!(
+                out,
+                "{} {} {}{}",
+                self.paint(format!("#{}", record.id).yellow().bold()),
+                self.paint(self.time(record.at, "%Y-%m-%d %H:%M").dimmed()),
+                self.paint(record.command.white()),
+                self.paint(notes.bright_black())
+            )?;
+            for change in &record.changes {
+                self.show_snapshot_change(out, change)?;
+            }
+        }
+        Ok(())
     }
 
-    pub fn show_kill_success(name: &str, count: u32) {
-        if count > 0 {
-            println!(
-                "{} Killed {} for {}",
-                "✓".green().bold(),
-                format!("{} process(es)", count).yellow(),
-                name.cyan().bold()
+    fn show_undo_result(&self, out: &mut dyn Write, records: &[HistoryRecord]) -> io::Result<()> {
+        if records.is_empty() {
+            return writeln!(out, "{} Nothing to undo.", self.paint("○".bright_black()));
+        }
+
+        for record in records {
+            writeln!(
+                out,
+                "{} Undid {} {}",
+                self.paint("✓".green().bold()),
+                self.paint(format!("#{}", record.id).yellow().bold()),
+                self.paint(record.command.white())
+            )?;
+            for change in &record.changes {
+                self.show_snapshot_change(out, change)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn show_audit_events(&self, out: &mut dyn Write, events: &[AuditEvent]) -> io::Result<()> {
+        if events.is_empty() {
+            return writeln!(
+                out,
+                "{} No audit events found.",
+                self.paint("○".bright_black())
             );
-        } else {
-            println!(
+        }
+
+        for event in events {
+            let marker = match event.outcome {
+                AuditOutcome::Success => "✓".green().bold(),
+                AuditOutcome::Failure => "✗".red().bold(),
+            };
+            let entry = match (&event.entry, event.location) {
+                (Some(name), Some(location)) => format!(
+                    " {} {}",
+                    self.paint(name.cyan().bold()),
+                    self.paint(format!("({})", location.as_str()).bright_black())
+                ),
+                (Some(name), None) => format!(" {}", self.paint(name.cyan().bold())),
+                (None, _) => String::new(),
+            };
+            writeln!(
+                out,
+                "{} {} {} {}{}",
+                self.paint(marker),
+                self.paint(self.time(event.at, "%Y-%m-%d %H:%M:%S").dimmed()),
+                self.paint(format!("{}@{}", event.user, event.host).yellow()),
+                self.paint(event.action.as_str().white().bold()),
+                entry
+            )?;
+            for (label, value) in [
+                ("Old value", &event.old_value),
+                ("New value", &event.new_value),
+                ("Detail", &event.detail),
+            ] {
+                if let Some(value) = value {
+                    writeln!(
+                        out,
+                        "    {} {}",
+                        self.paint(format!("{}:", label).dimmed()),
+                        self.paint(value.white())
+                    )?;
+                }
+            }
+            if let Some(error) = &event.error {
+                writeln!(
+                    out,
+                    "    {} {}",
+                    self.paint("Error:".dimmed()),
+                    self.paint(error.red())
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn show_kill_success(
+        &self,
+        out: &mut dyn Write,
+        name: &str,
+        results: &[ProcessKillResult],
+    ) -> io::Result<()> {
+        if results.is_empty() {
+            return writeln!(
+                out,
                 "{} No running processes found for {}",
-                "○".bright_black(),
-                name.cyan()
+                self.paint("○".bright_black()),
+                self.paint(name.cyan())
             );
         }
+
+        let stopped = results.iter().filter(|r| r.is_stopped()).count();
+        if stopped == results.len() {
+            writeln!(
+                out,
+                "{} Stopped {} for {}",
+                self.paint("✓".green().bold()),
+                self.paint(format!("{} process(es)", stopped).yellow()),
+                self.paint(name.cyan().bold())
+            )?;
+        } else {
+            writeln!(
+                out,
+                "{} Stopped {} of {} process(es) for {}",
+                self.paint("✗".red().bold()),
+                self.paint(stopped.to_string().yellow()),
+                results.len(),
+                self.paint(name.cyan().bold())
+            )?;
+        }
+        self.show_kill_results(out, results)
     }
 
-    pub fn show_kill_all_success(results: &[(String, u32)]) {
+    fn show_kill_all_success(
+        &self,
+        out: &mut dyn Write,
+        results: &[(String, Vec<ProcessKillResult>)],
+    ) -> io::Result<()> {
         if results.is_empty() {
-            println!(
+            return writeln!(
+                out,
                 "{} No running processes found for any startup entries",
-                "○".bright_black()
+                self.paint("○".bright_black())
             );
-        } else {
-            println!("{} Killed processes:", "✓".green().bold());
-            let total: u32 = results.iter().map(|(_, count)| count).sum();
-            for (exe_name, count) in results {
-                println!(
-                    "  {} {}: {}",
-                    "•".bright_blue(),
-                    exe_name.white(),
-                    format!("{} process(es)", count).yellow()
-                );
-            }
-            println!(
-                "\n{} {} process(es) killed",
-                "Total:".dimmed(),
-                total.to_string().green().bold()
+        }
+
+        writeln!(out, "{} Stopped processes:", self.paint("✓".green().bold()))?;
+        let mut total = 0;
+        for (label, processes) in results {
+            writeln!(
+                out,
+                "\n  {} {}",
+                self.paint("•".bright_blue()),
+                self.paint(label.cyan().bold())
+            )?;
+            self.show_kill_results(out, processes)?;
+            total += processes.iter().filter(|r| r.is_stopped()).count();
+        }
+        writeln!(
+            out,
+            "\n{} {} process(es) stopped",
+            self.paint("Total:".dimmed()),
+            self.paint(total.to_string().green().bold())
+        )
+    }
+
+    fn show_dry_run(&self, out: &mut dyn Write, report: &DryRunView) -> io::Result<()> {
+        if report.is_empty() {
+            return writeln!(
+                out,
+                "{} Dry run: nothing would change.",
+                self.paint("○".bright_black())
             );
         }
+
+        writeln!(
+            out,
+            "{} Dry run: nothing was changed. The command would:",
+            self.paint("○".bright_black())
+        )?;
+        for entry in &report.entries {
+            let (marker, label, value) = match entry.action {
+                "write" => ("+".green().bold(), "Value", entry.value.as_str()),
+                "delete" => ("-".red().bold(), "Value", entry.value.as_str()),
+                "enable" => ("~".yellow().bold(), "Startup", "enabled"),
+                _ => ("~".yellow().bold(), "Startup", "disabled"),
+            };
+            self.show_plan_header(out, marker, &entry.name, entry.location)?;
+            self.show_plan_line(out, label, value)?;
+        }
+        for launcher in &report.launchers {
+            let marker = match launcher.content {
+                Some(_) => "+".green().bold(),
+                None => "-".red().bold(),
+            };
+            writeln!(
+                out,
+                "  {} {} {}",
+                self.paint(marker),
+                self.paint(launcher.file.white()),
+                self.paint("(launcher)".bright_black())
+            )?;
+            for line in launcher.content.iter().flat_map(|content| content.lines()) {
+                writeln!(out, "      {}", self.paint(line.dimmed()))?;
+            }
+        }
+        if !report.processes.is_empty() {
+            writeln!(
+                out,
+                "  {} {}",
+                self.paint("✗".red().bold()),
+                self.paint(format!("Stop {} process(es):", report.processes.len()).yellow())
+            )?;
+            for process in &report.processes {
+                writeln!(
+                    out,
+                    "    {} {} {}",
+                    self.paint("PID:".dimmed()),
+                    self.paint(process.pid.to_string().yellow()),
+                    self.paint(process.name.white())
+                )?;
+            }
+        }
+        Ok(())
     }
 
-    pub fn show_error(error: &dyn std::error::Error) {
-        eprintln!("{} {}", "✗".red().bold(), error.to_string().red());
+    fn show_error(&self, out: &mut dyn Write, error: &StartupError) -> io::Result<()> {
+        writeln!(
+            out,
+            "{} {}",
+            self.paint("✗".red().bold()),
+            self.paint(error.to_string().red())
+        )
     }
 }
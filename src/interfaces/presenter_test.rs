@@ -0,0 +1,557 @@
+#[cfg(test)]
+mod tests {
+    use super::super::presenter::*;
+    use super::super::schema::*;
+    use crate::application::{
+        EditedEntry, EntryChange, ImportOutcome, KillOutcome, Plan, PlanField, PlannedChange,
+        ProcessKillResult,
+    };
+    use crate::domain::{
+        AuditAction, AuditActor, AuditEvent, ConsoleOptions, DesiredEntry, EntrySnapshot,
+        ExecutionMode, HistoryRecord, PowerShellOptions, RestartPolicy, SnapshotChange,
+        StartupCommand, StartupEntry, StartupLocation, TaskSettings, WindowStyle,
+    };
+    use crate::shared::error::StartupError;
+    use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+    use std::fs;
+    use std::io;
+    use std::path::Path;
+    use std::time::Duration;
+
+    /// Golden files live here; run the tests with `UPDATE_GOLDEN=1` to
+    /// rewrite them after changing a screen on purpose.
+    const GOLDEN: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/golden");
+
+    fn presenter() -> ConsolePresenter {
+        ConsolePresenter::new(false).with_timezone(FixedOffset::east_opt(0).unwrap())
+    }
+
+    fn render(show: impl FnOnce(&ConsolePresenter, &mut Vec<u8>) -> io::Result<()>) -> String {
+        let mut out = Vec::new();
+        show(&presenter(), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn assert_golden(name: &str, actual: &str) {
+        let path = Path::new(GOLDEN).join(format!("{}.txt", name));
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            fs::create_dir_all(GOLDEN).unwrap();
+            fs::write(&path, actual).unwrap();
+            return;
+        }
+        let expected = fs::read_to_string(&path).unwrap_or_else(|e| {
+            panic!(
+                "{}: {} (run with UPDATE_GOLDEN=1 to create it)",
+                path.display(),
+                e
+            )
+        });
+        assert_eq!(
+            actual,
+            expected.replace("\r\n", "\n"),
+            "{} is out of date (run with UPDATE_GOLDEN=1 to update it)",
+            path.display()
+        );
+    }
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 1, hour, minute, 0).unwrap()
+    }
+
+    fn entry_views() -> Vec<EntryView> {
+        vec![
+            EntryView {
+                name: "BunDevServer".to_string(),
+                location: StartupLocation::CurrentUserRun,
+                value: "wscript.exe \"C:\\Users\\me\\AppData\\Local\\startup-manager\\launchers\\BunDevServer.vbs\"".to_string(),
+                command: Some("bun run dev".to_string()),
+                workdir: Some("C:\\projects\\my-app".to_string()),
+                mode: Some("vbscript"),
+                execution_mode: Some(ExecutionMode::VBScript),
+                enabled: true,
+                disabled_at: None,
+                running: Some(true),
+                processes: vec![
+                    ProcessView {
+                        pid: 4242,
+                        name: "bun.exe".to_string(),
+                    },
+                    ProcessView {
+                        pid: 4243,
+                        name: "node.exe".to_string(),
+                    },
+                ],
+            },
+            EntryView {
+                name: "MyApp".to_string(),
+                location: StartupLocation::LocalMachineRun,
+                value: "C:\\Program Files\\MyApp\\myapp.exe".to_string(),
+                command: Some("C:\\Program Files\\MyApp\\myapp.exe".to_string()),
+                workdir: None,
+                mode: None,
+                execution_mode: None,
+                enabled: true,
+                disabled_at: None,
+                running: Some(false),
+                processes: Vec::new(),
+            },
+            EntryView {
+                name: "Sync".to_string(),
+                location: StartupLocation::CurrentUserRun,
+                value: "powershell.exe -WindowStyle Minimized -NoExit -Command \"sync.ps1\""
+                    .to_string(),
+                command: Some("sync.ps1".to_string()),
+                workdir: None,
+                mode: Some("powershell"),
+                execution_mode: Some(ExecutionMode::PowerShell(PowerShellOptions {
+                    window: WindowStyle::Minimized,
+                    no_exit: true,
+                })),
+                enabled: false,
+                disabled_at: Some(at(8, 30)),
+                running: None,
+                processes: Vec::new(),
+            },
+            EntryView {
+                name: "Tray".to_string(),
+                location: StartupLocation::TaskScheduler,
+                value: "\"C:\\Tools\\tray.exe\" --minimized".to_string(),
+                command: Some("C:\\Tools\\tray.exe --minimized".to_string()),
+                workdir: None,
+                mode: Some("scheduled-task"),
+                execution_mode: Some(ExecutionMode::ScheduledTask(TaskSettings {
+                    delay: Some(Duration::from_secs(30)),
+                    restart: Some(RestartPolicy {
+                        count: 3,
+                        interval: Duration::from_secs(60),
+                    }),
+                    hidden: false,
+                    highest_privileges: true,
+                })),
+                enabled: true,
+                disabled_at: None,
+                running: Some(false),
+                processes: Vec::new(),
+            },
+        ]
+    }
+
+    fn desired(name: &str, command: StartupCommand) -> DesiredEntry {
+        DesiredEntry {
+            name: name.to_string(),
+            command,
+            location: StartupLocation::CurrentUserRun,
+            enabled: true,
+        }
+    }
+
+    fn plan() -> Plan {
+        let watcher = StartupCommand::CommandWithArgs {
+            command: "npm".to_string(),
+            args: vec!["run".to_string(), "watch".to_string()],
+            workdir: Some("C:\\projects\\site".to_string()),
+            mode: ExecutionMode::Visible(ConsoleOptions { no_exit: true }),
+        };
+        let current = StartupEntry::new("Dev", "bun run dev");
+        Plan {
+            changes: vec![
+                PlannedChange::Add(desired("Watcher", watcher)),
+                PlannedChange::Change(Box::new(EntryChange {
+                    current: current.clone(),
+                    decoded: StartupCommand::parse(&current.command),
+                    desired: DesiredEntry {
+                        enabled: false,
+                        ..desired(
+                            "Dev",
+                            StartupCommand::CommandWithArgs {
+                                command: "bun".to_string(),
+                                args: vec!["run".to_string(), "start".to_string()],
+                                workdir: None,
+                                mode: ExecutionMode::VBScript,
+                            },
+                        )
+                    },
+                    fields: vec![PlanField::Command, PlanField::Mode, PlanField::Enabled],
+                })),
+                PlannedChange::Remove(
+                    StartupEntry::new("Old", "C:\\old.exe")
+                        .with_location(StartupLocation::LocalMachineRun),
+                ),
+            ],
+        }
+    }
+
+    fn snapshot(name: &str, value: &str, enabled: bool) -> EntrySnapshot {
+        EntrySnapshot {
+            name: name.to_string(),
+            location: StartupLocation::CurrentUserRun,
+            value: value.to_string(),
+            enabled,
+            disabled_at: None,
+            launcher: None,
+        }
+    }
+
+    fn records() -> Vec<HistoryRecord> {
+        vec![
+            HistoryRecord {
+                id: 2,
+                at: at(9, 15),
+                command: "undo".to_string(),
+                changes: vec![SnapshotChange {
+                    before: Some(snapshot("Dev", "bun run start", false)),
+                    after: Some(snapshot("Dev", "bun run dev", true)),
+                }],
+                reverts: vec![1],
+                undone: false,
+            },
+            HistoryRecord {
+                id: 1,
+                at: at(9, 0),
+                command: "apply startup.toml".to_string(),
+                changes: vec![
+                    SnapshotChange {
+                        before: None,
+                        after: Some(snapshot("Watcher", "npm run watch", true)),
+                    },
+                    SnapshotChange {
+                        before: Some(snapshot("Old", "C:\\old.exe", true)),
+                        after: None,
+                    },
+                ],
+                reverts: Vec::new(),
+                undone: true,
+            },
+        ]
+    }
+
+    fn killed(pid: u32, name: &str, outcome: KillOutcome) -> ProcessKillResult {
+        ProcessKillResult {
+            pid,
+            name: name.to_string(),
+            outcome,
+        }
+    }
+
+    #[test]
+    fn test_add_screens() {
+        let replaced = StartupEntry::new("Dev", "C:\\old\\dev.exe");
+        assert_golden(
+            "add",
+            &render(|p, out| p.show_success_add(out, "MyApp", None)),
+        );
+        assert_golden(
+            "add_command",
+            &render(|p, out| {
+                p.show_success_add_command(
+                    out,
+                    "Dev",
+                    "bun run dev",
+                    Some("C:\\projects\\my-app"),
+                    Some(&replaced),
+                )
+            }),
+        );
+    }
+
+    #[test]
+    fn test_edit_and_rename_screens() {
+        let before = StartupEntry::new(
+            "Dev",
+            "wscript.exe //B //Nologo \"C:\\launchers\\dev_1a2b.vbs\"",
+        );
+        let edited = EditedEntry {
+            before: before.clone(),
+            after: StartupEntry::new(
+                "Dev",
+                "powershell.exe -WindowStyle Minimized -NoProfile -Command \"bun run start\"",
+            ),
+            command: StartupCommand::CommandWithArgs {
+                command: "bun".to_string(),
+                args: vec!["run".to_string(), "start".to_string()],
+                workdir: Some("C:\\projects\\my-app".to_string()),
+                mode: ExecutionMode::PowerShell(PowerShellOptions {
+                    window: WindowStyle::Minimized,
+                    no_exit: false,
+                }),
+            },
+        };
+        let unchanged = EditedEntry {
+            after: before.clone(),
+            ..edited.clone()
+        };
+
+        assert_golden(
+            "edit",
+            &render(|p, out| {
+                p.show_success_edit(out, &edited)?;
+                p.show_success_edit(out, &unchanged)
+            }),
+        );
+        assert_golden(
+            "rename",
+            &render(|p, out| {
+                p.show_success_rename(out, "Dev", &StartupEntry::new("Server", "server.exe"))
+            }),
+        );
+    }
+
+    #[test]
+    fn test_remove_and_enable_screens() {
+        assert_golden(
+            "remove",
+            &render(|p, out| p.show_success_remove(out, "Dev", &["Dev_1a2b.vbs".to_string()])),
+        );
+        assert_golden(
+            "set_enabled",
+            &render(|p, out| {
+                p.show_success_set_enabled(out, "Dev", false, true)?;
+                p.show_success_set_enabled(out, "Dev", false, false)
+            }),
+        );
+    }
+
+    #[test]
+    fn test_entries_screens() {
+        assert_golden("entries_empty", &render(|p, out| p.show_entries(out, &[])));
+        assert_golden(
+            "entries",
+            &render(|p, out| p.show_entries(out, &entry_views())),
+        );
+    }
+
+    #[test]
+    fn test_gc_screens() {
+        let orphans = ["Dev_1a2b.vbs".to_string(), "Old_3c4d.vbs".to_string()];
+
+        assert_golden(
+            "gc",
+            &render(|p, out| {
+                p.show_gc_result(out, &[], false)?;
+                p.show_gc_result(out, &orphans, true)?;
+                p.show_gc_result(out, &orphans, false)
+            }),
+        );
+    }
+
+    #[test]
+    fn test_plan_and_apply_screens() {
+        let empty = Plan::default();
+
+        assert_golden("plan_empty", &render(|p, out| p.show_plan(out, &empty)));
+        assert_golden("plan", &render(|p, out| p.show_plan(out, &plan())));
+        assert_golden(
+            "apply",
+            &render(|p, out| {
+                p.show_apply_plan(out, &plan())?;
+                p.show_apply_result(out, &plan(), &["Old_3c4d.vbs".to_string()])
+            }),
+        );
+        assert_eq!(render(|p, out| p.show_apply_result(out, &empty, &[])), "");
+    }
+
+    #[test]
+    fn test_export_and_import_screens() {
+        assert_golden(
+            "export",
+            &render(|p, out| p.show_export_success(out, 3, 1, Path::new("backup.json"))),
+        );
+        assert_golden(
+            "import",
+            &render(|p, out| {
+                p.show_import_result(out, &[])?;
+                p.show_import_result(
+                    out,
+                    &[
+                        ImportOutcome::Added("Watcher".to_string()),
+                        ImportOutcome::Replaced("Dev".to_string()),
+                        ImportOutcome::Renamed {
+                            from: "MyApp".to_string(),
+                            to: "MyApp (2)".to_string(),
+                        },
+                        ImportOutcome::Skipped("Tray".to_string()),
+                        ImportOutcome::Removed("Old".to_string()),
+                    ],
+                )
+            }),
+        );
+    }
+
+    #[test]
+    fn test_history_screens() {
+        assert_golden(
+            "history",
+            &render(|p, out| {
+                p.show_history(out, &[])?;
+                p.show_history(out, &records())
+            }),
+        );
+        assert_golden(
+            "undo",
+            &render(|p, out| {
+                p.show_undo_result(out, &[])?;
+                p.show_undo_result(out, &records()[1..])
+            }),
+        );
+    }
+
+    #[test]
+    fn test_audit_log_screen() {
+        let actor = AuditActor {
+            user: "me".to_string(),
+            host: "desk".to_string(),
+        };
+        let events = [
+            AuditEvent::new(at(9, 0), &actor, AuditAction::Add).with_change(&SnapshotChange {
+                before: Some(snapshot("Dev", "bun run start", true)),
+                after: Some(snapshot("Dev", "bun run dev", true)),
+            }),
+            AuditEvent::new(at(9, 5), &actor, AuditAction::Kill)
+                .with_entry("Dev")
+                .with_detail("stopped 4242 bun.exe"),
+            AuditEvent::new(at(9, 10), &actor, AuditAction::Remove)
+                .with_entry("Ghost")
+                .with_error("Entry 'Ghost' not found in startup registry"),
+        ];
+
+        assert_golden(
+            "audit_log",
+            &render(|p, out| {
+                p.show_audit_events(out, &[])?;
+                p.show_audit_events(out, &events)
+            }),
+        );
+    }
+
+    #[test]
+    fn test_kill_screens() {
+        assert_golden(
+            "kill",
+            &render(|p, out| {
+                p.show_kill_success(out, "Dev", &[])?;
+                p.show_kill_success(
+                    out,
+                    "Dev",
+                    &[
+                        killed(4242, "bun.exe", KillOutcome::Closed),
+                        killed(4243, "node.exe", KillOutcome::Killed),
+                    ],
+                )?;
+                p.show_kill_success(
+                    out,
+                    "Dev",
+                    &[
+                        killed(4242, "bun.exe", KillOutcome::Closed),
+                        killed(
+                            4243,
+                            "node.exe",
+                            KillOutcome::Failed("access denied".into()),
+                        ),
+                    ],
+                )
+            }),
+        );
+        assert_golden(
+            "kill_all",
+            &render(|p, out| {
+                p.show_kill_all_success(out, &[])?;
+                p.show_kill_all_success(
+                    out,
+                    &[
+                        (
+                            "Dev".to_string(),
+                            vec![killed(4242, "bun.exe", KillOutcome::Closed)],
+                        ),
+                        (
+                            "Tray".to_string(),
+                            vec![killed(5150, "tray.exe", KillOutcome::Killed)],
+                        ),
+                    ],
+                )
+            }),
+        );
+    }
+
+    #[test]
+    fn test_dry_run_screen() {
+        let report = DryRunView {
+            entries: vec![
+                StagedEntryView {
+                    action: "write",
+                    name: "Dev".to_string(),
+                    location: StartupLocation::CurrentUserRun,
+                    value: "wscript.exe //B //Nologo \"%APPDATA%\\windows_startup_manager\\dev_1a2b.vbs\""
+                        .to_string(),
+                },
+                StagedEntryView {
+                    action: "delete",
+                    name: "Old".to_string(),
+                    location: StartupLocation::LocalMachineRun,
+                    value: "C:\\old.exe".to_string(),
+                },
+                StagedEntryView {
+                    action: "disable",
+                    name: "Tray".to_string(),
+                    location: StartupLocation::TaskScheduler,
+                    value: "C:\\Tools\\tray.exe".to_string(),
+                },
+            ],
+            launchers: vec![
+                StagedLauncherView {
+                    action: "write",
+                    file: "dev_1a2b.vbs".to_string(),
+                    content: Some(
+                        "Set WshShell = CreateObject(\"WScript.Shell\")\nWshShell.Run \"bun run dev\", 0, False\n"
+                            .to_string(),
+                    ),
+                },
+                StagedLauncherView {
+                    action: "delete",
+                    file: "old_3c4d.vbs".to_string(),
+                    content: None,
+                },
+            ],
+            processes: vec![ProcessView {
+                pid: 4242,
+                name: "bun.exe".to_string(),
+            }],
+        };
+        let empty = DryRunView {
+            entries: Vec::new(),
+            launchers: Vec::new(),
+            processes: Vec::new(),
+        };
+
+        assert_golden(
+            "dry_run",
+            &render(|p, out| {
+                p.show_dry_run(out, &empty)?;
+                p.show_dry_run(out, &report)
+            }),
+        );
+    }
+
+    #[test]
+    fn test_error_screen() {
+        assert_golden(
+            "error",
+            &render(|p, out| p.show_error(out, &StartupError::EntryNotFound("Dev".to_string()))),
+        );
+    }
+
+    #[test]
+    fn test_color_is_only_written_when_enabled() {
+        colored::control::set_override(true);
+        let mut colored = Vec::new();
+        ConsolePresenter::new(true)
+            .show_success_add(&mut colored, "MyApp", None)
+            .unwrap();
+
+        let colored = String::from_utf8(colored).unwrap();
+        assert!(colored.contains("\u{1b}["));
+        assert_eq!(
+            render(|p, out| p.show_success_add(out, "MyApp", None)),
+            "✓ Successfully added MyApp to startup.\n"
+        );
+    }
+}
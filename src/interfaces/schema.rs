@@ -0,0 +1,492 @@
+//! Documents written by `--output json` and `--output yaml`.
+//!
+//! Every document names its schema and carries [`SCHEMA_VERSION`]. Fields
+//! are never removed or renamed within a version, and fields without a
+//! value are written as `null` rather than left out.
+
+use crate::application::{
+    EditedEntry, ImportOutcome, KillOutcome, Plan, PlanField, PlannedChange, ProcessKillResult,
+};
+use crate::domain::{
+    AuditEvent, ExecutionMode, HistoryRecord, ModeSpec, ProcessInfo, ProcessMatcher,
+    StartupCommand, StartupEntry, StartupLocation,
+};
+use crate::infrastructure::{StagedChange, StagedLauncher};
+use crate::shared::error::StartupError;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::path::Path;
+
+/// Version of every schema; bumped when a document changes incompatibly.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// A document of one schema.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Document<T: Serialize> {
+    pub schema: &'static str,
+    pub version: u32,
+    #[serde(flatten)]
+    pub data: T,
+}
+
+impl<T: Serialize> Document<T> {
+    pub fn new(schema: &'static str, data: T) -> Self {
+        Self {
+            schema,
+            version: SCHEMA_VERSION,
+            data,
+        }
+    }
+}
+
+/// A startup entry and the processes it is running.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct EntryView {
+    pub name: String,
+    pub location: StartupLocation,
+    /// The registry value, shortcut command line or task action as stored.
+    pub value: String,
+    /// The command the entry runs, when its value could be decoded.
+    pub command: Option<String>,
+    pub workdir: Option<String>,
+    /// The `add-command --mode` the entry was added with.
+    pub mode: Option<&'static str>,
+    /// The mode with its options, for the table output.
+    #[serde(skip)]
+    pub execution_mode: Option<ExecutionMode>,
+    pub enabled: bool,
+    pub disabled_at: Option<DateTime<Utc>>,
+    /// Whether the entry's processes are running; `null` when they can't
+    /// be identified.
+    pub running: Option<bool>,
+    pub processes: Vec<ProcessView>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProcessView {
+    pub pid: u32,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct EntriesView {
+    pub entries: Vec<EntryView>,
+}
+
+/// The processes stopped for one entry, or one executable name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct KillView {
+    pub entry: String,
+    pub processes: Vec<KilledProcessView>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct KilledProcessView {
+    pub pid: u32,
+    pub name: String,
+    /// `closed`, `killed` or `failed`.
+    pub outcome: &'static str,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct KillResultsView {
+    pub results: Vec<KillView>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ErrorView {
+    pub error: ErrorDetails,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ErrorDetails {
+    /// A stable identifier such as `entry-not-found`.
+    pub kind: &'static str,
+    pub message: String,
+    /// Every problem found, for errors that report several.
+    pub problems: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AddedView {
+    pub name: String,
+    pub command: Option<String>,
+    pub workdir: Option<String>,
+    /// The value of the entry the new one replaced.
+    pub replaced: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct EditedView {
+    pub name: String,
+    pub location: StartupLocation,
+    /// The command the entry runs now.
+    pub command: String,
+    pub workdir: Option<String>,
+    /// The `add-command --mode` the entry runs with, `null` for a bare
+    /// executable.
+    pub mode: Option<&'static str>,
+    pub old_value: String,
+    pub new_value: String,
+    /// Whether the value changed.
+    pub changed: bool,
+}
+
+impl EditedView {
+    pub fn new(edited: &EditedEntry) -> Self {
+        Self {
+            name: edited.after.name.clone(),
+            location: edited.after.location,
+            command: edited.command.command_line(),
+            workdir: edited.command.workdir().map(str::to_string),
+            mode: edited
+                .command
+                .mode()
+                .map(|mode| ModeSpec::from_mode(&mode).mode.as_str()),
+            old_value: edited.before.command.clone(),
+            new_value: edited.after.command.clone(),
+            changed: edited.before != edited.after,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RenamedView {
+    pub name: String,
+    pub new_name: String,
+    pub location: StartupLocation,
+    /// The value of the entry, pointing at its moved launcher if it has one.
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RemovedView {
+    pub name: String,
+    pub deleted_launchers: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct EnabledView {
+    pub name: String,
+    pub enabled: bool,
+    /// Whether the entry was in the other state before.
+    pub changed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct GcView {
+    pub dry_run: bool,
+    pub launchers: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PlanView {
+    pub changes: Vec<PlannedChangeView>,
+    pub add: usize,
+    pub change: usize,
+    pub remove: usize,
+    /// Launchers deleted while applying; empty for `plan`.
+    pub deleted_launchers: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PlannedChangeView {
+    /// `add`, `change` or `remove`.
+    pub action: &'static str,
+    pub name: String,
+    pub location: StartupLocation,
+    /// The fields a change updates: `command`, `workdir`, `mode`,
+    /// `location` or `enabled`.
+    pub fields: Vec<&'static str>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ExportView {
+    pub count: usize,
+    pub skipped: usize,
+    pub file: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ImportView {
+    pub outcomes: Vec<ImportOutcomeView>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ImportOutcomeView {
+    /// `added`, `replaced`, `renamed`, `skipped` or `removed`.
+    pub outcome: &'static str,
+    pub name: String,
+    /// The name in the backup, for renamed entries.
+    pub renamed_from: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct HistoryView {
+    pub records: Vec<HistoryRecord>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AuditView {
+    pub events: Vec<AuditEvent>,
+}
+
+/// What a command run with `--dry-run` would have changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DryRunView {
+    pub entries: Vec<StagedEntryView>,
+    pub launchers: Vec<StagedLauncherView>,
+    /// The processes that would be stopped.
+    pub processes: Vec<ProcessView>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StagedEntryView {
+    /// `write`, `delete`, `enable` or `disable`.
+    pub action: &'static str,
+    pub name: String,
+    pub location: StartupLocation,
+    /// The registry value, shortcut command line or task action as it
+    /// would be stored, or as it was for deleted entries.
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StagedLauncherView {
+    /// `write` or `delete`.
+    pub action: &'static str,
+    pub file: String,
+    /// The script that would be written; `null` for deleted launchers.
+    pub content: Option<String>,
+}
+
+impl EntryView {
+    /// Describes `entry` with the processes of `running` it started.
+    pub fn new(entry: &StartupEntry, running: &[ProcessInfo]) -> Self {
+        let decoded = StartupCommand::parse(&entry.command);
+        let mode = decoded.as_ref().and_then(|c| c.mode());
+        let matcher = ProcessMatcher::for_entry(entry);
+        let processes: Vec<ProcessView> = matcher
+            .as_ref()
+            .map(|matcher| {
+                matcher
+                    .find(running)
+                    .into_iter()
+                    .map(|process| ProcessView {
+                        pid: process.pid,
+                        name: process.name.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            name: entry.name.clone(),
+            location: entry.location,
+            value: entry.command.clone(),
+            command: decoded.as_ref().map(|c| c.command_line()),
+            workdir: decoded
+                .as_ref()
+                .and_then(|c| c.workdir())
+                .map(str::to_string),
+            mode: mode.map(|mode| ModeSpec::from_mode(&mode).mode.as_str()),
+            execution_mode: mode,
+            enabled: entry.enabled,
+            disabled_at: entry.disabled_at,
+            running: matcher.map(|_| !processes.is_empty()),
+            processes,
+        }
+    }
+}
+
+impl DryRunView {
+    pub fn new(
+        changes: &[StagedChange],
+        launchers: &[StagedLauncher],
+        stopped: &[ProcessInfo],
+    ) -> Self {
+        Self {
+            entries: changes
+                .iter()
+                .map(|change| {
+                    let (action, entry) = match change {
+                        StagedChange::Write(entry) => ("write", entry),
+                        StagedChange::Delete(entry) => ("delete", entry),
+                        StagedChange::SetApproval(entry) if entry.enabled => ("enable", entry),
+                        StagedChange::SetApproval(entry) => ("disable", entry),
+                    };
+                    StagedEntryView {
+                        action,
+                        name: entry.name.clone(),
+                        location: entry.location,
+                        value: entry.command.clone(),
+                    }
+                })
+                .collect(),
+            launchers: launchers
+                .iter()
+                .map(|launcher| match launcher {
+                    StagedLauncher::Write { filename, content } => StagedLauncherView {
+                        action: "write",
+                        file: filename.clone(),
+                        content: Some(content.clone()),
+                    },
+                    StagedLauncher::Delete(filename) => StagedLauncherView {
+                        action: "delete",
+                        file: filename.clone(),
+                        content: None,
+                    },
+                })
+                .collect(),
+            processes: stopped
+                .iter()
+                .map(|process| ProcessView {
+                    pid: process.pid,
+                    name: process.name.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns whether the command would change nothing.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty() && self.launchers.is_empty() && self.processes.is_empty()
+    }
+}
+
+impl KillView {
+    pub fn new(entry: &str, results: &[ProcessKillResult]) -> Self {
+        Self {
+            entry: entry.to_string(),
+            processes: results
+                .iter()
+                .map(|result| {
+                    let (outcome, error) = match &result.outcome {
+                        KillOutcome::Closed => ("closed", None),
+                        KillOutcome::Killed => ("killed", None),
+                        KillOutcome::Failed(reason) => ("failed", Some(reason.clone())),
+                    };
+                    KilledProcessView {
+                        pid: result.pid,
+                        name: result.name.clone(),
+                        outcome,
+                        error,
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+impl ErrorView {
+    pub fn new(error: &StartupError) -> Self {
+        Self {
+            error: ErrorDetails {
+                kind: error.kind(),
+                message: error.to_string(),
+                problems: match error {
+                    StartupError::ImportRejected(problems) => problems.clone(),
+                    _ => Vec::new(),
+                },
+            },
+        }
+    }
+}
+
+impl PlanView {
+    pub fn new(plan: &Plan, deleted_launchers: &[String]) -> Self {
+        let (add, change, remove) = plan.counts();
+        let changes = plan
+            .changes
+            .iter()
+            .map(|planned| match planned {
+                PlannedChange::Add(desired) => PlannedChangeView {
+                    action: "add",
+                    name: desired.name.clone(),
+                    location: desired.location,
+                    fields: Vec::new(),
+                },
+                PlannedChange::Change(change) => PlannedChangeView {
+                    action: "change",
+                    name: change.desired.name.clone(),
+                    location: change.desired.location,
+                    fields: change.fields.iter().map(field_name).collect(),
+                },
+                PlannedChange::Remove(current) => PlannedChangeView {
+                    action: "remove",
+                    name: current.name.clone(),
+                    location: current.location,
+                    fields: Vec::new(),
+                },
+            })
+            .collect();
+        Self {
+            changes,
+            add,
+            change,
+            remove,
+            deleted_launchers: deleted_launchers.to_vec(),
+        }
+    }
+}
+
+fn field_name(field: &PlanField) -> &'static str {
+    match field {
+        PlanField::Command => "command",
+        PlanField::WorkingDirectory => "workdir",
+        PlanField::Mode => "mode",
+        PlanField::Location => "location",
+        PlanField::Enabled => "enabled",
+    }
+}
+
+impl ImportOutcomeView {
+    pub fn new(outcome: &ImportOutcome) -> Self {
+        let (kind, name, renamed_from) = match outcome {
+            ImportOutcome::Added(name) => ("added", name, None),
+            ImportOutcome::Replaced(name) => ("replaced", name, None),
+            ImportOutcome::Renamed { from, to } => ("renamed", to, Some(from.clone())),
+            ImportOutcome::Skipped(name) => ("skipped", name, None),
+            ImportOutcome::Removed(name) => ("removed", name, None),
+        };
+        Self {
+            outcome: kind,
+            name: name.clone(),
+            renamed_from,
+        }
+    }
+}
+
+impl ExportView {
+    pub fn new(count: usize, skipped: usize, file: &Path) -> Self {
+        Self {
+            count,
+            skipped,
+            file: file.display().to_string(),
+        }
+    }
+}
@@ -0,0 +1,182 @@
+#[cfg(test)]
+mod tests {
+    use super::super::schema::*;
+    use crate::application::{EditedEntry, KillOutcome, ProcessKillResult};
+    use crate::domain::{
+        ExecutionMode, ProcessInfo, StartupApproval, StartupCommand, StartupEntry,
+    };
+    use crate::infrastructure::{StagedChange, StagedLauncher};
+    use crate::shared::error::StartupError;
+    use serde_json::{Value, json};
+
+    fn to_value<T: serde::Serialize>(schema: &'static str, data: T) -> Value {
+        serde_json::to_value(Document::new(schema, data)).unwrap()
+    }
+
+    #[test]
+    fn test_entries_carry_their_schema_and_running_processes() {
+        let entry = StartupEntry::new("Notes", "notepad.exe C:\\notes.txt");
+        let running = [
+            ProcessInfo::new(10, "notepad.exe", "notepad.exe C:\\notes.txt"),
+            ProcessInfo::new(11, "notepad.exe", "notepad.exe C:\\other.txt"),
+        ];
+
+        let value = to_value(
+            "entries",
+            EntriesView {
+                entries: vec![EntryView::new(&entry, &running)],
+            },
+        );
+
+        assert_eq!(value["schema"], "entries");
+        assert_eq!(value["version"], SCHEMA_VERSION);
+        assert_eq!(
+            value["entries"][0],
+            json!({
+                "name": "Notes",
+                "location": "hkcu-run",
+                "value": "notepad.exe C:\\notes.txt",
+                "command": "notepad.exe C:\\notes.txt",
+                "workdir": null,
+                "mode": "visible",
+                "enabled": true,
+                "disabled-at": null,
+                "running": true,
+                "processes": [{ "pid": 10, "name": "notepad.exe" }],
+            })
+        );
+    }
+
+    #[test]
+    fn test_kill_results_name_every_outcome() {
+        let results = [
+            ProcessKillResult {
+                pid: 10,
+                name: "bun.exe".to_string(),
+                outcome: KillOutcome::Killed,
+            },
+            ProcessKillResult {
+                pid: 11,
+                name: "node.exe".to_string(),
+                outcome: KillOutcome::Failed("access denied".to_string()),
+            },
+        ];
+
+        let value = to_value(
+            "kill-results",
+            KillResultsView {
+                results: vec![KillView::new("Dev", &results)],
+            },
+        );
+
+        assert_eq!(
+            value["results"],
+            json!([{
+                "entry": "Dev",
+                "processes": [
+                    { "pid": 10, "name": "bun.exe", "outcome": "killed", "error": null },
+                    { "pid": 11, "name": "node.exe", "outcome": "failed", "error": "access denied" },
+                ],
+            }])
+        );
+    }
+
+    #[test]
+    fn test_edit_shows_the_new_command_and_both_values() {
+        let edited = EditedEntry {
+            before: StartupEntry::new("Tool", "tool.exe"),
+            after: StartupEntry::new("Tool", "tool.exe --tray"),
+            command: StartupCommand::CommandWithArgs {
+                command: "tool.exe".to_string(),
+                args: vec!["--tray".to_string()],
+                workdir: None,
+                mode: ExecutionMode::Visible(Default::default()),
+            },
+        };
+
+        assert_eq!(
+            to_value("edit", EditedView::new(&edited)),
+            json!({
+                "schema": "edit",
+                "version": SCHEMA_VERSION,
+                "name": "Tool",
+                "location": "hkcu-run",
+                "command": "tool.exe --tray",
+                "workdir": null,
+                "mode": "visible",
+                "old-value": "tool.exe",
+                "new-value": "tool.exe --tray",
+                "changed": true,
+            })
+        );
+    }
+
+    #[test]
+    fn test_errors_have_a_stable_kind() {
+        let not_found = to_value(
+            "error",
+            ErrorView::new(&StartupError::EntryNotFound("Dev".to_string())),
+        );
+        let rejected = to_value(
+            "error",
+            ErrorView::new(&StartupError::ImportRejected(vec![
+                "a".to_string(),
+                "b".to_string(),
+            ])),
+        );
+
+        assert_eq!(not_found["error"]["kind"], "entry-not-found");
+        assert_eq!(
+            not_found["error"]["message"],
+            "Entry 'Dev' not found in startup registry"
+        );
+        assert_eq!(not_found["error"]["problems"], json!([]));
+        assert_eq!(rejected["error"]["kind"], "import-rejected");
+        assert_eq!(rejected["error"]["problems"], json!(["a", "b"]));
+        assert_eq!(
+            ErrorView::new(&StartupError::EntryAlreadyExists("Dev".to_string()))
+                .error
+                .kind,
+            "entry-already-exists"
+        );
+        assert_eq!(
+            ErrorView::new(&StartupError::EntryInOtherLocation(
+                "Dev".to_string(),
+                "hkcu-run".to_string()
+            ))
+            .error
+            .kind,
+            "entry-in-other-location"
+        );
+    }
+
+    #[test]
+    fn test_dry_run_lists_values_launchers_and_processes() {
+        let disabled = StartupEntry::new("Tray", "tray.exe").with_approval(StartupApproval {
+            enabled: false,
+            disabled_at: None,
+        });
+        let report = DryRunView::new(
+            &[
+                StagedChange::Write(StartupEntry::new("Dev", "bun run dev")),
+                StagedChange::SetApproval(disabled),
+            ],
+            &[StagedLauncher::Delete("old.vbs".to_string())],
+            &[ProcessInfo::new(42, "bun.exe", "bun run dev")],
+        );
+
+        assert_eq!(
+            to_value("dry-run", report),
+            json!({
+                "schema": "dry-run",
+                "version": 1,
+                "entries": [
+                    {"action": "write", "name": "Dev", "location": "hkcu-run", "value": "bun run dev"},
+                    {"action": "disable", "name": "Tray", "location": "hkcu-run", "value": "tray.exe"},
+                ],
+                "launchers": [{"action": "delete", "file": "old.vbs", "content": null}],
+                "processes": [{"pid": 42, "name": "bun.exe"}],
+            })
+        );
+    }
+}
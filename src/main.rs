@@ -1,45 +1,292 @@
-mod application;
-mod domain;
-mod infrastructure;
-mod interfaces;
-mod shared;
-
-use application::{
-    AddCommandUseCase, AddExecutableUseCase, KillAllProcessesUseCase, KillProcessUseCase,
-    ListEntriesUseCase, RemoveEntryUseCase,
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use std::time::Duration;
+use windows_startup_manager::application::{
+    AddCommandUseCase, AddExecutableUseCase, ApplyManifestUseCase, CollectGarbageUseCase,
+    EditEntryUseCase, EntryEdits, ExportEntriesUseCase, ImportEntriesUseCase, ImportRegFileUseCase,
+    KillAllProcessesUseCase, KillOptions, KillProcessUseCase, ListAuditEventsUseCase,
+    ListEntriesUseCase, ListHistoryUseCase, PlanManifestUseCase, RecordAuditUseCase,
+    RecordHistoryUseCase, RemoveEntryUseCase, RenameEntryUseCase, SetEnabledUseCase,
+    UndoChangesUseCase,
 };
-use clap::Parser;
-use infrastructure::WindowsRegistryRepository;
-use interfaces::{Cli, Commands, ConsolePresenter};
+use windows_startup_manager::domain;
+use windows_startup_manager::domain::{
+    AuditAction, HistoryStore, LauncherStore, ProcessProvider, StartupRepository,
+};
+use windows_startup_manager::infrastructure::{
+    CompositeRepository, DryRunLauncherStore, DryRunProcessProvider, DryRunRepository,
+    FileSystemAuditLog, FileSystemHistoryStore, FileSystemLauncherStore, InMemoryHistoryStore,
+    NativeProcessProvider, SnapshotRepository, current_actor,
+};
+use windows_startup_manager::interfaces::{
+    Cli, Commands, DryRunFormatter, DryRunView, EntryView, ExportFormat, OutputFormatter, formatter,
+};
+use windows_startup_manager::shared::error::Result;
+
+/// Opens every startup location: the Run and RunOnce keys of the current
+/// user and of all users (both registry views) and the two startup folders.
+#[cfg(windows)]
+fn open_repository() -> Result<CompositeRepository> {
+    use windows_startup_manager::infrastructure::{
+        ScheduledTaskRepository, SchtasksTaskScheduler, StartupFolderRepository,
+        WindowsRegistryRepository,
+    };
+
+    let mut repository = CompositeRepository::new();
+    for location in domain::StartupLocation::ALL {
+        repository = if location.registry_key().is_some() {
+            repository.with(location, WindowsRegistryRepository::open(location)?)
+        } else if location.startup_folder().is_some() {
+            repository.with(location, StartupFolderRepository::open(location)?)
+        } else {
+            // Tasks start at the logon of the user who added them
+            let mut tasks = ScheduledTaskRepository::new(SchtasksTaskScheduler::new());
+            if let (Ok(domain), Ok(user)) = (std::env::var("USERDOMAIN"), std::env::var("USERNAME"))
+            {
+                tasks = tasks.with_user(format!("{}\\{}", domain, user));
+            }
+            repository.with(location, tasks)
+        };
+    }
+    Ok(repository)
+}
+
+/// The registry only exists on Windows; other platforms can build the
+/// library but have no startup repository to manage.
+#[cfg(not(windows))]
+fn open_repository() -> Result<CompositeRepository> {
+    Err(
+        windows_startup_manager::shared::error::StartupError::Unsupported(
+            "the Windows registry is only available on Windows".to_string(),
+        ),
+    )
+}
+
+/// Builds the kill options from the `kill`/`kill-all` flags.
+fn kill_options(by_name: bool, timeout: u64) -> KillOptions {
+    KillOptions {
+        strategy: if by_name {
+            domain::MatchStrategy::ImageName
+        } else {
+            domain::MatchStrategy::CommandLine
+        },
+        graceful_timeout: Duration::from_secs(timeout),
+    }
+}
+
+/// Reads a manifest, in the format its extension names.
+fn load_manifest(file: &Path) -> Result<domain::StartupManifest> {
+    let text = std::fs::read_to_string(file)?;
+    domain::StartupManifest::parse(&text, domain::ManifestFormat::from_path(file))
+}
 
 fn main() {
     // Parse CLI arguments
     let cli = Cli::parse();
+    let no_color = std::env::var("NO_COLOR").ok();
+    let color = cli
+        .color
+        .enabled(no_color.as_deref(), std::io::stdout().is_terminal());
+    // The presenter decides when to color, not the terminal detection of
+    // the coloring library
+    colored::control::set_override(color);
+    let output = formatter(cli.output, color);
+
+    // Commands that store or delete an entry can target a single location
+    let location = match &cli.command {
+        Commands::Add { location, .. }
+        | Commands::AddCommand { location, .. }
+        | Commands::Edit { location, .. }
+        | Commands::Rename { location, .. }
+        | Commands::Remove { location, .. }
+        | Commands::Enable { location, .. }
+        | Commands::Disable { location, .. } => *location,
+        _ => None,
+    };
+    // Adds place the entry in its location themselves and check every
+    // location for the name, so they keep all of them in scope
+    let scope = match &cli.command {
+        Commands::Add { .. } | Commands::AddCommand { .. } => None,
+        _ => location,
+    };
+
+    // Scheduled tasks can only be stored with Task Scheduler
+    let mode = match &cli.command {
+        Commands::AddCommand { mode, .. } => {
+            let mode = mode
+                .execution_mode()
+                .unwrap_or_else(|e| Cli::command().error(ErrorKind::ArgumentConflict, e).exit());
+            if let domain::ExecutionMode::ScheduledTask(_) = mode
+                && location.is_some_and(|l| l != domain::StartupLocation::TaskScheduler)
+            {
+                Cli::command()
+                    .error(
+                        ErrorKind::ArgumentConflict,
+                        "--mode scheduled-task can only be stored in --location task-scheduler",
+                    )
+                    .exit();
+            }
+            mode
+        }
+        _ => domain::ExecutionMode::default(),
+    };
+
+    // `edit` changes only the parts of the command it is given
+    let edits = match &cli.command {
+        Commands::Edit {
+            command,
+            args,
+            workdir,
+            no_workdir,
+            mode,
+            ..
+        } => {
+            let edits = EntryEdits {
+                command: command.clone(),
+                args: args.clone(),
+                workdir: if *no_workdir {
+                    Some(None)
+                } else {
+                    workdir.clone().map(Some)
+                },
+                mode: mode.changed_mode().unwrap_or_else(|e| {
+                    Cli::command().error(ErrorKind::ArgumentConflict, e).exit()
+                }),
+            };
+            if edits == EntryEdits::default() {
+                Cli::command()
+                    .error(
+                        ErrorKind::MissingRequiredArgument,
+                        "edit needs at least one of --command, --args, --workdir, --no-workdir or --mode",
+                    )
+                    .exit();
+            }
+            edits
+        }
+        _ => EntryEdits::default(),
+    };
+
+    // Commands that change entries or stop processes are audited
+    let audited = match &cli.command {
+        Commands::Add { name, .. } | Commands::AddCommand { name, .. } => {
+            Some((AuditAction::Add, Some(name.clone())))
+        }
+        Commands::Edit { name, .. } => Some((AuditAction::Edit, Some(name.clone()))),
+        Commands::Rename { name, .. } => Some((AuditAction::Rename, Some(name.clone()))),
+        Commands::Remove { name, .. } => Some((AuditAction::Remove, Some(name.clone()))),
+        Commands::Enable { name, .. } => Some((AuditAction::Enable, Some(name.clone()))),
+        Commands::Disable { name, .. } => Some((AuditAction::Disable, Some(name.clone()))),
+        Commands::Kill { name, .. } => Some((AuditAction::Kill, Some(name.clone()))),
+        Commands::KillAll { .. } => Some((AuditAction::Kill, None)),
+        Commands::Apply { .. } => Some((AuditAction::Apply, None)),
+        Commands::Import { .. } => Some((AuditAction::Import, None)),
+        Commands::Undo { .. } => Some((AuditAction::Undo, None)),
+        _ => None,
+    };
 
     // Initialize the repository (infrastructure layer)
-    let repository = match WindowsRegistryRepository::new() {
-        Ok(repo) => repo,
+    let repository = match open_repository() {
+        Ok(repo) => repo.select(scope),
+        Err(e) => {
+            output.error(&e);
+            std::process::exit(1);
+        }
+    };
+    let launchers = match FileSystemLauncherStore::from_appdata() {
+        Ok(launchers) => launchers,
         Err(e) => {
-            ConsolePresenter::show_error(&e);
+            output.error(&e);
             std::process::exit(1);
         }
     };
+    let history = match FileSystemHistoryStore::from_appdata() {
+        Ok(history) => history,
+        Err(e) => {
+            output.error(&e);
+            std::process::exit(1);
+        }
+    };
+    let audit_log = match FileSystemAuditLog::from_env() {
+        Ok(audit_log) => audit_log,
+        Err(e) => {
+            output.error(&e);
+            std::process::exit(1);
+        }
+    };
+    let processes = NativeProcessProvider::new();
+
+    // A dry run makes its changes to recording wrappers and a copy of the
+    // history, so it runs every check without changing anything
+    let staged = if cli.dry_run {
+        let history_copy = InMemoryHistoryStore::new();
+        if let Err(e) = history.load().and_then(|h| history_copy.save(&h)) {
+            output.error(&e);
+            std::process::exit(1);
+        }
+        Some((
+            DryRunRepository::new(&repository).select(scope),
+            DryRunLauncherStore::new(&launchers),
+            DryRunProcessProvider::new(&processes),
+            history_copy,
+        ))
+    } else {
+        None
+    };
+    let (repository, launchers, processes, history): (
+        &dyn StartupRepository,
+        &dyn LauncherStore,
+        &dyn ProcessProvider,
+        &dyn HistoryStore,
+    ) = match &staged {
+        Some((repository, launchers, processes, history)) => {
+            (repository, launchers, processes, history)
+        }
+        None => (&repository, &launchers, &processes, &history),
+    };
+    let dry_run_output = DryRunFormatter(output.as_ref());
+    let output: &dyn OutputFormatter = match staged {
+        Some(_) if audited.is_some() => &dry_run_output,
+        _ => output.as_ref(),
+    };
+
+    // Commands change entries through the recorder, which keeps the state
+    // of every entry they touch for `history` and `undo`
+    let recorder = SnapshotRepository::new(&repository, &launchers);
+    let command_line = std::env::args()
+        .skip(1)
+        .map(|arg| domain::cmdline::quote_arg(&arg))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut reverted = Vec::new();
+    let mut killed = Vec::new();
 
     // Execute the appropriate use case based on the command
     let result = match cli.command {
-        Commands::Add { name, path } => {
-            let use_case = AddExecutableUseCase::new(&repository);
-            use_case.execute(&name, &path).map(|_| {
-                ConsolePresenter::show_success_add(&name);
-            })
+        Commands::Add {
+            name, path, force, ..
+        } => {
+            let mut use_case = AddExecutableUseCase::new(&recorder).with_force(force);
+            if let Some(location) = location {
+                use_case = use_case.with_location(location);
+            }
+            use_case
+                .execute(&name, &path)
+                .and_then(|replaced| output.added(&name, replaced.as_ref()).map_err(Into::into))
         }
         Commands::AddCommand {
             name,
             command,
             args,
             workdir,
+            force,
+            ..
         } => {
-            let use_case = AddCommandUseCase::new(&repository);
+            let mut use_case = AddCommandUseCase::new(&recorder, &launchers).with_force(force);
+            if let Some(location) = location {
+                use_case = use_case.with_location(location);
+            }
             let command_display = if args.is_empty() {
                 command.clone()
             } else {
@@ -54,53 +301,243 @@ fn main() {
                     .and_then(|p| p.to_str().map(|s| s.to_string())),
             };
 
-            // Use VBScript mode by default for most reliable silent execution
-            use domain::ExecutionMode;
             use_case
-                .execute(
-                    &name,
-                    &command,
-                    args,
-                    working_dir.as_deref(),
-                    ExecutionMode::VBScript,
-                )
-                .map(|_| {
-                    ConsolePresenter::show_success_add_command(
-                        &name,
-                        &command_display,
-                        working_dir.as_deref(),
-                    );
+                .execute(&name, &command, args, working_dir.as_deref(), mode)
+                .and_then(|replaced| {
+                    output
+                        .added_command(
+                            &name,
+                            &command_display,
+                            working_dir.as_deref(),
+                            replaced.as_ref(),
+                        )
+                        .map_err(Into::into)
                 })
         }
-        Commands::Remove { name } => {
-            let use_case = RemoveEntryUseCase::new(&repository);
-            use_case.execute(&name).map(|_| {
-                ConsolePresenter::show_success_remove(&name);
+        Commands::Edit { name, .. } => {
+            let use_case = EditEntryUseCase::new(&recorder, &launchers);
+            use_case
+                .execute(&name, &edits)
+                .and_then(|edited| output.edited(&edited).map_err(Into::into))
+        }
+        Commands::Rename { name, new_name, .. } => {
+            let use_case = RenameEntryUseCase::new(&recorder, &launchers);
+            use_case
+                .execute(&name, &new_name)
+                .and_then(|renamed| output.renamed(&name, &renamed).map_err(Into::into))
+        }
+        Commands::Remove { name, .. } => {
+            let use_case = RemoveEntryUseCase::new(&recorder, &launchers);
+            use_case
+                .execute(&name)
+                .and_then(|deleted| output.removed(&name, &deleted).map_err(Into::into))
+        }
+        Commands::Enable { name, .. } => {
+            let use_case = SetEnabledUseCase::new(&recorder);
+            use_case
+                .execute(&name, true)
+                .and_then(|changed| output.set_enabled(&name, true, changed).map_err(Into::into))
+        }
+        Commands::Disable { name, .. } => {
+            let use_case = SetEnabledUseCase::new(&recorder);
+            use_case.execute(&name, false).and_then(|changed| {
+                output
+                    .set_enabled(&name, false, changed)
+                    .map_err(Into::into)
             })
         }
         Commands::List => {
             let use_case = ListEntriesUseCase::new(&repository);
-            use_case.execute().map(|entries| {
-                ConsolePresenter::show_entries(&entries);
+            use_case.execute().and_then(|entries| {
+                let running = processes.list_processes().unwrap_or_default();
+                let views: Vec<EntryView> = entries
+                    .iter()
+                    .map(|entry| EntryView::new(entry, &running))
+                    .collect();
+                output.entries(&views).map_err(Into::into)
             })
         }
-        Commands::Kill { name } => {
-            let use_case = KillProcessUseCase::new(&repository);
-            use_case.execute(&name).map(|count| {
-                ConsolePresenter::show_kill_success(&name, count);
+        Commands::Plan { file, prune } => {
+            let use_case = PlanManifestUseCase::new(&repository, &launchers);
+            load_manifest(&file)
+                .and_then(|manifest| use_case.execute(&manifest, prune))
+                .and_then(|plan| output.plan(&plan).map_err(Into::into))
+        }
+        Commands::Apply { file, prune } => {
+            let planner = PlanManifestUseCase::new(&repository, &launchers);
+            let use_case = ApplyManifestUseCase::new(&recorder, &launchers);
+            load_manifest(&file)
+                .and_then(|manifest| planner.execute(&manifest, prune))
+                .and_then(|plan| {
+                    output.applying(&plan)?;
+                    let deleted = if plan.is_empty() {
+                        Vec::new()
+                    } else {
+                        use_case.execute(&plan)?
+                    };
+                    output.applied(&plan, &deleted).map_err(Into::into)
+                })
+        }
+        Commands::Export { file, format } => {
+            let exported = match format {
+                ExportFormat::Json => ExportEntriesUseCase::new(&repository, &launchers)
+                    .execute()
+                    .map(|backup| {
+                        let json = backup.to_json() + "\n";
+                        (backup.entries.len(), 0, json.into_bytes())
+                    }),
+                // Only the entries of Run keys can be written to a .reg file
+                ExportFormat::Reg => {
+                    ListEntriesUseCase::new(&repository)
+                        .execute()
+                        .map(|entries| {
+                            let count = entries
+                                .iter()
+                                .filter(|e| e.location.registry_key().is_some())
+                                .count();
+                            let reg = domain::RegFile::from_entries(&entries);
+                            (count, entries.len() - count, reg.encode())
+                        })
+                }
+            };
+            exported.and_then(|(count, skipped, bytes)| match file {
+                Some(file) => {
+                    std::fs::write(&file, bytes)?;
+                    output.exported(count, skipped, &file).map_err(Into::into)
+                }
+                None => {
+                    let mut stdout = std::io::stdout().lock();
+                    stdout.write_all(&bytes)?;
+                    stdout.flush().map_err(Into::into)
+                }
             })
         }
-        Commands::KillAll => {
-            let use_case = KillAllProcessesUseCase::new(&repository);
-            use_case.execute().map(|results| {
-                ConsolePresenter::show_kill_all_success(&results);
+        Commands::Import { file, on_conflict } => {
+            let is_reg = file
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("reg"));
+            std::fs::read(&file)
+                .map_err(Into::into)
+                .and_then(|bytes| {
+                    if is_reg {
+                        let use_case = ImportRegFileUseCase::new(&recorder, &launchers);
+                        domain::RegFile::decode(&bytes)
+                            .and_then(|reg| reg.startup_changes())
+                            .and_then(|changes| use_case.execute(&changes, on_conflict.into()))
+                    } else {
+                        let use_case = ImportEntriesUseCase::new(&recorder, &launchers);
+                        domain::StartupBackup::from_json(&String::from_utf8_lossy(&bytes))
+                            .and_then(|backup| use_case.execute(&backup, on_conflict.into()))
+                    }
+                })
+                .and_then(|outcomes| output.imported(&outcomes).map_err(Into::into))
+        }
+        Commands::History { limit } => {
+            let use_case = ListHistoryUseCase::new(&history);
+            use_case
+                .execute(limit)
+                .and_then(|records| output.history(&records).map_err(Into::into))
+        }
+        Commands::Undo { count } => {
+            let use_case = UndoChangesUseCase::new(&recorder, &launchers, &history);
+            use_case.execute(count as usize).and_then(|records| {
+                reverted = records.iter().map(|record| record.id).collect();
+                output.undone(&records).map_err(Into::into)
             })
         }
+        Commands::Log {
+            entry,
+            action,
+            user,
+            since,
+            until,
+            failed,
+            limit,
+        } => {
+            let use_case = ListAuditEventsUseCase::new(&audit_log);
+            let filter = domain::AuditFilter {
+                entry,
+                action: action.map(Into::into),
+                user,
+                since,
+                until,
+                failures_only: failed,
+            };
+            use_case
+                .execute(&filter, limit)
+                .and_then(|events| output.audit_events(&events).map_err(Into::into))
+        }
+        Commands::Gc => {
+            let use_case = CollectGarbageUseCase::new(&repository, &launchers);
+            use_case
+                .execute(cli.dry_run)
+                .and_then(|orphans| output.gc(&orphans, cli.dry_run).map_err(Into::into))
+        }
+        Commands::Kill {
+            name,
+            by_name,
+            timeout,
+        } => {
+            let use_case = KillProcessUseCase::new(&repository, &processes);
+            use_case
+                .execute(&name, &kill_options(by_name, timeout))
+                .and_then(|results| {
+                    let shown = output.killed(&name, &results);
+                    if !results.is_empty() {
+                        killed = vec![(name.clone(), results)];
+                    }
+                    shown.map_err(Into::into)
+                })
+        }
+        Commands::KillAll { by_name, timeout } => {
+            let use_case = KillAllProcessesUseCase::new(&repository, &processes);
+            use_case
+                .execute(&kill_options(by_name, timeout))
+                .and_then(|results| {
+                    let shown = output.killed_all(&results);
+                    killed = results;
+                    shown.map_err(Into::into)
+                })
+        }
+    };
+
+    // A dry run reports what the command would have changed and leaves
+    // the history and audit log alone
+    if let Some((repository, launchers, processes, _)) = &staged {
+        let report = DryRunView::new(
+            &repository.changes(),
+            &launchers.changes(),
+            &processes.stopped(),
+        );
+        let shown = match audited {
+            Some(_) => result.and_then(|_| output.dry_run(&report).map_err(Into::into)),
+            None => result,
+        };
+        if let Err(e) = shown {
+            output.error(&e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Record what the command changed, even when it failed partway
+    let changes = recorder.changes();
+    let actor = current_actor();
+    let audit = RecordAuditUseCase::new(&audit_log, &actor);
+    let logged = match audited {
+        Some((AuditAction::Kill, entry)) => {
+            audit.execute_kill(entry.as_deref(), &killed, result.as_ref().err())
+        }
+        Some((action, entry)) => {
+            audit.execute(action, entry.as_deref(), &changes, result.as_ref().err())
+        }
+        None => Ok(()),
     };
+    let recorded = RecordHistoryUseCase::new(&history).execute(&command_line, changes, reverted);
 
     // Handle any errors
-    if let Err(e) = result {
-        ConsolePresenter::show_error(&e);
+    if let Err(e) = result.and(logged).and(recorded.map(|_| ())) {
+        output.error(&e);
         std::process::exit(1);
     }
 }
@@ -13,10 +13,34 @@ pub enum StartupError {
     /// Entry name is invalid.
     InvalidName(String),
     /// Entry not found in registry.
-    #[allow(dead_code)]
     EntryNotFound(String),
+    /// Entry name exists in more than one startup location.
+    AmbiguousEntry(String),
+    /// An entry of that name exists and replacing it wasn't asked for.
+    EntryAlreadyExists(String),
+    /// An entry of that name exists in another startup location, where
+    /// adding would not replace it: the name and that location.
+    EntryInOtherLocation(String, String),
+    /// Startup location name is not recognized.
+    InvalidLocation(String),
+    /// Startup location can only be changed with administrator rights.
+    ElevationRequired(String),
+    /// File contents do not follow the expected format.
+    InvalidFormat(String),
+    /// Entries of an import failed validation; nothing was written.
+    ImportRejected(Vec<String>),
+    /// An entry changed after the command `undo` would revert.
+    UndoConflict(String),
     /// Registry access error.
     RegistryError(String),
+    /// Task Scheduler rejected a task or could not be reached.
+    TaskSchedulerError(String),
+    /// Options of a scheduled task are out of the range Task Scheduler accepts.
+    InvalidTaskSettings(String),
+    /// Process inspection or termination error.
+    ProcessError(String),
+    /// Operation is not available on the current platform.
+    Unsupported(String),
     /// Generic I/O error.
     IoError(std::io::Error),
 }
@@ -37,12 +61,76 @@ impl fmt::Display for StartupError {
             StartupError::EntryNotFound(name) => {
                 write!(f, "Entry '{}' not found in startup registry", name)
             }
+            StartupError::AmbiguousEntry(name) => write!(
+                f,
+                "Entry '{}' exists in several startup locations; choose one with --location",
+                name
+            ),
+            StartupError::EntryAlreadyExists(name) => write!(
+                f,
+                "Entry '{}' already exists; use --force to replace it",
+                name
+            ),
+            StartupError::EntryInOtherLocation(name, location) => write!(
+                f,
+                "Entry '{}' already exists in {}; remove it there first",
+                name, location
+            ),
+            StartupError::InvalidLocation(msg) => write!(f, "Invalid startup location: {}", msg),
+            StartupError::ElevationRequired(location) => write!(
+                f,
+                "Administrator rights are required to change {}; run from an elevated prompt",
+                location
+            ),
+            StartupError::InvalidFormat(msg) => write!(f, "Invalid file format: {}", msg),
+            StartupError::ImportRejected(problems) => write!(
+                f,
+                "Nothing was imported; fix these entries first:\n  {}",
+                problems.join("\n  ")
+            ),
+            StartupError::UndoConflict(msg) => write!(f, "Cannot undo: {}", msg),
             StartupError::RegistryError(msg) => write!(f, "Registry error: {}", msg),
+            StartupError::TaskSchedulerError(msg) => write!(f, "Task Scheduler error: {}", msg),
+            StartupError::InvalidTaskSettings(msg) => {
+                write!(f, "Invalid scheduled task settings: {}", msg)
+            }
+            StartupError::ProcessError(msg) => write!(f, "Process error: {}", msg),
+            StartupError::Unsupported(msg) => {
+                write!(f, "Not supported on this platform: {}", msg)
+            }
             StartupError::IoError(err) => write!(f, "I/O error: {}", err),
         }
     }
 }
 
+impl StartupError {
+    /// Returns a stable identifier of the kind of error, for scripts
+    /// reading machine-readable output.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            StartupError::PathNotFound(_) => "path-not-found",
+            StartupError::DirectoryNotFound(_) => "directory-not-found",
+            StartupError::NotADirectory(_) => "not-a-directory",
+            StartupError::InvalidName(_) => "invalid-name",
+            StartupError::EntryNotFound(_) => "entry-not-found",
+            StartupError::AmbiguousEntry(_) => "ambiguous-entry",
+            StartupError::EntryAlreadyExists(_) => "entry-already-exists",
+            StartupError::EntryInOtherLocation(..) => "entry-in-other-location",
+            StartupError::InvalidLocation(_) => "invalid-location",
+            StartupError::ElevationRequired(_) => "elevation-required",
+            StartupError::InvalidFormat(_) => "invalid-format",
+            StartupError::ImportRejected(_) => "import-rejected",
+            StartupError::UndoConflict(_) => "undo-conflict",
+            StartupError::RegistryError(_) => "registry-error",
+            StartupError::TaskSchedulerError(_) => "task-scheduler-error",
+            StartupError::InvalidTaskSettings(_) => "invalid-task-settings",
+            StartupError::ProcessError(_) => "process-error",
+            StartupError::Unsupported(_) => "unsupported",
+            StartupError::IoError(_) => "io-error",
+        }
+    }
+}
+
 impl std::error::Error for StartupError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
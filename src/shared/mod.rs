@@ -1 +1,5 @@
 pub mod error;
+pub mod time;
+
+#[cfg(test)]
+mod time_test;
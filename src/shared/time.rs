@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+
+/// Number of 100-nanosecond intervals between 1601-01-01 and 1970-01-01.
+const FILETIME_UNIX_EPOCH: i64 = 116_444_736_000_000_000;
+
+/// Converts a Windows FILETIME (100-nanosecond intervals since
+/// 1601-01-01 UTC) to a UTC timestamp.
+pub fn from_filetime(filetime: u64) -> Option<DateTime<Utc>> {
+    let since_unix = i64::try_from(filetime).ok()? - FILETIME_UNIX_EPOCH;
+    DateTime::from_timestamp(
+        since_unix.div_euclid(10_000_000),
+        (since_unix.rem_euclid(10_000_000) * 100) as u32,
+    )
+}
+
+/// Converts a UTC timestamp to a Windows FILETIME.
+/// Times before 1601 are clamped to zero.
+pub fn to_filetime(time: DateTime<Utc>) -> u64 {
+    let intervals = time.timestamp() * 10_000_000
+        + i64::from(time.timestamp_subsec_nanos() / 100)
+        + FILETIME_UNIX_EPOCH;
+    u64::try_from(intervals).unwrap_or(0)
+}
@@ -0,0 +1,27 @@
+#[cfg(test)]
+mod tests {
+    use super::super::time::*;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_unix_epoch_filetime() {
+        let epoch = from_filetime(116_444_736_000_000_000).unwrap();
+
+        assert_eq!(epoch, Utc.timestamp_opt(0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_filetime_round_trip_keeps_100ns_precision() {
+        let time = Utc.timestamp_opt(1_700_000_000, 123_456_700).unwrap();
+
+        assert_eq!(from_filetime(to_filetime(time)), Some(time));
+    }
+
+    #[test]
+    fn test_filetime_before_unix_epoch() {
+        let time = Utc.with_ymd_and_hms(1601, 1, 1, 0, 0, 0).unwrap();
+
+        assert_eq!(to_filetime(time), 0);
+        assert_eq!(from_filetime(0), Some(time));
+    }
+}
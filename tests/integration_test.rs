@@ -1,75 +1,35 @@
-use std::cell::RefCell;
-use std::collections::HashMap;
-
-// Mock repository for testing
-struct MockRepository {
-    entries: RefCell<HashMap<String, String>>,
-}
-
-impl MockRepository {
-    fn new() -> Self {
-        Self {
-            entries: RefCell::new(HashMap::new()),
-        }
-    }
-
-    fn with_entries(entries: Vec<(&str, &str)>) -> Self {
-        let mut map = HashMap::new();
-        for (name, command) in entries {
-            map.insert(name.to_string(), command.to_string());
-        }
-        Self {
-            entries: RefCell::new(map),
-        }
-    }
-}
-
-// Import the domain types
-use windows_startup_manager::domain::{StartupEntry, StartupRepository};
-use windows_startup_manager::shared::error::{Result, StartupError};
-
-impl StartupRepository for MockRepository {
-    fn add(&self, entry: &StartupEntry) -> Result<()> {
-        self.entries
-            .borrow_mut()
-            .insert(entry.name.clone(), entry.command.clone());
-        Ok(())
-    }
-
-    fn remove(&self, name: &str) -> Result<()> {
-        if self.entries.borrow_mut().remove(name).is_some() {
-            Ok(())
-        } else {
-            Err(StartupError::RegistryError(format!(
-                "Entry '{}' not found",
-                name
-            )))
-        }
-    }
-
-    fn list(&self) -> Result<Vec<StartupEntry>> {
-        Ok(self
-            .entries
-            .borrow()
-            .iter()
-            .map(|(name, command)| StartupEntry::new(name.clone(), command.clone()))
-            .collect())
-    }
-
-    fn exists(&self, name: &str) -> Result<bool> {
-        Ok(self.entries.borrow().contains_key(name))
-    }
-}
+use windows_startup_manager::domain::ProcessInfo;
+use windows_startup_manager::domain::StartupRepository;
+use windows_startup_manager::infrastructure::{
+    CompositeRepository, FakeProcessProvider, InMemoryAuditLog, InMemoryHistoryStore,
+    InMemoryLauncherStore, InMemoryRepository, InMemoryTaskScheduler, RepositoryOperation,
+    ScheduledTaskRepository, SnapshotRepository, StartupFolderRepository,
+};
+use windows_startup_manager::shared::error::StartupError;
 
 #[cfg(test)]
 mod integration_tests {
     use super::*;
+    use std::time::Duration;
     use windows_startup_manager::application::*;
-    use windows_startup_manager::domain::ExecutionMode;
+    use windows_startup_manager::domain::{
+        AuditAction, AuditActor, AuditFilter, AuditOutcome, ConsoleOptions, ExecutionMode,
+        LauncherStore, ManifestFormat, MatchStrategy, RegChange, RegFile, RestartPolicy,
+        StartupApproval, StartupBackup, StartupCommand, StartupEntry, StartupLocation,
+        StartupManifest, TaskDefinition, TaskSettings,
+    };
+
+    /// Kill options that skip the graceful close step.
+    fn force_kill(strategy: MatchStrategy) -> KillOptions {
+        KillOptions {
+            strategy,
+            graceful_timeout: Duration::ZERO,
+        }
+    }
 
     #[test]
     fn test_add_executable_use_case() {
-        let repo = MockRepository::new();
+        let repo = InMemoryRepository::new();
         let use_case = AddExecutableUseCase::new(&repo);
 
         // Create a temp file for testing
@@ -90,7 +50,7 @@ mod integration_tests {
 
     #[test]
     fn test_add_executable_nonexistent_path() {
-        let repo = MockRepository::new();
+        let repo = InMemoryRepository::new();
         let use_case = AddExecutableUseCase::new(&repo);
 
         let result = use_case.execute("TestApp", "C:\\nonexistent\\app.exe");
@@ -101,7 +61,7 @@ mod integration_tests {
 
     #[test]
     fn test_add_executable_empty_name() {
-        let repo = MockRepository::new();
+        let repo = InMemoryRepository::new();
         let use_case = AddExecutableUseCase::new(&repo);
 
         let temp_file = std::env::temp_dir().join("test_app2.exe");
@@ -117,8 +77,9 @@ mod integration_tests {
 
     #[test]
     fn test_add_command_use_case() {
-        let repo = MockRepository::new();
-        let use_case = AddCommandUseCase::new(&repo);
+        let repo = InMemoryRepository::new();
+        let launchers = InMemoryLauncherStore::new();
+        let use_case = AddCommandUseCase::new(&repo, &launchers);
 
         let temp_dir = std::env::temp_dir();
 
@@ -138,10 +99,199 @@ mod integration_tests {
         assert!(entries[0].command.contains("wscript.exe"));
     }
 
+    #[test]
+    fn test_add_command_launchers_are_per_entry() {
+        let repo = InMemoryRepository::new();
+        let launchers = InMemoryLauncherStore::new();
+        let use_case = AddCommandUseCase::new(&repo, &launchers);
+        let web = std::env::temp_dir().join("wsm_launcher_web");
+        let api = std::env::temp_dir().join("wsm_launcher_api");
+        std::fs::create_dir_all(&web).unwrap();
+        std::fs::create_dir_all(&api).unwrap();
+
+        for (name, dir) in [("Web Dev", &web), ("Api Dev", &api)] {
+            use_case
+                .execute(
+                    name,
+                    "bun",
+                    vec!["run".to_string(), "dev".to_string()],
+                    Some(dir.to_str().unwrap()),
+                    ExecutionMode::VBScript,
+                )
+                .unwrap();
+        }
+
+        let entries = repo.list().unwrap();
+        let web_command = &entries[0].command;
+        let api_command = &entries[1].command;
+        assert_ne!(web_command, api_command);
+        assert!(web_command.contains("web_dev_"));
+
+        // Each entry still runs in its own directory
+        let read_launcher = |path: &str| launchers.file(&launchers.filename_of(path)?);
+        let web_decoded = StartupCommand::parse_with(web_command, read_launcher).unwrap();
+        let api_decoded = StartupCommand::parse_with(api_command, read_launcher).unwrap();
+        assert_eq!(web_decoded.workdir(), web.to_str());
+        assert_eq!(api_decoded.workdir(), api.to_str());
+
+        let manifest = launchers.manifest();
+        let web_launcher = manifest.launcher_for("Web Dev").unwrap();
+        assert!(web_command.contains(web_launcher));
+    }
+
+    #[test]
+    fn test_add_command_registry_value_points_at_written_launcher() {
+        let repo = InMemoryRepository::new();
+        let launchers = InMemoryLauncherStore::with_location("D:\\launchers");
+        let temp_dir = std::env::temp_dir();
+
+        AddCommandUseCase::new(&repo, &launchers)
+            .execute(
+                "Relocated",
+                "bun",
+                vec!["run".to_string(), "dev".to_string()],
+                Some(temp_dir.to_str().unwrap()),
+                ExecutionMode::VBScript,
+            )
+            .unwrap();
+
+        let filenames = launchers.filenames();
+        assert_eq!(filenames.len(), 1);
+        assert_eq!(
+            repo.entries()[0].command,
+            format!(
+                "wscript.exe //B //Nologo \"D:\\launchers\\{}\"",
+                filenames[0]
+            )
+        );
+        assert!(
+            launchers
+                .file(&filenames[0])
+                .unwrap()
+                .contains("WshShell.Run \"bun run dev\"")
+        );
+    }
+
+    #[test]
+    fn test_remove_deletes_owned_launcher() {
+        let repo = InMemoryRepository::new();
+        let launchers = InMemoryLauncherStore::new();
+        let temp_dir = std::env::temp_dir();
+        AddCommandUseCase::new(&repo, &launchers)
+            .execute(
+                "Removable",
+                "bun",
+                vec!["run".to_string(), "dev".to_string()],
+                Some(temp_dir.to_str().unwrap()),
+                ExecutionMode::VBScript,
+            )
+            .unwrap();
+        let launcher = launchers.referenced_by(&repo.entries()[0].command).unwrap();
+        assert!(launchers.file(&launcher).is_some());
+
+        let deleted = RemoveEntryUseCase::new(&repo, &launchers)
+            .execute("Removable")
+            .unwrap();
+
+        assert_eq!(deleted, vec![launcher.clone()]);
+        assert_eq!(launchers.file(&launcher), None);
+    }
+
+    #[test]
+    fn test_remove_keeps_launcher_used_by_another_entry() {
+        let repo = InMemoryRepository::new();
+        let launchers = InMemoryLauncherStore::new();
+        let temp_dir = std::env::temp_dir();
+        AddCommandUseCase::new(&repo, &launchers)
+            .execute(
+                "Shared",
+                "bun",
+                vec!["run".to_string(), "shared".to_string()],
+                Some(temp_dir.to_str().unwrap()),
+                ExecutionMode::VBScript,
+            )
+            .unwrap();
+        let command = repo.entries()[0].command.clone();
+        repo.seed(StartupEntry::new("Copy", command.clone()));
+        let launcher = launchers.referenced_by(&command).unwrap();
+
+        let deleted = RemoveEntryUseCase::new(&repo, &launchers)
+            .execute("Shared")
+            .unwrap();
+
+        assert!(deleted.is_empty());
+        assert!(launchers.file(&launcher).is_some());
+    }
+
+    #[test]
+    fn test_overwrite_deletes_previous_launcher() {
+        let repo = InMemoryRepository::new();
+        let launchers = InMemoryLauncherStore::new();
+        let use_case = AddCommandUseCase::new(&repo, &launchers).with_force(true);
+        let temp_dir = std::env::temp_dir();
+
+        use_case
+            .execute(
+                "Replaced",
+                "bun",
+                vec!["run".to_string(), "old".to_string()],
+                Some(temp_dir.to_str().unwrap()),
+                ExecutionMode::VBScript,
+            )
+            .unwrap();
+        let old_launcher = launchers.referenced_by(&repo.entries()[0].command).unwrap();
+        use_case
+            .execute(
+                "Replaced",
+                "bun",
+                vec!["run".to_string(), "new".to_string()],
+                Some(temp_dir.to_str().unwrap()),
+                ExecutionMode::VBScript,
+            )
+            .unwrap();
+        let new_launcher = launchers.referenced_by(&repo.entries()[0].command).unwrap();
+
+        assert_ne!(old_launcher, new_launcher);
+        assert_eq!(launchers.file(&old_launcher), None);
+        assert!(launchers.file(&new_launcher).is_some());
+    }
+
+    #[test]
+    fn test_gc_deletes_unreferenced_launchers() {
+        let repo = InMemoryRepository::new();
+        let launchers = InMemoryLauncherStore::new();
+        let temp_dir = std::env::temp_dir();
+        AddCommandUseCase::new(&repo, &launchers)
+            .execute(
+                "Kept",
+                "bun",
+                vec!["run".to_string(), "kept".to_string()],
+                Some(temp_dir.to_str().unwrap()),
+                ExecutionMode::VBScript,
+            )
+            .unwrap();
+        let kept = launchers.referenced_by(&repo.entries()[0].command).unwrap();
+        let orphan = "launcher_deadbeef.vbs".to_string();
+        launchers.write(&orphan, "WScript.Quit").unwrap();
+        let use_case = CollectGarbageUseCase::new(&repo, &launchers);
+
+        let preview = use_case.execute(true).unwrap();
+        assert!(preview.contains(&orphan));
+        assert!(!preview.contains(&kept));
+        assert!(launchers.file(&orphan).is_some());
+
+        let deleted = use_case.execute(false).unwrap();
+        assert_eq!(deleted, preview);
+        assert_eq!(launchers.file(&orphan), None);
+        assert!(launchers.file(&kept).is_some());
+        assert!(use_case.execute(true).unwrap().is_empty());
+    }
+
     #[test]
     fn test_add_command_invalid_workdir() {
-        let repo = MockRepository::new();
-        let use_case = AddCommandUseCase::new(&repo);
+        let repo = InMemoryRepository::new();
+        let launchers = InMemoryLauncherStore::new();
+        let use_case = AddCommandUseCase::new(&repo, &launchers);
 
         let result = use_case.execute(
             "TestServer",
@@ -160,8 +310,9 @@ mod integration_tests {
 
     #[test]
     fn test_remove_entry_use_case() {
-        let repo = MockRepository::with_entries(vec![("TestApp", "C:\\test.exe")]);
-        let use_case = RemoveEntryUseCase::new(&repo);
+        let repo = InMemoryRepository::with_entries(vec![("TestApp", "C:\\test.exe")]);
+        let launchers = InMemoryLauncherStore::new();
+        let use_case = RemoveEntryUseCase::new(&repo, &launchers);
 
         let result = use_case.execute("TestApp");
 
@@ -173,8 +324,9 @@ mod integration_tests {
 
     #[test]
     fn test_remove_nonexistent_entry() {
-        let repo = MockRepository::new();
-        let use_case = RemoveEntryUseCase::new(&repo);
+        let repo = InMemoryRepository::new();
+        let launchers = InMemoryLauncherStore::new();
+        let use_case = RemoveEntryUseCase::new(&repo, &launchers);
 
         let result = use_case.execute("NonExistent");
 
@@ -183,7 +335,7 @@ mod integration_tests {
 
     #[test]
     fn test_list_entries_use_case() {
-        let repo = MockRepository::with_entries(vec![
+        let repo = InMemoryRepository::with_entries(vec![
             ("App1", "C:\\app1.exe"),
             ("App2", "C:\\app2.exe"),
             ("App3", "C:\\app3.exe"),
@@ -199,7 +351,7 @@ mod integration_tests {
 
     #[test]
     fn test_list_entries_empty() {
-        let repo = MockRepository::new();
+        let repo = InMemoryRepository::new();
         let use_case = ListEntriesUseCase::new(&repo);
 
         let result = use_case.execute();
@@ -211,10 +363,11 @@ mod integration_tests {
 
     #[test]
     fn test_multiple_operations() {
-        let repo = MockRepository::new();
+        let repo = InMemoryRepository::new();
+        let launchers = InMemoryLauncherStore::new();
 
         // Add multiple entries
-        let add_use_case = AddCommandUseCase::new(&repo);
+        let add_use_case = AddCommandUseCase::new(&repo, &launchers);
         let temp_dir = std::env::temp_dir();
 
         add_use_case
@@ -243,7 +396,7 @@ mod integration_tests {
         assert_eq!(entries.len(), 2);
 
         // Remove one entry
-        let remove_use_case = RemoveEntryUseCase::new(&repo);
+        let remove_use_case = RemoveEntryUseCase::new(&repo, &launchers);
         remove_use_case.execute("Server1").unwrap();
 
         // Verify removal
@@ -254,7 +407,7 @@ mod integration_tests {
 
     #[test]
     fn test_repository_exists() {
-        let repo = MockRepository::with_entries(vec![("ExistingApp", "C:\\app.exe")]);
+        let repo = InMemoryRepository::with_entries(vec![("ExistingApp", "C:\\app.exe")]);
 
         assert!(repo.exists("ExistingApp").unwrap());
         assert!(!repo.exists("NonExistentApp").unwrap());
@@ -262,25 +415,1619 @@ mod integration_tests {
 
     #[test]
     fn test_add_duplicate_name() {
-        let repo = MockRepository::with_entries(vec![("App", "C:\\old.exe")]);
-        let use_case = AddCommandUseCase::new(&repo);
+        let repo = InMemoryRepository::with_entries(vec![("App", "C:\\old.exe")]);
+        let launchers = InMemoryLauncherStore::new();
         let temp_dir = std::env::temp_dir();
+        let add = |force: bool| {
+            AddCommandUseCase::new(&repo, &launchers)
+                .with_force(force)
+                .execute(
+                    "App",
+                    "new",
+                    vec![],
+                    Some(temp_dir.to_str().unwrap()),
+                    ExecutionMode::VBScript,
+                )
+        };
 
-        // Adding with same name should overwrite
-        use_case
+        // Adding with the same name fails and leaves the entry alone
+        assert!(matches!(add(false), Err(StartupError::EntryAlreadyExists(name)) if name == "App"));
+        assert_eq!(
+            repo.entries(),
+            vec![StartupEntry::new("App", "C:\\old.exe")]
+        );
+        assert!(launchers.filenames().is_empty());
+
+        // --force replaces it and hands back the old value
+        let replaced = add(true).unwrap().unwrap();
+        assert_eq!(replaced.command, "C:\\old.exe");
+        let entries = repo.list().unwrap();
+        assert_eq!(entries.len(), 1);
+        // VBScript mode wraps the command, so check for wscript instead
+        assert!(entries[0].command.contains("wscript.exe"));
+        assert!(!entries[0].command.contains("old.exe"));
+    }
+
+    #[test]
+    fn test_add_executable_duplicate_name() {
+        let repo = InMemoryRepository::with_entries(vec![("App", "C:\\old.exe")]);
+        let temp_file = std::env::temp_dir().join("test_app_duplicate.exe");
+        std::fs::write(&temp_file, "test").unwrap();
+        let path = temp_file.to_str().unwrap();
+
+        let rejected = AddExecutableUseCase::new(&repo).execute("App", path);
+        let replaced = AddExecutableUseCase::new(&repo)
+            .with_force(true)
+            .execute("App", path);
+        // Nothing is replaced when the name is new
+        let added = AddExecutableUseCase::new(&repo).execute("Other", path);
+
+        // Clean up
+        std::fs::remove_file(&temp_file).ok();
+
+        assert!(matches!(rejected, Err(StartupError::EntryAlreadyExists(_))));
+        assert_eq!(
+            replaced.unwrap().map(|e| e.command),
+            Some("C:\\old.exe".to_string())
+        );
+        assert_eq!(repo.entries()[0].command, path);
+        assert_eq!(added.unwrap(), None);
+    }
+
+    #[test]
+    fn test_add_force_does_not_duplicate_an_entry_of_another_location() {
+        let repo = CompositeRepository::new()
+            .with(
+                StartupLocation::CurrentUserRun,
+                InMemoryRepository::with_entries([("App", "C:\\old.exe")]),
+            )
+            .with(StartupLocation::LocalMachineRun, InMemoryRepository::new());
+        let launchers = InMemoryLauncherStore::new();
+        let temp_dir = std::env::temp_dir();
+        let temp_file = temp_dir.join("test_app_other_location.exe");
+        std::fs::write(&temp_file, "test").unwrap();
+
+        let executable = AddExecutableUseCase::new(&repo)
+            .with_location(StartupLocation::LocalMachineRun)
+            .with_force(true)
+            .execute("app", temp_file.to_str().unwrap());
+        let command = AddCommandUseCase::new(&repo, &launchers)
+            .with_location(StartupLocation::LocalMachineRun)
+            .with_force(true)
             .execute(
-                "App",
+                "app",
                 "new",
                 vec![],
-                Some(temp_dir.to_str().unwrap()),
+                temp_dir.to_str(),
                 ExecutionMode::VBScript,
-            )
-            .unwrap();
+            );
+        std::fs::remove_file(&temp_file).ok();
 
+        assert!(matches!(
+            executable,
+            Err(StartupError::EntryInOtherLocation(name, location))
+                if name == "App" && location == StartupLocation::CurrentUserRun.to_string()
+        ));
+        assert!(matches!(
+            command,
+            Err(StartupError::EntryInOtherLocation(..))
+        ));
         let entries = repo.list().unwrap();
         assert_eq!(entries.len(), 1);
-        // VBScript mode wraps the command, so check for wscript instead
-        assert!(entries[0].command.contains("wscript.exe"));
-        assert!(!entries[0].command.contains("old.exe"));
+        assert_eq!(entries[0].location, StartupLocation::CurrentUserRun);
+    }
+
+    /// Adds a VBScript-mode `Dev` entry running `bun run dev`.
+    fn add_dev(repo: &impl StartupRepository, launchers: &InMemoryLauncherStore) -> String {
+        let workdir = std::env::temp_dir();
+        AddCommandUseCase::new(repo, launchers)
+            .execute(
+                "Dev",
+                "bun",
+                vec!["run".to_string(), "dev".to_string()],
+                workdir.to_str(),
+                ExecutionMode::VBScript,
+            )
+            .unwrap();
+        launchers.filenames()[0].clone()
+    }
+
+    #[test]
+    fn test_edit_rewrites_launcher_and_keeps_state() {
+        let repo = InMemoryRepository::new();
+        let launchers = InMemoryLauncherStore::new();
+        let old_launcher = add_dev(&repo, &launchers);
+        SetEnabledUseCase::new(&repo).execute("Dev", false).unwrap();
+        let before = repo.entries()[0].clone();
+        let use_case = EditEntryUseCase::new(&repo, &launchers);
+        let edits = EntryEdits {
+            args: Some(vec!["run".to_string(), "start".to_string()]),
+            ..EntryEdits::default()
+        };
+
+        let edited = use_case.execute("dev", &edits).unwrap();
+
+        let after = repo.entries()[0].clone();
+        let new_launcher = launchers.referenced_by(&after.command).unwrap();
+        assert_eq!(edited.before, before);
+        assert_eq!(edited.after, after);
+        assert_eq!(edited.command.command_line(), "bun run start");
+        assert_eq!(edited.command.workdir(), std::env::temp_dir().to_str());
+        assert_eq!(edited.command.mode(), Some(ExecutionMode::VBScript));
+        assert!(!after.enabled);
+        assert_eq!(after.disabled_at, before.disabled_at);
+        assert_eq!(launchers.filenames(), std::slice::from_ref(&new_launcher));
+        assert_ne!(new_launcher, old_launcher);
+        assert!(
+            launchers
+                .file(&new_launcher)
+                .unwrap()
+                .contains("bun run start")
+        );
+        assert_eq!(
+            launchers.manifest().launcher_for("Dev"),
+            Some(new_launcher.as_str())
+        );
+
+        // The same edit again changes nothing
+        repo.clear_calls();
+        let unchanged = use_case.execute("Dev", &edits).unwrap();
+        assert_eq!(unchanged.before, unchanged.after);
+        assert!(
+            !repo
+                .calls()
+                .iter()
+                .any(|call| call.operation() == RepositoryOperation::Add)
+        );
+    }
+
+    #[test]
+    fn test_edit_clears_the_working_directory() {
+        let repo = InMemoryRepository::with_entries(vec![("Tool", "C:\\tools\\tool.exe")]);
+        let launchers = InMemoryLauncherStore::new();
+        add_dev(&repo, &launchers);
+        let use_case = EditEntryUseCase::new(&repo, &launchers);
+        let clear = EntryEdits {
+            workdir: Some(None),
+            ..EntryEdits::default()
+        };
+
+        let edited = use_case.execute("Dev", &clear).unwrap();
+        let tool = use_case.execute("Tool", &clear).unwrap();
+
+        assert_eq!(edited.command.workdir(), None);
+        assert_eq!(edited.command.command_line(), "bun run dev");
+        let launcher = launchers.referenced_by(&edited.after.command).unwrap();
+        assert!(
+            !launchers
+                .file(&launcher)
+                .unwrap()
+                .contains("CurrentDirectory")
+        );
+        // A bare executable has no working directory and stays as it is
+        assert_eq!(tool.after.command, "C:\\tools\\tool.exe");
+    }
+
+    #[test]
+    fn test_edit_decodes_executables_and_rejects_moves() {
+        let repo = InMemoryRepository::with_entries(vec![
+            ("Tool", "C:\\tools\\tool.exe"),
+            ("Agent", "C:\\agent\\agent.exe"),
+        ]);
+        let launchers = InMemoryLauncherStore::new();
+        let use_case = EditEntryUseCase::new(&repo, &launchers);
+
+        // Arguments turn the executable into a plain command line
+        use_case
+            .execute(
+                "Tool",
+                &EntryEdits {
+                    args: Some(vec!["--tray".to_string()]),
+                    ..EntryEdits::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(repo.entries()[0].command, "C:\\tools\\tool.exe --tray");
+        assert!(launchers.filenames().is_empty());
+
+        let moved = use_case.execute(
+            "Agent",
+            &EntryEdits {
+                mode: Some(ExecutionMode::ScheduledTask(TaskSettings::default())),
+                ..EntryEdits::default()
+            },
+        );
+        let missing_path = use_case.execute(
+            "Agent",
+            &EntryEdits {
+                command: Some("C:\\nonexistent\\agent.exe".to_string()),
+                ..EntryEdits::default()
+            },
+        );
+        let missing = use_case.execute("Missing", &EntryEdits::default());
+
+        assert!(matches!(moved, Err(StartupError::Unsupported(_))));
+        assert!(matches!(missing_path, Err(StartupError::PathNotFound(_))));
+        assert!(matches!(missing, Err(StartupError::EntryNotFound(_))));
+        assert_eq!(repo.entries()[1].command, "C:\\agent\\agent.exe");
+    }
+
+    #[test]
+    fn test_rename_moves_value_and_launcher() {
+        let repo = InMemoryRepository::with_entries(vec![("Other", "other.exe")]);
+        let launchers = InMemoryLauncherStore::new();
+        let old_launcher = add_dev(&repo, &launchers);
+        SetEnabledUseCase::new(&repo).execute("Dev", false).unwrap();
+        let disabled_at = repo.entries()[1].disabled_at;
+        let use_case = RenameEntryUseCase::new(&repo, &launchers);
+
+        let renamed = use_case.execute("dev", "Server").unwrap();
+
+        let new_launcher = launchers.referenced_by(&renamed.command).unwrap();
+        assert_eq!(
+            repo.entries(),
+            vec![repo.entries()[0].clone(), renamed.clone()]
+        );
+        assert_eq!(renamed.name, "Server");
+        assert!(!renamed.enabled);
+        assert_eq!(renamed.disabled_at, disabled_at);
+        assert!(new_launcher.starts_with("server_"));
+        assert_eq!(launchers.file(&old_launcher), None);
+        assert!(
+            launchers
+                .file(&new_launcher)
+                .unwrap()
+                .contains("bun run dev")
+        );
+        assert_eq!(launchers.manifest().launcher_for("Dev"), None);
+        assert_eq!(
+            launchers.manifest().launcher_for("Server"),
+            Some(new_launcher.as_str())
+        );
+
+        let taken = use_case.execute("Server", "Other");
+        assert!(matches!(taken, Err(StartupError::EntryAlreadyExists(name)) if name == "Other"));
+        // Changing the case of the name is not a conflict
+        assert_eq!(use_case.execute("Server", "server").unwrap().name, "server");
+        assert_eq!(repo.entries().len(), 2);
+    }
+
+    /// Repository failing to store entries named `rejected`.
+    struct RejectingRepository {
+        inner: InMemoryRepository,
+        rejected: &'static str,
+    }
+
+    impl StartupRepository for RejectingRepository {
+        fn add(&self, entry: &StartupEntry) -> Result<(), StartupError> {
+            if entry.name == self.rejected {
+                return Err(StartupError::RegistryError("access denied".to_string()));
+            }
+            self.inner.add(entry)
+        }
+
+        fn remove(&self, name: &str) -> Result<(), StartupError> {
+            self.inner.remove(name)
+        }
+
+        fn list(&self) -> Result<Vec<StartupEntry>, StartupError> {
+            self.inner.list()
+        }
+
+        fn exists(&self, name: &str) -> Result<bool, StartupError> {
+            self.inner.exists(name)
+        }
+
+        fn set_approval(&self, name: &str, approval: StartupApproval) -> Result<(), StartupError> {
+            self.inner.set_approval(name, approval)
+        }
+    }
+
+    #[test]
+    fn test_rename_is_reverted_when_storing_fails() {
+        let repo = RejectingRepository {
+            inner: InMemoryRepository::new(),
+            rejected: "Server",
+        };
+        let launchers = InMemoryLauncherStore::new();
+        let launcher = add_dev(&repo, &launchers);
+        SetEnabledUseCase::new(&repo).execute("Dev", false).unwrap();
+        let original = repo.inner.entries();
+
+        let result = RenameEntryUseCase::new(&repo, &launchers).execute("Dev", "Server");
+
+        assert!(matches!(result, Err(StartupError::RegistryError(_))));
+        assert_eq!(repo.inner.entries(), original);
+        assert_eq!(launchers.filenames(), std::slice::from_ref(&launcher));
+        assert_eq!(
+            launchers.manifest().launcher_for("Dev"),
+            Some(launcher.as_str())
+        );
+    }
+
+    #[test]
+    fn test_update_and_rename_apply_to_the_holding_location() {
+        let repo = CompositeRepository::new()
+            .with(
+                StartupLocation::CurrentUserRun,
+                InMemoryRepository::with_entries([("Dev", "dev.exe")]),
+            )
+            .with(
+                StartupLocation::LocalMachineRun,
+                InMemoryRepository::with_entries([("Agent", "agent.exe")]),
+            );
+
+        let missing = repo.update(&StartupEntry::new("Missing", "missing.exe"));
+        let taken = repo.rename("Dev", "Agent");
+        repo.update(&StartupEntry::new("Agent", "agent.exe --tray"))
+            .unwrap();
+        repo.rename("Dev", "Server").unwrap();
+
+        assert!(matches!(missing, Err(StartupError::EntryNotFound(_))));
+        assert!(matches!(taken, Err(StartupError::EntryAlreadyExists(_))));
+        let entries: Vec<_> = repo
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|e| (e.name, e.command, e.location))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                (
+                    "Server".to_string(),
+                    "dev.exe".to_string(),
+                    StartupLocation::CurrentUserRun
+                ),
+                (
+                    "Agent".to_string(),
+                    "agent.exe --tray".to_string(),
+                    StartupLocation::LocalMachineRun
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_kill_process_use_case() {
+        let repo = InMemoryRepository::with_entries(vec![("Notes", "notepad.exe C:\\notes.txt")]);
+        let processes = FakeProcessProvider::with_processes(vec![
+            ProcessInfo::new(100, "notepad.exe", "notepad.exe C:\\notes.txt"),
+            ProcessInfo::new(200, "explorer.exe", "explorer.exe"),
+        ]);
+        let use_case = KillProcessUseCase::new(&repo, &processes);
+
+        let count = use_case
+            .execute("Notes", &force_kill(MatchStrategy::CommandLine))
+            .unwrap();
+
+        assert_eq!(count.len(), 1);
+        assert_eq!(processes.killed(), vec![100]);
+    }
+
+    #[test]
+    fn test_kill_process_unknown_entry() {
+        let repo = InMemoryRepository::new();
+        let processes = FakeProcessProvider::new();
+        let use_case = KillProcessUseCase::new(&repo, &processes);
+
+        let result = use_case.execute("Missing", &KillOptions::default());
+
+        assert!(matches!(result, Err(StartupError::EntryNotFound(_))));
+    }
+
+    #[test]
+    fn test_kill_process_ignores_case_and_rejects_duplicates() {
+        let repo = CompositeRepository::new()
+            .with(
+                StartupLocation::CurrentUserRun,
+                InMemoryRepository::with_entries([("MyApp", "app.exe --user")]),
+            )
+            .with(
+                StartupLocation::LocalMachineRun,
+                InMemoryRepository::with_entries([("Agent", "agent.exe"), ("MYAPP", "app.exe")]),
+            );
+        let processes = FakeProcessProvider::with_processes(vec![
+            ProcessInfo::new(1, "agent.exe", "agent.exe"),
+            ProcessInfo::new(2, "app.exe", "app.exe"),
+        ]);
+        let use_case = KillProcessUseCase::new(&repo, &processes);
+
+        let duplicate = use_case.execute("myapp", &force_kill(MatchStrategy::ImageName));
+        let results = use_case
+            .execute("agent", &force_kill(MatchStrategy::CommandLine))
+            .unwrap();
+
+        assert!(matches!(duplicate, Err(StartupError::AmbiguousEntry(_))));
+        assert_eq!(results.len(), 1);
+        assert_eq!(processes.killed(), vec![1]);
+    }
+
+    #[test]
+    fn test_kill_all_processes_use_case() {
+        let repo =
+            InMemoryRepository::with_entries(vec![("Notes", "notepad.exe"), ("Calc", "calc.exe")]);
+        let processes = FakeProcessProvider::with_processes(vec![
+            ProcessInfo::new(1, "notepad.exe", "notepad.exe"),
+            ProcessInfo::new(2, "notepad.exe", "notepad.exe"),
+            ProcessInfo::new(3, "explorer.exe", "explorer.exe"),
+        ]);
+        let use_case = KillAllProcessesUseCase::new(&repo, &processes);
+
+        let results = use_case
+            .execute(&force_kill(MatchStrategy::ImageName))
+            .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "notepad.exe");
+        assert_eq!(results[0].1.len(), 2);
+        assert_eq!(processes.processes().len(), 1);
+    }
+
+    #[test]
+    fn test_kill_process_leaves_same_image_with_other_arguments() {
+        let repo = InMemoryRepository::with_entries(vec![("Dev", "bun.exe run dev")]);
+        let processes = FakeProcessProvider::with_processes(vec![
+            ProcessInfo::new(1, "bun.exe", "bun.exe run dev"),
+            ProcessInfo::new(2, "bun.exe", "bun.exe run build"),
+        ]);
+        let use_case = KillProcessUseCase::new(&repo, &processes);
+
+        let results = use_case
+            .execute("Dev", &force_kill(MatchStrategy::CommandLine))
+            .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(processes.killed(), vec![1]);
+    }
+
+    #[test]
+    fn test_kill_process_by_name_kills_every_instance() {
+        let repo = InMemoryRepository::with_entries(vec![("Dev", "bun.exe run dev")]);
+        let processes = FakeProcessProvider::with_processes(vec![
+            ProcessInfo::new(1, "bun.exe", "bun.exe run dev"),
+            ProcessInfo::new(2, "bun.exe", "bun.exe run build"),
+        ]);
+        let use_case = KillProcessUseCase::new(&repo, &processes);
+
+        let results = use_case
+            .execute("Dev", &force_kill(MatchStrategy::ImageName))
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+    }
+
+    #[test]
+    fn test_kill_all_groups_by_entry_name() {
+        let repo = InMemoryRepository::with_entries(vec![
+            ("Dev", "bun.exe run dev"),
+            ("Idle", "calc.exe"),
+        ]);
+        let processes = FakeProcessProvider::with_processes(vec![
+            ProcessInfo::new(1, "bun.exe", "bun.exe run dev"),
+            ProcessInfo::new(2, "bun.exe", "bun.exe run build"),
+        ]);
+        let use_case = KillAllProcessesUseCase::new(&repo, &processes);
+
+        let results = use_case
+            .execute(&force_kill(MatchStrategy::CommandLine))
+            .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "Dev");
+        assert_eq!(processes.killed(), vec![1]);
+    }
+
+    #[test]
+    fn test_kill_process_stops_whole_process_tree() {
+        let repo = InMemoryRepository::with_entries(vec![("Dev", "bun.exe run dev")]);
+        let processes = FakeProcessProvider::with_processes(vec![
+            ProcessInfo::new(10, "bun.exe", "bun.exe run dev"),
+            ProcessInfo::new(11, "node.exe", "node vite.js").with_parent(10),
+            ProcessInfo::new(12, "esbuild.exe", "esbuild --service").with_parent(11),
+            ProcessInfo::new(20, "node.exe", "node other.js").with_parent(1),
+        ]);
+        let use_case = KillProcessUseCase::new(&repo, &processes);
+
+        let results = use_case
+            .execute("Dev", &force_kill(MatchStrategy::CommandLine))
+            .unwrap();
+
+        let pids: Vec<_> = results.iter().map(|r| r.pid).collect();
+        assert_eq!(pids, vec![10, 11, 12]);
+        assert!(results.iter().all(|r| r.outcome == KillOutcome::Killed));
+        assert_eq!(processes.processes().len(), 1);
+    }
+
+    #[test]
+    fn test_kill_process_closes_gracefully_before_killing() {
+        let repo = InMemoryRepository::with_entries(vec![("Dev", "bun.exe run dev")]);
+        let processes = FakeProcessProvider::with_processes(vec![
+            ProcessInfo::new(10, "bun.exe", "bun.exe run dev"),
+            ProcessInfo::new(11, "node.exe", "node watcher.js").with_parent(10),
+        ]);
+        processes.ignore_close(11);
+        let use_case = KillProcessUseCase::new(&repo, &processes);
+        let options = KillOptions {
+            strategy: MatchStrategy::CommandLine,
+            graceful_timeout: Duration::from_millis(20),
+        };
+
+        let results = use_case.execute("Dev", &options).unwrap();
+
+        assert_eq!(results[0].outcome, KillOutcome::Closed);
+        assert_eq!(results[1].outcome, KillOutcome::Killed);
+        assert_eq!(processes.closed(), vec![10]);
+        assert_eq!(processes.killed(), vec![11]);
+    }
+
+    #[test]
+    fn test_kill_process_counts_children_that_exit_with_their_parent() {
+        let repo = InMemoryRepository::with_entries(vec![("Dev", "bun.exe run dev")]);
+        let processes = FakeProcessProvider::with_processes(vec![
+            ProcessInfo::new(10, "bun.exe", "bun.exe run dev"),
+            ProcessInfo::new(11, "conhost.exe", "conhost.exe 0xffffffff").with_parent(10),
+        ]);
+        processes.exit_with_parent(11);
+        let use_case = KillProcessUseCase::new(&repo, &processes);
+
+        let results = use_case
+            .execute("Dev", &force_kill(MatchStrategy::CommandLine))
+            .unwrap();
+
+        assert_eq!(results[0].outcome, KillOutcome::Killed);
+        assert_eq!(results[1].outcome, KillOutcome::Closed);
+        assert_eq!(processes.killed(), vec![10]);
+        assert!(processes.processes().is_empty());
+    }
+
+    #[test]
+    fn test_kill_process_reports_failures_per_pid() {
+        let repo = InMemoryRepository::with_entries(vec![("Dev", "bun.exe run dev")]);
+        let processes = FakeProcessProvider::with_processes(vec![
+            ProcessInfo::new(10, "bun.exe", "bun.exe run dev"),
+            ProcessInfo::new(11, "node.exe", "node server.js").with_parent(10),
+        ]);
+        processes.fail_kill(11, "access denied");
+        let use_case = KillProcessUseCase::new(&repo, &processes);
+
+        let results = use_case
+            .execute("Dev", &force_kill(MatchStrategy::CommandLine))
+            .unwrap();
+
+        assert!(results[0].is_stopped());
+        assert!(!results[1].is_stopped());
+        assert!(
+            matches!(&results[1].outcome, KillOutcome::Failed(msg) if msg.contains("access denied"))
+        );
+    }
+
+    #[test]
+    fn test_add_and_remove_in_selected_location() {
+        let user_run = InMemoryRepository::new();
+        user_run.seed(StartupEntry::new("Dev", "old.exe"));
+        let repo = CompositeRepository::new()
+            .with(StartupLocation::CurrentUserRun, user_run)
+            .with(StartupLocation::LocalMachineRun, InMemoryRepository::new())
+            .select(Some(StartupLocation::LocalMachineRun));
+        let launchers = InMemoryLauncherStore::new();
+
+        AddCommandUseCase::new(&repo, &launchers)
+            .execute(
+                "Dev",
+                "bun",
+                vec![],
+                None,
+                ExecutionMode::Visible(ConsoleOptions::default()),
+            )
+            .unwrap();
+
+        let repo = repo.select(None);
+        let locations: Vec<_> = repo
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|e| e.location)
+            .collect();
+        assert_eq!(
+            locations,
+            vec![
+                StartupLocation::CurrentUserRun,
+                StartupLocation::LocalMachineRun
+            ]
+        );
+
+        let result = RemoveEntryUseCase::new(&repo, &launchers).execute("Dev");
+        assert!(matches!(result, Err(StartupError::AmbiguousEntry(_))));
+
+        let repo = repo.select(Some(StartupLocation::LocalMachineRun));
+        RemoveEntryUseCase::new(&repo, &launchers)
+            .execute("Dev")
+            .unwrap();
+        let remaining = repo.select(None).list().unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].command, "old.exe");
+    }
+
+    #[test]
+    fn test_disable_and_enable_entry() {
+        let repo = InMemoryRepository::with_entries(vec![("Dev", "bun.exe run dev")]);
+        let use_case = SetEnabledUseCase::new(&repo);
+
+        assert!(use_case.execute("Dev", false).unwrap());
+        let disabled = repo.entries()[0].clone();
+        assert!(!disabled.enabled);
+        assert!(disabled.disabled_at.is_some());
+
+        // Disabling again keeps the original time
+        assert!(!use_case.execute("Dev", false).unwrap());
+        assert_eq!(repo.entries()[0].disabled_at, disabled.disabled_at);
+
+        assert!(use_case.execute("Dev", true).unwrap());
+        let enabled = &repo.entries()[0];
+        assert!(enabled.enabled);
+        assert_eq!(enabled.disabled_at, None);
+        assert_eq!(enabled.command, "bun.exe run dev");
+    }
+
+    #[test]
+    fn test_disable_missing_entry() {
+        let repo = InMemoryRepository::new();
+        let use_case = SetEnabledUseCase::new(&repo);
+
+        let result = use_case.execute("Missing", false);
+
+        assert!(matches!(result, Err(StartupError::EntryNotFound(_))));
+    }
+
+    #[test]
+    fn test_disable_is_unsupported_without_approval_storage() {
+        let dir = std::env::temp_dir().join("wsm_integration_disable_folder");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("App.lnk"), "").unwrap();
+        let repo = StartupFolderRepository::new(&dir, StartupLocation::UserStartupFolder);
+
+        let result = SetEnabledUseCase::new(&repo).execute("App", false);
+
+        std::fs::remove_dir_all(&dir).ok();
+        assert!(matches!(result, Err(StartupError::Unsupported(_))));
+    }
+
+    #[test]
+    fn test_add_command_as_scheduled_task() {
+        let repo = CompositeRepository::new()
+            .with(StartupLocation::CurrentUserRun, InMemoryRepository::new())
+            .with(
+                StartupLocation::TaskScheduler,
+                ScheduledTaskRepository::new(InMemoryTaskScheduler::new()).with_user(r"PC\dev"),
+            );
+        let launchers = InMemoryLauncherStore::new();
+        let settings = TaskSettings {
+            delay: Some(Duration::from_secs(30)),
+            restart: Some(RestartPolicy {
+                count: 3,
+                interval: Duration::from_secs(60),
+            }),
+            hidden: false,
+            highest_privileges: true,
+        };
+
+        AddCommandUseCase::new(&repo, &launchers)
+            .execute(
+                "Sync",
+                "sync.exe",
+                vec!["--tray".to_string()],
+                None,
+                ExecutionMode::ScheduledTask(settings),
+            )
+            .unwrap();
+
+        let entries = repo.list().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].location, StartupLocation::TaskScheduler);
+        assert!(launchers.filenames().is_empty());
+        let task = TaskDefinition::from_xml(&entries[0].command).unwrap();
+        assert_eq!(task.command_line(), "sync.exe --tray");
+        assert_eq!(task.user_id.as_deref(), Some(r"PC\dev"));
+        assert_eq!(task.settings, settings);
+    }
+
+    #[test]
+    fn test_add_scheduled_task_rejects_invalid_restart_interval() {
+        let repo = InMemoryRepository::new();
+        let launchers = InMemoryLauncherStore::new();
+        let settings = TaskSettings {
+            restart: Some(RestartPolicy {
+                count: 3,
+                interval: Duration::from_secs(10),
+            }),
+            ..TaskSettings::default()
+        };
+
+        let result = AddCommandUseCase::new(&repo, &launchers).execute(
+            "Sync",
+            "sync.exe",
+            vec![],
+            None,
+            ExecutionMode::ScheduledTask(settings),
+        );
+
+        assert!(matches!(result, Err(StartupError::InvalidTaskSettings(_))));
+        assert!(repo.list().unwrap().is_empty());
+    }
+
+    fn manifest(toml: &str) -> StartupManifest {
+        StartupManifest::parse(toml, ManifestFormat::Toml).unwrap()
+    }
+
+    #[test]
+    fn test_plan_manifest_adds_changes_and_prunes() {
+        let user_run = InMemoryRepository::new();
+        user_run.seed(StartupEntry::new("Dev", "bun run start"));
+        user_run.seed(StartupEntry::new("Same", "app --tray"));
+        user_run.seed(StartupEntry::new("Old", "old.exe"));
+        let machine_run = InMemoryRepository::new();
+        machine_run.seed(StartupEntry::new("System", "system.exe"));
+        let repo = CompositeRepository::new()
+            .with(StartupLocation::CurrentUserRun, user_run)
+            .with(StartupLocation::LocalMachineRun, machine_run);
+        let launchers = InMemoryLauncherStore::new();
+        let manifest = manifest(
+            r#"
+            [[entries]]
+            name = "Dev"
+            command = "bun"
+            args = ["run", "dev"]
+            mode = "visible"
+
+            [[entries]]
+            name = "same"
+            command = "app"
+            args = ["--tray"]
+            mode = "visible"
+
+            [[entries]]
+            name = "Web"
+            command = "node"
+            args = ["server.js"]
+            "#,
+        );
+        let use_case = PlanManifestUseCase::new(&repo, &launchers);
+
+        let plan = use_case.execute(&manifest, false).unwrap();
+        assert_eq!(plan.counts(), (1, 1, 0));
+        assert!(matches!(
+            &plan.changes[0],
+            PlannedChange::Change(change) if change.fields == [PlanField::Command]
+        ));
+        assert!(matches!(&plan.changes[1], PlannedChange::Add(entry) if entry.name == "Web"));
+
+        // Only the locations the manifest uses are pruned
+        let plan = use_case.execute(&manifest, true).unwrap();
+        assert_eq!(plan.counts(), (1, 1, 1));
+        assert!(matches!(&plan.changes[2], PlannedChange::Remove(entry) if entry.name == "Old"));
+    }
+
+    #[test]
+    fn test_plan_manifest_rejects_ambiguous_names() {
+        let repo = CompositeRepository::new()
+            .with(
+                StartupLocation::CurrentUserRun,
+                InMemoryRepository::with_entries(vec![("Dev", "a.exe")]),
+            )
+            .with(
+                StartupLocation::LocalMachineRun,
+                InMemoryRepository::with_entries(vec![("Dev", "b.exe")]),
+            );
+        let launchers = InMemoryLauncherStore::new();
+        let manifest = manifest("[[entries]]\nname = \"Dev\"\ncommand = \"bun\"");
+
+        let result = PlanManifestUseCase::new(&repo, &launchers).execute(&manifest, false);
+
+        assert!(matches!(result, Err(StartupError::AmbiguousEntry(_))));
+    }
+
+    #[test]
+    fn test_apply_manifest_converges() {
+        let repo = InMemoryRepository::with_entries(vec![("Old", "old.exe")]);
+        let launchers = InMemoryLauncherStore::new();
+        let manifest = manifest(
+            r#"
+            [[entries]]
+            name = "Dev"
+            command = "bun"
+            args = ["run", "dev"]
+
+            [[entries]]
+            name = "Api"
+            command = "python"
+            args = ["-m", "http.server"]
+            mode = "powershell"
+            enabled = false
+            "#,
+        );
+        let planner = PlanManifestUseCase::new(&repo, &launchers);
+
+        let plan = planner.execute(&manifest, true).unwrap();
+        ApplyManifestUseCase::new(&repo, &launchers)
+            .execute(&plan)
+            .unwrap();
+
+        let entries = repo.list().unwrap();
+        let names: Vec<_> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["Dev", "Api"]);
+        assert!(entries[0].enabled);
+        assert!(!entries[1].enabled);
+        let launcher = launchers.referenced_by(&entries[0].command).unwrap();
+        assert_eq!(
+            launchers.load_manifest().unwrap().launcher_for("Dev"),
+            Some(launcher.as_str())
+        );
+
+        assert!(planner.execute(&manifest, true).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_apply_manifest_replaces_changed_launchers() {
+        let repo = InMemoryRepository::new();
+        let launchers = InMemoryLauncherStore::new();
+        let planner = PlanManifestUseCase::new(&repo, &launchers);
+        let use_case = ApplyManifestUseCase::new(&repo, &launchers);
+        let dev = |script: &str| {
+            manifest(&format!(
+                "[[entries]]\nname = \"Dev\"\ncommand = \"bun\"\nargs = [\"run\", \"{}\"]",
+                script
+            ))
+        };
+
+        use_case
+            .execute(&planner.execute(&dev("dev"), false).unwrap())
+            .unwrap();
+        let first = launchers.filenames();
+        let deleted = use_case
+            .execute(&planner.execute(&dev("start"), false).unwrap())
+            .unwrap();
+
+        assert_eq!(deleted, first);
+        assert_eq!(launchers.filenames().len(), 1);
+        assert_ne!(launchers.filenames(), first);
+    }
+
+    #[test]
+    fn test_apply_manifest_reverts_when_a_change_fails() {
+        let repo = InMemoryRepository::with_entries(vec![("Dev", "bun run start")]);
+        let launchers = InMemoryLauncherStore::new();
+        let before = repo.entries();
+        let manifest = manifest(
+            r#"
+            [[entries]]
+            name = "Dev"
+            command = "bun"
+            args = ["run", "dev"]
+
+            [[entries]]
+            name = "Notes"
+            command = "notes"
+            mode = "visible"
+            enabled = false
+            "#,
+        );
+        let plan = PlanManifestUseCase::new(&repo, &launchers)
+            .execute(&manifest, false)
+            .unwrap();
+        repo.fail_on(RepositoryOperation::SetApproval, "access denied");
+
+        let result = ApplyManifestUseCase::new(&repo, &launchers).execute(&plan);
+
+        assert!(matches!(result, Err(StartupError::RegistryError(_))));
+        assert_eq!(repo.entries(), before);
+        assert!(launchers.filenames().is_empty());
+    }
+
+    /// Two in-memory Run keys, like the registry has.
+    fn run_keys() -> CompositeRepository {
+        CompositeRepository::new()
+            .with(StartupLocation::CurrentUserRun, InMemoryRepository::new())
+            .with(StartupLocation::LocalMachineRun, InMemoryRepository::new())
+    }
+
+    fn backup_of(entries: &str) -> StartupBackup {
+        StartupBackup::from_json(&format!(
+            r#"{{"version": 1, "exported-at": "2026-03-01T08:30:00Z", "entries": [{}]}}"#,
+            entries
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_export_and_import_restore_entries() {
+        let workdir = std::env::temp_dir();
+        let source = run_keys();
+        let source_launchers = InMemoryLauncherStore::new();
+        AddCommandUseCase::new(&source, &source_launchers)
+            .with_location(StartupLocation::LocalMachineRun)
+            .execute(
+                "Dev",
+                "bun",
+                vec!["run".to_string(), "dev".to_string()],
+                workdir.to_str(),
+                ExecutionMode::VBScript,
+            )
+            .unwrap();
+        AddCommandUseCase::new(&source, &source_launchers)
+            .execute(
+                "Api",
+                "python",
+                vec!["-m".to_string(), "http.server".to_string()],
+                None,
+                ExecutionMode::Visible(ConsoleOptions { no_exit: true }),
+            )
+            .unwrap();
+        SetEnabledUseCase::new(&source)
+            .execute("Api", false)
+            .unwrap();
+
+        let json = ExportEntriesUseCase::new(&source, &source_launchers)
+            .execute()
+            .unwrap()
+            .to_json();
+        let backup = StartupBackup::from_json(&json).unwrap();
+        let dev = backup
+            .entries
+            .iter()
+            .find(|e| e.entry.name == "Dev")
+            .unwrap();
+        assert!(dev.launcher.is_some());
+
+        let target = run_keys();
+        let target_launchers = InMemoryLauncherStore::new();
+        let outcomes = ImportEntriesUseCase::new(&target, &target_launchers)
+            .execute(&backup, ConflictStrategy::Skip)
+            .unwrap();
+
+        assert_eq!(
+            outcomes,
+            [
+                ImportOutcome::Added("Api".to_string()),
+                ImportOutcome::Added("Dev".to_string())
+            ]
+        );
+        assert_eq!(target.list().unwrap(), source.list().unwrap());
+        assert_eq!(target_launchers.filenames(), source_launchers.filenames());
+        let exported = ExportEntriesUseCase::new(&target, &target_launchers)
+            .execute()
+            .unwrap();
+        assert_eq!(exported.entries, backup.entries);
+    }
+
+    #[test]
+    fn test_import_conflict_strategies() {
+        let backup = backup_of(
+            r#"{"name": "Dev", "command": "bun", "args": ["run", "dev"], "mode": "visible",
+                "location": "hklm-run", "enabled": true, "value": "cmd.exe /c bun run dev"}"#,
+        );
+        let import = |strategy| {
+            let repo = run_keys();
+            repo.add(&StartupEntry::new("dev", "old.exe")).unwrap();
+            let launchers = InMemoryLauncherStore::new();
+            let outcomes = ImportEntriesUseCase::new(&repo, &launchers)
+                .execute(&backup, strategy)
+                .unwrap();
+            let entries: Vec<_> = repo
+                .list()
+                .unwrap()
+                .into_iter()
+                .map(|e| (e.name, e.location))
+                .collect();
+            (outcomes, entries)
+        };
+
+        let (outcomes, entries) = import(ConflictStrategy::Skip);
+        assert_eq!(outcomes, [ImportOutcome::Skipped("Dev".to_string())]);
+        assert_eq!(
+            entries,
+            [("dev".to_string(), StartupLocation::CurrentUserRun)]
+        );
+
+        let (outcomes, entries) = import(ConflictStrategy::Overwrite);
+        assert_eq!(outcomes, [ImportOutcome::Replaced("Dev".to_string())]);
+        assert_eq!(
+            entries,
+            [("Dev".to_string(), StartupLocation::LocalMachineRun)]
+        );
+
+        let (outcomes, entries) = import(ConflictStrategy::Rename);
+        assert_eq!(
+            outcomes,
+            [ImportOutcome::Renamed {
+                from: "Dev".to_string(),
+                to: "Dev (2)".to_string()
+            }]
+        );
+        assert_eq!(
+            entries,
+            [
+                ("dev".to_string(), StartupLocation::CurrentUserRun),
+                ("Dev (2)".to_string(), StartupLocation::LocalMachineRun)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_import_reports_every_problem_before_writing() {
+        let backup = backup_of(
+            r#"{"name": "Ok", "command": "bun", "location": "hkcu-run", "value": "bun"},
+               {"name": "App", "path": "C:\\missing\\app.exe", "value": "C:\\missing\\app.exe"},
+               {"name": "Dev", "command": "bun", "workdir": "C:\\missing\\dev", "value": "bun"},
+               {"name": "Gone", "location": "hkcu-run", "value": "wscript.exe gone.vbs"},
+               {"name": "ok", "path": "C:\\Windows", "value": "C:\\Windows"}"#,
+        );
+        let repo = run_keys();
+        let launchers = InMemoryLauncherStore::new();
+
+        let result =
+            ImportEntriesUseCase::new(&repo, &launchers).execute(&backup, ConflictStrategy::Skip);
+
+        let Err(StartupError::ImportRejected(problems)) = result else {
+            panic!("expected the import to be rejected");
+        };
+        assert_eq!(problems.len(), 4, "{:?}", problems);
+        assert!(problems[0].starts_with("'App': The specified path does not exist"));
+        assert!(problems[1].starts_with("'Dev': The specified directory does not exist"));
+        assert!(problems[2].contains("'Gone': its command could not be decoded"));
+        assert!(problems[3].contains("'ok': listed more than once"));
+        assert!(repo.list().unwrap().is_empty());
+        assert!(launchers.filenames().is_empty());
+    }
+
+    #[test]
+    fn test_import_rejects_disabled_entries_without_approval_storage() {
+        let dir = std::env::temp_dir().join("wsm_integration_import_folder");
+        std::fs::remove_dir_all(&dir).ok();
+        let backup = backup_of(
+            r#"{"name": "Ok", "command": "bun", "location": "hkcu-run", "enabled": false, "value": "bun"},
+               {"name": "Tray", "command": "bun", "location": "startup-folder", "enabled": false, "value": "bun"}"#,
+        );
+        let repo = run_keys().with(
+            StartupLocation::UserStartupFolder,
+            StartupFolderRepository::new(&dir, StartupLocation::UserStartupFolder),
+        );
+        let launchers = InMemoryLauncherStore::new();
+
+        let result =
+            ImportEntriesUseCase::new(&repo, &launchers).execute(&backup, ConflictStrategy::Skip);
+
+        let written = dir.exists();
+        std::fs::remove_dir_all(&dir).ok();
+        let Err(StartupError::ImportRejected(problems)) = result else {
+            panic!("expected the import to be rejected");
+        };
+        assert_eq!(problems.len(), 1, "{:?}", problems);
+        assert!(problems[0].starts_with("'Tray': entries in"));
+        assert!(problems[0].ends_with("cannot be disabled"));
+        assert!(!written);
+        assert!(repo.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_import_takes_back_written_entries_when_one_fails() {
+        let backup = backup_of(
+            r#"{"name": "Dev", "command": "bun", "args": ["run", "dev"], "mode": "vbscript",
+                "location": "hkcu-run", "enabled": true,
+                "value": "wscript.exe \"%APPDATA%\\windows_startup_manager\\dev.vbs\""},
+               {"name": "Api", "command": "bun", "location": "hklm-run", "enabled": false, "value": "bun"}"#,
+        );
+        let machine_run = InMemoryRepository::new();
+        machine_run.fail_on(RepositoryOperation::SetApproval, "access denied");
+        let repo = CompositeRepository::new()
+            .with(
+                StartupLocation::CurrentUserRun,
+                InMemoryRepository::with_entries([("Other", "other.exe")]),
+            )
+            .with(StartupLocation::LocalMachineRun, machine_run);
+        let launchers = InMemoryLauncherStore::new();
+
+        let result = ImportEntriesUseCase::new(&repo, &launchers)
+            .execute(&backup, ConflictStrategy::Overwrite);
+
+        assert!(matches!(result, Err(StartupError::RegistryError(_))));
+        let names: Vec<_> = repo.list().unwrap().into_iter().map(|e| e.name).collect();
+        assert_eq!(names, ["Other"]);
+        assert!(launchers.filenames().is_empty());
+        assert_eq!(launchers.load_manifest().unwrap().iter().count(), 0);
+    }
+
+    #[test]
+    fn test_import_puts_back_replaced_entries_without_leaving_changes() {
+        let backup = backup_of(
+            r#"{"name": "Dev", "command": "dev.exe", "location": "hkcu-run", "enabled": true, "value": "dev.exe"},
+               {"name": "Tool", "command": "tool.exe", "location": "hkcu-run", "enabled": true, "value": "tool.exe"},
+               {"name": "Api", "command": "bun", "location": "hklm-run", "enabled": false, "value": "bun"}"#,
+        );
+        let machine_run = InMemoryRepository::with_entries([("Tool", "old-tool.exe")]);
+        machine_run.fail_on(RepositoryOperation::SetApproval, "access denied");
+        let repo = CompositeRepository::new()
+            .with(
+                StartupLocation::CurrentUserRun,
+                InMemoryRepository::with_entries([("Dev", "old-dev.exe")]),
+            )
+            .with(StartupLocation::LocalMachineRun, machine_run);
+        let launchers = InMemoryLauncherStore::new();
+        let before = repo.list().unwrap();
+        let recorder = SnapshotRepository::new(&repo, &launchers);
+
+        let result = ImportEntriesUseCase::new(&recorder, &launchers)
+            .execute(&backup, ConflictStrategy::Overwrite);
+
+        assert!(matches!(result, Err(StartupError::RegistryError(_))));
+        assert_eq!(repo.list().unwrap(), before);
+        assert!(recorder.changes().is_empty());
+    }
+
+    #[test]
+    fn test_import_reg_file_sets_and_deletes_entries() {
+        let user_run = InMemoryRepository::new();
+        user_run.seed(StartupEntry::new("Legacy", "legacy.exe"));
+        let repo = CompositeRepository::new()
+            .with(StartupLocation::CurrentUserRun, user_run)
+            .with(
+                StartupLocation::LocalMachineRun32,
+                InMemoryRepository::new(),
+            );
+        let launchers = InMemoryLauncherStore::new();
+        let changes = RegFile::decode(include_bytes!("fixtures/startup_utf16.reg"))
+            .unwrap()
+            .startup_changes()
+            .unwrap();
+
+        let outcomes = ImportRegFileUseCase::new(&repo, &launchers)
+            .execute(&changes, ConflictStrategy::Skip)
+            .unwrap();
+
+        assert_eq!(
+            outcomes,
+            [
+                ImportOutcome::Added("Notes".to_string()),
+                ImportOutcome::Added("Sync".to_string()),
+                ImportOutcome::Removed("Legacy".to_string()),
+                ImportOutcome::Added("Agent".to_string()),
+            ]
+        );
+        let entries = repo.list().unwrap();
+        let names: Vec<_> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["Notes", "Sync", "Agent"]);
+        assert_eq!(
+            entries[0].command,
+            r#""C:\Program Files\Notes\notes.exe" --tray"#
+        );
+        assert!(!entries[0].enabled);
+        assert!(entries[0].disabled_at.is_some());
+        assert!(entries[1].enabled);
+        assert_eq!(entries[2].location, StartupLocation::LocalMachineRun32);
+    }
+
+    #[test]
+    fn test_reg_export_restores_run_key_entries() {
+        let source = run_keys();
+        let launchers = InMemoryLauncherStore::new();
+        AddCommandUseCase::new(&source, &launchers)
+            .execute(
+                "Dev",
+                "bun",
+                vec!["run".to_string(), "dev".to_string()],
+                None,
+                ExecutionMode::VBScript,
+            )
+            .unwrap();
+        source
+            .add(
+                &StartupEntry::new("Agent", r#""C:\Agent\agent.exe" --quiet"#)
+                    .with_location(StartupLocation::LocalMachineRun),
+            )
+            .unwrap();
+        // StartupApproved values keep the time to 100 nanoseconds
+        let disabled_at = chrono::DateTime::from_timestamp(1_772_353_800, 0).unwrap();
+        source
+            .set_approval("Agent", StartupApproval::disabled_since(disabled_at))
+            .unwrap();
+        let entries = source.list().unwrap();
+
+        let bytes = RegFile::from_entries(&entries).encode();
+        let changes = RegFile::decode(&bytes).unwrap().startup_changes().unwrap();
+        let target = run_keys();
+        ImportRegFileUseCase::new(&target, &launchers)
+            .execute(&changes, ConflictStrategy::Skip)
+            .unwrap();
+
+        assert_eq!(target.list().unwrap(), entries);
+    }
+
+    #[test]
+    fn test_import_reg_file_rejects_missing_launchers() {
+        let launchers = InMemoryLauncherStore::new();
+        let command = StartupCommand::CommandWithArgs {
+            command: "bun".to_string(),
+            args: vec![],
+            workdir: None,
+            mode: ExecutionMode::VBScript,
+        };
+        let changes = [
+            RegChange::Set(StartupEntry::new(
+                "Dev",
+                command.to_registry_value("Dev", &launchers),
+            )),
+            RegChange::Set(StartupEntry::new(" ", "app.exe")),
+        ];
+        let repo = run_keys();
+
+        let result = ImportRegFileUseCase::new(&repo, &launchers)
+            .execute(&changes, ConflictStrategy::Overwrite);
+
+        let Err(StartupError::ImportRejected(problems)) = result else {
+            panic!("expected the import to be rejected");
+        };
+        assert_eq!(problems.len(), 2, "{:?}", problems);
+        assert!(problems[0].contains("doesn't exist on this machine"));
+        assert!(problems[1].contains("Entry name cannot be empty"));
+        assert!(repo.list().unwrap().is_empty());
+    }
+
+    type Recorder<'a> = SnapshotRepository<'a, InMemoryRepository, InMemoryLauncherStore>;
+
+    /// Runs a command through a recorder and adds what it changed to `history`.
+    fn recorded<T>(
+        repo: &InMemoryRepository,
+        launchers: &InMemoryLauncherStore,
+        history: &InMemoryHistoryStore,
+        command: &str,
+        run: impl FnOnce(&Recorder) -> T,
+    ) -> T {
+        let recorder = SnapshotRepository::new(repo, launchers);
+        let result = run(&recorder);
+        RecordHistoryUseCase::new(history)
+            .execute(command, recorder.changes(), Vec::new())
+            .unwrap();
+        result
+    }
+
+    /// Runs `undo` through a recorder, recording it like the CLI does.
+    fn undo(
+        repo: &InMemoryRepository,
+        launchers: &InMemoryLauncherStore,
+        history: &InMemoryHistoryStore,
+        count: usize,
+    ) -> Result<Vec<u64>, StartupError> {
+        let recorder = SnapshotRepository::new(repo, launchers);
+        let reverted: Vec<u64> = UndoChangesUseCase::new(&recorder, launchers, history)
+            .execute(count)?
+            .iter()
+            .map(|record| record.id)
+            .collect();
+        RecordHistoryUseCase::new(history)
+            .execute("undo", recorder.changes(), reverted.clone())
+            .unwrap();
+        Ok(reverted)
+    }
+
+    #[test]
+    fn test_undo_restores_a_replaced_entry_and_its_launcher() {
+        let repo = InMemoryRepository::new();
+        let launchers = InMemoryLauncherStore::new();
+        let history = InMemoryHistoryStore::new();
+        let workdir = std::env::temp_dir();
+        let add = |recorder: &Recorder, script: &str| {
+            AddCommandUseCase::new(recorder, &launchers)
+                .with_force(true)
+                .execute(
+                    "Dev",
+                    "bun",
+                    vec!["run".to_string(), script.to_string()],
+                    workdir.to_str(),
+                    ExecutionMode::VBScript,
+                )
+                .unwrap()
+        };
+
+        recorded(
+            &repo,
+            &launchers,
+            &history,
+            "add-command Dev bun run dev",
+            |r| add(r, "dev"),
+        );
+        let original = repo.entries();
+        let original_launcher = launchers.referenced_by(&original[0].command).unwrap();
+        let content = launchers.file(&original_launcher).unwrap();
+        recorded(
+            &repo,
+            &launchers,
+            &history,
+            "add-command Dev bun run watch",
+            |r| add(r, "watch"),
+        );
+        let replacement = launchers.referenced_by(&repo.entries()[0].command).unwrap();
+        assert_eq!(launchers.filenames(), [replacement]);
+
+        assert_eq!(undo(&repo, &launchers, &history, 1).unwrap(), [2]);
+
+        assert_eq!(repo.entries(), original);
+        assert_eq!(
+            launchers.filenames(),
+            std::slice::from_ref(&original_launcher)
+        );
+        assert_eq!(launchers.file(&original_launcher), Some(content));
+        assert_eq!(
+            launchers.manifest().launcher_for("Dev"),
+            Some(original_launcher.as_str())
+        );
+        let records = history.history().records;
+        assert_eq!(records.len(), 3);
+        assert!(records[1].undone);
+        assert_eq!(records[2].reverts, [2]);
+        let before = records[1].changes[0].before.as_ref().unwrap();
+        assert_eq!(
+            before.launcher.as_ref().unwrap().filename,
+            original_launcher
+        );
+    }
+
+    #[test]
+    fn test_undo_reverts_a_rename() {
+        let repo = InMemoryRepository::new();
+        let launchers = InMemoryLauncherStore::new();
+        let history = InMemoryHistoryStore::new();
+        recorded(&repo, &launchers, &history, "add-command Dev", |r| {
+            add_dev(r, &launchers)
+        });
+        let original = repo.entries();
+        let launcher = launchers.filenames();
+
+        recorded(&repo, &launchers, &history, "rename Dev Server", |r| {
+            RenameEntryUseCase::new(r, &launchers)
+                .execute("Dev", "Server")
+                .unwrap()
+        });
+        assert_eq!(history.history().records[1].changes.len(), 2);
+        undo(&repo, &launchers, &history, 1).unwrap();
+
+        assert_eq!(repo.entries(), original);
+        assert_eq!(launchers.filenames(), launcher);
+    }
+
+    #[test]
+    fn test_recorder_edits_and_renames_in_the_holding_location() {
+        let repo = CompositeRepository::new()
+            .with(StartupLocation::CurrentUserRun, InMemoryRepository::new())
+            .with(
+                StartupLocation::LocalMachineRun,
+                InMemoryRepository::with_entries([("Tool", "C:\\tools\\tool.exe")]),
+            );
+        let launchers = InMemoryLauncherStore::new();
+        let recorder = SnapshotRepository::new(&repo, &launchers);
+        let edits = EntryEdits {
+            args: Some(vec!["--tray".to_string()]),
+            ..EntryEdits::default()
+        };
+
+        EditEntryUseCase::new(&recorder, &launchers)
+            .execute("tool", &edits)
+            .unwrap();
+        RenameEntryUseCase::new(&recorder, &launchers)
+            .execute("Tool", "Tray")
+            .unwrap();
+
+        let entries: Vec<_> = repo
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|e| (e.name, e.command, e.location))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![(
+                "Tray".to_string(),
+                "C:\\tools\\tool.exe --tray".to_string(),
+                StartupLocation::LocalMachineRun
+            )]
+        );
+        let changes: Vec<_> = recorder
+            .changes()
+            .into_iter()
+            .map(|c| {
+                (
+                    c.name().to_string(),
+                    c.location(),
+                    c.before.map(|s| s.value),
+                    c.after.map(|s| s.value),
+                )
+            })
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    "Tool".to_string(),
+                    StartupLocation::LocalMachineRun,
+                    Some("C:\\tools\\tool.exe".to_string()),
+                    None
+                ),
+                (
+                    "Tray".to_string(),
+                    StartupLocation::LocalMachineRun,
+                    None,
+                    Some("C:\\tools\\tool.exe --tray".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_undo_reverts_several_commands_most_recent_first() {
+        let repo = InMemoryRepository::new();
+        let launchers = InMemoryLauncherStore::new();
+        let history = InMemoryHistoryStore::new();
+        let exe = std::env::temp_dir().join("test_undo_app.exe");
+        std::fs::write(&exe, "test").unwrap();
+
+        recorded(&repo, &launchers, &history, "add App", |r| {
+            AddExecutableUseCase::new(r).execute("App", exe.to_str().unwrap())
+        })
+        .unwrap();
+        let added = repo.entries();
+        recorded(&repo, &launchers, &history, "disable App", |r| {
+            SetEnabledUseCase::new(r).execute("App", false)
+        })
+        .unwrap();
+        recorded(&repo, &launchers, &history, "remove App", |r| {
+            RemoveEntryUseCase::new(r, &launchers).execute("App")
+        })
+        .unwrap();
+        std::fs::remove_file(&exe).ok();
+
+        assert_eq!(undo(&repo, &launchers, &history, 2).unwrap(), [3, 2]);
+        assert_eq!(repo.entries(), added);
+
+        // Undo skips the records of undo itself and goes further back
+        assert_eq!(undo(&repo, &launchers, &history, 5).unwrap(), [1]);
+        assert!(repo.entries().is_empty());
+        assert!(undo(&repo, &launchers, &history, 1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_undo_refuses_entries_changed_since() {
+        let repo = InMemoryRepository::new();
+        let launchers = InMemoryLauncherStore::new();
+        let history = InMemoryHistoryStore::new();
+        recorded(&repo, &launchers, &history, "import backup.reg", |r| {
+            r.add(&StartupEntry::new("App", "app.exe"))
+        })
+        .unwrap();
+        repo.add(&StartupEntry::new("App", "app.exe --tray"))
+            .unwrap();
+
+        let error = undo(&repo, &launchers, &history, 1).unwrap_err();
+
+        assert!(matches!(error, StartupError::UndoConflict(_)), "{}", error);
+        assert!(error.to_string().contains("changed after #1"));
+        assert_eq!(repo.entries()[0].command, "app.exe --tray");
+        assert!(!history.history().records[0].undone);
+    }
+
+    fn builder() -> AuditActor {
+        AuditActor {
+            user: "CORP\\builder".to_string(),
+            host: "BUILD01".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_audit_records_old_and_new_values_and_failures() {
+        let repo = InMemoryRepository::with_entries([("Dev", "old.exe")]);
+        let launchers = InMemoryLauncherStore::new();
+        let log = InMemoryAuditLog::new();
+        let actor = builder();
+        let audit = RecordAuditUseCase::new(&log, &actor);
+
+        let recorder = SnapshotRepository::new(&repo, &launchers);
+        recorder.add(&StartupEntry::new("Dev", "new.exe")).unwrap();
+        audit
+            .execute(AuditAction::Add, Some("Dev"), &recorder.changes(), None)
+            .unwrap();
+        let recorder = SnapshotRepository::new(&repo, &launchers);
+        let error = RemoveEntryUseCase::new(&recorder, &launchers)
+            .execute("Missing")
+            .unwrap_err();
+        audit
+            .execute(
+                AuditAction::Remove,
+                Some("Missing"),
+                &recorder.changes(),
+                Some(&error),
+            )
+            .unwrap();
+
+        let events = log.events();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].user, "CORP\\builder");
+        assert_eq!(events[0].host, "BUILD01");
+        assert_eq!(events[0].entry.as_deref(), Some("Dev"));
+        assert_eq!(events[0].location, Some(StartupLocation::CurrentUserRun));
+        assert_eq!(events[0].old_value.as_deref(), Some("old.exe"));
+        assert_eq!(events[0].new_value.as_deref(), Some("new.exe"));
+        assert_eq!(events[0].outcome, AuditOutcome::Success);
+        assert_eq!(events[1].entry.as_deref(), Some("Missing"));
+        assert_eq!(events[1].outcome, AuditOutcome::Failure);
+        assert!(events[1].error.as_deref().unwrap().contains("not found"));
+    }
+
+    #[test]
+    fn test_audit_records_the_processes_a_kill_stopped() {
+        let repo = InMemoryRepository::with_entries(vec![("Dev", "bun.exe run dev")]);
+        let processes = FakeProcessProvider::with_processes(vec![
+            ProcessInfo::new(10, "bun.exe", "bun.exe run dev"),
+            ProcessInfo::new(11, "node.exe", "node server.js").with_parent(10),
+        ]);
+        processes.fail_kill(11, "access denied");
+        let log = InMemoryAuditLog::new();
+        let actor = builder();
+
+        let results = KillAllProcessesUseCase::new(&repo, &processes)
+            .execute(&force_kill(MatchStrategy::CommandLine))
+            .unwrap();
+        RecordAuditUseCase::new(&log, &actor)
+            .execute_kill(None, &results, None)
+            .unwrap();
+
+        let events = log.events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].action, AuditAction::Kill);
+        assert_eq!(events[0].entry.as_deref(), Some("Dev"));
+        assert_eq!(
+            events[0].detail.as_deref(),
+            Some("10 bun.exe killed, 11 node.exe failed (Process error: access denied)")
+        );
+        assert_eq!(events[0].outcome, AuditOutcome::Failure);
+    }
+
+    #[test]
+    fn test_audit_log_query_filters_and_keeps_the_latest() {
+        let log = InMemoryAuditLog::new();
+        let actor = builder();
+        let audit = RecordAuditUseCase::new(&log, &actor);
+        for name in ["A", "B", "C"] {
+            audit
+                .execute(AuditAction::Enable, Some(name), &[], None)
+                .unwrap();
+        }
+        audit
+            .execute(AuditAction::Disable, Some("A"), &[], None)
+            .unwrap();
+        let use_case = ListAuditEventsUseCase::new(&log);
+
+        let enabled = use_case
+            .execute(
+                &AuditFilter {
+                    action: Some(AuditAction::Enable),
+                    ..AuditFilter::default()
+                },
+                2,
+            )
+            .unwrap();
+        let about_a = use_case
+            .execute(
+                &AuditFilter {
+                    entry: Some("a".to_string()),
+                    ..AuditFilter::default()
+                },
+                10,
+            )
+            .unwrap();
+
+        let names = |events: &[windows_startup_manager::domain::AuditEvent]| -> Vec<String> {
+            events.iter().filter_map(|e| e.entry.clone()).collect()
+        };
+        assert_eq!(names(&enabled), ["B", "C"]);
+        assert_eq!(about_a.len(), 2);
+        assert_eq!(about_a[1].action, AuditAction::Disable);
     }
 }